@@ -0,0 +1,208 @@
+use crate::checkpoint::CHECKPOINT_INTERVAL;
+use crate::config::Settings;
+use create2_core::Create2Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+// 文件格式：魔数、位数、哈希个数、已记录的批次数（均为小端），之后是位图
+const MAGIC: &[u8; 8] = b"C2BLOOM1";
+const HEADER_LEN: usize = 8 + 8 + 4 + 8;
+
+// 新建过滤器的默认容量（批次数）和该容量下的误判率，约23MB
+pub const DEFAULT_BLOOM_CAPACITY: usize = 10_000_000;
+pub const DEFAULT_BLOOM_FP_RATE: f64 = 0.0001;
+
+// --bloom 文件：记录已经完整搜索过的salt批次。过滤器按批次而不是按salt记录：一个批次由
+// (种子, 随机数流, 批次起点的随机数位置) 确定，不看salt本身，用同一个种子重新运行（丢了检查点、没加 --resume
+// 或多台机器用了相同 --seed）时跳过这些批次；调用方跳过时直接把随机数位置移到批次末尾，不再生成其中的salt。
+// 批次大小不同的运行之间起点对不上，互相不会命中。
+// 误判只会让一个没搜过的批次被跳过，不会重复搜索；误判率越低文件越大
+#[derive(Debug)]
+pub struct SaltBloom {
+    path: PathBuf,
+    bits: Vec<u64>,
+    hashes: u32,
+    inserted: u64,
+    saved_at: Instant,
+}
+
+impl SaltBloom {
+    // 未指定 --bloom 时返回None；文件已存在时沿用其大小，忽略 --bloom-capacity 和 --bloom-fp-rate
+    pub fn open(settings: &Settings) -> Result<Option<Self>, Create2Error> {
+        let path = match settings.bloom {
+            Some(ref path) => path,
+            None => return Ok(None),
+        };
+        let bloom = match Self::load(path)? {
+            Some(bloom) => bloom,
+            None => Self::new(path, settings.bloom_capacity, settings.bloom_fp_rate)?,
+        };
+        Ok(Some(bloom))
+    }
+
+    // 按 m = -n·ln(p)/ln²2、k = m/n·ln2 选择位数和哈希个数
+    pub fn new(path: &Path, capacity: usize, fp_rate: f64) -> Result<Self, Create2Error> {
+        if capacity == 0 {
+            return Err(Create2Error::Config("--bloom-capacity must be positive".to_string()));
+        }
+        if !(fp_rate > 0.0 && fp_rate < 1.0) {
+            return Err(Create2Error::Config(format!("--bloom-fp-rate must be between 0 and 1, got {}", fp_rate)));
+        }
+        let ln2 = std::f64::consts::LN_2;
+        let bits = (-(capacity as f64) * fp_rate.ln() / (ln2 * ln2)).ceil().max(64.0);
+        let hashes = (bits / capacity as f64 * ln2).round().clamp(1.0, 32.0) as u32;
+        Ok(SaltBloom {
+            path: path.to_path_buf(),
+            bits: vec![0; (bits as usize).div_ceil(64)],
+            hashes,
+            inserted: 0,
+            saved_at: Instant::now(),
+        })
+    }
+
+    // 文件不存在时返回None
+    pub fn load(path: &Path) -> Result<Option<Self>, Create2Error> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let invalid = |reason: &str| Create2Error::Config(format!("Invalid bloom filter {}: {}", path.display(), reason));
+        let data = fs::read(path).map_err(|e| Create2Error::io(format!("Failed to read bloom filter {}", path.display()), e))?;
+        if data.len() < HEADER_LEN || &data[..8] != MAGIC {
+            return Err(invalid("not a bloom filter file"));
+        }
+        let num_bits = u64::from_le_bytes(data[8..16].try_into().unwrap());
+        let hashes = u32::from_le_bytes(data[16..20].try_into().unwrap());
+        let inserted = u64::from_le_bytes(data[20..28].try_into().unwrap());
+        let body = &data[HEADER_LEN..];
+        if num_bits == 0 || num_bits % 64 != 0 || body.len() as u64 != num_bits / 8 || hashes == 0 {
+            return Err(invalid("truncated or corrupt"));
+        }
+        Ok(Some(SaltBloom {
+            path: path.to_path_buf(),
+            bits: body.chunks_exact(8).map(|word| u64::from_le_bytes(word.try_into().unwrap())).collect(),
+            hashes,
+            inserted,
+            saved_at: Instant::now(),
+        }))
+    }
+
+    // 启动时和validate中显示的摘要
+    pub fn describe(&self) -> String {
+        format!(
+            "{}，已记录 {} 个批次，{:.1} MB，当前误判率约 {:.2e}",
+            self.path.display(),
+            self.inserted,
+            (self.bits.len() * 8) as f64 / 1_048_576.0,
+            self.fp_rate()
+        )
+    }
+
+    // 按已记录的批次数估算当前误判率，超过容量后会迅速上升
+    fn fp_rate(&self) -> f64 {
+        let bits = (self.bits.len() * 64) as f64;
+        let k = self.hashes as f64;
+        (1.0 - (-k * self.inserted as f64 / bits).exp()).powf(k)
+    }
+
+    pub fn contains(&self, seed: u64, stream: usize, position: u128) -> bool {
+        self.indexes(seed, stream, position)
+            .all(|index| self.bits[index / 64] & (1 << (index % 64)) != 0)
+    }
+
+    pub fn insert(&mut self, seed: u64, stream: usize, position: u128) {
+        for index in self.indexes(seed, stream, position) {
+            self.bits[index / 64] |= 1 << (index % 64);
+        }
+        self.inserted += 1;
+    }
+
+    // 与检查点相同的间隔写入
+    pub fn save_if_due(&mut self) -> Result<(), Create2Error> {
+        if self.saved_at.elapsed() >= CHECKPOINT_INTERVAL {
+            self.save()?;
+            self.saved_at = Instant::now();
+        }
+        Ok(())
+    }
+
+    // 先写临时文件再rename，中途被杀掉也不会留下半个文件
+    pub fn save(&self) -> Result<(), Create2Error> {
+        let mut data = Vec::with_capacity(HEADER_LEN + self.bits.len() * 8);
+        data.extend_from_slice(MAGIC);
+        data.extend_from_slice(&((self.bits.len() * 64) as u64).to_le_bytes());
+        data.extend_from_slice(&self.hashes.to_le_bytes());
+        data.extend_from_slice(&self.inserted.to_le_bytes());
+        for word in &self.bits {
+            data.extend_from_slice(&word.to_le_bytes());
+        }
+        let tmp_path = self.path.with_extension("tmp");
+        fs::write(&tmp_path, data)
+            .and_then(|_| fs::rename(&tmp_path, &self.path))
+            .map_err(|e| Create2Error::io(format!("Failed to write bloom filter {}", self.path.display()), e))
+    }
+
+    // 双重哈希 h1 + i·h2；哈希必须跨版本稳定，不能用std的DefaultHasher
+    fn indexes(&self, seed: u64, stream: usize, position: u128) -> impl Iterator<Item = usize> {
+        let h1 = mix(seed ^ mix(stream as u64 ^ mix(position as u64 ^ mix((position >> 64) as u64))));
+        let h2 = mix(h1) | 1;
+        let bits = (self.bits.len() * 64) as u64;
+        (0..self.hashes as u64).map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % bits) as usize)
+    }
+}
+
+// splitmix64 的终结函数
+fn mix(mut z: u64) -> u64 {
+    z = z.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("create2-cli-{}-{}.bloom", name, std::process::id()))
+    }
+
+    #[test]
+    fn false_positive_rate_stays_near_the_target() {
+        let mut bloom = SaltBloom::new(&temp_path("fp"), 10_000, 0.01).unwrap();
+        for i in 0..10_000u128 {
+            bloom.insert(7, 0, i * 4000);
+        }
+        assert!((0..10_000u128).all(|i| bloom.contains(7, 0, i * 4000)));
+        // 其他位置、其他随机数流和其他种子都是没记录过的批次
+        let false_positives = (0..100_000u128)
+            .filter(|i| match i % 3 {
+                0 => bloom.contains(7, 0, i * 4000 + 1),
+                1 => bloom.contains(7, 1, i * 4000),
+                _ => bloom.contains(8, 0, i * 4000),
+            })
+            .count();
+        assert!(false_positives < 2_000, "{} false positives in 100000 lookups", false_positives);
+        assert!((bloom.fp_rate() - 0.01).abs() < 0.005);
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let path = temp_path("round-trip");
+        let mut bloom = SaltBloom::new(&path, 1_000, 0.001).unwrap();
+        for i in 0..500u128 {
+            bloom.insert(42, (i % 4) as usize, i << 70);
+        }
+        bloom.save().unwrap();
+        let loaded = SaltBloom::load(&path).unwrap().unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!((loaded.bits.as_slice(), loaded.hashes, loaded.inserted), (bloom.bits.as_slice(), bloom.hashes, 500));
+        assert!((0..500u128).all(|i| loaded.contains(42, (i % 4) as usize, i << 70)));
+
+        assert!(SaltBloom::load(&path).unwrap().is_none());
+        fs::write(&path, b"C2BLOOM1 truncated").unwrap();
+        let error = SaltBloom::load(&path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(error, Create2Error::Config(_)));
+    }
+}
@@ -0,0 +1,233 @@
+use crate::config::{Objective, Scheme, Settings};
+use crate::output::MatchRecord;
+use create2_core::Create2Error;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+// 运行中每隔多久保存一次 --resume 文件
+pub const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(10);
+
+// find模式的检查点：重启后从保存的尝试次数、用时和随机数位置继续搜索
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub implementation: String,
+    #[serde(default)]
+    pub implementations: Vec<String>,
+    pub deployer: String,
+    #[serde(default)]
+    pub deployers: Vec<String>,
+    #[serde(default)]
+    pub init_code_hash: Option<String>,
+    #[serde(default)]
+    pub scheme: Scheme,
+    #[serde(default)]
+    pub proxy_args: Option<String>,
+    #[serde(default)]
+    pub proxy_bytecode: Option<PathBuf>,
+    #[serde(default)]
+    pub account_owner: Option<String>,
+    pub pattern: String,
+    #[serde(default)]
+    pub patterns: Vec<String>,
+    #[serde(default)]
+    pub suffix: Option<String>,
+    #[serde(default)]
+    pub ignore_case: bool,
+    #[serde(default)]
+    pub regex: Option<String>,
+    #[serde(default)]
+    pub mask: Option<String>,
+    #[serde(default)]
+    pub objective: Option<Objective>,
+    #[serde(default)]
+    pub vanity: Option<String>,
+    #[serde(default)]
+    pub score: Option<String>,
+    #[serde(default)]
+    pub top: usize,
+    pub seed: u64,
+    pub attempts: usize,
+    pub elapsed_secs: f64,
+    // 每个随机数流已消耗的位置：CPU为ChaCha的word位置（并行版每个线程一个），GPU为已生成的批次种子位置
+    pub rng_positions: Vec<u128>,
+    pub matches: Vec<MatchRecord>,
+    // GPU版本：批次种子由批次位置决定，恢复时要求相同的批处理大小，并跳过该批次中已检查的地址
+    #[serde(default)]
+    pub batch_size: usize,
+    #[serde(default)]
+    pub batch_offset: usize,
+    // GPU版本：每线程地址数决定批次内每个序号的salt，恢复时同样要求一致；早期的检查点固定为4
+    #[serde(default = "crate::tune::default_addresses_per_thread")]
+    pub addresses_per_thread: usize,
+    #[serde(skip)]
+    saved_at: Option<Instant>,
+}
+
+impl Checkpoint {
+    pub fn new(settings: &Settings, seed: u64, streams: usize) -> Self {
+        Checkpoint {
+            implementation: settings.implementation.clone(),
+            implementations: settings.implementations.clone(),
+            deployer: settings.deployer.clone(),
+            deployers: settings.deployers.clone(),
+            init_code_hash: settings.init_code_hash.clone(),
+            scheme: settings.scheme,
+            proxy_args: settings.proxy_args.clone(),
+            proxy_bytecode: settings.proxy_bytecode.clone(),
+            account_owner: settings.account_owner.clone(),
+            pattern: settings.pattern.clone(),
+            patterns: settings.patterns.clone(),
+            suffix: settings.suffix.clone(),
+            ignore_case: settings.ignore_case,
+            regex: settings.regex.clone(),
+            mask: settings.mask.clone(),
+            objective: settings.objective,
+            vanity: settings.vanity.clone(),
+            score: settings.score.clone(),
+            top: settings.top,
+            seed,
+            attempts: 0,
+            elapsed_secs: 0.0,
+            rng_positions: vec![0; streams],
+            matches: Vec::new(),
+            batch_size: settings.batch_size,
+            batch_offset: 0,
+            addresses_per_thread: settings.addresses_per_thread,
+            saved_at: Some(Instant::now()),
+        }
+    }
+
+    // 指定了 --resume 时从文件恢复，否则从头开始
+    pub fn open(settings: &Settings, seed: u64, streams: usize) -> Result<Self, Create2Error> {
+        let path = match settings.resume {
+            Some(ref path) => path,
+            None => return Ok(Self::new(settings, seed, streams)),
+        };
+        let checkpoint = Self::resume(path, settings, seed, streams)?;
+        if checkpoint.attempts > 0 {
+            status!(
+                "♻️  从检查点 {} 恢复: 已尝试 {} 次, 已找到 {} 个地址, 已用时 {:.1}s",
+                path.display(),
+                checkpoint.attempts,
+                checkpoint.matches.len(),
+                checkpoint.elapsed_secs
+            );
+        }
+        Ok(checkpoint)
+    }
+
+    // 文件不存在时返回None，从头开始搜索
+    pub fn load(path: &Path) -> Result<Option<Self>, Create2Error> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(path)
+            .map_err(|e| Create2Error::io(format!("Failed to read checkpoint {}", path.display()), e))?;
+        let checkpoint = serde_json::from_str(&content)
+            .map_err(|e| Create2Error::serde(format!("Invalid checkpoint {}", path.display()), e))?;
+        Ok(Some(checkpoint))
+    }
+
+    // 读取检查点并确认与本次运行的参数一致；不存在时生成新的检查点
+    pub fn resume(path: &Path, settings: &Settings, seed: u64, streams: usize) -> Result<Self, Create2Error> {
+        let mut checkpoint = match Self::load(path)? {
+            Some(checkpoint) => checkpoint,
+            None => return Ok(Self::new(settings, seed, streams)),
+        };
+
+        if checkpoint.implementation != settings.implementation
+            || checkpoint.implementations != settings.implementations
+            || checkpoint.deployer != settings.deployer
+            || checkpoint.deployers != settings.deployers
+            || checkpoint.init_code_hash != settings.init_code_hash
+            || checkpoint.scheme != settings.scheme
+            || checkpoint.proxy_args != settings.proxy_args
+            || checkpoint.proxy_bytecode != settings.proxy_bytecode
+            || checkpoint.account_owner != settings.account_owner
+            || checkpoint.pattern != settings.pattern
+            || checkpoint.patterns != settings.patterns
+            || checkpoint.suffix != settings.suffix
+            || checkpoint.ignore_case != settings.ignore_case
+            || checkpoint.regex != settings.regex
+            || checkpoint.mask != settings.mask
+            || checkpoint.objective != settings.objective
+            || checkpoint.vanity != settings.vanity
+            || checkpoint.score != settings.score
+            || (checkpoint.score.is_some() && checkpoint.top != settings.top)
+        {
+            return Err(Create2Error::Config(format!(
+                "Checkpoint {} was saved for a different implementation, deployer, init code hash, proxy scheme or pattern",
+                path.display()
+            )));
+        }
+        if settings.seed.is_some_and(|seed| seed != checkpoint.seed) {
+            return Err(Create2Error::Config(format!(
+                "Checkpoint {} was saved with seed {}, which conflicts with --seed",
+                path.display(),
+                checkpoint.seed
+            )));
+        }
+        if checkpoint.rng_positions.len() != streams {
+            return Err(Create2Error::Config(format!(
+                "Checkpoint {} was saved with {} random streams, this run uses {} (use the same --threads)",
+                path.display(),
+                checkpoint.rng_positions.len(),
+                streams
+            )));
+        }
+
+        checkpoint.saved_at = Some(Instant::now());
+        Ok(checkpoint)
+    }
+
+    // GPU版本：批次种子按批次位置生成，恢复时必须使用相同的批处理大小
+    pub fn check_batch_size(&self, batch_size: usize) -> Result<(), Create2Error> {
+        if self.batch_size != batch_size {
+            return Err(Create2Error::Config(format!(
+                "Checkpoint was saved with --batch-size {}, this run uses {}",
+                self.batch_size, batch_size
+            )));
+        }
+        Ok(())
+    }
+
+    // GPU版本：salt按线程生成，换了每线程地址数后 batch_offset 之前的序号不再是已检查过的salt
+    pub fn check_addresses_per_thread(&self, addresses_per_thread: usize) -> Result<(), Create2Error> {
+        if self.addresses_per_thread != addresses_per_thread {
+            return Err(Create2Error::Config(format!(
+                "Checkpoint was saved with --addresses-per-thread {}, this run uses {}",
+                self.addresses_per_thread, addresses_per_thread
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_secs_f64(self.elapsed_secs)
+    }
+
+    // 距上次保存超过 CHECKPOINT_INTERVAL 时写入；未指定 --resume 时什么都不做
+    pub fn save_if_due(&mut self, path: Option<&Path>) -> Result<(), Create2Error> {
+        let due = self.saved_at.is_none_or(|saved_at| saved_at.elapsed() >= CHECKPOINT_INTERVAL);
+        match path {
+            Some(path) if due => {
+                self.save(path)?;
+                self.saved_at = Some(Instant::now());
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // 先写临时文件再rename，中途被杀掉也不会留下半个检查点
+    pub fn save(&self, path: &Path) -> Result<(), Create2Error> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| Create2Error::serde("Failed to serialize checkpoint", e))?;
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, json)
+            .and_then(|_| fs::rename(&tmp_path, path))
+            .map_err(|e| Create2Error::io(format!("Failed to write checkpoint {}", path.display()), e))
+    }
+}
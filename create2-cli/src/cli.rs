@@ -0,0 +1,458 @@
+use crate::config::{parse_duration, split_list, Config, Verbosity};
+use create2_core::Create2Error;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+// 五个版本共用同一套子命令和参数名；CPU版本拒绝GPU专用参数（见Args::reject_gpu_flags），其余不适用于当前后端的参数会被忽略
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    #[default]
+    Bench,
+    BenchFind,
+    Test,
+    Find,
+    Predict,
+    Nonces,
+    Compare,
+    Validate,
+    Tune,
+    Completions(Shell),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl FromStr for Shell {
+    type Err = Create2Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bash" => Ok(Shell::Bash),
+            "zsh" => Ok(Shell::Zsh),
+            "fish" => Ok(Shell::Fish),
+            _ => Err(Create2Error::Config(format!("Unsupported shell: {} (expected bash, zsh or fish)", s))),
+        }
+    }
+}
+
+impl FromStr for Command {
+    type Err = Create2Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bench" => Ok(Command::Bench),
+            "bench-find" => Ok(Command::BenchFind),
+            "test" => Ok(Command::Test),
+            "find" => Ok(Command::Find),
+            "predict" => Ok(Command::Predict),
+            "nonces" => Ok(Command::Nonces),
+            "compare" => Ok(Command::Compare),
+            "validate" => Ok(Command::Validate),
+            "tune" => Ok(Command::Tune),
+            _ => Err(Create2Error::Config(format!(
+                "Unknown command: {} (expected bench, bench-find, test, find, predict, nonces, compare, validate, tune or completions)",
+                s
+            ))),
+        }
+    }
+}
+
+pub struct Args {
+    pub command: Command,
+    pub help: bool,
+    pub config_path: Option<PathBuf>,
+    pub overrides: Config,
+}
+
+impl Args {
+    // CPU版本没有GPU后端，命令行上出现只对GPU版本生效的参数时报错，而不是静默忽略；
+    // bin为调用方的 env!("CARGO_BIN_NAME")
+    pub fn reject_gpu_flags(&self, bin: &str) -> Result<(), Create2Error> {
+        let overrides = &self.overrides;
+        let flags: Vec<&str> = [
+            ("--backend", overrides.backend.is_some()),
+            ("--hybrid", overrides.hybrid.is_some()),
+            ("--batch-size", overrides.batch_size.is_some()),
+            ("--batches-per-dispatch", overrides.batches_per_dispatch.is_some()),
+            ("--addresses-per-thread", overrides.addresses_per_thread.is_some()),
+            ("--command-queues", overrides.command_queues.is_some()),
+            ("--tune-cache", overrides.tune_cache.is_some()),
+        ]
+        .into_iter()
+        .filter(|&(_, set)| set)
+        .map(|(flag, _)| flag)
+        .collect();
+        if flags.is_empty() {
+            return Ok(());
+        }
+        Err(Create2Error::Config(format!(
+            "{} runs on the CPU and does not accept GPU-only flags: {} (use rust-gpu-evm, rust-gpu-tron or rust-gpu-cuda)",
+            bin,
+            flags.join(", ")
+        )))
+    }
+}
+
+fn flag_value(args: &mut impl Iterator<Item = String>, flag: &str) -> Result<String, Create2Error> {
+    args.next().ok_or_else(|| Create2Error::Config(format!("Missing value for {}", flag)))
+}
+
+fn flag_number<T: FromStr>(args: &mut impl Iterator<Item = String>, flag: &str) -> Result<T, Create2Error> {
+    let value = flag_value(args, flag)?;
+    value
+        .parse()
+        .map_err(|_| Create2Error::Config(format!("{} expects a number, got {}", flag, value)))
+}
+
+fn flag_count(args: &mut impl Iterator<Item = String>, flag: &str) -> Result<usize, Create2Error> {
+    let value = flag_value(args, flag)?;
+    match value.parse() {
+        Ok(count) if count > 0 => Ok(count),
+        _ => Err(Create2Error::Config(format!("{} expects a positive integer, got {}", flag, value))),
+    }
+}
+
+pub fn parse_args(argv: impl IntoIterator<Item = String>) -> Result<Args, Create2Error> {
+    let mut args = argv.into_iter().skip(1);
+    let mut parsed = Args {
+        command: Command::default(),
+        help: false,
+        config_path: None,
+        overrides: Config::default(),
+    };
+    let mut command_seen = false;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" | "help" => parsed.help = true,
+            "--config" => parsed.config_path = Some(PathBuf::from(flag_value(&mut args, &arg)?)),
+            "--implementation" => parsed.overrides.implementation = Some(flag_value(&mut args, &arg)?),
+            "--implementations" => parsed.overrides.implementations = Some(split_list(&flag_value(&mut args, &arg)?)),
+            "--deployer" => parsed.overrides.deployer = Some(flag_value(&mut args, &arg)?),
+            "--deployers" => parsed.overrides.deployers = Some(split_list(&flag_value(&mut args, &arg)?)),
+            "--chain" => parsed.overrides.chain = Some(flag_value(&mut args, &arg)?.parse()?),
+            "--init-code-hash" => parsed.overrides.init_code_hash = Some(flag_value(&mut args, &arg)?),
+            "--scheme" => parsed.overrides.scheme = Some(flag_value(&mut args, &arg)?.parse()?),
+            "--proxy-args" => parsed.overrides.proxy_args = Some(flag_value(&mut args, &arg)?),
+            "--proxy-bytecode" => parsed.overrides.proxy_bytecode = Some(PathBuf::from(flag_value(&mut args, &arg)?)),
+            "--account-owner" => parsed.overrides.account_owner = Some(flag_value(&mut args, &arg)?),
+            "--batch-size" => parsed.overrides.batch_size = Some(flag_count(&mut args, &arg)?),
+            "--backend" => parsed.overrides.backend = Some(flag_value(&mut args, &arg)?.parse()?),
+            "--threads" => parsed.overrides.threads = Some(flag_count(&mut args, &arg)?),
+            "--hybrid" => parsed.overrides.hybrid = Some(true),
+            "--batches-per-dispatch" => parsed.overrides.batches_per_dispatch = Some(flag_count(&mut args, &arg)?),
+            "--addresses-per-thread" => parsed.overrides.addresses_per_thread = Some(flag_count(&mut args, &arg)?),
+            "--command-queues" => parsed.overrides.command_queues = Some(flag_count(&mut args, &arg)?),
+            "--pattern" => parsed.overrides.pattern = Some(flag_value(&mut args, &arg)?),
+            "--position" => parsed.overrides.position = Some(flag_value(&mut args, &arg)?.parse()?),
+            "--patterns" => parsed.overrides.patterns = Some(split_list(&flag_value(&mut args, &arg)?)),
+            "--patterns-file" => parsed.overrides.patterns_file = Some(PathBuf::from(flag_value(&mut args, &arg)?)),
+            "--suffix" => parsed.overrides.suffix = Some(flag_value(&mut args, &arg)?),
+            "--ignore-case" => parsed.overrides.ignore_case = Some(true),
+            "--regex" => parsed.overrides.regex = Some(flag_value(&mut args, &arg)?),
+            "--mask" => parsed.overrides.mask = Some(flag_value(&mut args, &arg)?),
+            "--objective" => parsed.overrides.objective = Some(flag_value(&mut args, &arg)?.parse()?),
+            "--vanity" => parsed.overrides.vanity = Some(flag_value(&mut args, &arg)?),
+            "--score" => parsed.overrides.score = Some(flag_value(&mut args, &arg)?),
+            "--top" => parsed.overrides.top = Some(flag_count(&mut args, &arg)?),
+            "-o" | "--output" => parsed.overrides.output = Some(PathBuf::from(flag_value(&mut args, &arg)?)),
+            "--results" => parsed.overrides.results = Some(PathBuf::from(flag_value(&mut args, &arg)?)),
+            "--notify-url" => parsed.overrides.notify_url = Some(flag_value(&mut args, &arg)?),
+            "--json" => parsed.overrides.json = Some(true),
+            "--seed" => parsed.overrides.seed = Some(flag_number(&mut args, &arg)?),
+            "--max-attempts" => parsed.overrides.max_attempts = Some(flag_count(&mut args, &arg)?),
+            "--max-time" => parsed.overrides.max_time = Some(flag_count(&mut args, &arg)? as u64),
+            "--max-matches" => parsed.overrides.max_matches = Some(flag_count(&mut args, &arg)?),
+            "--first-match" => parsed.overrides.first_match = Some(true),
+            "--bench-difficulty" => parsed.overrides.bench_difficulty = Some(flag_count(&mut args, &arg)?),
+            "--nonce-start" => parsed.overrides.nonce_start = Some(flag_number(&mut args, &arg)?),
+            "--nonce-count" => parsed.overrides.nonce_count = Some(flag_count(&mut args, &arg)?),
+            "--salts-file" => parsed.overrides.salts_file = Some(PathBuf::from(flag_value(&mut args, &arg)?)),
+            "--salt-policy" => parsed.overrides.salt_policy = Some(flag_value(&mut args, &arg)?.parse()?),
+            "--resume" => parsed.overrides.resume = Some(PathBuf::from(flag_value(&mut args, &arg)?)),
+            "--bloom" => parsed.overrides.bloom = Some(PathBuf::from(flag_value(&mut args, &arg)?)),
+            "--bloom-capacity" => parsed.overrides.bloom_capacity = Some(flag_count(&mut args, &arg)?),
+            "--bloom-fp-rate" => parsed.overrides.bloom_fp_rate = Some(flag_number(&mut args, &arg)?),
+            "--tune-cache" => parsed.overrides.tune_cache = Some(PathBuf::from(flag_value(&mut args, &arg)?)),
+            "--profile" => parsed.overrides.profile = Some(flag_value(&mut args, &arg)?.parse()?),
+            "--progress-interval" => parsed.overrides.progress_interval = Some(flag_count(&mut args, &arg)?),
+            "--progress-every" => parsed.overrides.progress_every = Some(parse_duration(&flag_value(&mut args, &arg)?)?),
+            "-q" | "--quiet" => parsed.overrides.verbosity = Some(Verbosity::Quiet),
+            // -v 可以重复，-v -v 等同于 -vv
+            "-v" | "--verbose" => {
+                parsed.overrides.verbosity = match parsed.overrides.verbosity {
+                    Some(Verbosity::Verbose | Verbosity::Trace) => Some(Verbosity::Trace),
+                    _ => Some(Verbosity::Verbose),
+                }
+            }
+            "-vv" => parsed.overrides.verbosity = Some(Verbosity::Trace),
+            _ if arg.starts_with('-') => return Err(Create2Error::Config(format!("Unknown flag: {}", arg))),
+            "completions" if !command_seen => {
+                parsed.command = Command::Completions(flag_value(&mut args, &arg)?.parse()?);
+                command_seen = true;
+            }
+            _ if !command_seen => {
+                parsed.command = arg.parse()?;
+                command_seen = true;
+            }
+            _ => return Err(Create2Error::Config(format!("Unexpected argument: {}", arg))),
+        }
+    }
+
+    Ok(parsed)
+}
+
+// 帮助信息和补全脚本共用的参数表，新增参数时和parse_args一起更新
+struct Flag {
+    short: Option<&'static str>,
+    long: &'static str,
+    // 参数值占位符，<path> 在补全时匹配文件名
+    value: Option<&'static str>,
+    choices: &'static [&'static str],
+    help: &'static str,
+}
+
+const fn flag(long: &'static str, value: Option<&'static str>, help: &'static str) -> Flag {
+    Flag {
+        short: None,
+        long,
+        value,
+        choices: &[],
+        help,
+    }
+}
+
+const FLAGS: &[Flag] = &[
+    flag("--config", Some("<path>"), "配置文件，默认读取当前目录的 create2.toml"),
+    flag("--implementation", Some("<addr>"), "实现合约地址"),
+    flag("--implementations", Some("<list>"), "逗号分隔的多个实现合约，代替 --implementation；find模式每个salt对每个实现合约都计算一次，并报告命中的实现合约"),
+    flag("--deployer", Some("<addr>"), "部署者地址，或工厂名：eip2470 / deterministic-deployment-proxy / safe-singleton-factory / immutable-create2-factory / createx"),
+    flag("--deployers", Some("<list>"), "逗号分隔的多个部署者（可用工厂名），代替 --deployer；find模式每个salt对每个部署者都计算一次，并报告命中的部署者"),
+    Flag {
+        choices: &[
+            "ethereum", "sepolia", "bsc", "bsc-testnet", "polygon", "polygon-amoy", "tron", "tron-nile", "tron-shasta", "zksync",
+            "zksync-sepolia",
+        ],
+        ..flag("--chain", Some("<name>"), "链预设，未指定 --deployer 时使用该链的CREATE2工厂（EVM链为deterministic-deployment-proxy）")
+    },
+    flag("--init-code-hash", Some("<hash>"), "任意合约的init code哈希，按通用CREATE2公式计算，忽略实现合约"),
+    Flag {
+        choices: &["minimal", "minimal-push0", "cwia", "metaproxy", "erc1967", "simple-account", "custom"],
+        ..flag("--scheme", Some("<name>"), "代理方案：minimal（EIP-1167）/ minimal-push0（Solady PUSH0最小代理）/ cwia（clones with immutable args）/ metaproxy（EIP-3448）/ erc1967 / simple-account（ERC-4337 SimpleAccountFactory）/ custom（自定义init code模板）")
+    },
+    flag("--proxy-args", Some("<hex>"), "cwia的不可变参数、metaproxy的metadata或erc1967的ABI编码构造参数，0x前缀的hex"),
+    flag("--proxy-bytecode", Some("<path>"), "erc1967和simple-account代理合约的creation code文件（0x前缀的hex），custom为可含 {implementation} 占位符的init code模板"),
+    flag("--account-owner", Some("<addr>"), "simple-account账户的owner地址"),
+    flag("--batch-size", Some("<n>"), "GPU批处理大小"),
+    Flag {
+        choices: &["auto", "metal", "cuda", "opencl", "wgpu", "vulkan"],
+        ..flag("--backend", Some("<name>"), "GPU计算后端：auto（默认，按顺序尝试该版本支持的后端）/ metal / cuda / opencl / wgpu（WGSL，经Metal、Vulkan或DX12运行）/ vulkan（只用Vulkan的wgpu）；Metal版本只支持metal，CUDA版本支持其余几种")
+    },
+    flag("--threads", Some("<n>"), "CPU线程数"),
+    flag("--hybrid", None, "find模式在Metal GPU搜索的同时用 --threads 个CPU线程（默认全部核心）搜索同一规则；仅Metal EVM版本"),
+    flag("--batches-per-dispatch", Some("<n>"), "find模式每次GPU调度在内核里连续搜索的批次数（默认1，最多1024），只拷回候选地址，减少主机往返；只在GPU端过滤（单个前缀、后缀或掩码规则）时生效，仅Metal版本"),
+    flag("--addresses-per-thread", Some("<n>"), "每个GPU线程计算的地址数（线程粗化系数，默认4或tune测得的值，最多64）；仅Metal版本"),
+    flag("--command-queues", Some("<n>"), "每块GPU的命令队列数（默认1，最多16），同时提交的独立批次轮流使用各队列并发执行，不必在一个队列上排队；仅Metal版本"),
+    flag("--pattern", Some("<str>"), "find模式搜索的地址片段"),
+    Flag {
+        choices: &["prefix", "suffix", "contains"],
+        ..flag("--position", Some("<pos>"), "匹配位置：prefix / suffix / contains")
+    },
+    flag("--patterns", Some("<list>"), "逗号分隔的多个规则，代替 --pattern / --position 并分别计数；每项可带 prefix: / suffix: / contains: / mask: / regex: / vanity: 前缀"),
+    flag("--patterns-file", Some("<path>"), "规则文件，每行一个 --patterns 的规则（可混合前缀、后缀和掩码），忽略空行和 # 注释；与 --patterns 合并"),
+    flag("--suffix", Some("<str>"), "同时要求地址以该片段结尾，与 --position prefix 组合即首尾同时匹配"),
+    flag("--ignore-case", None, "--pattern / --suffix 不区分大小写；默认必须与EIP-55 checksum的大小写完全一致，仅EVM版本"),
+    flag("--regex", Some("<re>"), "用正则匹配完整的checksum地址（EVM含0x），优先于 --pattern / --position / --suffix"),
+    flag("--mask", Some("<mask>"), "按半字节匹配的地址掩码，? 为通配符，例如 0x????dead，不足40位时其余为通配符；仅EVM版本"),
+    Flag {
+        choices: &["leading-zeros"],
+        ..flag("--objective", Some("<name>"), "搜索目标：leading-zeros（不断刷新前导零字节数的纪录），优先于所有匹配规则；仅EVM版本")
+    },
+    flag("--vanity", Some("<rule>"), "内置靓号规则：repeated:6（末尾6位相同）或 palindrome:8（末尾8位为回文），代替所有匹配规则；仅EVM版本"),
+    flag("--score", Some("<fn>"), "打分函数：zeros（零的个数）、repeated（最长的相同字符连续段）或 weights:0=2,f=1（按每个字符加权），保留得分最高的 --top 个地址；仅EVM版本"),
+    flag("--top", Some("<n>"), "--score 保留的地址个数，默认10"),
+    Flag {
+        short: Some("-o"),
+        ..flag("--output", Some("<path>"), "追加写入benchmark汇总和找到的地址")
+    },
+    flag("--results", Some("<path>"), "find和nonces模式每找到一个地址就追加一行JSON（含后端和匹配规则）并fsync"),
+    flag("--notify-url", Some("<url>"), "每找到一个地址就向该webhook POST一个JSON（salt、地址、匹配规则、尝试次数等），发送失败只警告"),
+    flag("--json", None, "以JSON输出最终结果，其余信息转到stderr"),
+    flag("--seed", Some("<u64>"), "随机salt的PRNG种子"),
+    flag("--max-attempts", Some("<n>"), "find模式最大尝试次数"),
+    flag("--max-time", Some("<secs>"), "find模式最长搜索时间（秒）"),
+    flag("--max-matches", Some("<n>"), "find模式最多找到的地址数"),
+    flag("--first-match", None, "找到第一个地址即停止，stdout只输出一行结果并以0退出；达到尝试次数或时间上限仍未找到时以2退出"),
+    flag("--bench-difficulty", Some("<n>"), "bench-find模式合成后缀的长度（EVM为十六进制0，TRON为Base58的1），默认4"),
+    flag("--nonce-start", Some("<u64>"), "nonces模式的起始nonce，默认0"),
+    flag("--nonce-count", Some("<n>"), "nonces模式计算的nonce个数"),
+    flag("--salts-file", Some("<path>"), "predict模式的salt文件，每行一个，- 表示stdin"),
+    Flag {
+        choices: &["pad", "bytes32", "hash-if-long"],
+        ..flag("--salt-policy", Some("<name>"), "salt文件每行的解释方式：pad（原始字节补零，最多32字节）/ bytes32（0x + 64位hex原样使用）/ hash-if-long（超过32字节时取keccak256，同viem）")
+    },
+    flag("--resume", Some("<path>"), "find模式的检查点文件，存在时从中恢复，运行中定期保存"),
+    flag("--bloom", Some("<path>"), "find模式已搜索批次的布隆过滤器文件，用同一个种子重新运行时跳过已搜索过的批次，运行中定期保存"),
+    flag("--bloom-capacity", Some("<n>"), "新建布隆过滤器时预计记录的批次数，默认10000000"),
+    flag("--bloom-fp-rate", Some("<p>"), "新建布隆过滤器在容量内的误判率（误判的批次会被跳过），默认0.0001；越低文件越大"),
+    flag("--tune-cache", Some("<path>"), "tune模式写入的调优结果文件，Metal版本运行时自动加载；EVM默认 create2-tune-gpu.json，TRON默认 create2-tune-tron.json"),
+    Flag {
+        choices: &["battery", "balanced", "max"],
+        ..flag("--profile", Some("<name>"), "性能预设：battery / balanced / max，设置线程数、GPU批处理大小和进度刷新间隔")
+    },
+    flag("--progress-interval", Some("<n>"), "两次进度刷新之间至少间隔的尝试次数"),
+    flag("--progress-every", Some("<time>"), "两次进度刷新之间至少间隔的时间，例如 250ms / 2s"),
+    Flag {
+        short: Some("-q"),
+        ..flag("--quiet", None, "只输出最终结果，不显示进度")
+    },
+    Flag {
+        short: Some("-v"),
+        ..flag("--verbose", None, "输出额外的诊断信息，-vv 包含缓冲池统计")
+    },
+    Flag {
+        short: Some("-h"),
+        ..flag("--help", None, "显示帮助")
+    },
+];
+
+const COMMANDS: &[(&str, &str)] = &[
+    ("bench", "运行CREATE2地址预测benchmark（默认）"),
+    ("bench-find", "用固定难度的合成后缀跑完整的find流程，报告每秒找到的地址数"),
+    ("test", "用固定参数计算一次地址，校验实现是否正确"),
+    ("find", "搜索匹配 --pattern 的地址"),
+    ("predict", "逐个计算 --salts-file 中每个salt对应的地址"),
+    ("nonces", "按nonce顺序计算部署者的CREATE地址，输出匹配 --pattern 的地址和TPS"),
+    ("compare", "用同一组salt依次运行EIP-1167、通用CREATE2和CREATE3预测，分别报告TPS"),
+    ("validate", "只检查地址、salt、设备和检查点，打印运行计划，不执行计算"),
+    ("tune", "Metal版本扫描批处理大小、每组线程数和每线程地址数，把TPS最高的组合写入 --tune-cache"),
+    ("completions", "输出 bash / zsh / fish 的补全脚本"),
+];
+
+const SHELLS: &[&str] = &["bash", "zsh", "fish"];
+
+// bin为调用方的 env!("CARGO_BIN_NAME")
+pub fn print_usage(bin: &str) {
+    println!("用法: {} [命令] [选项]", bin);
+    println!();
+    println!("子命令:");
+    for (name, help) in COMMANDS {
+        let name = if *name == "completions" { "completions <shell>" } else { name };
+        println!("  {:<25}{}", name, help);
+    }
+    println!();
+    println!("选项:");
+    for flag in FLAGS {
+        let mut label = match flag.short {
+            Some(short) => format!("{}, {}", short, flag.long),
+            None => flag.long.to_string(),
+        };
+        if let Some(value) = flag.value {
+            label = format!("{} {}", label, value);
+        }
+        println!("  {:<25}{}", label, flag.help);
+    }
+}
+
+pub fn print_completions(shell: Shell, bin: &str) {
+    match shell {
+        Shell::Bash => print_bash_completions(bin),
+        Shell::Zsh => print_zsh_completions(bin),
+        Shell::Fish => print_fish_completions(bin),
+    }
+}
+
+fn print_bash_completions(bin: &str) {
+    let function = format!("_{}", bin.replace('-', "_"));
+    let value_flags = |filter: fn(&Flag) -> bool| {
+        FLAGS
+            .iter()
+            .filter(|flag| flag.value.is_some() && filter(flag))
+            .flat_map(|flag| flag.short.into_iter().chain([flag.long]))
+            .collect::<Vec<_>>()
+            .join("|")
+    };
+    let words = COMMANDS
+        .iter()
+        .map(|(name, _)| *name)
+        .chain(FLAGS.iter().flat_map(|flag| flag.short.into_iter().chain([flag.long])))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    println!("{}() {{", function);
+    println!("    local cur=\"${{COMP_WORDS[COMP_CWORD]}}\" prev=\"${{COMP_WORDS[COMP_CWORD-1]}}\"");
+    println!("    case \"$prev\" in");
+    println!("        {})", value_flags(|flag| flag.value == Some("<path>")));
+    println!("            COMPREPLY=($(compgen -f -- \"$cur\")); return ;;");
+    for flag in FLAGS.iter().filter(|flag| !flag.choices.is_empty()) {
+        println!("        {})", flag.long);
+        println!("            COMPREPLY=($(compgen -W \"{}\" -- \"$cur\")); return ;;", flag.choices.join(" "));
+    }
+    println!("        completions)");
+    println!("            COMPREPLY=($(compgen -W \"{}\" -- \"$cur\")); return ;;", SHELLS.join(" "));
+    println!("        {})", value_flags(|flag| flag.value != Some("<path>") && flag.choices.is_empty()));
+    println!("            return ;;");
+    println!("    esac");
+    println!("    COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))", words);
+    println!("}}");
+    println!("complete -F {} {}", function, bin);
+}
+
+fn print_zsh_completions(bin: &str) {
+    println!("#compdef {}", bin);
+    println!();
+    println!("_arguments \\");
+    for flag in FLAGS {
+        let names = match flag.short {
+            // -v 可以重复
+            Some(short) if flag.long == "--verbose" => format!("'*'{{{},{}}}'", short, flag.long),
+            Some(short) => format!("'({} {})'{{{},{}}}'", short, flag.long, short, flag.long),
+            None => format!("'{}", flag.long),
+        };
+        let action = match flag.value {
+            Some("<path>") => ":path:_files".to_string(),
+            Some(_) if !flag.choices.is_empty() => format!(":value:({})", flag.choices.join(" ")),
+            Some(value) => format!(":{}:", value.trim_matches(['<', '>'])),
+            None => String::new(),
+        };
+        println!("  {}[{}]{}' \\", names, flag.help, action);
+    }
+    let commands = COMMANDS
+        .iter()
+        .map(|(name, help)| format!("{}\\:\"{}\"", name, help))
+        .collect::<Vec<_>>()
+        .join(" ");
+    println!("  '1:command:(({}))' \\", commands);
+    println!("  '2:shell:({})'", SHELLS.join(" "));
+}
+
+fn print_fish_completions(bin: &str) {
+    println!("complete -c {} -f", bin);
+    for (name, help) in COMMANDS {
+        println!("complete -c {} -n __fish_use_subcommand -a {} -d '{}'", bin, name, help);
+    }
+    println!(
+        "complete -c {} -n '__fish_seen_subcommand_from completions' -a '{}'",
+        bin,
+        SHELLS.join(" ")
+    );
+    for flag in FLAGS {
+        let mut line = format!("complete -c {}", bin);
+        if let Some(short) = flag.short {
+            line.push_str(&format!(" -s {}", short.trim_start_matches('-')));
+        }
+        line.push_str(&format!(" -l {}", flag.long.trim_start_matches('-')));
+        match flag.value {
+            Some("<path>") => line.push_str(" -r -F"),
+            Some(_) if !flag.choices.is_empty() => line.push_str(&format!(" -x -a '{}'", flag.choices.join(" "))),
+            Some(_) => line.push_str(" -x"),
+            None => {}
+        }
+        line.push_str(&format!(" -d '{}'", flag.help));
+        println!("{}", line);
+    }
+}
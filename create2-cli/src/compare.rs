@@ -0,0 +1,72 @@
+use crate::config::Settings;
+use crate::output::{self, BenchSummary, Output};
+use create2_core::{init_code_hash, predict_create3, predict_deterministic_address_bytes, Address, Create2Template};
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::hint::black_box;
+use std::time::Instant;
+
+// 每种方案计算的次数，salt预先生成，三种方案使用同一组输入
+const COMPARE_OPERATIONS: usize = 1_000_000;
+
+// compare子命令：用同一组随机salt依次计算EIP-1167（每次重新哈希代理init code）、通用CREATE2（init code哈希预先算好）
+// 和CREATE3（CREATE2部署代理后再CREATE）的地址，分别报告TPS；所有版本都在CPU上单线程计算
+pub fn run_scheme_comparison(settings: &Settings) -> Result<(), Box<dyn std::error::Error>> {
+    let implementation: Address = settings.implementation.parse()?;
+    let deployer: Address = settings.deployer.parse()?;
+    // 未指定 --init-code-hash 时用最小代理的init code哈希，此时CREATE2与EIP-1167的地址相同
+    let hash = settings.init_code_hash()?.unwrap_or_else(|| init_code_hash(&implementation));
+    let create2 = Create2Template::from_init_code_hash(deployer, hash);
+    let output = Output::open(settings.output.as_deref())?;
+    let seed = settings.seed.unwrap_or_else(rand::random);
+
+    status!("⚖️  CREATE2方案吞吐量对比");
+    status!("每种方案计算量: {} 次", COMPARE_OPERATIONS);
+    status!("实现合约: {}", settings.implementation);
+    status!("部署者: {}", settings.deployer);
+    status!("随机种子: {}", seed);
+    status!("--------------------------------------------------------------------------------");
+
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let salts: Vec<[u8; 32]> = (0..COMPARE_OPERATIONS)
+        .map(|_| {
+            let mut salt = [0u8; 32];
+            rng.fill_bytes(&mut salt);
+            salt
+        })
+        .collect();
+
+    let results = [
+        ("eip1167", time_scheme("eip1167", &salts, |salt| {
+            predict_deterministic_address_bytes(implementation.as_bytes(), deployer.as_bytes(), salt)
+        })),
+        ("create2", time_scheme("create2", &salts, |salt| create2.predict_bytes(salt))),
+        ("create3", time_scheme("create3", &salts, |salt| {
+            *predict_create3(&deployer, *salt).expect("32-byte salts are always valid").as_bytes()
+        })),
+    ];
+
+    summary!("📊 各方案结果:");
+    summary!("==================================================");
+    let baseline_tps = COMPARE_OPERATIONS as f64 / results[0].1;
+    for (name, elapsed) in results {
+        let mut summary = BenchSummary::new(name, COMPARE_OPERATIONS, elapsed);
+        summary.threads = Some(1);
+        summary.seed = Some(seed);
+        summary!("{:<8} 平均TPS: {:>14.2} ops/sec | 每次操作耗时: {:>6.3} μs | 相对eip1167: {:.2}x",
+            name, summary.avg_tps, summary.us_per_op, summary.avg_tps / baseline_tps);
+        output::emit_json(&summary)?;
+        output.record(&summary.to_string())?;
+    }
+    Ok(())
+}
+
+// 返回计算所有salt的用时(秒)
+fn time_scheme(name: &str, salts: &[[u8; 32]], predict: impl Fn(&[u8; 32]) -> [u8; 20]) -> f64 {
+    status!("⏱️  正在计算 {} ...", name);
+    let start_time = Instant::now();
+    for salt in salts {
+        black_box(predict(black_box(salt)));
+    }
+    start_time.elapsed().as_secs_f64()
+}
@@ -0,0 +1,1083 @@
+use create2_core::salt::SaltPolicy;
+use crate::pattern::AddressKind;
+use crate::tune::TunedParams;
+use create2_core::{
+    chain_preset, factory_preset, parse_init_code_hash, parse_proxy_args, Address, AddressFormat, ChainPreset, Create2Error,
+    Create2Flavor, Create2Template, InitCodeTemplate, ProxyScheme, CHAIN_PRESETS,
+};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+// 所有benchmark共用同一份配置文件，默认从当前目录加载
+pub const DEFAULT_CONFIG_FILE: &str = "create2.toml";
+
+// nonces模式默认计算的nonce个数
+pub const DEFAULT_NONCE_COUNT: usize = 1_000_000;
+
+// bench-find模式默认的合成后缀长度，EVM上平均每65536次尝试匹配一次
+pub const DEFAULT_BENCH_DIFFICULTY: usize = 4;
+
+// 环境变量前缀，例如 CREATE2_IMPLEMENTATION、CREATE2_BATCH_SIZE
+const ENV_PREFIX: &str = "CREATE2_";
+
+// find模式中pattern在地址里的匹配位置
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PatternPosition {
+    Prefix,
+    #[default]
+    Suffix,
+    Contains,
+}
+
+impl FromStr for PatternPosition {
+    type Err = Create2Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "prefix" => Ok(PatternPosition::Prefix),
+            "suffix" => Ok(PatternPosition::Suffix),
+            "contains" => Ok(PatternPosition::Contains),
+            _ => Err(Create2Error::Config(format!("Invalid pattern position: {} (expected prefix, suffix or contains)", s))),
+        }
+    }
+}
+
+// find模式的搜索目标：leading-zeros不再匹配固定的片段，而是不断刷新前导零字节数的纪录，
+// 前导零越多的合约地址作为calldata越省gas
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Objective {
+    #[serde(rename = "leading-zeros")]
+    LeadingZeros,
+}
+
+impl FromStr for Objective {
+    type Err = Create2Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "leading-zeros" => Ok(Objective::LeadingZeros),
+            _ => Err(Create2Error::Config(format!("Invalid objective: {} (expected leading-zeros)", s))),
+        }
+    }
+}
+
+impl std::fmt::Display for Objective {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Objective::LeadingZeros => write!(f, "leading-zeros"),
+        }
+    }
+}
+
+impl std::fmt::Display for PatternPosition {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            PatternPosition::Prefix => write!(f, "prefix"),
+            PatternPosition::Suffix => write!(f, "suffix"),
+            PatternPosition::Contains => write!(f, "contains"),
+        }
+    }
+}
+
+// --scheme：代理合约的init code方案，cwia的不可变参数、metaproxy的metadata和erc1967的构造参数由 --proxy-args 给出，
+// erc1967和simple-account的creation code从 --proxy-bytecode 文件读取，simple-account的owner由 --account-owner 给出；
+// custom的 --proxy-bytecode 是带 {implementation} 占位符的init code模板；minimal-push0为Solady的PUSH0最小代理
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Scheme {
+    #[default]
+    Minimal,
+    #[serde(rename = "minimal-push0")]
+    MinimalPush0,
+    Cwia,
+    Metaproxy,
+    Erc1967,
+    #[serde(rename = "simple-account")]
+    SimpleAccount,
+    Custom,
+}
+
+impl FromStr for Scheme {
+    type Err = Create2Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "minimal" => Ok(Scheme::Minimal),
+            "minimal-push0" => Ok(Scheme::MinimalPush0),
+            "cwia" => Ok(Scheme::Cwia),
+            "metaproxy" => Ok(Scheme::Metaproxy),
+            "erc1967" => Ok(Scheme::Erc1967),
+            "simple-account" => Ok(Scheme::SimpleAccount),
+            "custom" => Ok(Scheme::Custom),
+            _ => Err(Create2Error::Config(format!("Invalid scheme: {} (expected minimal, minimal-push0, cwia, metaproxy, erc1967, simple-account or custom)", s))),
+        }
+    }
+}
+
+impl std::fmt::Display for Scheme {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Scheme::Minimal => write!(f, "minimal"),
+            Scheme::MinimalPush0 => write!(f, "minimal-push0"),
+            Scheme::Cwia => write!(f, "cwia"),
+            Scheme::Metaproxy => write!(f, "metaproxy"),
+            Scheme::Erc1967 => write!(f, "erc1967"),
+            Scheme::SimpleAccount => write!(f, "simple-account"),
+            Scheme::Custom => write!(f, "custom"),
+        }
+    }
+}
+
+// --chain：按名字选择的链预设，未指定 --deployer 时以该链的CREATE2工厂为部署者；
+// 链的地址格式和CREATE2公式必须与当前版本一致，见Settings::check_chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct Chain(pub &'static ChainPreset);
+
+impl FromStr for Chain {
+    type Err = Create2Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        chain_preset(s).map(Chain).ok_or_else(|| {
+            let names: Vec<&str> = CHAIN_PRESETS.iter().map(|chain| chain.name).collect();
+            Create2Error::Config(format!("Invalid chain: {} (expected {})", s, names.join(", ")))
+        })
+    }
+}
+
+impl TryFrom<String> for Chain {
+    type Error = Create2Error;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl std::fmt::Display for Chain {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.0.name)
+    }
+}
+
+// 输出详细程度：-q 只输出最终结果，-v / -vv 输出额外的诊断信息
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Verbosity {
+    Quiet,
+    #[default]
+    Normal,
+    Verbose,
+    Trace,
+}
+
+impl FromStr for Verbosity {
+    type Err = Create2Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "quiet" => Ok(Verbosity::Quiet),
+            "normal" => Ok(Verbosity::Normal),
+            "verbose" => Ok(Verbosity::Verbose),
+            "trace" => Ok(Verbosity::Trace),
+            _ => Err(Create2Error::Config(format!("Invalid verbosity: {} (expected quiet, normal, verbose or trace)", s))),
+        }
+    }
+}
+
+// --profile 预设：只填充没有显式设置的线程数、GPU批处理大小，并决定进度刷新间隔
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Profile {
+    Battery,
+    Balanced,
+    Max,
+}
+
+impl Profile {
+    fn threads(self) -> Option<usize> {
+        let cores = std::thread::available_parallelism().map_or(1, |n| n.get());
+        match self {
+            Profile::Battery => Some((cores / 4).max(1)),
+            Profile::Balanced => Some((cores / 2).max(1)),
+            // 使用全部核心
+            Profile::Max => None,
+        }
+    }
+
+    fn batch_size(self) -> Option<usize> {
+        match self {
+            Profile::Battery => Some(32768),
+            Profile::Balanced => Some(131072),
+            Profile::Max => None,
+        }
+    }
+
+    fn progress_every(self) -> Duration {
+        match self {
+            Profile::Battery => Duration::from_secs(1),
+            Profile::Balanced => Duration::from_millis(500),
+            Profile::Max => Duration::from_millis(100),
+        }
+    }
+}
+
+impl FromStr for Profile {
+    type Err = Create2Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "battery" => Ok(Profile::Battery),
+            "balanced" => Ok(Profile::Balanced),
+            "max" => Ok(Profile::Max),
+            _ => Err(Create2Error::Config(format!("Invalid profile: {} (expected battery, balanced or max)", s))),
+        }
+    }
+}
+
+// --backend：GPU版本使用的计算后端，每个GPU版本只编译了其中一部分，见Settings::check_backend；
+// auto按该版本的顺序依次尝试，metal版本只有Metal，cuda版本依次为CUDA、OpenCL、wgpu、Vulkan；
+// vulkan是只用Vulkan API的wgpu
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GpuBackend {
+    #[default]
+    Auto,
+    Metal,
+    Cuda,
+    Opencl,
+    Wgpu,
+    Vulkan,
+}
+
+impl FromStr for GpuBackend {
+    type Err = Create2Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(GpuBackend::Auto),
+            "metal" => Ok(GpuBackend::Metal),
+            "cuda" => Ok(GpuBackend::Cuda),
+            "opencl" => Ok(GpuBackend::Opencl),
+            "wgpu" => Ok(GpuBackend::Wgpu),
+            "vulkan" => Ok(GpuBackend::Vulkan),
+            _ => Err(Create2Error::Config(format!("Invalid backend: {} (expected auto, metal, cuda, opencl, wgpu or vulkan)", s))),
+        }
+    }
+}
+
+impl std::fmt::Display for GpuBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            GpuBackend::Auto => write!(f, "auto"),
+            GpuBackend::Metal => write!(f, "metal"),
+            GpuBackend::Cuda => write!(f, "cuda"),
+            GpuBackend::Opencl => write!(f, "opencl"),
+            GpuBackend::Wgpu => write!(f, "wgpu"),
+            GpuBackend::Vulkan => write!(f, "vulkan"),
+        }
+    }
+}
+
+// 配置层：配置文件、环境变量和命令行参数都解析成这个结构，未设置的字段为None
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub implementation: Option<String>,
+    pub implementations: Option<Vec<String>>,
+    pub deployer: Option<String>,
+    pub deployers: Option<Vec<String>>,
+    pub chain: Option<Chain>,
+    pub init_code_hash: Option<String>,
+    pub scheme: Option<Scheme>,
+    pub proxy_args: Option<String>,
+    pub proxy_bytecode: Option<PathBuf>,
+    pub account_owner: Option<String>,
+    pub batch_size: Option<usize>,
+    pub backend: Option<GpuBackend>,
+    pub threads: Option<usize>,
+    pub hybrid: Option<bool>,
+    pub batches_per_dispatch: Option<usize>,
+    pub addresses_per_thread: Option<usize>,
+    pub command_queues: Option<usize>,
+    pub pattern: Option<String>,
+    pub position: Option<PatternPosition>,
+    pub patterns: Option<Vec<String>>,
+    pub patterns_file: Option<PathBuf>,
+    pub suffix: Option<String>,
+    pub ignore_case: Option<bool>,
+    pub regex: Option<String>,
+    pub mask: Option<String>,
+    pub objective: Option<Objective>,
+    pub vanity: Option<String>,
+    pub score: Option<String>,
+    pub top: Option<usize>,
+    pub output: Option<PathBuf>,
+    pub results: Option<PathBuf>,
+    pub notify_url: Option<String>,
+    pub json: Option<bool>,
+    pub seed: Option<u64>,
+    pub max_attempts: Option<usize>,
+    pub max_time: Option<u64>,
+    pub max_matches: Option<usize>,
+    pub first_match: Option<bool>,
+    pub bench_difficulty: Option<usize>,
+    pub nonce_start: Option<u64>,
+    pub nonce_count: Option<usize>,
+    pub verbosity: Option<Verbosity>,
+    pub salts_file: Option<PathBuf>,
+    pub salt_policy: Option<SaltPolicy>,
+    pub resume: Option<PathBuf>,
+    pub bloom: Option<PathBuf>,
+    pub bloom_capacity: Option<usize>,
+    pub bloom_fp_rate: Option<f64>,
+    pub tune_cache: Option<PathBuf>,
+    pub profile: Option<Profile>,
+    pub progress_interval: Option<usize>,
+    #[serde(deserialize_with = "deserialize_duration")]
+    pub progress_every: Option<Duration>,
+}
+
+// 合并默认值之后的最终运行参数
+#[derive(Debug, Clone)]
+pub struct Settings {
+    pub implementation: String,
+    // 非空时find模式代替implementation，每个salt对每个实现合约各计算一个地址
+    pub implementations: Vec<String>,
+    pub deployer: String,
+    // 非空时find模式代替deployer，每个salt对每个部署者各计算一个地址
+    pub deployers: Vec<String>,
+    pub chain: Option<Chain>,
+    // 设置后按通用CREATE2公式计算，忽略implementation
+    pub init_code_hash: Option<String>,
+    pub scheme: Scheme,
+    // 0x前缀的hex，cwia的不可变参数、metaproxy的metadata或erc1967的ABI编码构造参数
+    pub proxy_args: Option<String>,
+    // erc1967代理合约的creation code，文件内容为0x前缀的hex
+    pub proxy_bytecode: Option<PathBuf>,
+    // simple-account账户的owner，即createAccount(owner, salt)的owner
+    pub account_owner: Option<String>,
+    pub batch_size: usize,
+    pub backend: GpuBackend,
+    pub threads: Option<usize>,
+    // Metal EVM版本的find模式同时用 --threads 个CPU线程搜索
+    pub hybrid: bool,
+    // Metal版本的find模式每次调度在GPU上连续搜索的批次数，只在GPU端过滤时生效
+    pub batches_per_dispatch: usize,
+    // Metal版本每个GPU线程计算的地址数（线程粗化系数）
+    pub addresses_per_thread: usize,
+    // Metal版本每块设备的命令队列数，独立的批次轮流提交到各队列上并发执行
+    pub command_queues: usize,
+    pub pattern: String,
+    pub position: PatternPosition,
+    // 非空时代替pattern和position，每一项是一个单独计数的规则
+    pub patterns: Vec<String>,
+    // 在 --pattern 之外同时要求的后缀
+    pub suffix: Option<String>,
+    // pattern和suffix默认与checksum地址的大小写完全一致，设置后只比较小写hex
+    pub ignore_case: bool,
+    // 设置后find和nonces模式改为用正则匹配完整地址，忽略pattern、position和suffix
+    pub regex: Option<String>,
+    // 带 ? 通配符的半字节掩码，优先级在regex之后、pattern之前
+    pub mask: Option<String>,
+    // 设置后优先于所有匹配规则
+    pub objective: Option<Objective>,
+    // 内置靓号规则 repeated:n / palindrome:n，代替所有匹配规则
+    pub vanity: Option<String>,
+    // 打分函数，设置后find和nonces模式保留得分最高的top个地址，代替所有匹配规则
+    pub score: Option<String>,
+    pub top: usize,
+    pub output: Option<PathBuf>,
+    // 找到的地址逐行写成JSON并fsync
+    pub results: Option<PathBuf>,
+    // 找到地址时POST JSON通知的webhook
+    pub notify_url: Option<String>,
+    pub json: bool,
+    pub seed: Option<u64>,
+    pub max_attempts: Option<usize>,
+    pub max_time: Option<u64>,
+    pub max_matches: Option<usize>,
+    // 找到第一个地址就停止，stdout只输出这一行结果，没找到时以退出码2结束
+    pub first_match: bool,
+    // bench-find模式合成后缀的长度
+    pub bench_difficulty: usize,
+    pub nonce_start: u64,
+    pub nonce_count: usize,
+    pub verbosity: Verbosity,
+    pub salts_file: Option<PathBuf>,
+    // predict模式如何把salt文件的每一行换成32字节salt
+    pub salt_policy: SaltPolicy,
+    pub resume: Option<PathBuf>,
+    // 已尝试批次的布隆过滤器文件，用同一个种子重新运行时跳过已搜索过的批次
+    pub bloom: Option<PathBuf>,
+    // 新建过滤器时预计记录的批次数和该数量下的误判率，决定文件大小
+    pub bloom_capacity: usize,
+    pub bloom_fp_rate: f64,
+    // tune子命令写入、之后的Metal版本运行自动加载的调优结果文件
+    pub tune_cache: Option<PathBuf>,
+    // 从tune_cache读取的调优结果，由load_settings填充
+    pub tuned: Option<TunedParams>,
+    pub profile: Option<Profile>,
+    pub progress_interval: usize,
+    pub progress_every: Duration,
+}
+
+impl Settings {
+    // find模式的停止条件，尝试次数、运行时间(秒)、找到的地址数任一达到上限即停止
+    pub fn find_limit_reached(&self, attempts: usize, matches: usize, elapsed: Duration) -> bool {
+        self.max_attempts.is_some_and(|max| attempts >= max)
+            || self.max_time.is_some_and(|max| elapsed.as_secs() >= max)
+            || self.max_matches.is_some_and(|max| matches >= max)
+    }
+
+    // bench-find：把匹配规则换成固定的合成后缀，按给定的尝试次数跑完整的find流程；
+    // 不读写检查点和布隆过滤器，不写 --results 也不发通知，找到的地址照常输出
+    pub fn bench_find(&self, pattern: String, attempts: usize, seed: u64) -> Settings {
+        Settings {
+            pattern,
+            position: PatternPosition::Suffix,
+            patterns: Vec::new(),
+            suffix: None,
+            ignore_case: false,
+            regex: None,
+            mask: None,
+            objective: None,
+            vanity: None,
+            score: None,
+            results: None,
+            notify_url: None,
+            seed: Some(seed),
+            max_attempts: Some(attempts),
+            max_time: None,
+            max_matches: None,
+            first_match: false,
+            resume: None,
+            bloom: None,
+            ..self.clone()
+        }
+    }
+
+    // zkSync Era的CREATE2公式不同，TRON链只能用TRON版本，EVM链只能用EVM版本
+    pub fn check_chain(&self, kind: AddressKind) -> Result<(), Create2Error> {
+        let Some(Chain(chain)) = self.chain else {
+            return Ok(());
+        };
+        if chain.flavor == Create2Flavor::ZkSync {
+            return Err(Create2Error::Config(format!(
+                "--chain {} derives CREATE2 addresses with zkSync's own formula (create2_core::predict_zksync_create2), which this build does not implement",
+                chain.name
+            )));
+        }
+        match (chain.format, kind) {
+            (AddressFormat::Evm, AddressKind::Evm) | (AddressFormat::Tron, AddressKind::Tron) => Ok(()),
+            (AddressFormat::Tron, AddressKind::Evm) => Err(Create2Error::Config(format!("--chain {} uses Tron addresses, use the Tron build", chain.name))),
+            (AddressFormat::Evm, AddressKind::Tron) => Err(Create2Error::Config(format!("--chain {} uses EVM addresses, use an EVM build", chain.name))),
+        }
+    }
+
+    // available为该版本编译进来的后端；auto总是可用，CPU版本不检查
+    pub fn check_backend(&self, available: &[GpuBackend]) -> Result<(), Create2Error> {
+        if self.backend == GpuBackend::Auto || available.contains(&self.backend) {
+            return Ok(());
+        }
+        let names: Vec<String> = available.iter().map(GpuBackend::to_string).collect();
+        Err(Create2Error::Config(format!("--backend {} is not available in this build (available: auto, {})", self.backend, names.join(", "))))
+    }
+
+    pub fn init_code_hash(&self) -> Result<Option<[u8; 32]>, Create2Error> {
+        self.init_code_hash.as_deref().map(parse_init_code_hash).transpose()
+    }
+
+    // 没有指定 --proxy-args 时参数为空；erc1967没有参数时按ERC1967Proxy(implementation, "")编码
+    pub fn proxy_scheme(&self) -> Result<ProxyScheme, Create2Error> {
+        let args = || parse_proxy_args(self.proxy_args.as_deref().unwrap_or("0x"));
+        Ok(match self.scheme {
+            Scheme::Minimal => ProxyScheme::MinimalProxy,
+            Scheme::MinimalPush0 => ProxyScheme::MinimalProxyPush0,
+            Scheme::Cwia => ProxyScheme::ClonesWithImmutableArgs(args()?),
+            Scheme::Metaproxy => ProxyScheme::MetaProxy(args()?),
+            Scheme::Erc1967 => ProxyScheme::Erc1967 {
+                creation_code: self.proxy_creation_code()?,
+                constructor_args: self.proxy_args.as_deref().map(parse_proxy_args).transpose()?,
+            },
+            Scheme::SimpleAccount => {
+                let owner = self.account_owner.as_deref().ok_or_else(|| {
+                    Create2Error::InvalidProxyArgs("--scheme simple-account requires --account-owner".to_string())
+                })?;
+                ProxyScheme::SimpleAccount {
+                    creation_code: self.proxy_creation_code()?,
+                    owner: owner.parse()?,
+                }
+            }
+            Scheme::Custom => {
+                let template = InitCodeTemplate::parse(self.read_proxy_bytecode()?.trim())?;
+                // 各个后端都只用一个固定的init code哈希
+                if template.has_salt() {
+                    return Err(Create2Error::InvalidProxyArgs(
+                        "{salt} placeholders in --proxy-bytecode need per-salt hashing, which the search backends do not support"
+                            .to_string(),
+                    ));
+                }
+                ProxyScheme::Custom(template)
+            }
+        })
+    }
+
+    fn proxy_creation_code(&self) -> Result<Vec<u8>, Create2Error> {
+        parse_proxy_args(self.read_proxy_bytecode()?.trim())
+    }
+
+    fn read_proxy_bytecode(&self) -> Result<String, Create2Error> {
+        let path = self.proxy_bytecode.as_deref().ok_or_else(|| {
+            Create2Error::InvalidProxyArgs(format!("--scheme {} requires --proxy-bytecode", self.scheme))
+        })?;
+        std::fs::read_to_string(path)
+            .map_err(|e| Create2Error::io(format!("Failed to read proxy bytecode {}", path.display()), e))
+    }
+
+    // find模式的实现合约：指定了 --implementations 时为该列表，否则只有 --implementation；
+    // --init-code-hash 不使用实现合约，多个实现合约只会得到相同的地址
+    pub fn find_implementations(&self) -> Vec<&str> {
+        if self.implementations.is_empty() || self.init_code_hash.is_some() {
+            vec![self.implementation.as_str()]
+        } else {
+            self.implementations.iter().map(String::as_str).collect()
+        }
+    }
+
+    // find模式的部署者：指定了 --deployers 时为该列表，否则只有 --deployer
+    pub fn find_deployers(&self) -> Vec<&str> {
+        if self.deployers.is_empty() {
+            vec![self.deployer.as_str()]
+        } else {
+            self.deployers.iter().map(String::as_str).collect()
+        }
+    }
+
+    // find模式每个salt计算的 (实现合约, 部署者) 组合，按实现合约、部署者的顺序排列
+    pub fn find_targets(&self) -> Vec<(&str, &str)> {
+        let deployers = self.find_deployers();
+        self.find_implementations()
+            .into_iter()
+            .flat_map(|implementation| deployers.iter().map(move |deployer| (implementation, *deployer)))
+            .collect()
+    }
+
+    // 多个实现合约或部署者时，找到的地址注明是哪一个；只有一个时为None，输出保持不变
+    pub fn describe_target(&self, (implementation, deployer): (&str, &str)) -> (Option<String>, Option<String>) {
+        (
+            (self.find_implementations().len() > 1).then(|| implementation.to_string()),
+            (self.find_deployers().len() > 1).then(|| deployer.to_string()),
+        )
+    }
+
+    // EVM版本的预测模板：指定了 --init-code-hash 时用通用CREATE2公式，否则按 --scheme 构造代理的init code
+    pub fn template(&self) -> Result<Create2Template, Create2Error> {
+        self.template_for(&self.implementation, &self.deployer)
+    }
+
+    // find模式每个 (实现合约, 部署者) 组合一个模板，顺序与find_targets相同
+    pub fn templates(&self) -> Result<Vec<Create2Template>, Create2Error> {
+        self.find_targets()
+            .into_iter()
+            .map(|(implementation, deployer)| self.template_for(implementation, deployer))
+            .collect()
+    }
+
+    fn template_for(&self, implementation: &str, deployer: &str) -> Result<Create2Template, Create2Error> {
+        let deployer: Address = deployer.parse()?;
+        match self.init_code_hash()? {
+            Some(hash) => Ok(Create2Template::from_init_code_hash(deployer, hash)),
+            None => Create2Template::from_scheme(&self.proxy_scheme()?, implementation.parse::<Address>()?, deployer),
+        }
+    }
+}
+
+impl Config {
+    pub fn load(path: &Path) -> Result<Self, Create2Error> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| Create2Error::io(format!("Failed to read config file {}", path.display()), e))?;
+        toml::from_str(&content)
+            .map_err(|e| Create2Error::serde(format!("Invalid config file {}", path.display()), e))
+    }
+
+    // 显式指定的路径必须存在；默认文件不存在时使用空配置
+    pub fn discover(path: Option<&Path>) -> Result<Self, Create2Error> {
+        match path {
+            Some(path) => Self::load(path),
+            None => {
+                let default_path = Path::new(DEFAULT_CONFIG_FILE);
+                if default_path.exists() {
+                    Self::load(default_path)
+                } else {
+                    Ok(Self::default())
+                }
+            }
+        }
+    }
+
+    // 读取 CREATE2_* 环境变量，未设置或为空的变量忽略
+    pub fn from_env() -> Result<Self, Create2Error> {
+        Ok(Config {
+            implementation: env_var("IMPLEMENTATION"),
+            implementations: env_var("IMPLEMENTATIONS").map(|value| split_list(&value)),
+            deployer: env_var("DEPLOYER"),
+            deployers: env_var("DEPLOYERS").map(|value| split_list(&value)),
+            chain: env_parse("CHAIN")?,
+            init_code_hash: env_var("INIT_CODE_HASH"),
+            scheme: env_parse("SCHEME")?,
+            proxy_args: env_var("PROXY_ARGS"),
+            proxy_bytecode: env_var("PROXY_BYTECODE").map(PathBuf::from),
+            account_owner: env_var("ACCOUNT_OWNER"),
+            batch_size: env_parse("BATCH_SIZE")?,
+            backend: env_parse("BACKEND")?,
+            threads: env_parse("THREADS")?,
+            hybrid: env_parse("HYBRID")?,
+            batches_per_dispatch: env_parse("BATCHES_PER_DISPATCH")?,
+            addresses_per_thread: env_parse("ADDRESSES_PER_THREAD")?,
+            command_queues: env_parse("COMMAND_QUEUES")?,
+            pattern: env_var("PATTERN"),
+            position: env_var("POSITION").map(|value| value.parse()).transpose()?,
+            patterns: env_var("PATTERNS").map(|value| split_list(&value)),
+            patterns_file: env_var("PATTERNS_FILE").map(PathBuf::from),
+            suffix: env_var("SUFFIX"),
+            ignore_case: env_parse("IGNORE_CASE")?,
+            regex: env_var("REGEX"),
+            mask: env_var("MASK"),
+            objective: env_parse("OBJECTIVE")?,
+            vanity: env_var("VANITY"),
+            score: env_var("SCORE"),
+            top: env_parse("TOP")?,
+            output: env_var("OUTPUT").map(PathBuf::from),
+            results: env_var("RESULTS").map(PathBuf::from),
+            notify_url: env_var("NOTIFY_URL"),
+            json: env_parse("JSON")?,
+            seed: env_parse("SEED")?,
+            max_attempts: env_parse("MAX_ATTEMPTS")?,
+            max_time: env_parse("MAX_TIME")?,
+            max_matches: env_parse("MAX_MATCHES")?,
+            first_match: env_parse("FIRST_MATCH")?,
+            bench_difficulty: env_parse("BENCH_DIFFICULTY")?,
+            nonce_start: env_parse("NONCE_START")?,
+            nonce_count: env_parse("NONCE_COUNT")?,
+            verbosity: env_parse("VERBOSITY")?,
+            salts_file: env_var("SALTS_FILE").map(PathBuf::from),
+            salt_policy: env_parse("SALT_POLICY")?,
+            resume: env_var("RESUME").map(PathBuf::from),
+            bloom: env_var("BLOOM").map(PathBuf::from),
+            bloom_capacity: env_parse("BLOOM_CAPACITY")?,
+            bloom_fp_rate: env_parse("BLOOM_FP_RATE")?,
+            tune_cache: env_var("TUNE_CACHE").map(PathBuf::from),
+            profile: env_parse("PROFILE")?,
+            progress_interval: env_parse("PROGRESS_INTERVAL")?,
+            progress_every: env_var("PROGRESS_EVERY").map(|value| parse_duration(&value)).transpose()?,
+        })
+    }
+
+    // other中已设置的字段覆盖当前值
+    pub fn merge(self, other: Config) -> Config {
+        Config {
+            implementation: other.implementation.or(self.implementation),
+            implementations: other.implementations.or(self.implementations),
+            deployer: other.deployer.or(self.deployer),
+            deployers: other.deployers.or(self.deployers),
+            chain: other.chain.or(self.chain),
+            init_code_hash: other.init_code_hash.or(self.init_code_hash),
+            scheme: other.scheme.or(self.scheme),
+            proxy_args: other.proxy_args.or(self.proxy_args),
+            proxy_bytecode: other.proxy_bytecode.or(self.proxy_bytecode),
+            account_owner: other.account_owner.or(self.account_owner),
+            batch_size: other.batch_size.or(self.batch_size),
+            backend: other.backend.or(self.backend),
+            threads: other.threads.or(self.threads),
+            hybrid: other.hybrid.or(self.hybrid),
+            batches_per_dispatch: other.batches_per_dispatch.or(self.batches_per_dispatch),
+            addresses_per_thread: other.addresses_per_thread.or(self.addresses_per_thread),
+            command_queues: other.command_queues.or(self.command_queues),
+            pattern: other.pattern.or(self.pattern),
+            position: other.position.or(self.position),
+            patterns: other.patterns.or(self.patterns),
+            patterns_file: other.patterns_file.or(self.patterns_file),
+            suffix: other.suffix.or(self.suffix),
+            ignore_case: other.ignore_case.or(self.ignore_case),
+            regex: other.regex.or(self.regex),
+            mask: other.mask.or(self.mask),
+            objective: other.objective.or(self.objective),
+            vanity: other.vanity.or(self.vanity),
+            score: other.score.or(self.score),
+            top: other.top.or(self.top),
+            output: other.output.or(self.output),
+            results: other.results.or(self.results),
+            notify_url: other.notify_url.or(self.notify_url),
+            json: other.json.or(self.json),
+            seed: other.seed.or(self.seed),
+            max_attempts: other.max_attempts.or(self.max_attempts),
+            max_time: other.max_time.or(self.max_time),
+            max_matches: other.max_matches.or(self.max_matches),
+            first_match: other.first_match.or(self.first_match),
+            bench_difficulty: other.bench_difficulty.or(self.bench_difficulty),
+            nonce_start: other.nonce_start.or(self.nonce_start),
+            nonce_count: other.nonce_count.or(self.nonce_count),
+            verbosity: other.verbosity.or(self.verbosity),
+            salts_file: other.salts_file.or(self.salts_file),
+            salt_policy: other.salt_policy.or(self.salt_policy),
+            resume: other.resume.or(self.resume),
+            bloom: other.bloom.or(self.bloom),
+            bloom_capacity: other.bloom_capacity.or(self.bloom_capacity),
+            bloom_fp_rate: other.bloom_fp_rate.or(self.bloom_fp_rate),
+            tune_cache: other.tune_cache.or(self.tune_cache),
+            profile: other.profile.or(self.profile),
+            progress_interval: other.progress_interval.or(self.progress_interval),
+            progress_every: other.progress_every.or(self.progress_every),
+        }
+    }
+
+    pub fn resolve(self, defaults: Settings) -> Settings {
+        let profile = self.profile.or(defaults.profile);
+        let first_match = self.first_match.unwrap_or(defaults.first_match);
+        let chain = self.chain.or(defaults.chain);
+        // 显式的 --deployer 优先，其次是 --chain 的工厂
+        let chain_factory = chain.and_then(|Chain(chain)| chain.factory).map(|factory| factory.to_string());
+        Settings {
+            implementation: self.implementation.unwrap_or(defaults.implementation),
+            implementations: self.implementations.unwrap_or(defaults.implementations),
+            deployer: self
+                .deployer
+                .map_or_else(|| chain_factory.unwrap_or(defaults.deployer), resolve_deployer),
+            deployers: self
+                .deployers
+                .map_or(defaults.deployers, |deployers| deployers.into_iter().map(resolve_deployer).collect()),
+            chain,
+            init_code_hash: self.init_code_hash.or(defaults.init_code_hash),
+            scheme: self.scheme.unwrap_or(defaults.scheme),
+            proxy_args: self.proxy_args.or(defaults.proxy_args),
+            proxy_bytecode: self.proxy_bytecode.or(defaults.proxy_bytecode),
+            account_owner: self.account_owner.or(defaults.account_owner),
+            batch_size: self
+                .batch_size
+                .or_else(|| profile.and_then(Profile::batch_size))
+                .unwrap_or(defaults.batch_size),
+            backend: self.backend.unwrap_or(defaults.backend),
+            threads: self
+                .threads
+                .or_else(|| profile.and_then(Profile::threads))
+                .or(defaults.threads),
+            hybrid: self.hybrid.unwrap_or(defaults.hybrid),
+            batches_per_dispatch: self.batches_per_dispatch.unwrap_or(defaults.batches_per_dispatch),
+            addresses_per_thread: self.addresses_per_thread.unwrap_or(defaults.addresses_per_thread),
+            command_queues: self.command_queues.unwrap_or(defaults.command_queues),
+            pattern: self.pattern.unwrap_or(defaults.pattern),
+            position: self.position.unwrap_or(defaults.position),
+            patterns: self.patterns.unwrap_or(defaults.patterns),
+            suffix: self.suffix.or(defaults.suffix),
+            ignore_case: self.ignore_case.unwrap_or(defaults.ignore_case),
+            regex: self.regex.or(defaults.regex),
+            mask: self.mask.or(defaults.mask),
+            objective: self.objective.or(defaults.objective),
+            vanity: self.vanity.or(defaults.vanity),
+            score: self.score.or(defaults.score),
+            top: self.top.unwrap_or(defaults.top),
+            output: self.output.or(defaults.output),
+            results: self.results.or(defaults.results),
+            notify_url: self.notify_url.or(defaults.notify_url),
+            json: self.json.unwrap_or(defaults.json),
+            seed: self.seed.or(defaults.seed),
+            max_attempts: self.max_attempts.or(defaults.max_attempts),
+            max_time: self.max_time.or(defaults.max_time),
+            // --first-match 相当于 --max-matches 1
+            max_matches: if first_match { Some(1) } else { self.max_matches.or(defaults.max_matches) },
+            first_match,
+            bench_difficulty: self.bench_difficulty.unwrap_or(defaults.bench_difficulty),
+            nonce_start: self.nonce_start.unwrap_or(defaults.nonce_start),
+            nonce_count: self.nonce_count.unwrap_or(defaults.nonce_count),
+            verbosity: self.verbosity.unwrap_or(defaults.verbosity),
+            salts_file: self.salts_file.or(defaults.salts_file),
+            salt_policy: self.salt_policy.unwrap_or(defaults.salt_policy),
+            resume: self.resume.or(defaults.resume),
+            bloom: self.bloom.or(defaults.bloom),
+            bloom_capacity: self.bloom_capacity.unwrap_or(defaults.bloom_capacity),
+            bloom_fp_rate: self.bloom_fp_rate.unwrap_or(defaults.bloom_fp_rate),
+            tune_cache: self.tune_cache.or(defaults.tune_cache),
+            tuned: defaults.tuned,
+            profile,
+            progress_interval: self.progress_interval.unwrap_or(defaults.progress_interval),
+            progress_every: self
+                .progress_every
+                .or_else(|| profile.map(Profile::progress_every))
+                .unwrap_or(defaults.progress_every),
+        }
+    }
+}
+
+// 解析 250ms / 2s / 1m 形式的时间间隔
+pub fn parse_duration(value: &str) -> Result<Duration, Create2Error> {
+    let invalid = || Create2Error::Config(format!("Invalid duration: {} (expected e.g. 250ms, 2s or 1m)", value));
+    let split = value.find(|c: char| !c.is_ascii_digit()).unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let number: u64 = number.parse().map_err(|_| invalid())?;
+    match unit {
+        "ms" => Ok(Duration::from_millis(number)),
+        "s" => Ok(Duration::from_secs(number)),
+        "m" => number.checked_mul(60).map(Duration::from_secs).ok_or_else(invalid),
+        _ => Err(invalid()),
+    }
+}
+
+// 配置文件中的时间间隔写成字符串，例如 progress_every = "250ms"
+fn deserialize_duration<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Duration>, D::Error> {
+    let value = String::deserialize(deserializer)?;
+    parse_duration(&value).map(Some).map_err(serde::de::Error::custom)
+}
+
+// 命令行和环境变量中的列表用逗号分隔，忽略空项
+pub fn split_list(value: &str) -> Vec<String> {
+    value.split(',').map(str::trim).filter(|item| !item.is_empty()).map(String::from).collect()
+}
+
+// 部署者也可以写成知名工厂的名字，例如 eip2470、createx，换成对应的地址
+pub fn resolve_deployer(deployer: String) -> String {
+    factory_preset(&deployer).map_or(deployer, |address| address.to_string())
+}
+
+fn env_var(name: &str) -> Option<String> {
+    std::env::var(format!("{}{}", ENV_PREFIX, name))
+        .ok()
+        .filter(|value| !value.is_empty())
+}
+
+fn env_parse<T: FromStr>(name: &str) -> Result<Option<T>, Create2Error> {
+    env_var(name)
+        .map(|value| {
+            value
+                .parse()
+                .map_err(|_| Create2Error::Config(format!("Invalid {}{}: {}", ENV_PREFIX, name, value)))
+        })
+        .transpose()
+}
+
+// 配置文件 < 环境变量 < 命令行参数
+pub fn load_settings(config_path: Option<PathBuf>, overrides: Config, defaults: Settings) -> Result<Settings, Create2Error> {
+    let config_path = config_path.or_else(|| env_var("CONFIG").map(PathBuf::from));
+    let config = Config::discover(config_path.as_deref())?
+        .merge(Config::from_env()?)
+        .merge(overrides);
+    let patterns_file = config.patterns_file.clone();
+    let batch_size_set = config.batch_size.is_some();
+    let addresses_per_thread_set = config.addresses_per_thread.is_some();
+    let mut settings = config.resolve(defaults);
+    // 文件中的规则接在 --patterns 之后，检查点按展开后的规则比较
+    if let Some(path) = patterns_file {
+        settings.patterns.extend(read_patterns_file(&path)?);
+    }
+    // tune测得的批处理大小只填充 --batch-size 和 --profile 都没有决定的批处理大小，每线程地址数同理
+    if let Some(ref path) = settings.tune_cache {
+        settings.tuned = TunedParams::load(path)?;
+    }
+    if let Some(ref tuned) = settings.tuned {
+        if !batch_size_set && settings.profile.and_then(Profile::batch_size).is_none() {
+            settings.batch_size = tuned.batch_size;
+        }
+        if !addresses_per_thread_set {
+            settings.addresses_per_thread = tuned.addresses_per_thread;
+        }
+    }
+    Ok(settings)
+}
+
+// --patterns-file：每行一个规则，写法与 --patterns 的每一项相同；忽略空行和 # 开头的注释
+fn read_patterns_file(path: &Path) -> Result<Vec<String>, Create2Error> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| Create2Error::io(format!("Failed to read patterns file {}", path.display()), e))?;
+    let patterns: Vec<String> = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(String::from)
+        .collect();
+    if patterns.is_empty() {
+        return Err(Create2Error::Config(format!("Patterns file {} contains no patterns", path.display())));
+    }
+    Ok(patterns)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    // 各模块测试共用的默认运行参数，相当于没有任何配置时的EVM版本
+    pub(crate) fn defaults() -> Settings {
+        Settings {
+            implementation: "0x1111111111111111111111111111111111111111".to_string(),
+            implementations: Vec::new(),
+            deployer: "0x2222222222222222222222222222222222222222".to_string(),
+            deployers: Vec::new(),
+            chain: None,
+            init_code_hash: None,
+            scheme: Scheme::Minimal,
+            proxy_args: None,
+            proxy_bytecode: None,
+            account_owner: None,
+            batch_size: 262144,
+            backend: GpuBackend::Auto,
+            threads: None,
+            hybrid: false,
+            batches_per_dispatch: 1,
+            addresses_per_thread: 4,
+            command_queues: 1,
+            pattern: "default".to_string(),
+            position: PatternPosition::Suffix,
+            patterns: Vec::new(),
+            suffix: None,
+            ignore_case: false,
+            regex: None,
+            mask: None,
+            objective: None,
+            vanity: None,
+            score: None,
+            top: 10,
+            output: None,
+            results: None,
+            notify_url: None,
+            json: false,
+            seed: None,
+            max_attempts: None,
+            max_time: None,
+            max_matches: None,
+            first_match: false,
+            bench_difficulty: DEFAULT_BENCH_DIFFICULTY,
+            nonce_start: 0,
+            nonce_count: DEFAULT_NONCE_COUNT,
+            verbosity: Verbosity::Normal,
+            salts_file: None,
+            salt_policy: SaltPolicy::Pad,
+            resume: None,
+            bloom: None,
+            bloom_capacity: 1_000_000,
+            bloom_fp_rate: 0.001,
+            tune_cache: None,
+            tuned: None,
+            profile: None,
+            progress_interval: 10000,
+            progress_every: Duration::from_millis(100),
+        }
+    }
+
+    #[test]
+    fn merge_prefers_fields_set_by_the_later_source() {
+        let file: Config = toml::from_str("pattern = \"file\"\ntop = 3\njson = true").unwrap();
+        let env = Config {
+            pattern: Some("env".to_string()),
+            top: Some(5),
+            ..Config::default()
+        };
+        let cli = Config {
+            top: Some(7),
+            ..Config::default()
+        };
+        let merged = file.merge(env).merge(cli);
+        assert_eq!(merged.pattern.as_deref(), Some("env"));
+        assert_eq!(merged.top, Some(7));
+        // 后面的来源没有设置的字段保留前面的值
+        assert_eq!(merged.json, Some(true));
+    }
+
+    // 只有这个测试读写 CREATE2_* 环境变量，避免并行运行的测试互相影响
+    #[test]
+    fn load_settings_reads_file_then_env_then_cli() {
+        let path = std::env::temp_dir().join(format!("create2-cli-config-{}.toml", std::process::id()));
+        std::fs::write(&path, "pattern = \"f11e\"\nsuffix = \"f11e\"\ntop = 3\nseed = 1\n").unwrap();
+        std::env::set_var("CREATE2_SUFFIX", "e4e4");
+        std::env::set_var("CREATE2_TOP", "5");
+        let cli = Config {
+            top: Some(7),
+            ..Config::default()
+        };
+        let settings = load_settings(Some(path.clone()), cli, defaults());
+        std::env::remove_var("CREATE2_SUFFIX");
+        std::env::remove_var("CREATE2_TOP");
+        std::fs::remove_file(&path).unwrap();
+
+        let settings = settings.unwrap();
+        assert_eq!(settings.pattern, "f11e");
+        assert_eq!(settings.suffix.as_deref(), Some("e4e4"));
+        assert_eq!(settings.top, 7);
+        assert_eq!(settings.seed, Some(1));
+        // 三个来源都没有设置时用二进制的默认值
+        assert_eq!(settings.position, PatternPosition::Suffix);
+    }
+
+    #[test]
+    fn unknown_config_keys_are_rejected() {
+        assert!(toml::from_str::<Config>("patern = \"dead\"").is_err());
+    }
+
+    #[test]
+    fn config_file_errors_keep_their_source() {
+        use std::error::Error;
+
+        let path = std::env::temp_dir().join(format!("create2-cli-bad-config-{}.toml", std::process::id()));
+        std::fs::write(&path, "patern = \"dead\"\n").unwrap();
+        let invalid = Config::load(&path);
+        std::fs::remove_file(&path).unwrap();
+        let error = invalid.unwrap_err();
+        assert!(matches!(error, Create2Error::Serde { .. }));
+        assert!(error.source().is_some_and(|source| source.to_string().contains("patern")));
+
+        let missing = Config::load(&path).unwrap_err();
+        assert!(matches!(missing, Create2Error::Io { .. }));
+        assert!(missing.source().is_some());
+    }
+
+    #[test]
+    fn profile_fills_only_unset_values() {
+        let battery = Config {
+            profile: Some(Profile::Battery),
+            ..Config::default()
+        }
+        .resolve(defaults());
+        assert_eq!(battery.batch_size, 32768);
+        assert_eq!(battery.progress_every, Duration::from_secs(1));
+        assert!(battery.threads.is_some_and(|threads| threads >= 1));
+
+        let explicit = Config {
+            profile: Some(Profile::Battery),
+            batch_size: Some(1024),
+            threads: Some(3),
+            progress_every: Some(Duration::from_millis(250)),
+            ..Config::default()
+        }
+        .resolve(defaults());
+        assert_eq!(explicit.batch_size, 1024);
+        assert_eq!(explicit.threads, Some(3));
+        assert_eq!(explicit.progress_every, Duration::from_millis(250));
+
+        // max使用全部核心和二进制默认的批处理大小
+        let max = Config {
+            profile: Some(Profile::Max),
+            ..Config::default()
+        }
+        .resolve(defaults());
+        assert_eq!(max.batch_size, 262144);
+        assert_eq!(max.threads, None);
+        assert_eq!(max.progress_every, Duration::from_millis(100));
+    }
+
+    #[test]
+    fn parse_duration_accepts_ms_s_and_m() {
+        assert_eq!(parse_duration("250ms").unwrap(), Duration::from_millis(250));
+        assert_eq!(parse_duration("2s").unwrap(), Duration::from_secs(2));
+        assert_eq!(parse_duration("1m").unwrap(), Duration::from_secs(60));
+        assert_eq!(parse_duration("0ms").unwrap(), Duration::ZERO);
+        assert_eq!(parse_duration("18446744073709551615ms").unwrap(), Duration::from_millis(u64::MAX));
+    }
+
+    #[test]
+    fn parse_duration_rejects_missing_units_and_overflow() {
+        for value in ["0", "250", "", "ms", "1h", "1 s", "-1s", "1.5s", "18446744073709551616s", "307445734561825861m"] {
+            assert!(parse_duration(value).is_err(), "{}", value);
+        }
+        assert!(parse_duration("307445734561825860m").is_ok());
+    }
+}
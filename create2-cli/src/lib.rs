@@ -0,0 +1,37 @@
+// 五个benchmark共用的命令行层：子命令和参数、配置文件与环境变量、匹配规则、输出和结果文件、
+// 检查点、布隆过滤器、调优缓存和通知；output的日志宏用 #[macro_use] extern crate create2_cli 引入
+#[macro_use]
+pub mod output;
+pub mod bloom;
+pub mod checkpoint;
+pub mod cli;
+pub mod compare;
+pub mod config;
+pub mod nonces;
+pub mod notify;
+pub mod pattern;
+pub mod progress;
+pub mod prompt;
+pub mod salts;
+pub mod score;
+pub mod tune;
+pub mod validate;
+
+use std::time::Duration;
+
+pub fn format_duration(duration: Duration) -> String {
+    let total_secs = duration.as_secs_f64();
+    
+    if total_secs < 60.0 {
+        format!("{:.1}s", total_secs)
+    } else if total_secs < 3600.0 {
+        let mins = (total_secs / 60.0) as u32;
+        let secs = total_secs % 60.0;
+        format!("{}m{:.1}s", mins, secs)
+    } else {
+        let hours = (total_secs / 3600.0) as u32;
+        let mins = ((total_secs % 3600.0) / 60.0) as u32;
+        let secs = total_secs % 60.0;
+        format!("{}h{}m{:.1}s", hours, mins, secs)
+    }
+}
@@ -0,0 +1,103 @@
+use crate::config::Settings;
+use crate::notify::Notifier;
+use crate::output::{self, BenchSummary, MatchRecord, Output, ResultsFile};
+use crate::pattern::{AddressKind, PatternSet};
+use crate::progress::ProgressReporter;
+use create2_core::{predict_create, Address};
+use std::time::Instant;
+
+// 每隔多少个nonce检查一次 --max-time / --max-matches
+const CHECK_INTERVAL: usize = 1000;
+
+// nonces子命令：按顺序计算部署者从 --nonce-start 开始 --nonce-count 个nonce的CREATE地址，
+// 输出匹配 --pattern 的地址，最后打印TPS以便和CREATE2对比；所有版本都在CPU上单线程计算
+pub fn run_nonce_sweep(settings: &Settings) -> Result<(), Box<dyn std::error::Error>> {
+    let pattern = PatternSet::from_settings(settings, AddressKind::Evm)?;
+    let sender: Address = settings.deployer.parse()?;
+    let output = Output::open(settings.output.as_deref())?;
+    let results = ResultsFile::open(settings.results.as_deref(), "create", pattern.spec())?;
+    let notifier = Notifier::new(settings.notify_url.as_deref(), "create", pattern.spec())?;
+    let end = settings
+        .nonce_start
+        .checked_add(settings.nonce_count as u64)
+        .ok_or("--nonce-start + --nonce-count exceeds the u64 nonce range")?;
+
+    status!("🔢 按nonce计算{}的CREATE地址...", pattern);
+    if let Some(attempts) = pattern.expected_attempts() {
+        status!("难度: 平均每 {:.0} 个nonce匹配一次", attempts);
+    }
+    pattern.print_rules();
+    status!("部署者: {}", settings.deployer);
+    status!("Nonce范围: {}..{}", settings.nonce_start, end);
+    status!("--------------------------------------------------------------------------------");
+
+    let start_time = Instant::now();
+    let reporter = ProgressReporter::new(settings);
+    let mut attempts = 0;
+    let mut matches = 0;
+    // --score 结束时从这里取出榜单
+    let mut found = Vec::new();
+
+    for nonce in settings.nonce_start..end {
+        let address = predict_create(&sender, nonce).to_string();
+        attempts += 1;
+
+        if pattern.matches(&address) {
+            matches += 1;
+            let elapsed = start_time.elapsed();
+            summary!("\n✨ 找到目标地址!");
+            summary!("  Nonce: {}", nonce);
+            summary!("  Address: {}", address);
+            if let Some(score) = pattern.score(&address) {
+                summary!("  {}", score);
+            }
+            if let Some(rules) = pattern.matched_rules(&address) {
+                summary!("  匹配规则: {}", rules);
+            }
+            summary!("  用时: {}", crate::format_duration(elapsed));
+            summary!("--------------------------------------------------------------------------------");
+
+            let record = MatchRecord {
+                salt: None,
+                nonce: Some(nonce),
+                address,
+                implementation: None,
+                deployer: None,
+                attempts,
+                elapsed_secs: elapsed.as_secs_f64(),
+            };
+            output::emit_match(&record)?;
+            output.record(&record.to_string())?;
+            results.append(&record)?;
+            notifier.notify(&record);
+            found.push(record);
+        }
+
+        if attempts % CHECK_INTERVAL == 0 {
+            if let Some(tick) = reporter.tick(attempts) {
+                progress!("\r已计算: {} | 平均TPS: {:.0} | 当前TPS: {:.0} | 用时: {}{}{}     ",
+                    tick.count, tick.avg_tps, tick.current_tps, crate::format_duration(tick.elapsed),
+                    tick.eta(pattern.expected_attempts()), pattern.best(&found));
+            }
+            if settings.find_limit_reached(attempts, matches, start_time.elapsed()) {
+                break;
+            }
+        }
+        if settings.max_matches.is_some_and(|max| matches >= max) {
+            break;
+        }
+    }
+
+    let elapsed = start_time.elapsed();
+    summary!("\n🏁 扫描结束: 已计算 {} 个nonce, 找到 {} 个地址, 用时 {}",
+        attempts, matches, crate::format_duration(elapsed));
+    pattern.print_hits();
+    pattern.print_top(&found, &output)?;
+
+    let mut summary = BenchSummary::new("create", attempts, elapsed.as_secs_f64());
+    summary.threads = Some(1);
+    summary!("平均TPS:      {:.2} ops/sec", summary.avg_tps);
+    output::emit_json(&summary)?;
+    output.record(&summary.to_string())?;
+    Ok(())
+}
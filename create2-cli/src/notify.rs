@@ -0,0 +1,53 @@
+use crate::output::{error_chain, MatchRecord, ResultLine};
+use create2_core::Create2Error;
+use std::sync::Arc;
+use std::time::Duration;
+
+// 单次通知的超时，webhook没有响应时不会长时间卡住搜索
+const NOTIFY_TIMEOUT: Duration = Duration::from_secs(10);
+
+// --notify-url：每找到一个地址就POST一个JSON（与 --results 的一行相同），远程机器上长时间搜索时不用盯着终端；
+// 发送失败只打印警告，不中断搜索
+pub struct Notifier {
+    target: Option<(ureq::Agent, String)>,
+    backend: &'static str,
+    pattern: String,
+}
+
+impl Notifier {
+    pub fn new(url: Option<&str>, backend: &'static str, pattern: String) -> Result<Self, Create2Error> {
+        let target = match url {
+            Some(url) if !url.starts_with("http://") && !url.starts_with("https://") => {
+                return Err(Create2Error::Config(format!("--notify-url must be an http:// or https:// URL, got {}", url)));
+            }
+            Some(url) => {
+                // 使用系统的TLS实现（macOS为Security.framework），不需要额外编译C代码
+                let tls = native_tls::TlsConnector::new().map_err(|e| Create2Error::network("Failed to initialize TLS for --notify-url", e))?;
+                let agent = ureq::AgentBuilder::new().timeout(NOTIFY_TIMEOUT).tls_connector(Arc::new(tls)).build();
+                Some((agent, url.to_string()))
+            }
+            None => None,
+        };
+        Ok(Notifier { target, backend, pattern })
+    }
+
+    pub fn notify(&self, record: &MatchRecord) {
+        let Some((ref agent, ref url)) = self.target else {
+            return;
+        };
+        let line = ResultLine { record, backend: self.backend, pattern: &self.pattern };
+        let sent = serde_json::to_string(&line)
+            .map_err(|e| Create2Error::serde("Failed to serialize JSON", e))
+            .and_then(|json| {
+                agent
+                    .post(url)
+                    .set("Content-Type", "application/json")
+                    .send_string(&json)
+                    .map(|_| ())
+                    .map_err(|e| Create2Error::network(format!("Failed to POST {}", url), e))
+            });
+        if let Err(e) = sent {
+            eprintln!("⚠️  通知发送失败: {}", error_chain(&e));
+        }
+    }
+}
@@ -0,0 +1,514 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use crate::checkpoint::Checkpoint;
+use crate::config::{Settings, Verbosity};
+use crate::format_duration;
+use crate::notify::Notifier;
+use crate::pattern::PatternSet;
+use create2_core::Create2Error;
+use std::sync::atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// --json 模式下stdout只输出JSON，其余提示信息转到stderr
+static JSON_MODE: AtomicBool = AtomicBool::new(false);
+
+pub fn set_json_mode(enabled: bool) {
+    JSON_MODE.store(enabled, Ordering::Relaxed);
+}
+
+pub fn json_mode() -> bool {
+    JSON_MODE.load(Ordering::Relaxed)
+}
+
+// --first-match 模式下stdout只输出找到的那一个地址，供shell脚本读取，其余提示信息同样转到stderr
+static FIRST_MATCH_MODE: AtomicBool = AtomicBool::new(false);
+
+pub fn set_first_match_mode(enabled: bool) {
+    FIRST_MATCH_MODE.store(enabled, Ordering::Relaxed);
+}
+
+// stdout是否只留给机器读取的结果
+pub fn stdout_reserved() -> bool {
+    json_mode() || FIRST_MATCH_MODE.load(Ordering::Relaxed)
+}
+
+static VERBOSITY: AtomicU8 = AtomicU8::new(Verbosity::Normal as u8);
+
+pub fn set_verbosity(verbosity: Verbosity) {
+    VERBOSITY.store(verbosity as u8, Ordering::Relaxed);
+}
+
+pub fn enabled(level: Verbosity) -> bool {
+    VERBOSITY.load(Ordering::Relaxed) >= level as u8
+}
+
+// 按详细程度过滤后输出一行，--json 和 --first-match 模式下转到stderr
+#[macro_export]
+macro_rules! log_line {
+    ($level:expr, $($arg:tt)*) => {
+        if $crate::output::enabled($level) {
+            if $crate::output::stdout_reserved() {
+                eprintln!($($arg)*);
+            } else {
+                println!($($arg)*);
+            }
+        }
+    };
+}
+
+// 最终结果（benchmark汇总、找到的地址），-q 模式下也会输出
+#[macro_export]
+macro_rules! summary {
+    ($($arg:tt)*) => {
+        $crate::log_line!($crate::config::Verbosity::Quiet, $($arg)*)
+    };
+}
+
+// 普通提示信息，用法同println!
+#[macro_export]
+macro_rules! status {
+    ($($arg:tt)*) => {
+        $crate::log_line!($crate::config::Verbosity::Normal, $($arg)*)
+    };
+}
+
+// -v 的诊断信息
+#[macro_export]
+macro_rules! verbose {
+    ($($arg:tt)*) => {
+        $crate::log_line!($crate::config::Verbosity::Verbose, $($arg)*)
+    };
+}
+
+// -vv 的诊断信息
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        $crate::log_line!($crate::config::Verbosity::Trace, $($arg)*)
+    };
+}
+
+// 进度行（不换行），用法同print!，输出后立即flush；-q 模式下不输出
+#[macro_export]
+macro_rules! progress {
+    ($($arg:tt)*) => {{
+        use std::io::Write;
+        if $crate::output::enabled($crate::config::Verbosity::Normal) {
+            if $crate::output::stdout_reserved() {
+                eprint!($($arg)*);
+                std::io::stderr().flush().unwrap();
+            } else {
+                print!($($arg)*);
+                std::io::stdout().flush().unwrap();
+            }
+        }
+    }};
+}
+
+// benchmark最终汇总
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchSummary {
+    pub backend: String,
+    pub operations: usize,
+    pub elapsed_secs: f64,
+    pub avg_tps: f64,
+    pub us_per_op: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub threads: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub batch_size: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    // bench-find：合成后缀的长度、找到的地址数和每秒找到的地址数
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub difficulty: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matches: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matches_per_sec: Option<f64>,
+    // Metal版本：每块设备的计算管线和调度形状
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pipelines: Vec<PipelineStats>,
+}
+
+impl BenchSummary {
+    pub fn new(backend: &str, operations: usize, elapsed_secs: f64) -> Self {
+        BenchSummary {
+            backend: backend.to_string(),
+            operations,
+            elapsed_secs,
+            avg_tps: operations as f64 / elapsed_secs,
+            us_per_op: elapsed_secs * 1_000_000.0 / operations as f64,
+            threads: None,
+            batch_size: None,
+            device: None,
+            seed: None,
+            difficulty: None,
+            matches: None,
+            matches_per_sec: None,
+            pipelines: Vec::new(),
+        }
+    }
+}
+
+impl fmt::Display for BenchSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "bench backend={} ops={} elapsed={:.3}s avg_tps={:.2} us_per_op={:.2}",
+            self.backend, self.operations, self.elapsed_secs, self.avg_tps, self.us_per_op
+        )?;
+        if let Some(threads) = self.threads {
+            write!(f, " threads={}", threads)?;
+        }
+        if let Some(batch_size) = self.batch_size {
+            write!(f, " batch_size={}", batch_size)?;
+        }
+        if let Some(ref device) = self.device {
+            write!(f, " device=\"{}\"", device)?;
+        }
+        if let Some(seed) = self.seed {
+            write!(f, " seed={}", seed)?;
+        }
+        if let Some(difficulty) = self.difficulty {
+            write!(f, " difficulty={}", difficulty)?;
+        }
+        if let Some(matches) = self.matches {
+            write!(f, " matches={}", matches)?;
+        }
+        if let Some(matches_per_sec) = self.matches_per_sec {
+            write!(f, " matches_per_sec={:.2}", matches_per_sec)?;
+        }
+        for pipeline in &self.pipelines {
+            write!(f, " pipeline=\"{}\"", pipeline)?;
+        }
+        Ok(())
+    }
+}
+
+// 计算管线的上限（每组最多线程数、SIMD宽度、静态线程组内存）和一个批次在该设备上的调度形状，
+// 让同一TPS在不同设备、不同调优结果之间可以对照
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineStats {
+    pub device: String,
+    pub max_threads_per_group: usize,
+    pub thread_execution_width: usize,
+    pub static_threadgroup_memory: usize,
+    pub thread_groups: usize,
+    pub threads_per_group: usize,
+    pub addresses_per_thread: usize,
+}
+
+impl fmt::Display for PipelineStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: {}x{}x{} max_threads={} simd_width={} threadgroup_memory={}",
+            self.device,
+            self.thread_groups,
+            self.threads_per_group,
+            self.addresses_per_thread,
+            self.max_threads_per_group,
+            self.thread_execution_width,
+            self.static_threadgroup_memory
+        )
+    }
+}
+
+// find模式找到的地址；GPU版的salt为内核生成的32个hex字符，按字符串salt使用
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchRecord {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub salt: Option<String>,
+    // nonces模式的CREATE地址没有salt，记录nonce
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<u64>,
+    pub address: String,
+    // --implementations / --deployers 有多个时记录命中的实现合约和部署者
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub implementation: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deployer: Option<String>,
+    pub attempts: usize,
+    pub elapsed_secs: f64,
+}
+
+impl fmt::Display for MatchRecord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "match")?;
+        if let Some(ref salt) = self.salt {
+            write!(f, " salt={}", salt)?;
+        }
+        if let Some(nonce) = self.nonce {
+            write!(f, " nonce={}", nonce)?;
+        }
+        write!(f, " address={}", self.address)?;
+        if let Some(ref implementation) = self.implementation {
+            write!(f, " implementation={}", implementation)?;
+        }
+        if let Some(ref deployer) = self.deployer {
+            write!(f, " deployer={}", deployer)?;
+        }
+        write!(f, " attempts={} elapsed={:.3}s", self.attempts, self.elapsed_secs)
+    }
+}
+
+// predict模式每个salt的计算结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Prediction {
+    pub salt: String,
+    pub address: String,
+}
+
+impl fmt::Display for Prediction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "prediction salt={} address={}", self.salt, self.address)
+    }
+}
+
+// 打印错误及其source链，代替main直接返回Err时的Debug输出
+pub fn report_error(error: &dyn std::error::Error) {
+    eprintln!("Error: {}", error);
+    let mut source = error.source();
+    while let Some(cause) = source {
+        eprintln!("  Caused by: {}", cause);
+        source = cause.source();
+    }
+}
+
+// 单行形式的错误及其source链，用于运行中的警告和validate的检查结果
+pub fn error_chain(error: &dyn std::error::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
+}
+
+// --json 模式下打印一行JSON到stdout
+pub fn emit_json<T: Serialize>(value: &T) -> Result<(), Create2Error> {
+    if json_mode() {
+        let json = serde_json::to_string(value).map_err(|e| Create2Error::serde("Failed to serialize JSON", e))?;
+        println!("{}", json);
+    }
+    Ok(())
+}
+
+// 找到的地址：--json 时输出JSON，否则只在 --first-match 模式下输出 match salt=... address=... 一行文本
+pub fn emit_match(record: &MatchRecord) -> Result<(), Create2Error> {
+    if json_mode() {
+        emit_json(record)
+    } else {
+        if FIRST_MATCH_MODE.load(Ordering::Relaxed) {
+            println!("{}", record);
+        }
+        Ok(())
+    }
+}
+
+// predict模式的单条结果：stdout输出 "salt address"（--json 时为JSON行），同时写入结果文件
+pub fn emit_prediction(output: &Output, prediction: &Prediction) -> Result<(), Create2Error> {
+    if json_mode() {
+        emit_json(prediction)?;
+    } else {
+        summary!("{} {}", prediction.salt, prediction.address);
+    }
+    output.record(&prediction.to_string())
+}
+
+// --output 指定的结果文件，未指定时所有写入都是空操作
+pub struct Output {
+    file: Option<Mutex<File>>,
+}
+
+impl Output {
+    pub fn open(path: Option<&Path>) -> Result<Self, Create2Error> {
+        let file = path.map(|path| open_append(path, "output")).transpose()?.map(Mutex::new);
+        Ok(Output { file })
+    }
+
+    // 每条记录单独一行，加锁后整行写入并立即flush，多线程写入也不会交错
+    pub fn record(&self, line: &str) -> Result<(), Create2Error> {
+        if let Some(ref file) = self.file {
+            let mut file = file.lock().unwrap();
+            file.write_all(format!("{}\n", line).as_bytes())
+                .and_then(|_| file.flush())
+                .map_err(|e| Create2Error::io("Failed to write output file", e))?;
+        }
+        Ok(())
+    }
+}
+
+// --results 文件：每找到一个地址追加一行JSON并fsync，进程中途被杀也不会丢失已找到的地址
+pub struct ResultsFile {
+    file: Option<Mutex<File>>,
+    backend: &'static str,
+    pattern: String,
+}
+
+// --results 文件的一行和 --notify-url 的请求体，在MatchRecord之外记录后端和匹配规则
+#[derive(Serialize)]
+pub struct ResultLine<'a> {
+    #[serde(flatten)]
+    pub record: &'a MatchRecord,
+    pub backend: &'a str,
+    pub pattern: &'a str,
+}
+
+impl ResultsFile {
+    pub fn open(path: Option<&Path>, backend: &'static str, pattern: String) -> Result<Self, Create2Error> {
+        let file = path.map(|path| open_append(path, "results")).transpose()?.map(Mutex::new);
+        Ok(ResultsFile { file, backend, pattern })
+    }
+
+    pub fn append(&self, record: &MatchRecord) -> Result<(), Create2Error> {
+        if let Some(ref file) = self.file {
+            let line = ResultLine { record, backend: self.backend, pattern: &self.pattern };
+            let json = serde_json::to_string(&line).map_err(|e| Create2Error::serde("Failed to serialize JSON", e))?;
+            let mut file = file.lock().unwrap();
+            file.write_all(format!("{}\n", json).as_bytes())
+                .and_then(|_| file.sync_data())
+                .map_err(|e| Create2Error::io("Failed to write results file", e))?;
+        }
+        Ok(())
+    }
+}
+
+// 追加模式打开，长时间运行或多次运行的结果不会互相覆盖；父目录不存在时自动创建
+fn open_append(path: &Path, what: &str) -> Result<File, Create2Error> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .map_err(|e| Create2Error::io(format!("Failed to create directory {}", parent.display()), e))?;
+    }
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| Create2Error::io(format!("Failed to open {} file {}", what, path.display()), e))
+}
+
+// find模式找到地址后的共同出口：各版本的搜索循环（GPU批次、CPU线程和 --hybrid 的CPU线程）都在这里计数、打印、
+// 输出、写 --results 和发通知；返回的记录由调用方和随机数位置一起合并进检查点，恢复时不会重复或跳过salt
+pub struct MatchSink<'a> {
+    settings: &'a Settings,
+    targets: &'a [(&'a str, &'a str)],
+    pattern: &'a PatternSet,
+    output: &'a Output,
+    results: &'a ResultsFile,
+    notifier: &'a Notifier,
+    matches: AtomicUsize,
+    start_time: Instant,
+    elapsed_offset: Duration,
+}
+
+impl<'a> MatchSink<'a> {
+    // 计数和用时从检查点继续，targets与 Settings::find_targets 的顺序相同
+    pub fn resumed(
+        settings: &'a Settings,
+        targets: &'a [(&'a str, &'a str)],
+        pattern: &'a PatternSet,
+        output: &'a Output,
+        results: &'a ResultsFile,
+        notifier: &'a Notifier,
+        checkpoint: &Checkpoint,
+    ) -> Self {
+        MatchSink {
+            settings,
+            targets,
+            pattern,
+            output,
+            results,
+            notifier,
+            matches: AtomicUsize::new(checkpoint.matches.len()),
+            start_time: Instant::now(),
+            elapsed_offset: checkpoint.elapsed(),
+        }
+    }
+
+    // 已找到的地址数，含检查点中恢复的
+    pub fn count(&self) -> usize {
+        self.matches.load(Ordering::Relaxed)
+    }
+
+    // 多个线程或GPU和CPU可能同时命中，超出 --max-matches 的地址返回None，不再输出
+    pub fn report(&self, target: usize, salt: String, address: &str, attempts: usize) -> Result<Option<MatchRecord>, Create2Error> {
+        let found = self.matches.fetch_add(1, Ordering::Relaxed) + 1;
+        if self.settings.max_matches.is_some_and(|max| found > max) {
+            return Ok(None);
+        }
+        let elapsed = self.elapsed_offset + self.start_time.elapsed();
+        // 只有一个实现合约和部署者时不重复输出
+        let (implementation, deployer) = self.settings.describe_target(self.targets[target]);
+        summary!("\n✨ 找到目标地址!");
+        summary!("  Salt: {}", salt);
+        summary!("  Address: {}", address);
+        if let Some(ref implementation) = implementation {
+            summary!("  Implementation: {}", implementation);
+        }
+        if let Some(ref deployer) = deployer {
+            summary!("  Deployer: {}", deployer);
+        }
+        if let Some(score) = self.pattern.score(address) {
+            summary!("  {}", score);
+        }
+        if let Some(rules) = self.pattern.matched_rules(address) {
+            summary!("  匹配规则: {}", rules);
+        }
+        summary!("  尝试次数: {}", attempts);
+        summary!("  用时: {}", format_duration(elapsed));
+        summary!("--------------------------------------------------------------------------------");
+
+        let record = MatchRecord {
+            salt: Some(salt),
+            nonce: None,
+            address: address.to_string(),
+            implementation,
+            deployer,
+            attempts,
+            elapsed_secs: elapsed.as_secs_f64(),
+        };
+        emit_match(&record)?;
+        self.output.record(&record.to_string())?;
+        self.results.append(&record)?;
+        self.notifier.notify(&record);
+        Ok(Some(record))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::tests::defaults;
+    use crate::pattern::AddressKind;
+
+    #[test]
+    fn match_sink_stops_reporting_past_max_matches() {
+        let mut settings = defaults();
+        settings.pattern = "dAC1".to_string();
+        settings.max_matches = Some(1);
+        let targets = settings.find_targets();
+        let pattern = PatternSet::from_settings(&settings, AddressKind::Evm).unwrap();
+        let output = Output::open(None).unwrap();
+        let results = ResultsFile::open(None, "test", pattern.spec()).unwrap();
+        let notifier = Notifier::new(None, "test", pattern.spec()).unwrap();
+        let checkpoint = Checkpoint::open(&settings, 7, 1).unwrap();
+        let sink = MatchSink::resumed(&settings, &targets, &pattern, &output, &results, &notifier, &checkpoint);
+
+        let usdt = "0xdAC17F958D2ee523a2206206994597C13D831ec7";
+        let record = sink.report(0, "00".repeat(16), usdt, 42).unwrap().unwrap();
+        assert_eq!((record.salt.as_deref(), record.address.as_str(), record.attempts), (Some("00".repeat(16).as_str()), usdt, 42));
+        assert_eq!((record.implementation, record.deployer), (None, None));
+        // 另一个线程同时找到的第二个地址超出 --max-matches，不再输出
+        assert!(sink.report(0, "11".repeat(16), usdt, 43).unwrap().is_none());
+        assert_eq!(sink.count(), 2);
+    }
+}
@@ -0,0 +1,777 @@
+use crate::config::{Objective, PatternPosition, Settings};
+use crate::output::{self, MatchRecord, Output};
+use crate::score::{ScoredRecord, Scorer, TopScores};
+use create2_core::{Address, Create2Error, NibbleMask, Vanity};
+use regex::Regex;
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+// 记住最近报告过的多少个地址，用于跳过重复的结果
+const RECENT_MATCHES: usize = 4096;
+
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+// 地址格式：EVM为0x开头的checksum十六进制地址，TRON为T开头的base58地址
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressKind {
+    Evm,
+    Tron,
+}
+
+// find模式的地址匹配规则，区分大小写比较
+pub struct Pattern {
+    kind: AddressKind,
+    rule: Rule,
+}
+
+enum Rule {
+    // --pattern / --position，EVM地址只比较0x之后的部分；
+    // suffix为 --suffix 额外要求的后缀，与前缀组合即可搜索首尾都有特征的地址；
+    // 默认必须与EIP-55 checksum的大小写完全一致，ignore_case时片段已转成小写，与小写地址比较
+    Fragment {
+        value: String,
+        position: PatternPosition,
+        suffix: Option<String>,
+        ignore_case: bool,
+    },
+    // --regex，与完整的地址字符串（EVM含0x）比较，只编译一次
+    Regex(Regex),
+    // --mask，在地址的20个字节上按半字节比较，不区分大小写
+    Mask(NibbleMask),
+    // --vanity，内置的重复或回文规则，同样在原始字节上比较
+    Vanity(Vanity),
+    // --objective leading-zeros，前导零字节数超过目前纪录即算匹配；并行版多个线程共用同一个纪录
+    LeadingZeros { best: AtomicUsize },
+    // --score，得分能排进目前前 --top 名的地址算作匹配，结束时从找到的地址中取出最终榜单
+    Top { scorer: Scorer, top: TopScores },
+}
+
+// GPU内核拷回结果之前用来筛选地址的逐字符条件：chars[i]为第i个字符要求的值，0为任意字符（EVM不含0x）；
+// 只是必要条件，拷回的候选地址仍要经过matches确认。只有GPU版使用
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CharFilter {
+    pub chars: Vec<u8>,
+    pub ignore_case: bool,
+}
+
+impl Pattern {
+    // 优先级为 --score / --objective、--vanity、--regex、--mask，否则为 --pattern / --position 加上可选的 --suffix
+    pub fn from_settings(settings: &Settings, kind: AddressKind) -> Result<Self, Create2Error> {
+        if settings.vanity.is_some() && (settings.score.is_some() || settings.objective.is_some()) {
+            return Err(Create2Error::Config("--vanity replaces the match rules like --score and --objective, use only one".to_string()));
+        }
+        if let Some(ref scorer) = settings.score {
+            if settings.objective.is_some() {
+                return Err(Create2Error::Config("--score and --objective both replace the match rules, use only one".to_string()));
+            }
+            if kind == AddressKind::Tron {
+                return Err(Create2Error::Config("--score rates hex nibbles and needs an EVM build".to_string()));
+            }
+            let top = TopScores::new(settings.top);
+            return Ok(Pattern { kind, rule: Rule::Top { scorer: scorer.parse()?, top } });
+        }
+        if let Some(Objective::LeadingZeros) = settings.objective {
+            if kind == AddressKind::Tron {
+                return Err(Create2Error::Config("--objective leading-zeros counts hex bytes and needs an EVM build".to_string()));
+            }
+            let best = AtomicUsize::new(0);
+            return Ok(Pattern { kind, rule: Rule::LeadingZeros { best } });
+        }
+        if let Some(ref vanity) = settings.vanity {
+            if kind == AddressKind::Tron {
+                return Err(Create2Error::Config("--vanity compares hex nibbles and needs an EVM build".to_string()));
+            }
+            let vanity = vanity.parse()?;
+            return Ok(Pattern { kind, rule: Rule::Vanity(vanity) });
+        }
+        if let Some(ref regex) = settings.regex {
+            let regex = Regex::new(regex).map_err(|e| Create2Error::InvalidPattern(format!("--regex {}: {}", regex, e)))?;
+            return Ok(Pattern { kind, rule: Rule::Regex(regex) });
+        }
+        if let Some(ref mask) = settings.mask {
+            if kind == AddressKind::Tron {
+                return Err(Create2Error::Config("--mask compares hex nibbles and needs an EVM build".to_string()));
+            }
+            let mask = mask.parse()?;
+            return Ok(Pattern { kind, rule: Rule::Mask(mask) });
+        }
+        if settings.ignore_case && kind == AddressKind::Tron {
+            return Err(Create2Error::Config("--ignore-case only applies to hex addresses, base58 is case-sensitive".to_string()));
+        }
+        let normalize = |value: String| if settings.ignore_case { value.to_ascii_lowercase() } else { value };
+        let value = normalize(fragment(&settings.pattern, settings.position, kind)?);
+        let suffix = settings
+            .suffix
+            .as_deref()
+            .map(|suffix| fragment(suffix, PatternPosition::Suffix, kind).map(normalize))
+            .transpose()?;
+        if let Some(ref suffix) = suffix {
+            if settings.position == PatternPosition::Prefix && value.len() + suffix.len() > address_len(kind) {
+                return Err(Create2Error::Config(format!(
+                    "--pattern and --suffix together exceed the {} characters of an address",
+                    address_len(kind)
+                )));
+            }
+        }
+        Ok(Pattern {
+            kind,
+            rule: Rule::Fragment {
+                value,
+                position: settings.position,
+                suffix,
+                ignore_case: settings.ignore_case,
+            },
+        })
+    }
+
+    // --patterns 的一项：prefix:、suffix:、contains:、mask:、regex:、vanity: 开头，或者不带前缀、使用 --position；
+    // --suffix 和 --ignore-case 同样适用于每一项
+    fn from_spec(spec: &str, settings: &Settings, kind: AddressKind) -> Result<Self, Create2Error> {
+        let mut settings = settings.clone();
+        settings.regex = None;
+        settings.mask = None;
+        settings.vanity = None;
+        match spec.split_once(':') {
+            Some(("regex", regex)) => settings.regex = Some(regex.to_string()),
+            Some(("mask", mask)) => settings.mask = Some(mask.to_string()),
+            Some(("vanity", vanity)) => settings.vanity = Some(vanity.to_string()),
+            Some((position, value)) => {
+                settings.position = position.parse()?;
+                settings.pattern = value.to_string();
+            }
+            None => settings.pattern = spec.to_string(),
+        }
+        Self::from_settings(&settings, kind)
+    }
+
+    #[inline(always)]
+    pub fn matches(&self, address: &str) -> bool {
+        self.admits(address) && self.accept(address)
+    }
+
+    // 不改变纪录和榜单的判断：前缀、正则等规则的结果就是最终结果；
+    // LeadingZeros和Top只比较当前的纪录和门槛，是否真的打破纪录、进入榜单由accept决定
+    #[inline(always)]
+    fn admits(&self, address: &str) -> bool {
+        let (value, position, suffix, ignore_case) = match self.rule {
+            Rule::Fragment { ref value, position, ref suffix, ignore_case } => (value, position, suffix, ignore_case),
+            Rule::Regex(ref regex) => return regex.is_match(address),
+            Rule::Mask(ref mask) => return address.parse::<Address>().is_ok_and(|address| mask.matches_address(&address)),
+            Rule::Vanity(ref vanity) => return address.parse::<Address>().is_ok_and(|address| vanity.matches_address(&address)),
+            Rule::LeadingZeros { ref best } => {
+                let zeros = leading_zero_bytes(address);
+                return zeros > 0 && best.load(Ordering::Relaxed) < zeros;
+            }
+            Rule::Top { ref scorer, ref top } => {
+                return address.parse::<Address>().is_ok_and(|address| top.admits(scorer.score(address.as_bytes())))
+            }
+        };
+        // 不区分大小写时在栈上转成小写，不分配内存
+        let mut lowercase = [0u8; 40];
+        let address = match self.kind {
+            AddressKind::Evm if ignore_case => {
+                lowercase.copy_from_slice(&address.as_bytes()[2..]);
+                lowercase.make_ascii_lowercase();
+                std::str::from_utf8(&lowercase).unwrap_or_default()
+            }
+            AddressKind::Evm => &address[2..],
+            AddressKind::Tron => address,
+        };
+        let matched = match position {
+            PatternPosition::Prefix => address.starts_with(value.as_str()),
+            PatternPosition::Suffix => address.ends_with(value.as_str()),
+            PatternPosition::Contains => address.contains(value.as_str()),
+        };
+        matched && suffix.as_ref().is_none_or(|suffix| address.ends_with(suffix.as_str()))
+    }
+
+    // admits通过之后记下新纪录或把得分放进榜单；其他线程可能先一步抬高了门槛，所以仍可能返回false
+    fn accept(&self, address: &str) -> bool {
+        match self.rule {
+            Rule::LeadingZeros { ref best } => {
+                let zeros = leading_zero_bytes(address);
+                best.fetch_max(zeros, Ordering::Relaxed) < zeros
+            }
+            Rule::Top { ref scorer, ref top } => {
+                address.parse::<Address>().is_ok_and(|address| top.offer(scorer.score(address.as_bytes())))
+            }
+            _ => true,
+        }
+    }
+
+    // 随机地址匹配的概率，前缀和 --suffix 按相互独立估算；contains按片段可能出现的位置数累加；
+    // 正则无法估算，返回None
+    pub fn probability(&self) -> Option<f64> {
+        let (value, position, suffix, ignore_case) = match self.rule {
+            Rule::Fragment { ref value, position, ref suffix, ignore_case } => (value, position, suffix, ignore_case),
+            Rule::Regex(_) | Rule::Top { .. } => return None,
+            Rule::Mask(ref mask) => return Some(16f64.powi(-(mask.fixed_nibbles() as i32))),
+            Rule::Vanity(ref vanity) => return Some(vanity.probability()),
+            // 打破当前纪录需要多一个零字节
+            Rule::LeadingZeros { ref best } => return Some(256f64.powi(-(best.load(Ordering::Relaxed) as i32 + 1))),
+        };
+        let sequence_probability = |value: &str| sequence_probability(self.kind, value, ignore_case);
+        let probability = match (self.kind, position) {
+            // TRON地址总是以T开头，第二个字符只在25个字符中取值
+            (AddressKind::Tron, PatternPosition::Prefix) => {
+                let second = if value.len() > 1 { 1.0 / 25.0 } else { 1.0 };
+                second * sequence_probability(value.get(2..).unwrap_or(""))
+            }
+            (_, PatternPosition::Contains) => {
+                let positions = address_len(self.kind) - value.len() + 1;
+                (sequence_probability(value) * positions as f64).min(1.0)
+            }
+            _ => sequence_probability(value),
+        };
+        let suffix = suffix.as_deref().map_or(1.0, sequence_probability);
+        Some(probability * suffix)
+    }
+
+    // 搜索目标下地址的得分，用于在找到的地址旁边显示；普通的匹配规则返回None
+    pub fn score(&self, address: &str) -> Option<String> {
+        match self.rule {
+            Rule::LeadingZeros { .. } => Some(format!("前导零字节: {}（目前最佳）", leading_zero_bytes(address))),
+            Rule::Top { ref scorer, .. } => Some(format!("得分: {}（{}）", self.rank_score(address)?, scorer)),
+            _ => None,
+        }
+    }
+
+    // 按得分排名的搜索目标下地址的得分，用于进度行的目前最佳和结束时的榜单；普通的匹配规则返回None
+    fn rank_score(&self, address: &str) -> Option<u32> {
+        match self.rule {
+            Rule::LeadingZeros { .. } => Some(leading_zero_bytes(address) as u32),
+            Rule::Top { ref scorer, .. } => address.parse::<Address>().ok().map(|address| scorer.score(address.as_bytes())),
+            _ => None,
+        }
+    }
+
+    // 写入 --results 的规则描述，与 --patterns 的写法相同，--suffix 和 --ignore-case 用 + 连接
+    pub fn spec(&self) -> String {
+        match self.rule {
+            Rule::Fragment { ref value, position, ref suffix, ignore_case } => {
+                let mut spec = format!("{}:{}", position, value);
+                if let Some(ref suffix) = suffix {
+                    spec.push_str(&format!("+suffix:{}", suffix));
+                }
+                if ignore_case {
+                    spec.push_str("+ignore-case");
+                }
+                spec
+            }
+            Rule::Regex(ref regex) => format!("regex:{}", regex),
+            Rule::Mask(ref mask) => format!("mask:{}", mask),
+            Rule::Vanity(ref vanity) => format!("vanity:{}", vanity),
+            Rule::LeadingZeros { .. } => format!("objective:{}", Objective::LeadingZeros),
+            Rule::Top { ref scorer, .. } => format!("score:{}", scorer),
+        }
+    }
+
+    // 前缀、后缀（加上 --suffix）和掩码都落在固定的位置上；contains、正则和搜索目标无法逐字符表示
+    fn char_filter(&self) -> Option<CharFilter> {
+        let mut chars = vec![0u8; address_len(self.kind)];
+        let mut place = |offset: usize, value: &str| chars[offset..offset + value.len()].copy_from_slice(value.as_bytes());
+        let ignore_case = match self.rule {
+            Rule::Fragment { ref value, position, ref suffix, ignore_case } => {
+                match position {
+                    PatternPosition::Prefix => place(0, value),
+                    PatternPosition::Suffix => place(address_len(self.kind) - value.len(), value),
+                    PatternPosition::Contains => return None,
+                }
+                if let Some(ref suffix) = suffix {
+                    place(address_len(self.kind) - suffix.len(), suffix);
+                }
+                ignore_case
+            }
+            // 掩码不区分大小写，固定的半字节写成小写十六进制字符
+            Rule::Mask(ref mask) => {
+                for (i, c) in mask.to_string()[2..].bytes().enumerate() {
+                    if c != b'?' {
+                        chars[i] = c;
+                    }
+                }
+                true
+            }
+            Rule::Regex(_) | Rule::Vanity(_) | Rule::LeadingZeros { .. } | Rule::Top { .. } => return None,
+        };
+        Some(CharFilter { chars, ignore_case })
+    }
+
+    // --resume 时用检查点里已找到的地址恢复纪录和榜单，避免重新报告更差的地址
+    pub fn restore(&self, matches: &[MatchRecord]) {
+        match self.rule {
+            Rule::LeadingZeros { ref best } => {
+                for record in matches {
+                    best.fetch_max(leading_zero_bytes(&record.address), Ordering::Relaxed);
+                }
+            }
+            Rule::Top { ref top, .. } => {
+                for score in matches.iter().filter_map(|record| self.rank_score(&record.address)) {
+                    top.offer(score);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+// --patterns 的规则列表：每个候选地址与所有规则比较，任一规则匹配即算找到，并分别统计每个规则的命中次数；
+// 没有 --patterns 时只有 --pattern 等选项构成的一个规则
+pub struct PatternSet {
+    patterns: Vec<(Pattern, AtomicUsize)>,
+    recent: RecentMatches,
+    // 每次尝试（一个salt）检查的地址数，多个实现合约或部署者时大于1
+    addresses_per_salt: usize,
+}
+
+// 最近报告过的地址：随机salt可能重复，同一个地址只报告一次；容量有限，超出时忘记最早的地址
+struct RecentMatches {
+    inner: Mutex<(HashSet<String>, VecDeque<String>)>,
+}
+
+impl RecentMatches {
+    fn new() -> Self {
+        RecentMatches { inner: Mutex::new((HashSet::new(), VecDeque::new())) }
+    }
+
+    // 第一次出现时记下并返回true
+    fn insert(&self, address: &str) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        let (seen, order) = &mut *inner;
+        if !seen.insert(address.to_string()) {
+            return false;
+        }
+        order.push_back(address.to_string());
+        if order.len() > RECENT_MATCHES {
+            if let Some(oldest) = order.pop_front() {
+                seen.remove(&oldest);
+            }
+        }
+        true
+    }
+}
+
+impl PatternSet {
+    // --score、--objective 和 --vanity 优先于 --patterns
+    pub fn from_settings(settings: &Settings, kind: AddressKind) -> Result<Self, Create2Error> {
+        let replaced = settings.objective.is_some() || settings.score.is_some() || settings.vanity.is_some();
+        let patterns = if settings.patterns.is_empty() || replaced {
+            vec![Pattern::from_settings(settings, kind)?]
+        } else {
+            settings
+                .patterns
+                .iter()
+                .map(|spec| Pattern::from_spec(spec, settings, kind))
+                .collect::<Result<_, _>>()?
+        };
+        Ok(PatternSet {
+            patterns: patterns.into_iter().map(|pattern| (pattern, AtomicUsize::new(0))).collect(),
+            recent: RecentMatches::new(),
+            addresses_per_salt: 1,
+        })
+    }
+
+    // find模式每个salt对应的地址数，平均尝试次数按salt计算
+    pub fn per_salt(mut self, addresses: usize) -> Self {
+        self.addresses_per_salt = addresses.max(1);
+        self
+    }
+
+    // 检查所有规则而不是遇到第一个就返回，保证每个规则的计数都准确；最近报告过的地址不再算作匹配，
+    // 只有命中时才查询，不影响未命中时的速度。去重在accept之前，重复的地址不会再次刷新纪录或占用榜单
+    #[inline(always)]
+    pub fn matches(&self, address: &str) -> bool {
+        let mut admitted = Vec::new();
+        for (i, (pattern, _)) in self.patterns.iter().enumerate() {
+            if pattern.admits(address) {
+                admitted.push(i);
+            }
+        }
+        if admitted.is_empty() {
+            return false;
+        }
+        if !self.recent.insert(address) {
+            verbose!("跳过重复的地址 {}", address);
+            return false;
+        }
+        let mut matched = false;
+        for i in admitted {
+            let (pattern, hits) = &self.patterns[i];
+            if pattern.accept(address) {
+                hits.fetch_add(1, Ordering::Relaxed);
+                matched = true;
+            }
+        }
+        matched
+    }
+
+    // 平均每找到一个地址需要的尝试次数；任一规则匹配的概率不超过各规则概率之和，规则很难时两者基本相等
+    pub fn expected_attempts(&self) -> Option<f64> {
+        let probability: Option<f64> = self.patterns.iter().map(|(pattern, _)| pattern.probability()).sum();
+        probability.map(|probability| 1.0 / (probability * self.addresses_per_salt as f64).min(1.0))
+    }
+
+    pub fn score(&self, address: &str) -> Option<String> {
+        self.patterns.first().and_then(|(pattern, _)| pattern.score(address))
+    }
+
+    // 多个规则用逗号分隔
+    pub fn spec(&self) -> String {
+        let specs: Vec<String> = self.patterns.iter().map(|(pattern, _)| pattern.spec()).collect();
+        specs.join(",")
+    }
+
+    // 只有一个规则且能逐字符表示时，GPU版在内核里先筛掉不可能匹配的地址
+    pub fn char_filter(&self) -> Option<CharFilter> {
+        match self.patterns.as_slice() {
+            [(pattern, _)] => pattern.char_filter(),
+            _ => None,
+        }
+    }
+
+    // 多个规则时列出找到的地址匹配了哪些规则；只用于报告，不计数
+    pub fn matched_rules(&self, address: &str) -> Option<String> {
+        if self.patterns.len() < 2 {
+            return None;
+        }
+        let rules: Vec<String> = self
+            .patterns
+            .iter()
+            .filter(|(pattern, _)| pattern.matches(address))
+            .map(|(pattern, _)| pattern.to_string())
+            .collect();
+        Some(rules.join("、"))
+    }
+
+    // --resume 时恢复纪录、每个规则的计数和最近报告过的地址
+    pub fn restore(&self, matches: &[MatchRecord]) {
+        for record in matches.iter().rev().take(RECENT_MATCHES).rev() {
+            self.recent.insert(&record.address);
+        }
+        for (pattern, hits) in &self.patterns {
+            pattern.restore(matches);
+            if self.patterns.len() > 1 {
+                let restored = matches.iter().filter(|record| pattern.matches(&record.address)).count();
+                hits.fetch_add(restored, Ordering::Relaxed);
+            }
+        }
+    }
+
+    // 搜索开始前列出所有规则，结束后输出每个规则的命中次数；只有一个规则时不输出
+    pub fn print_rules(&self) {
+        if self.patterns.len() > 1 {
+            for (i, (pattern, _)) in self.patterns.iter().enumerate() {
+                status!("  规则{}: {}", i + 1, pattern);
+            }
+        }
+    }
+
+    pub fn print_hits(&self) {
+        if self.patterns.len() > 1 {
+            summary!("📊 各规则命中次数:");
+            for (pattern, hits) in &self.patterns {
+                summary!("  {}: {}", pattern, hits.load(Ordering::Relaxed));
+            }
+        }
+    }
+
+    // --score 和 --objective 时进度行末尾显示目前最佳的地址，包括得分、salt（nonces模式为nonce）和找到时的用时；
+    // 其他规则或还没有找到地址时为空
+    pub fn best(&self, matches: &[MatchRecord]) -> String {
+        let Some((pattern @ Pattern { rule: Rule::LeadingZeros { .. } | Rule::Top { .. }, .. }, _)) = self.patterns.first() else {
+            return String::new();
+        };
+        let ranked = ScoredRecord::rank(matches, |record| pattern.rank_score(&record.address).unwrap_or(0), 1);
+        let Some(best) = ranked.first() else {
+            return String::new();
+        };
+        let source = match (&best.record.salt, best.record.nonce) {
+            (Some(salt), _) => format!(" | Salt: {}", salt),
+            (None, Some(nonce)) => format!(" | Nonce: {}", nonce),
+            (None, None) => String::new(),
+        };
+        format!(
+            " | 最佳: 得分 {} {}{} | {}时找到",
+            best.score,
+            best.record.address,
+            source,
+            crate::format_duration(Duration::from_secs_f64(best.record.elapsed_secs))
+        )
+    }
+
+    // --score 和 --objective 时在结束时打印找到的地址中得分最高的 --top 个（--objective 为所有刷新过纪录的地址），
+    // 并写入 --output 和JSON输出；找到的地址都已经写进检查点，--resume 继续搜索后榜单同样完整
+    pub fn print_top(&self, matches: &[MatchRecord], output: &Output) -> Result<(), Create2Error> {
+        let (pattern, scorer, top) = match self.patterns.first() {
+            Some((pattern @ Pattern { rule: Rule::Top { ref scorer, ref top }, .. }, _)) => (pattern, scorer.to_string(), top.capacity()),
+            Some((pattern @ Pattern { rule: Rule::LeadingZeros { .. }, .. }, _)) => (pattern, "前导零字节".to_string(), matches.len()),
+            _ => return Ok(()),
+        };
+        let ranked = ScoredRecord::rank(matches, |record| pattern.rank_score(&record.address).unwrap_or(0), top);
+        summary!("🏆 得分最高的 {} 个地址（{}）:", ranked.len(), scorer);
+        for entry in &ranked {
+            match entry.record.salt {
+                Some(ref salt) => summary!("  #{} 得分 {} | Salt: {} | Address: {}", entry.rank, entry.score, salt, entry.record.address),
+                None => summary!("  #{} 得分 {} | Address: {}", entry.rank, entry.score, entry.record.address),
+            }
+            output::emit_json(entry)?;
+            output.record(&entry.to_string())?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for PatternSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.patterns.as_slice() {
+            [(pattern, _)] => pattern.fmt(f),
+            patterns => write!(f, "匹配 {} 个规则中任意一个", patterns.len()),
+        }
+    }
+}
+
+// 0x之后连续的 "00" 个数
+fn leading_zero_bytes(address: &str) -> usize {
+    address.as_bytes()[2..].chunks(2).take_while(|byte| *byte == b"00").count()
+}
+
+// 检查 --pattern 或 --suffix 的长度和字符集，返回参与比较的部分
+fn fragment(value: &str, position: PatternPosition, kind: AddressKind) -> Result<String, Create2Error> {
+    let value = match kind {
+        // 前缀匹配允许写成 0xdead 的形式
+        AddressKind::Evm if position == PatternPosition::Prefix => value.strip_prefix("0x").unwrap_or(value),
+        _ => value,
+    };
+    let max_len = address_len(kind);
+    if value.is_empty() || value.len() > max_len {
+        return Err(Create2Error::InvalidPattern(format!("length must be between 1 and {}, got {}", max_len, value.len())));
+    }
+    match kind {
+        AddressKind::Evm if !value.bytes().all(|b| b.is_ascii_hexdigit()) => {
+            return Err(Create2Error::InvalidPattern(format!("{} must only contain hex characters", value)));
+        }
+        AddressKind::Tron if !value.chars().all(|c| BASE58_ALPHABET.contains(c)) => {
+            return Err(Create2Error::InvalidPattern(format!("{} must only contain base58 characters", value)));
+        }
+        AddressKind::Tron if position == PatternPosition::Prefix => check_tron_prefix(value)?,
+        _ => {}
+    }
+    Ok(value.to_string())
+}
+
+// 参与比较的字符数，EVM地址不含0x
+fn address_len(kind: AddressKind) -> usize {
+    match kind {
+        AddressKind::Evm => 40,
+        AddressKind::Tron => 34,
+    }
+}
+
+// checksum地址中每个数字出现的概率为1/16，区分大小写时字母还要再乘上大小写的1/2；base58字符按1/58估算
+fn sequence_probability(kind: AddressKind, value: &str, ignore_case: bool) -> f64 {
+    value
+        .chars()
+        .map(|c| match kind {
+            AddressKind::Evm if ignore_case || c.is_ascii_digit() => 1.0 / 16.0,
+            AddressKind::Evm => 1.0 / 32.0,
+            AddressKind::Tron => 1.0 / 58.0,
+        })
+        .product()
+}
+
+// 0x41版本字节使TRON地址的base58编码总是以T开头，第二个字符只能在 9..=Z 之间，
+// 不满足的前缀永远找不到，直接报错而不是空跑
+fn check_tron_prefix(value: &str) -> Result<(), Create2Error> {
+    let mut chars = value.chars();
+    if chars.next() != Some('T') {
+        return Err(Create2Error::InvalidPattern(format!("TRON addresses always start with T, prefix {} never matches", value)));
+    }
+    // 前面已检查过base58字符集，I和O不会出现
+    match chars.next() {
+        Some(second) if !matches!(second, '9' | 'A'..='Z') => Err(Create2Error::InvalidPattern(format!(
+            "the second character of a TRON address is between 9 and Z, prefix {} never matches",
+            value
+        ))),
+        _ => Ok(()),
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (value, position, suffix, ignore_case) = match self.rule {
+            Rule::Fragment { ref value, position, ref suffix, ignore_case } => (value, position, suffix, ignore_case),
+            Rule::Regex(ref regex) => return write!(f, "匹配正则 {}", regex),
+            Rule::Mask(ref mask) => return write!(f, "符合掩码 {}", mask),
+            Rule::Vanity(Vanity::Repeated(n)) => return write!(f, "末尾 {} 位相同", n),
+            Rule::Vanity(Vanity::Palindrome(n)) => return write!(f, "末尾 {} 位为回文", n),
+            Rule::LeadingZeros { .. } => return write!(f, "前导零字节不断刷新纪录"),
+            Rule::Top { ref scorer, ref top } => return write!(f, "得分（{}）排进前 {} 名", scorer, top.capacity()),
+        };
+        let prefix = match self.kind {
+            AddressKind::Evm => "0x",
+            AddressKind::Tron => "",
+        };
+        match position {
+            PatternPosition::Prefix => write!(f, "以 {}{} 开头", prefix, value)?,
+            PatternPosition::Suffix => write!(f, "以 {} 结尾", value)?,
+            PatternPosition::Contains => write!(f, "含有 {} 片段", value)?,
+        }
+        if let Some(ref suffix) = suffix {
+            write!(f, "且以 {} 结尾", suffix)?;
+        }
+        if ignore_case {
+            write!(f, "（不区分大小写）")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::tests::defaults;
+
+    const USDT: &str = "0xdAC17F958D2ee523a2206206994597C13D831ec7";
+    const USDC: &str = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+    const TRON_USDT: &str = "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t";
+
+    fn pattern(kind: AddressKind, configure: impl FnOnce(&mut Settings)) -> Result<Pattern, Create2Error> {
+        let mut settings = defaults();
+        configure(&mut settings);
+        Pattern::from_settings(&settings, kind)
+    }
+
+    fn fragment_pattern(value: &str, position: PatternPosition) -> Pattern {
+        pattern(AddressKind::Evm, |settings| {
+            settings.pattern = value.to_string();
+            settings.position = position;
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn fragments_match_the_exact_checksum_case() {
+        assert!(fragment_pattern("dAC1", PatternPosition::Prefix).matches(USDT));
+        assert!(fragment_pattern("0xdAC1", PatternPosition::Prefix).matches(USDT));
+        assert!(fragment_pattern("1ec7", PatternPosition::Suffix).matches(USDT));
+        assert!(fragment_pattern("Eb0cE", PatternPosition::Contains).matches(USDC));
+        // 同样的十六进制数字，大小写与EIP-55 checksum不一致
+        assert!(!fragment_pattern("dac1", PatternPosition::Prefix).matches(USDT));
+        assert!(!fragment_pattern("DAC1", PatternPosition::Prefix).matches(USDT));
+        assert!(!fragment_pattern("1EC7", PatternPosition::Suffix).matches(USDT));
+        assert!(!fragment_pattern("eb0ce", PatternPosition::Contains).matches(USDC));
+        // 前缀不会匹配到地址中间或末尾
+        assert!(!fragment_pattern("1ec7", PatternPosition::Prefix).matches(USDT));
+    }
+
+    #[test]
+    fn ignore_case_compares_lowercase_hex() {
+        let ignore_case = |value: &str, position| {
+            pattern(AddressKind::Evm, |settings| {
+                settings.pattern = value.to_string();
+                settings.position = position;
+                settings.ignore_case = true;
+            })
+            .unwrap()
+        };
+        assert!(ignore_case("DAC1", PatternPosition::Prefix).matches(USDT));
+        assert!(ignore_case("1EC7", PatternPosition::Suffix).matches(USDT));
+        assert!(ignore_case("eb0ce", PatternPosition::Contains).matches(USDC));
+        assert!(!ignore_case("dac2", PatternPosition::Prefix).matches(USDT));
+    }
+
+    #[test]
+    fn prefix_and_suffix_must_both_match() {
+        let sandwich = |prefix: &str, suffix: &str| {
+            pattern(AddressKind::Evm, |settings| {
+                settings.pattern = prefix.to_string();
+                settings.position = PatternPosition::Prefix;
+                settings.suffix = Some(suffix.to_string());
+            })
+            .unwrap()
+        };
+        assert!(sandwich("dAC1", "1ec7").matches(USDT));
+        assert!(!sandwich("dAC1", "1ec8").matches(USDT));
+        assert!(!sandwich("dAC2", "1ec7").matches(USDT));
+        assert!(!sandwich("dAC1", "1EC7").matches(USDT));
+        let too_long = pattern(AddressKind::Evm, |settings| {
+            settings.pattern = "a".repeat(30);
+            settings.position = PatternPosition::Prefix;
+            settings.suffix = Some("b".repeat(11));
+        });
+        assert!(too_long.is_err());
+    }
+
+    #[test]
+    fn regex_matches_the_full_address() {
+        let regex = |regex: &str| {
+            pattern(AddressKind::Evm, |settings| settings.regex = Some(regex.to_string())).unwrap()
+        };
+        assert!(regex("^0xdAC17F.*1ec7$").matches(USDT));
+        assert!(regex("(?i)^0xdac17f").matches(USDT));
+        assert!(!regex("^0xDAC17F").matches(USDT));
+        assert!(!regex("^dAC17F").matches(USDT));
+        assert!(pattern(AddressKind::Evm, |settings| settings.regex = Some("(".to_string())).is_err());
+    }
+
+    #[test]
+    fn pattern_set_counts_hits_per_rule() {
+        let mut settings = defaults();
+        settings.patterns = vec!["prefix:dAC1".to_string(), "suffix:6eB48".to_string(), "contains:2ee5".to_string()];
+        let set = PatternSet::from_settings(&settings, AddressKind::Evm).unwrap();
+        assert!(set.matches(USDT));
+        assert!(set.matches(USDC));
+        assert!(!set.matches("0x0000000000000000000000000000000000000000"));
+        let hits: Vec<usize> = set.patterns.iter().map(|(_, hits)| hits.load(Ordering::Relaxed)).collect();
+        assert_eq!(hits, [1, 1, 1]);
+        assert_eq!(set.matched_rules(USDT).unwrap(), "以 0xdAC1 开头、含有 2ee5 片段");
+        assert_eq!(set.spec(), "prefix:dAC1,suffix:6eB48,contains:2ee5");
+    }
+
+    #[test]
+    fn duplicate_addresses_do_not_take_a_place_in_the_top_scores() {
+        let mut settings = defaults();
+        settings.score = Some("zeros".to_string());
+        settings.top = 2;
+        let set = PatternSet::from_settings(&settings, AddressKind::Evm).unwrap();
+        // USDT有2个为0的半字节
+        assert!(set.matches(USDT));
+        assert!(!set.matches(USDT));
+        assert_eq!(set.patterns[0].1.load(Ordering::Relaxed), 1);
+        // 榜单里只有一个USDT，第二个名额还空着，只有1个0的地址也能进入
+        assert!(set.matches("0x1111111111111111111111111111111111111110"));
+        assert_eq!(set.patterns[0].1.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn tron_fragments_are_case_sensitive_base58() {
+        let tron = |value: &str, position| {
+            pattern(AddressKind::Tron, |settings| {
+                settings.pattern = value.to_string();
+                settings.position = position;
+            })
+        };
+        assert!(tron("TR7N", PatternPosition::Prefix).unwrap().matches(TRON_USDT));
+        assert!(tron("Lj6t", PatternPosition::Suffix).unwrap().matches(TRON_USDT));
+        assert!(!tron("TR7n", PatternPosition::Prefix).unwrap().matches(TRON_USDT));
+        assert!(!tron("LJ6t", PatternPosition::Suffix).unwrap().matches(TRON_USDT));
+        // 0不在base58字母表中
+        assert!(tron("T0", PatternPosition::Prefix).is_err());
+        assert!(tron("tR7N", PatternPosition::Prefix).is_err());
+        assert!(tron("T1", PatternPosition::Prefix).is_err());
+        assert!(pattern(AddressKind::Tron, |settings| settings.ignore_case = true).is_err());
+        assert!(pattern(AddressKind::Tron, |settings| settings.mask = Some("0x????".to_string())).is_err());
+    }
+
+    #[test]
+    fn tron_prefixes_start_with_t_and_a_second_character_from_9_to_z() {
+        for prefix in ["T", "T9", "TA", "TR7N", "TZ"] {
+            assert!(check_tron_prefix(prefix).is_ok(), "{}", prefix);
+        }
+        for prefix in ["R7N", "T1", "T8", "Ta", "Tz"] {
+            assert!(check_tron_prefix(prefix).is_err(), "{}", prefix);
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use crate::config::Settings;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// find模式开始前校准吞吐量的计时长度
+const CALIBRATION: Duration = Duration::from_millis(200);
+
+// 进度刷新节奏：距上次刷新至少 --progress-interval 次尝试、且至少 --progress-every 时间才刷新一次；
+// 并行版所有线程共用同一个reporter，同一时刻只有一个线程输出
+pub struct ProgressReporter {
+    interval: usize,
+    every: Duration,
+    start: Instant,
+    elapsed_offset: Duration,
+    last_count: AtomicUsize,
+    last_time: Mutex<Instant>,
+}
+
+// 一次进度刷新时的计数和速度
+pub struct ProgressTick {
+    pub count: usize,
+    pub elapsed: Duration,
+    pub avg_tps: f64,
+    pub current_tps: f64,
+}
+
+impl ProgressReporter {
+    pub fn new(settings: &Settings) -> Self {
+        Self::resumed(settings, 0, Duration::ZERO)
+    }
+
+    // 从检查点恢复时带上已有的尝试次数和用时，平均TPS按整个搜索计算
+    pub fn resumed(settings: &Settings, count: usize, elapsed_offset: Duration) -> Self {
+        let start = Instant::now();
+        ProgressReporter {
+            interval: settings.progress_interval,
+            every: settings.progress_every,
+            start,
+            elapsed_offset,
+            last_count: AtomicUsize::new(count),
+            last_time: Mutex::new(start),
+        }
+    }
+
+    // 到了刷新时间返回当前速度，否则返回None；其他线程正在刷新时直接跳过
+    #[inline(always)]
+    pub fn tick(&self, count: usize) -> Option<ProgressTick> {
+        if count < self.last_count.load(Ordering::Relaxed).saturating_add(self.interval) {
+            return None;
+        }
+        let mut last_time = self.last_time.try_lock().ok()?;
+        let now = Instant::now();
+        if now.duration_since(*last_time) < self.every {
+            return None;
+        }
+        // 拿到锁之前其他线程可能已经用更大的计数刷新过
+        let last_count = self.last_count.load(Ordering::Relaxed);
+        if count <= last_count {
+            return None;
+        }
+        Some(self.advance(&mut last_time, now, count, last_count))
+    }
+
+    // 最后一次刷新，不受间隔限制
+    pub fn finish(&self, count: usize) -> ProgressTick {
+        let mut last_time = self.last_time.lock().unwrap();
+        let last_count = self.last_count.load(Ordering::Relaxed).min(count);
+        self.advance(&mut last_time, Instant::now(), count, last_count)
+    }
+
+    fn advance(&self, last_time: &mut Instant, now: Instant, count: usize, last_count: usize) -> ProgressTick {
+        let elapsed = self.elapsed_offset + now.duration_since(self.start);
+        let avg_tps = count as f64 / elapsed.as_secs_f64();
+        let interval_elapsed = now.duration_since(*last_time).as_secs_f64();
+        let current_tps = if count > last_count && interval_elapsed > 0.0 {
+            (count - last_count) as f64 / interval_elapsed
+        } else {
+            avg_tps
+        };
+
+        *last_time = now;
+        self.last_count.store(count, Ordering::Relaxed);
+        ProgressTick {
+            count,
+            elapsed,
+            avg_tps,
+            current_tps,
+        }
+    }
+}
+
+impl ProgressTick {
+    // 进度行末尾按实测平均TPS更新的预计用时；无法估算难度时为空
+    pub fn eta(&self, expected_attempts: Option<f64>) -> String {
+        expected_attempts.map_or_else(String::new, |attempts| format!(" | 预计每个: {}", format_eta(attempts, self.avg_tps)))
+    }
+}
+
+// 搜索开始前的吞吐量校准：反复执行run（返回本次计算的地址数）直到满CALIBRATION，返回每秒尝试次数；
+// run至少执行一次，GPU一个批次超过CALIBRATION时按这一个批次计算
+pub fn calibrate(mut run: impl FnMut() -> usize) -> f64 {
+    let start = Instant::now();
+    let mut count = 0;
+    loop {
+        count += run();
+        let elapsed = start.elapsed();
+        if elapsed >= CALIBRATION {
+            return count as f64 / elapsed.as_secs_f64();
+        }
+    }
+}
+
+// 平均每找到一个地址的预计用时；每次尝试相互独立，已经搜索过的时间不会缩短之后的等待。
+// 长片段的预计用时远超Duration的范围，超过一天后按天、年输出
+pub fn format_eta(expected_attempts: f64, tps: f64) -> String {
+    const DAY: f64 = 86400.0;
+    const YEAR: f64 = 365.25 * DAY;
+    let secs = expected_attempts / tps;
+    if !secs.is_finite() {
+        "未知".to_string()
+    } else if secs >= 1000.0 * YEAR {
+        format!("{:.2e}年", secs / YEAR)
+    } else if secs >= YEAR {
+        format!("{:.1}年", secs / YEAR)
+    } else if secs >= DAY {
+        format!("{:.1}天", secs / DAY)
+    } else {
+        crate::format_duration(Duration::from_secs_f64(secs))
+    }
+}
@@ -0,0 +1,58 @@
+use crate::cli::Command;
+use crate::config::{resolve_deployer, Settings};
+use create2_core::Create2Error;
+use std::io::{self, IsTerminal, Write};
+use std::path::PathBuf;
+
+// 不带任何参数且stdin、stdout都是终端时才交互询问；脚本和管道调用保持原有行为
+pub fn interactive() -> bool {
+    std::env::args_os().len() == 1 && io::stdin().is_terminal() && io::stdout().is_terminal()
+}
+
+// 依次询问实现合约、部署者和运行模式，直接回车使用方括号里的当前值（配置文件、环境变量或内置默认值）
+pub fn ask(settings: &mut Settings, check_address: impl Fn(&str) -> Result<(), Create2Error>) -> Result<Command, Create2Error> {
+    println!("未指定任何参数，请输入运行参数（直接回车使用方括号中的值）");
+    settings.implementation = ask_until_valid("实现合约地址", &settings.implementation, &check_address)?;
+    settings.deployer = resolve_deployer(ask_until_valid("部署者地址或工厂名", &settings.deployer, |deployer| {
+        check_address(&resolve_deployer(deployer.to_string()))
+    })?);
+    let command = ask_until_valid("运行模式 (bench / test / find / predict / validate)", "bench", |mode| {
+        mode.parse::<Command>().map(|_| ())
+    })?
+    .parse()?;
+
+    // predict没有salt文件无法运行，顺便问一下
+    if command == Command::Predict && settings.salts_file.is_none() {
+        settings.salts_file = Some(PathBuf::from(ask_value("salt文件（- 表示stdin）", "-")?));
+    }
+    println!();
+    Ok(command)
+}
+
+// 输入不合法时打印原因并重新询问
+fn ask_until_valid(label: &str, default: &str, check: impl Fn(&str) -> Result<(), Create2Error>) -> Result<String, Create2Error> {
+    loop {
+        let value = ask_value(label, default)?;
+        match check(&value) {
+            Ok(()) => return Ok(value),
+            Err(e) => println!("❌ {}", e),
+        }
+    }
+}
+
+fn ask_value(label: &str, default: &str) -> Result<String, Create2Error> {
+    print!("{} [{}]: ", label, default);
+    io::stdout().flush().map_err(|e| Create2Error::io("Failed to write prompt", e))?;
+
+    let mut line = String::new();
+    let read = io::stdin()
+        .read_line(&mut line)
+        .map_err(|e| Create2Error::io("Failed to read input", e))?;
+    // Ctrl+D
+    if read == 0 {
+        return Err(Create2Error::Cancelled);
+    }
+
+    let value = line.trim();
+    Ok(if value.is_empty() { default } else { value }.to_string())
+}
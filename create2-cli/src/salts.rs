@@ -0,0 +1,66 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+use create2_core::salt::SaltPolicy;
+use create2_core::Create2Error;
+
+pub use create2_core::salt::MAX_SALT_LEN;
+
+// 逐行读取 --salts-file，按批次返回，文件再大也不会一次性读入内存
+pub struct SaltReader {
+    reader: Box<dyn BufRead>,
+    policy: SaltPolicy,
+    line_number: usize,
+}
+
+impl SaltReader {
+    // 路径为 - 时从stdin读取；每一行按policy换成32字节salt
+    pub fn open(path: &Path, policy: SaltPolicy) -> Result<Self, Create2Error> {
+        let reader: Box<dyn BufRead> = if path == Path::new("-") {
+            Box::new(BufReader::new(io::stdin()))
+        } else {
+            let file = File::open(path)
+                .map_err(|e| Create2Error::io(format!("Failed to open salts file {}", path.display()), e))?;
+            Box::new(BufReader::new(file))
+        };
+
+        Ok(SaltReader {
+            reader,
+            policy,
+            line_number: 0,
+        })
+    }
+
+    // 最多读取max个 (原始行, salt)，跳过空行；返回空Vec表示已读完
+    pub fn read_batch(&mut self, max: usize) -> Result<Vec<(String, [u8; 32])>, Create2Error> {
+        let mut salts = Vec::with_capacity(max);
+        let mut line = String::new();
+
+        while salts.len() < max {
+            line.clear();
+            let read = self
+                .reader
+                .read_line(&mut line)
+                .map_err(|e| Create2Error::io(format!("Failed to read salts file at line {}", self.line_number + 1), e))?;
+            if read == 0 {
+                break;
+            }
+            self.line_number += 1;
+
+            let salt = line.trim_end_matches(['\n', '\r']);
+            if salt.is_empty() {
+                continue;
+            }
+            let bytes = self.policy.to_salt(salt).map_err(|e| match e {
+                Create2Error::InvalidSalt(reason) => {
+                    Create2Error::InvalidSalt(format!("salt at line {}: {}", self.line_number, reason))
+                }
+                e => e,
+            })?;
+            salts.push((salt.to_string(), bytes));
+        }
+
+        Ok(salts)
+    }
+}
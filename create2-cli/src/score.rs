@@ -0,0 +1,166 @@
+use crate::output::MatchRecord;
+use create2_core::Create2Error;
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Mutex;
+
+// --score 的打分函数，都在地址的40个半字节上计算：
+// zeros为零的个数，repeated为最长的相同半字节连续段，weights:0=2,f=1 为每个半字节按值加权求和（未列出的值为0分）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Scorer {
+    Zeros,
+    Repeated,
+    Weights([u32; 16]),
+}
+
+impl Scorer {
+    #[inline(always)]
+    pub fn score(&self, address: &[u8; 20]) -> u32 {
+        let nibbles = address.iter().flat_map(|byte| [byte >> 4, byte & 0x0f]);
+        match self {
+            Scorer::Zeros => nibbles.filter(|nibble| *nibble == 0).count() as u32,
+            Scorer::Repeated => {
+                let (longest, _, _) = nibbles.fold((0, 0, 16), |(longest, run, last), nibble| {
+                    let run = if nibble == last { run + 1 } else { 1 };
+                    (longest.max(run), run, nibble)
+                });
+                longest
+            }
+            Scorer::Weights(weights) => nibbles.map(|nibble| weights[nibble as usize]).sum(),
+        }
+    }
+}
+
+impl FromStr for Scorer {
+    type Err = Create2Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "zeros" => Ok(Scorer::Zeros),
+            "repeated" => Ok(Scorer::Repeated),
+            _ => {
+                let spec = s
+                    .strip_prefix("weights:")
+                    .ok_or_else(|| Create2Error::Config(format!("Invalid score function: {} (expected zeros, repeated or weights:<nibble>=<weight>,...)", s)))?;
+                let mut weights = [0u32; 16];
+                for entry in spec.split(',').filter(|entry| !entry.is_empty()) {
+                    let invalid = || Create2Error::Config(format!("Invalid score weight {} (expected e.g. 0=2 with a hex nibble)", entry));
+                    let (nibble, weight) = entry.split_once('=').ok_or_else(invalid)?;
+                    let mut chars = nibble.trim().chars();
+                    let nibble = match (chars.next().and_then(|c| c.to_digit(16)), chars.next()) {
+                        (Some(nibble), None) => nibble as usize,
+                        _ => return Err(invalid()),
+                    };
+                    weights[nibble] = weight.trim().parse().map_err(|_| invalid())?;
+                }
+                if weights.iter().all(|weight| *weight == 0) {
+                    return Err(Create2Error::Config(format!("Score function {} gives every address 0 points", s)));
+                }
+                Ok(Scorer::Weights(weights))
+            }
+        }
+    }
+}
+
+impl fmt::Display for Scorer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Scorer::Zeros => write!(f, "zeros"),
+            Scorer::Repeated => write!(f, "repeated"),
+            Scorer::Weights(weights) => {
+                let entries: Vec<String> = weights
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, weight)| **weight > 0)
+                    .map(|(nibble, weight)| format!("{:x}={}", nibble, weight))
+                    .collect();
+                write!(f, "weights:{}", entries.join(","))
+            }
+        }
+    }
+}
+
+// 容量为 --top 的最小堆，堆顶是目前第N名的得分；只有严格高于它的地址才进入榜单，
+// 堆满后把门槛放进原子变量，绝大多数候选地址不用加锁就能排除
+pub struct TopScores {
+    capacity: usize,
+    heap: Mutex<BinaryHeap<Reverse<u32>>>,
+    threshold: AtomicU32,
+}
+
+impl TopScores {
+    pub fn new(capacity: usize) -> Self {
+        TopScores {
+            capacity: capacity.max(1),
+            heap: Mutex::new(BinaryHeap::with_capacity(capacity + 1)),
+            threshold: AtomicU32::new(0),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    // 只与当前门槛比较，不改变榜单；满员前门槛为0，正分都能通过
+    #[inline(always)]
+    pub fn admits(&self, score: u32) -> bool {
+        score > self.threshold.load(Ordering::Relaxed)
+    }
+
+    // 得分能进入榜单时记下并返回true，挤掉的是原来的最后一名；0分的地址不计
+    #[inline(always)]
+    pub fn offer(&self, score: u32) -> bool {
+        if score <= self.threshold.load(Ordering::Relaxed) {
+            return false;
+        }
+        let mut heap = self.heap.lock().unwrap();
+        if heap.len() >= self.capacity && heap.peek().is_some_and(|Reverse(lowest)| score <= *lowest) {
+            return false;
+        }
+        heap.push(Reverse(score));
+        if heap.len() > self.capacity {
+            heap.pop();
+        }
+        if heap.len() >= self.capacity {
+            let Reverse(lowest) = heap.peek().copied().unwrap_or_default();
+            self.threshold.store(lowest, Ordering::Relaxed);
+        }
+        true
+    }
+}
+
+// 结束时输出的榜单条目，按得分从高到低排列
+#[derive(Debug, Clone, Serialize)]
+pub struct ScoredRecord {
+    pub rank: usize,
+    pub score: u32,
+    #[serde(flatten)]
+    pub record: MatchRecord,
+}
+
+impl ScoredRecord {
+    // 进入过榜单的地址都在找到的地址里，从中取得分最高的N个；得分相同时先找到的排在前面
+    pub fn rank(matches: &[MatchRecord], score: impl Fn(&MatchRecord) -> u32, top: usize) -> Vec<Self> {
+        let mut scored: Vec<(u32, &MatchRecord)> = matches.iter().map(|record| (score(record), record)).collect();
+        scored.sort_by_key(|(score, _)| Reverse(*score));
+        scored
+            .into_iter()
+            .take(top)
+            .enumerate()
+            .map(|(i, (score, record))| ScoredRecord { rank: i + 1, score, record: record.clone() })
+            .collect()
+    }
+}
+
+impl fmt::Display for ScoredRecord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "top rank={} score={} ", self.rank, self.score)?;
+        // 复用MatchRecord的格式，去掉开头的 match
+        let record = self.record.to_string();
+        write!(f, "{}", record.strip_prefix("match ").unwrap_or(&record))
+    }
+}
@@ -0,0 +1,60 @@
+use create2_core::Create2Error;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+// tune子命令的结果：在device上测得TPS最高的批处理大小、每组线程数和每线程地址数。
+// 之后的运行从 --tune-cache 文件加载，批处理大小和每线程地址数只填充没有显式设置的 --batch-size、--addresses-per-thread
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TunedParams {
+    pub device: String,
+    pub batch_size: usize,
+    pub threads_per_group: usize,
+    // 早期的调优结果没有这一项，按当时固定的4处理
+    #[serde(default = "default_addresses_per_thread")]
+    pub addresses_per_thread: usize,
+    pub tps: f64,
+}
+
+// 检查点也用它补全早期文件
+pub fn default_addresses_per_thread() -> usize {
+    4
+}
+
+impl TunedParams {
+    // 文件不存在时返回None
+    pub fn load(path: &Path) -> Result<Option<Self>, Create2Error> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(path)
+            .map_err(|e| Create2Error::io(format!("Failed to read tune cache {}", path.display()), e))?;
+        let tuned: TunedParams = serde_json::from_str(&content)
+            .map_err(|e| Create2Error::serde(format!("Invalid tune cache {}", path.display()), e))?;
+        if tuned.batch_size == 0 || tuned.threads_per_group == 0 || tuned.addresses_per_thread == 0 {
+            return Err(Create2Error::Config(format!(
+                "Invalid tune cache {}: batch size, threads per group and addresses per thread must be positive",
+                path.display()
+            )));
+        }
+        Ok(Some(tuned))
+    }
+
+    // 先写临时文件再rename，中途被杀掉也不会留下半个文件
+    pub fn save(&self, path: &Path) -> Result<(), Create2Error> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| Create2Error::serde("Failed to serialize tune cache", e))?;
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, json)
+            .and_then(|_| fs::rename(&tmp_path, path))
+            .map_err(|e| Create2Error::io(format!("Failed to write tune cache {}", path.display()), e))
+    }
+
+    // 启动时和validate中显示的摘要
+    pub fn describe(&self) -> String {
+        format!(
+            "{}：批处理大小 {}，每组线程数 {}，每线程地址数 {}，{:.0} TPS",
+            self.device, self.batch_size, self.threads_per_group, self.addresses_per_thread, self.tps
+        )
+    }
+}
@@ -0,0 +1,147 @@
+use crate::bloom::SaltBloom;
+use crate::checkpoint::Checkpoint;
+use crate::output::error_chain;
+use crate::config::{Chain, Scheme, Settings};
+use crate::pattern::{AddressKind, PatternSet};
+use crate::salts::{SaltReader, MAX_SALT_LEN};
+use create2_core::salt::SaltPolicy;
+use create2_core::{Create2Error, ProxyScheme};
+use std::path::Path;
+
+// validate子命令：逐项检查运行参数并打印结果，不做任何哈希计算；所有问题收集后一起报告
+pub struct Validation {
+    problems: usize,
+}
+
+impl Validation {
+    // new会打印标题，不适合作为Default
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        status!("🔎 检查运行参数（不执行任何计算）");
+        status!("--------------------------------------------------------------------------------");
+        Validation { problems: 0 }
+    }
+
+    pub fn check(&mut self, label: &str, result: Result<String, Create2Error>) {
+        match result {
+            Ok(detail) => status!("✅ {}: {}", label, detail),
+            Err(e) => {
+                self.problems += 1;
+                summary!("❌ {}: {}", label, error_chain(&e));
+            }
+        }
+    }
+
+    // 各个版本共有的检查：--chain、init code哈希或代理方案、随机种子、find模式的pattern和布隆过滤器、predict模式的salt文件、nonces模式的范围
+    pub fn check_common(&mut self, settings: &Settings, kind: AddressKind) {
+        if let Some(Chain(chain)) = settings.chain {
+            let detail = settings.check_chain(kind).map(|_| format!("{}（chain id {}）", chain.name, chain.chain_id));
+            self.check("链", detail);
+        }
+        if let Some(ref hash) = settings.init_code_hash {
+            let detail = settings.init_code_hash().map(|_| format!("{}（通用CREATE2，忽略实现合约）", hash));
+            self.check("Init code hash", detail);
+        } else if settings.scheme != Scheme::Minimal {
+            let detail = settings
+                .proxy_scheme()
+                .map(|scheme| match scheme {
+                    ProxyScheme::ClonesWithImmutableArgs(args) => format!("{}，{} 字节不可变参数", settings.scheme, args.len()),
+                    ProxyScheme::MetaProxy(metadata) => format!("{}，{} 字节metadata", settings.scheme, metadata.len()),
+                    ProxyScheme::Erc1967 { creation_code, constructor_args } => match constructor_args {
+                        Some(args) => format!("{}，{} 字节creation code，{} 字节构造参数", settings.scheme, creation_code.len(), args.len()),
+                        None => format!("{}，{} 字节creation code，构造参数为(implementation, \"\")", settings.scheme, creation_code.len()),
+                    },
+                    ProxyScheme::SimpleAccount { creation_code, owner } => {
+                        format!("{}，{} 字节creation code，owner {}", settings.scheme, creation_code.len(), owner)
+                    }
+                    _ => settings.scheme.to_string(),
+                });
+            self.check("代理方案", detail);
+        }
+        let seed = settings
+            .seed
+            .map_or_else(|| "未设置，运行时随机生成".to_string(), |seed| seed.to_string());
+        self.check("随机种子", Ok(seed));
+        let pattern = PatternSet::from_settings(settings, kind).map(|pattern| pattern.per_salt(settings.find_targets().len()));
+        self.check("find模式", pattern.map(|pattern| {
+            match pattern.expected_attempts() {
+                Some(attempts) => format!("搜索{}的地址，平均每 {:.0} 次尝试匹配一次", pattern, attempts),
+                None => format!("搜索{}的地址", pattern),
+            }
+        }));
+        if settings.bloom.is_some() {
+            self.check("布隆过滤器", SaltBloom::open(settings).map(|bloom| bloom.map(|bloom| bloom.describe()).unwrap_or_default()));
+        }
+        if let Some(ref tuned) = settings.tuned {
+            self.check("调优结果", Ok(tuned.describe()));
+        }
+        if let Some(ref path) = settings.salts_file {
+            self.check("predict模式", count_salts(path, settings.salt_policy));
+        }
+        // TRON版本不支持nonces模式
+        if kind == AddressKind::Evm {
+            let nonces = match settings.nonce_start.checked_add(settings.nonce_count as u64) {
+                Some(end) => Ok(format!("计算nonce {}..{} 的CREATE地址", settings.nonce_start, end)),
+                None => Err(Create2Error::Config("--nonce-start + --nonce-count exceeds the u64 nonce range".to_string())),
+            };
+            self.check("nonces模式", nonces);
+        }
+    }
+
+    // 指定了 --resume 时读取检查点并确认能恢复，返回检查点供各版本做额外检查
+    pub fn check_checkpoint(&mut self, settings: &Settings, streams: usize) -> Option<Checkpoint> {
+        let path = settings.resume.as_deref()?;
+        if !path.exists() {
+            self.check("检查点", Ok(format!("{} 不存在，将从头开始搜索", path.display())));
+            return None;
+        }
+        match Checkpoint::resume(path, settings, settings.seed.unwrap_or_default(), streams) {
+            Ok(checkpoint) => {
+                self.check(
+                    "检查点",
+                    Ok(format!(
+                        "从 {} 恢复: 已尝试 {} 次, 已找到 {} 个地址",
+                        path.display(),
+                        checkpoint.attempts,
+                        checkpoint.matches.len()
+                    )),
+                );
+                Some(checkpoint)
+            }
+            Err(e) => {
+                self.check("检查点", Err(e));
+                None
+            }
+        }
+    }
+
+    pub fn finish(self) -> Result<(), Create2Error> {
+        status!("--------------------------------------------------------------------------------");
+        if self.problems > 0 {
+            return Err(Create2Error::Config(format!("Validation failed with {} problem(s)", self.problems)));
+        }
+        summary!("✅ 检查通过");
+        Ok(())
+    }
+}
+
+// 读完整个salt文件检查每一行；stdin只能读一次，留到运行时再检查
+fn count_salts(path: &Path, policy: SaltPolicy) -> Result<String, Create2Error> {
+    if path == Path::new("-") {
+        return Ok("从stdin读取salt，运行时逐行检查".to_string());
+    }
+    let mut reader = SaltReader::open(path, policy)?;
+    let mut count = 0;
+    loop {
+        let salts = reader.read_batch(65536)?;
+        if salts.is_empty() {
+            break;
+        }
+        count += salts.len();
+    }
+    Ok(match policy {
+        SaltPolicy::Pad => format!("{} 中有 {} 个salt，均不超过 {} 字节", path.display(), count, MAX_SALT_LEN),
+        SaltPolicy::Bytes32 => format!("{} 中有 {} 个bytes32 salt", path.display(), count),
+        SaltPolicy::HashIfLong => format!("{} 中有 {} 个salt，超过 {} 字节的取keccak256", path.display(), count, MAX_SALT_LEN),
+    })
+}
@@ -0,0 +1,90 @@
+use crate::checksum::checksum_address_into;
+use crate::{hex, Create2Error};
+use alloc::string::{String, ToString};
+use core::fmt;
+use core::str::FromStr;
+
+// 20字节EVM地址：Display输出EIP-55 checksum格式，FromStr要求0x + 40位十六进制，不区分大小写，也不校验checksum
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Address([u8; 20]);
+
+impl Address {
+    pub const fn new(bytes: [u8; 20]) -> Self {
+        Address(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 20] {
+        &self.0
+    }
+
+    // 不带0x的40位小写hex
+    pub fn to_hex(&self) -> String {
+        hex::encode(&self.0)
+    }
+}
+
+impl From<[u8; 20]> for Address {
+    fn from(bytes: [u8; 20]) -> Self {
+        Address(bytes)
+    }
+}
+
+impl FromStr for Address {
+    type Err = Create2Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix("0x") {
+            Some(digits) if digits.len() == 40 && hex::is_hex(digits) => {
+                let mut bytes = [0u8; 20];
+                hex::decode_into(digits, &mut bytes);
+                Ok(Address(bytes))
+            }
+            _ => Err(Create2Error::InvalidAddress(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut checksummed = [0u8; 42];
+        checksum_address_into(&self.0, &mut checksummed);
+        f.write_str(unsafe { core::str::from_utf8_unchecked(&checksummed) })
+    }
+}
+
+impl fmt::Debug for Address {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Address({})", self)
+    }
+}
+
+// 序列化为checksum格式的字符串，反序列化时和FromStr一样不区分大小写
+#[cfg(feature = "serde")]
+impl serde::Serialize for Address {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Address {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+// alloy / foundry工具链的地址类型，两者都是20字节，直接互转
+#[cfg(feature = "alloy")]
+impl From<alloy_primitives::Address> for Address {
+    fn from(address: alloy_primitives::Address) -> Self {
+        Address(address.into_array())
+    }
+}
+
+#[cfg(feature = "alloy")]
+impl From<Address> for alloy_primitives::Address {
+    fn from(address: Address) -> Self {
+        alloy_primitives::Address::new(address.0)
+    }
+}
@@ -0,0 +1,32 @@
+use crate::Create2Error;
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+// 嵌入方用来中途停止批量预测和搜索的标志；克隆出的token共享同一个标志，可以交给其他线程调用cancel()。
+// 取消后正在执行的批次在下一个salt（GPU为下一次dispatch）前停下，取消不可撤销
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    #[inline(always)]
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    // 批量接口在每个salt或每次dispatch前调用，取消后返回Create2Error::Cancelled
+    #[inline(always)]
+    pub fn check(&self) -> Result<(), Create2Error> {
+        if self.is_cancelled() {
+            return Err(Create2Error::Cancelled);
+        }
+        Ok(())
+    }
+}
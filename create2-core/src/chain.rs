@@ -0,0 +1,80 @@
+use crate::{Address, DETERMINISTIC_DEPLOYMENT_PROXY};
+
+// CREATE2哈希输入的首字节：EVM链为0xff，TRON把它换成了地址前缀0x41
+pub const EVM_CREATE2_PREFIX: u8 = 0xff;
+pub const TRON_CREATE2_PREFIX: u8 = 0x41;
+
+// 地址的显示方式：EVM为EIP-55 checksum的hex，TRON为0x41 + 20字节的Base58Check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressFormat {
+    Evm,
+    Tron,
+}
+
+// 链上CREATE2地址的计算方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Create2Flavor {
+    // keccak256(prefix ++ deployer ++ salt ++ init_code_hash)[12..]
+    Prefix(u8),
+    // zkSync Era的ContractDeployer有自己的公式，见predict_zksync_create2
+    ZkSync,
+}
+
+// 一条链的CREATE2参数，factory为该链上可用的CREATE2工厂，没有时为None
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChainPreset {
+    pub name: &'static str,
+    pub chain_id: u64,
+    pub flavor: Create2Flavor,
+    pub format: AddressFormat,
+    pub factory: Option<Address>,
+}
+
+const fn evm(name: &'static str, chain_id: u64) -> ChainPreset {
+    ChainPreset {
+        name,
+        chain_id,
+        flavor: Create2Flavor::Prefix(EVM_CREATE2_PREFIX),
+        format: AddressFormat::Evm,
+        factory: Some(DETERMINISTIC_DEPLOYMENT_PROXY),
+    }
+}
+
+const fn tron(name: &'static str, chain_id: u64) -> ChainPreset {
+    ChainPreset {
+        name,
+        chain_id,
+        flavor: Create2Flavor::Prefix(TRON_CREATE2_PREFIX),
+        format: AddressFormat::Tron,
+        factory: None,
+    }
+}
+
+const fn zksync(name: &'static str, chain_id: u64) -> ChainPreset {
+    ChainPreset {
+        name,
+        chain_id,
+        flavor: Create2Flavor::ZkSync,
+        format: AddressFormat::Evm,
+        factory: None,
+    }
+}
+
+// EVM链的工厂为deterministic-deployment-proxy；TRON的chain id为创世区块哈希的后4字节
+pub const CHAIN_PRESETS: &[ChainPreset] = &[
+    evm("ethereum", 1),
+    evm("sepolia", 11155111),
+    evm("bsc", 56),
+    evm("bsc-testnet", 97),
+    evm("polygon", 137),
+    evm("polygon-amoy", 80002),
+    tron("tron", 728126428),
+    tron("tron-nile", 3448148188),
+    tron("tron-shasta", 2494104990),
+    zksync("zksync", 324),
+    zksync("zksync-sepolia", 300),
+];
+
+pub fn chain_preset(name: &str) -> Option<&'static ChainPreset> {
+    CHAIN_PRESETS.iter().find(|chain| chain.name == name)
+}
@@ -0,0 +1,62 @@
+use crate::hex;
+use alloc::string::String;
+use sha3::{Digest, Keccak256};
+
+// EIP-55：对40位小写hex地址做Keccak256，哈希对应半字节>=8的字母转大写，返回带0x前缀的地址
+#[inline(always)]
+pub fn to_checksum_address(address: &str) -> String {
+    let address_hash = Keccak256::digest(address.as_bytes());
+    
+    let mut checksum = String::with_capacity(42);
+    checksum.push_str("0x");
+    
+    for (i, c) in address.chars().enumerate() {
+        if c.is_ascii_digit() {
+            checksum.push(c);
+        } else {
+            let byte_index = i / 2;
+            let nibble_index = i % 2;
+            let byte_value = address_hash[byte_index];
+            let nibble_value = if nibble_index == 0 {
+                byte_value >> 4
+            } else {
+                byte_value & 0x0f
+            };
+            
+            if nibble_value >= 8 {
+                checksum.push(c.to_ascii_uppercase());
+            } else {
+                checksum.push(c);
+            }
+        }
+    }
+    
+    checksum
+}
+
+// 字节版本：把20字节地址的EIP-55格式（0x + 40个字符）写入out，不做堆分配
+#[inline(always)]
+pub fn checksum_address_into(address: &[u8; 20], out: &mut [u8; 42]) {
+    out[0] = b'0';
+    out[1] = b'x';
+    hex::encode_into(address, &mut out[2..]);
+    let address_hash = Keccak256::digest(&out[2..]);
+
+    for (i, c) in out[2..].iter_mut().enumerate() {
+        let nibble_value = if i % 2 == 0 {
+            address_hash[i / 2] >> 4
+        } else {
+            address_hash[i / 2] & 0x0f
+        };
+        if nibble_value >= 8 {
+            c.make_ascii_uppercase();
+        }
+    }
+}
+
+pub fn checksum_address(address: &[u8; 20]) -> String {
+    let mut out = [0u8; 42];
+    checksum_address_into(address, &mut out);
+    // 输出都是ASCII
+    String::from(unsafe { core::str::from_utf8_unchecked(&out) })
+}
@@ -0,0 +1,52 @@
+use crate::salt::IntoSalt;
+use crate::{predict_create, predict_create2, Address, Create2Error};
+
+// CREATE3（0xsequence / Solady）：先用CREATE2部署固定的16字节代理 67363d3d37363d34f03d5260086018f3，
+// 再由代理以nonce 1 CREATE目标合约，因此地址只取决于部署者和salt，与目标合约的init code无关；
+// 这是代理init code的keccak256
+pub const CREATE3_PROXY_INIT_CODE_HASH: [u8; 32] = [
+    0x21, 0xc3, 0x5d, 0xbe, 0x1b, 0x34, 0x4a, 0x24, 0x88, 0xcf, 0x33, 0x21, 0xd6, 0xce, 0x54, 0x2f,
+    0x8e, 0x9f, 0x30, 0x55, 0x44, 0xff, 0x09, 0xe4, 0x99, 0x3a, 0x62, 0x31, 0x9a, 0x49, 0x7c, 0x1f,
+];
+
+pub fn predict_create3(deployer: &Address, salt: impl IntoSalt) -> Result<Address, Create2Error> {
+    let proxy = predict_create2(deployer, salt, &CREATE3_PROXY_INIT_CODE_HASH)?;
+    Ok(predict_create(&proxy, 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_init_code_hash;
+    use sha3::{Digest, Keccak256};
+
+    #[test]
+    fn proxy_init_code_hash() {
+        let proxy_init_code = [0x67, 0x36, 0x3d, 0x3d, 0x37, 0x36, 0x3d, 0x34, 0xf0, 0x3d, 0x52, 0x60, 0x08, 0x60, 0x18, 0xf3];
+        assert_eq!(<[u8; 32]>::from(Keccak256::digest(proxy_init_code)), CREATE3_PROXY_INIT_CODE_HASH);
+    }
+
+    // 按Solady CREATE3.predictDeterministicAddress的两步推导（CREATE2部署代理，代理以nonce 1 CREATE）
+    // 用alloy-primitives独立算出；第二个salt是keccak256("create3")
+    #[test]
+    fn matches_two_step_derivation() {
+        let create3_salt = "0x67f25bf1f60bc82eeadcce2b7d6251d016f73e97e287d0439a4c80da2a395420";
+        for (deployer, salt, expected) in [
+            ("0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf", [0u8; 32], "0x8fBd321Ab6153F6c287F5391AFe276F6b45863ce"),
+            (
+                "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf",
+                parse_init_code_hash(create3_salt).unwrap(),
+                "0x863AfaEf8cafbf580c200075155E74179216E39E",
+            ),
+            ("0xba5Ed099633D3B313e4D5F7bdc1305d3c28ba5Ed", [0u8; 32], "0x7734b8eA7048ef3FC5F8604D9Dd88199AB88cf5a"),
+            (
+                "0xba5Ed099633D3B313e4D5F7bdc1305d3c28ba5Ed",
+                parse_init_code_hash(create3_salt).unwrap(),
+                "0xaC350FCBE5F402A3A9Ed3fBF8a51BcE311Ac1c18",
+            ),
+        ] {
+            let deployer: Address = deployer.parse().unwrap();
+            assert_eq!(predict_create3(&deployer, salt).unwrap(), expected.parse().unwrap());
+        }
+    }
+}
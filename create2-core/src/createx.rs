@@ -0,0 +1,213 @@
+use crate::{Address, Create2Error};
+use alloc::format;
+use sha3::{Digest, Keccak256};
+
+// CreateX工厂在各条链上的统一部署地址 0xba5Ed099633D3B313e4D5F7bdc1305d3c28ba5Ed
+pub const CREATEX_ADDRESS: Address = Address::new([
+    0xba, 0x5e, 0xd0, 0x99, 0x63, 0x3d, 0x3b, 0x31, 0x3e, 0x4d,
+    0x5f, 0x7b, 0xdc, 0x13, 0x05, 0xd3, 0xc2, 0x8b, 0xa5, 0xed,
+]);
+
+// CreateX salt的前20字节和第21字节决定保护方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaltGuard {
+    // 前20字节为msg.sender，只有该地址能部署；cross_chain为true时再加入chain id，其他链上地址不同
+    Sender { cross_chain: bool },
+    // 前20字节为0，只加入chain id
+    CrossChain,
+    // 不做保护，CreateX仍会先对salt做一次keccak256
+    Unprotected,
+}
+
+// 按CreateX的约定拼出原始salt：前20字节 + 保护标志字节 + 11字节自定义部分
+pub fn createx_salt(guard: SaltGuard, sender: &Address, entropy: &[u8; 11]) -> [u8; 32] {
+    let mut salt = [0u8; 32];
+    match guard {
+        SaltGuard::Sender { cross_chain } => {
+            salt[..20].copy_from_slice(sender.as_bytes());
+            salt[20] = cross_chain as u8;
+        }
+        SaltGuard::CrossChain => salt[20] = 0x01,
+        SaltGuard::Unprotected => {}
+    }
+    salt[21..].copy_from_slice(entropy);
+    salt
+}
+
+// CreateX的_guard：原始salt在CREATE2之前按sender和chain id重新哈希，结果才是实际使用的salt；
+// 前20字节为sender或0时第21字节必须是0x00或0x01，否则合约会revert
+pub fn createx_guarded_salt(salt: &[u8; 32], sender: &Address, chain_id: u64) -> Result<[u8; 32], Create2Error> {
+    let mut chain_word = [0u8; 32];
+    chain_word[24..].copy_from_slice(&chain_id.to_be_bytes());
+    let prefix = &salt[..20];
+    let flag = salt[20];
+
+    let hash = if prefix == sender.as_bytes() {
+        match flag {
+            // keccak256(abi.encode(msg.sender, block.chainid, salt))
+            0x01 => {
+                let mut input = [0u8; 96];
+                input[12..32].copy_from_slice(sender.as_bytes());
+                input[32..64].copy_from_slice(&chain_word);
+                input[64..].copy_from_slice(salt);
+                Keccak256::digest(input)
+            }
+            // keccak256(bytes32(msg.sender) ++ salt)
+            0x00 => {
+                let mut input = [0u8; 64];
+                input[12..32].copy_from_slice(sender.as_bytes());
+                input[32..].copy_from_slice(salt);
+                Keccak256::digest(input)
+            }
+            _ => return Err(invalid_flag(salt)),
+        }
+    } else if prefix == [0u8; 20] && flag != 0x00 {
+        if flag != 0x01 {
+            return Err(invalid_flag(salt));
+        }
+        // keccak256(bytes32(block.chainid) ++ salt)
+        let mut input = [0u8; 64];
+        input[..32].copy_from_slice(&chain_word);
+        input[32..].copy_from_slice(salt);
+        Keccak256::digest(input)
+    } else {
+        // 其余情况：keccak256(abi.encode(salt))
+        Keccak256::digest(salt)
+    };
+    Ok(hash.into())
+}
+
+fn invalid_flag(salt: &[u8; 32]) -> Create2Error {
+    Create2Error::InvalidSalt(format!(
+        "CreateX salts starting with the sender or zero address need 0x00 or 0x01 as the 21st byte, got 0x{:02x}",
+        salt[20]
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_init_code_hash, predict_create2};
+
+    // 私钥1对应的地址作为msg.sender，init code为单字节0x00；期望值按CreateX _guard的Solidity公式
+    // 用alloy-primitives的keccak256和create2独立算出，不经过本模块的实现
+    const SENDER: &str = "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf";
+    const ENTROPY: [u8; 11] = [0x42; 11];
+
+    fn sender() -> Address {
+        SENDER.parse().unwrap()
+    }
+
+    fn hash(hex: &str) -> [u8; 32] {
+        parse_init_code_hash(hex).unwrap()
+    }
+
+    fn createx_address(guarded: &[u8; 32]) -> Address {
+        let init_code_hash = hash("0xbc36789e7a1e281436464229828f817d6612f7b477d66591ff96a9e064bcc98a");
+        predict_create2(&CREATEX_ADDRESS, *guarded, &init_code_hash).unwrap()
+    }
+
+    #[test]
+    fn createx_salt_layout() {
+        assert_eq!(
+            createx_salt(SaltGuard::Sender { cross_chain: true }, &sender(), &ENTROPY),
+            hash("0x7e5f4552091a69125d5dfcb7b8c2659029395bdf014242424242424242424242")
+        );
+        assert_eq!(
+            createx_salt(SaltGuard::Sender { cross_chain: false }, &sender(), &ENTROPY),
+            hash("0x7e5f4552091a69125d5dfcb7b8c2659029395bdf004242424242424242424242")
+        );
+        assert_eq!(
+            createx_salt(SaltGuard::CrossChain, &sender(), &ENTROPY),
+            hash("0x0000000000000000000000000000000000000000014242424242424242424242")
+        );
+        assert_eq!(
+            createx_salt(SaltGuard::Unprotected, &sender(), &ENTROPY),
+            hash("0x0000000000000000000000000000000000000000004242424242424242424242")
+        );
+    }
+
+    #[test]
+    fn guarded_salt_per_guard() {
+        let cases = [
+            // keccak256(abi.encode(msg.sender, block.chainid, salt))
+            (
+                SaltGuard::Sender { cross_chain: true },
+                1,
+                "0xe940c74070eee31d3d394e94ea5d269de3e90a48d3eed6da242ffbe89175b5d5",
+                "0x89C4b61346f8204f6A3A1a2fEF38a756b8a71C1A",
+            ),
+            (
+                SaltGuard::Sender { cross_chain: true },
+                10,
+                "0xbd7d422c02f68863487301bb8ef70e19560fa6180d9a869f1e4d80db3df6be38",
+                "0x2d01d6BC1f143AD82216641d4736e628F69eA178",
+            ),
+            // keccak256(bytes32(msg.sender) ++ salt)，与链无关
+            (
+                SaltGuard::Sender { cross_chain: false },
+                1,
+                "0xc3438d8e7dfd9796772e2c9383b55aaa6ab39366d5fda559eb6b2b6c80ae3bcd",
+                "0xC7cfeeDBF2a7C527a7A43CC79aA3d102eD2d7C9D",
+            ),
+            (
+                SaltGuard::Sender { cross_chain: false },
+                10,
+                "0xc3438d8e7dfd9796772e2c9383b55aaa6ab39366d5fda559eb6b2b6c80ae3bcd",
+                "0xC7cfeeDBF2a7C527a7A43CC79aA3d102eD2d7C9D",
+            ),
+            // keccak256(bytes32(block.chainid) ++ salt)
+            (
+                SaltGuard::CrossChain,
+                1,
+                "0xbcc064ecf741ea2e098a73c8d4d3f3b09da905e6dab4031727dfcaa0c3c0ccdf",
+                "0xB972b853dE767741054C5B709f6Fc88974032B53",
+            ),
+            (
+                SaltGuard::CrossChain,
+                10,
+                "0x8b987b910d3b2fa5e20b7375c3fb64d13b4ed7729b27caba55251fd1f0255714",
+                "0x2079616f799C039e9a83c5fA02A6fCFa43B9a429",
+            ),
+            // keccak256(abi.encode(salt))
+            (
+                SaltGuard::Unprotected,
+                1,
+                "0xf28978ae2474cf553a0136f459710361308f3c5e96c17031a65e61b68f8122b6",
+                "0x46dB3E92D3dDef7A2482bBF179D7e97AF8Dcd716",
+            ),
+            (
+                SaltGuard::Unprotected,
+                10,
+                "0xf28978ae2474cf553a0136f459710361308f3c5e96c17031a65e61b68f8122b6",
+                "0x46dB3E92D3dDef7A2482bBF179D7e97AF8Dcd716",
+            ),
+        ];
+        for (guard, chain_id, guarded, address) in cases {
+            let salt = createx_salt(guard, &sender(), &ENTROPY);
+            let actual = createx_guarded_salt(&salt, &sender(), chain_id).unwrap();
+            assert_eq!(actual, hash(guarded), "{:?} on chain {}", guard, chain_id);
+            assert_eq!(createx_address(&actual), address.parse().unwrap(), "{:?} on chain {}", guard, chain_id);
+        }
+    }
+
+    #[test]
+    fn sender_salt_from_another_caller_is_hashed_unprotected() {
+        let salt = createx_salt(SaltGuard::Sender { cross_chain: true }, &sender(), &ENTROPY);
+        let other = Address::new([0x11; 20]);
+        assert_eq!(
+            createx_guarded_salt(&salt, &other, 1).unwrap(),
+            hash("0x5f928af08dd21ce913b20b9ff4241ff7fddafbb8a03d563dac915ccf4c590e59")
+        );
+    }
+
+    #[test]
+    fn invalid_protection_flag_is_rejected() {
+        let mut salt = createx_salt(SaltGuard::Sender { cross_chain: true }, &sender(), &ENTROPY);
+        salt[20] = 0x02;
+        assert!(matches!(createx_guarded_salt(&salt, &sender(), 1), Err(Create2Error::InvalidSalt(_))));
+        let mut salt = createx_salt(SaltGuard::CrossChain, &sender(), &ENTROPY);
+        salt[20] = 0x02;
+        assert!(matches!(createx_guarded_salt(&salt, &sender(), 1), Err(Create2Error::InvalidSalt(_))));
+    }
+}
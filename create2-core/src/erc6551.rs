@@ -0,0 +1,119 @@
+use crate::salt::IntoSalt;
+use crate::search::search_with;
+use crate::{Address, CancellationToken, Create2Error, Create2Template};
+use core::ops::ControlFlow;
+use sha3::{Digest, Keccak256};
+
+// ERC-6551 v0.3注册表，在各条链上都部署在同一个地址 0x000000006551c19487814612e58FE06813775758
+pub const ERC6551_REGISTRY: Address = Address::new([
+    0x00, 0x00, 0x00, 0x00, 0x65, 0x51, 0xc1, 0x94, 0x87, 0x81,
+    0x46, 0x12, 0xe5, 0x8f, 0xe0, 0x68, 0x13, 0x77, 0x57, 0x58,
+]);
+
+// 运行时代码为0xad字节的ERC-1167变体：最小代理后面附加 salt、chainId、tokenContract、tokenId 四个word
+const HEADER: [u8; 20] = [
+    0x3d, 0x60, 0xad, 0x80, 0x60, 0x0a, 0x3d, 0x39, 0x81, 0xf3,
+    0x36, 0x3d, 0x3d, 0x37, 0x3d, 0x3d, 0x3d, 0x36, 0x3d, 0x73,
+];
+const FOOTER: [u8; 15] = [0x5a, 0xf4, 0x3d, 0x82, 0x80, 0x3e, 0x90, 0x3d, 0x91, 0x60, 0x2b, 0x57, 0xfd, 0x5b, 0xf3];
+
+// 一个NFT的token bound account，对应registry.account(implementation, salt, chainId, tokenContract, tokenId)；
+// salt既是CREATE2的salt又写在init code里，所以每个salt都要重新哈希init code，不能用Create2Template
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Erc6551Account {
+    pub registry: Address,
+    pub implementation: Address,
+    pub chain_id: u64,
+    pub token_contract: Address,
+    // uint256，大端
+    pub token_id: [u8; 32],
+}
+
+impl Erc6551Account {
+    // 使用官方注册表；其他注册表直接修改registry字段
+    pub fn new(implementation: Address, chain_id: u64, token_contract: Address, token_id: [u8; 32]) -> Self {
+        Erc6551Account {
+            registry: ERC6551_REGISTRY,
+            implementation,
+            chain_id,
+            token_contract,
+            token_id,
+        }
+    }
+
+    // 注册表用CREATE2部署的183字节init code
+    pub fn init_code(&self, salt: &[u8; 32]) -> [u8; 183] {
+        let mut init_code = [0u8; 183];
+        init_code[..20].copy_from_slice(&HEADER);
+        init_code[20..40].copy_from_slice(self.implementation.as_bytes());
+        init_code[40..55].copy_from_slice(&FOOTER);
+        init_code[55..87].copy_from_slice(salt);
+        init_code[111..119].copy_from_slice(&self.chain_id.to_be_bytes());
+        init_code[131..151].copy_from_slice(self.token_contract.as_bytes());
+        init_code[151..].copy_from_slice(&self.token_id);
+        init_code
+    }
+
+    pub fn predict(&self, salt: impl IntoSalt) -> Result<Address, Create2Error> {
+        let salt = salt.to_salt()?;
+        let init_code_hash: [u8; 32] = Keccak256::digest(self.init_code(&salt)).into();
+        Ok(Create2Template::from_init_code_hash(self.registry, init_code_hash).predict_bytes(&salt).into())
+    }
+
+    // 与Create2Template::search相同，用于挖掘靓号TBA地址；每个salt需要两次Keccak256
+    pub fn search<S, P, F, E>(
+        &self,
+        salt_source: S,
+        cancel: &CancellationToken,
+        predicate: P,
+        on_match: F,
+    ) -> Result<usize, E>
+    where
+        S: IntoIterator,
+        S::Item: IntoSalt,
+        P: FnMut(&Address) -> bool,
+        F: FnMut(&S::Item, &Address, usize) -> Result<ControlFlow<()>, E>,
+        E: From<Create2Error>,
+    {
+        search_with(|salt| self.predict(salt), salt_source, cancel, predicate, on_match)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // BAYC合约的token bound account；期望值按参考ERC6551Registry.account()的init code布局
+    // （header + implementation + footer + abi.encode(salt, chainId, tokenContract, tokenId)）用alloy-primitives独立算出
+    const BAYC: &str = "0xBC4CA0EdA7647A8aB7C2061c2E118A18a936f13D";
+
+    fn account(chain_id: u64, token_id: u64) -> Erc6551Account {
+        let mut id = [0u8; 32];
+        id[24..].copy_from_slice(&token_id.to_be_bytes());
+        Erc6551Account::new(Address::new([0x11; 20]), chain_id, BAYC.parse().unwrap(), id)
+    }
+
+    #[test]
+    fn matches_registry_account() {
+        for (chain_id, token_id, salt, expected) in [
+            (1, 1, 0u64, "0xEaE520FB7dA844965cFb52Ac3963A130736dcD62"),
+            (1, 1, 1, "0x577C3aB685dc05D50c0E5B3d78B57a0a4Da2A838"),
+            (8453, 9999, 0, "0x3fAD27ca4Fb71Dc8844cc90b12AbFbC5213ed5A4"),
+        ] {
+            let predicted = account(chain_id, token_id).predict(salt).unwrap();
+            assert_eq!(predicted, expected.parse().unwrap(), "chain {} token {} salt {}", chain_id, token_id, salt);
+        }
+    }
+
+    #[test]
+    fn init_code_layout() {
+        let init_code = account(1, 1).init_code(&[0x22; 32]);
+        assert_eq!(&init_code[..20], &HEADER);
+        assert_eq!(&init_code[20..40], &[0x11; 20]);
+        assert_eq!(&init_code[40..55], &FOOTER);
+        assert_eq!(&init_code[55..87], &[0x22; 32]);
+        assert_eq!(init_code[118], 1);
+        assert_eq!(&init_code[131..151], BAYC.parse::<Address>().unwrap().as_bytes());
+        assert_eq!(init_code[182], 1);
+    }
+}
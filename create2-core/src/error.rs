@@ -0,0 +1,108 @@
+use alloc::string::String;
+use thiserror::Error;
+
+// 所有crate共用的错误类型：地址、salt、init code哈希、代理参数和匹配规则格式、运行参数、GPU初始化和计算、取消、
+// 文件读写、JSON/TOML解析和网络请求；Io、Serde和Network保留底层错误作为source
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Create2Error {
+    #[error("Invalid address format: {0}")]
+    InvalidAddress(String),
+    #[error("Invalid salt: {0}")]
+    InvalidSalt(String),
+    #[error("Invalid init code hash: {0}")]
+    InvalidInitCodeHash(String),
+    #[error("Invalid proxy arguments: {0}")]
+    InvalidProxyArgs(String),
+    #[error("Invalid pattern: {0}")]
+    InvalidPattern(String),
+    // 命令行参数、配置文件和环境变量的取值不合法，或者与检查点记录的参数冲突；信息本身已指明是哪个参数
+    #[error("{0}")]
+    Config(String),
+    #[error("GPU initialization failed: {0}")]
+    GpuInit(String),
+    #[error("GPU dispatch failed: {0}")]
+    GpuDispatch(String),
+    #[error("Operation cancelled")]
+    Cancelled,
+    #[cfg(feature = "std")]
+    #[error("{context}")]
+    Io {
+        context: String,
+        #[source]
+        #[cfg_attr(feature = "serde", serde(with = "io_error"))]
+        source: std::io::Error,
+    },
+    // 检查点、调优缓存、配置文件等的JSON/TOML读写
+    #[cfg(feature = "std")]
+    #[error("{context}")]
+    Serde {
+        context: String,
+        #[source]
+        #[cfg_attr(feature = "serde", serde(with = "boxed_error"))]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    // --notify-url的TLS初始化和HTTP请求
+    #[cfg(feature = "std")]
+    #[error("{context}")]
+    Network {
+        context: String,
+        #[source]
+        #[cfg_attr(feature = "serde", serde(with = "boxed_error"))]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
+
+#[cfg(feature = "std")]
+impl Create2Error {
+    pub fn io(context: impl Into<String>, source: std::io::Error) -> Self {
+        Create2Error::Io {
+            context: context.into(),
+            source,
+        }
+    }
+
+    pub fn serde(context: impl Into<String>, source: impl std::error::Error + Send + Sync + 'static) -> Self {
+        Create2Error::Serde {
+            context: context.into(),
+            source: Box::new(source),
+        }
+    }
+
+    pub fn network(context: impl Into<String>, source: impl std::error::Error + Send + Sync + 'static) -> Self {
+        Create2Error::Network {
+            context: context.into(),
+            source: Box::new(source),
+        }
+    }
+}
+
+// io::Error本身不能序列化，按错误信息写出，读回时变成ErrorKind::Other
+#[cfg(all(feature = "std", feature = "serde"))]
+mod io_error {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(error: &std::io::Error, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(error)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<std::io::Error, D::Error> {
+        String::deserialize(deserializer).map(std::io::Error::other)
+    }
+}
+
+// 同样只保留错误信息
+#[cfg(all(feature = "std", feature = "serde"))]
+mod boxed_error {
+    use core::fmt::Display;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::error::Error;
+
+    pub fn serialize<E: Display, S: Serializer>(error: &E, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(error)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Box<dyn Error + Send + Sync>, D::Error> {
+        String::deserialize(deserializer).map(Into::into)
+    }
+}
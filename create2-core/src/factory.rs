@@ -0,0 +1,43 @@
+use crate::{Address, CREATEX_ADDRESS};
+
+// 常用的CREATE2工厂，在各条链上都部署在同一个地址，作为部署者时直接用名字选择
+
+// EIP-2470 SingletonFactory 0xce0042B868300000d44A59004Da54A005ffdcf9f
+pub const SINGLETON_FACTORY: Address = Address::new([
+    0xce, 0x00, 0x42, 0xb8, 0x68, 0x30, 0x00, 0x00, 0xd4, 0x4a,
+    0x59, 0x00, 0x4d, 0xa5, 0x4a, 0x00, 0x5f, 0xfd, 0xcf, 0x9f,
+]);
+
+// Arachnid的deterministic-deployment-proxy，Foundry默认的CREATE2部署者 0x4e59b44847b379578588920cA78FbF26c0B4956C
+pub const DETERMINISTIC_DEPLOYMENT_PROXY: Address = Address::new([
+    0x4e, 0x59, 0xb4, 0x48, 0x47, 0xb3, 0x79, 0x57, 0x85, 0x88,
+    0x92, 0x0c, 0xa7, 0x8f, 0xbf, 0x26, 0xc0, 0xb4, 0x95, 0x6c,
+]);
+
+// Safe的safe-singleton-factory 0x914d7Fec6aaC8cd542e72Bca78B30650d45643d7
+pub const SAFE_SINGLETON_FACTORY: Address = Address::new([
+    0x91, 0x4d, 0x7f, 0xec, 0x6a, 0xac, 0x8c, 0xd5, 0x42, 0xe7,
+    0x2b, 0xca, 0x78, 0xb3, 0x06, 0x50, 0xd4, 0x56, 0x43, 0xd7,
+]);
+
+// 0age的ImmutableCreate2Factory 0x0000000000FFe8B47B3e2130213B802212439497
+pub const IMMUTABLE_CREATE2_FACTORY: Address = Address::new([
+    0x00, 0x00, 0x00, 0x00, 0x00, 0xff, 0xe8, 0xb4, 0x7b, 0x3e,
+    0x21, 0x30, 0x21, 0x3b, 0x80, 0x22, 0x12, 0x43, 0x94, 0x97,
+]);
+
+// (名字, 地址)，名字用于 --deployer 和 factory_preset
+pub const FACTORY_PRESETS: &[(&str, Address)] = &[
+    ("eip2470", SINGLETON_FACTORY),
+    ("deterministic-deployment-proxy", DETERMINISTIC_DEPLOYMENT_PROXY),
+    ("safe-singleton-factory", SAFE_SINGLETON_FACTORY),
+    ("immutable-create2-factory", IMMUTABLE_CREATE2_FACTORY),
+    ("createx", CREATEX_ADDRESS),
+];
+
+pub fn factory_preset(name: &str) -> Option<Address> {
+    FACTORY_PRESETS
+        .iter()
+        .find(|(preset, _)| *preset == name)
+        .map(|(_, address)| *address)
+}
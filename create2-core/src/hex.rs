@@ -0,0 +1,58 @@
+use alloc::string::String;
+use alloc::vec;
+
+// 查找表优化的hex解码
+const HEX_DECODE_TABLE: [u8; 256] = {
+    let mut table = [0xff; 256];
+    let mut i = b'0';
+    while i <= b'9' {
+        table[i as usize] = i - b'0';
+        i += 1;
+    }
+    let mut i = b'a';
+    while i <= b'f' {
+        table[i as usize] = i - b'a' + 10;
+        i += 1;
+    }
+    let mut i = b'A';
+    while i <= b'F' {
+        table[i as usize] = i - b'A' + 10;
+        i += 1;
+    }
+    table
+};
+
+const HEX_CHARS: &[u8; 16] = b"0123456789abcdef";
+
+// 不做校验，调用方需保证输入是 output.len() * 2 个十六进制字符
+#[inline(always)]
+pub fn decode_into(hex_str: &str, output: &mut [u8]) {
+    let hex_bytes = hex_str.as_bytes();
+    for (i, out) in output.iter_mut().enumerate() {
+        let idx = i * 2;
+        let high = HEX_DECODE_TABLE[hex_bytes[idx] as usize];
+        let low = HEX_DECODE_TABLE[hex_bytes[idx + 1] as usize];
+        *out = (high << 4) | low;
+    }
+}
+
+// 小写hex，output长度至少为 bytes.len() * 2
+#[inline(always)]
+pub fn encode_into(bytes: &[u8], output: &mut [u8]) {
+    for (i, &byte) in bytes.iter().enumerate() {
+        let idx = i * 2;
+        output[idx] = HEX_CHARS[(byte >> 4) as usize];
+        output[idx + 1] = HEX_CHARS[(byte & 0xf) as usize];
+    }
+}
+
+pub fn encode(bytes: &[u8]) -> String {
+    let mut output = vec![0u8; bytes.len() * 2];
+    encode_into(bytes, &mut output);
+    // HEX_CHARS都是ASCII
+    unsafe { String::from_utf8_unchecked(output) }
+}
+
+pub fn is_hex(hex_str: &str) -> bool {
+    hex_str.bytes().all(|b| HEX_DECODE_TABLE[b as usize] != 0xff)
+}
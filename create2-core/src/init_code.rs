@@ -0,0 +1,125 @@
+use crate::salt::IntoSalt;
+use crate::search::search_with;
+use crate::{hex, Address, CancellationToken, Create2Error, Create2Template};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::ControlFlow;
+use sha3::{Digest, Keccak256};
+
+// 用户提供的init code模板：0x前缀的hex中可以插入占位符 {implementation}（20字节地址）和 {salt}（32字节CREATE2 salt），
+// 标出编译产物里immutable地址或salt所在的位置，由crate填入后再哈希，不限于内置的几种代理字节码
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InitCodeTemplate {
+    code: Vec<u8>,
+    implementation_offsets: Vec<usize>,
+    salt_offsets: Vec<usize>,
+}
+
+impl InitCodeTemplate {
+    pub fn parse(template: &str) -> Result<Self, Create2Error> {
+        let invalid = |reason: String| Create2Error::InvalidProxyArgs(format!("init code template {}", reason));
+        let mut rest = template
+            .strip_prefix("0x")
+            .ok_or_else(|| invalid(format!("{} does not start with 0x", template)))?;
+        let mut code = Vec::with_capacity(rest.len() / 2);
+        let mut implementation_offsets = Vec::new();
+        let mut salt_offsets = Vec::new();
+
+        while !rest.is_empty() {
+            if let Some(placeholder) = rest.strip_prefix('{') {
+                let end = placeholder
+                    .find('}')
+                    .ok_or_else(|| invalid("has an unterminated placeholder".into()))?;
+                let (offsets, len) = match &placeholder[..end] {
+                    "implementation" => (&mut implementation_offsets, 20),
+                    "salt" => (&mut salt_offsets, 32),
+                    name => {
+                        return Err(invalid(format!(
+                            "has unknown placeholder {{{}}} (expected {{implementation}} or {{salt}})",
+                            name
+                        )))
+                    }
+                };
+                offsets.push(code.len());
+                code.resize(code.len() + len, 0);
+                rest = &placeholder[end + 1..];
+            } else {
+                let digits = &rest[..rest.find('{').unwrap_or(rest.len())];
+                if digits.len() % 2 != 0 || !hex::is_hex(digits) {
+                    return Err(invalid(format!("has invalid hex {}", digits)));
+                }
+                let start = code.len();
+                code.resize(start + digits.len() / 2, 0);
+                hex::decode_into(digits, &mut code[start..]);
+                rest = &rest[digits.len()..];
+            }
+        }
+        Ok(InitCodeTemplate {
+            code,
+            implementation_offsets,
+            salt_offsets,
+        })
+    }
+
+    // 含 {salt} 时init code哈希随salt变化，不能用Create2Template，要用下面的predict和search
+    pub fn has_salt(&self) -> bool {
+        !self.salt_offsets.is_empty()
+    }
+
+    // 填入占位符后的init code；模板里没有对应的占位符时该值不使用
+    pub fn init_code(&self, implementation: &Address, salt: &[u8; 32]) -> Vec<u8> {
+        let mut code = self.code.clone();
+        splice(&mut code, &self.implementation_offsets, implementation.as_bytes());
+        splice(&mut code, &self.salt_offsets, salt);
+        code
+    }
+
+    pub fn predict(&self, implementation: &Address, deployer: &Address, salt: impl IntoSalt) -> Result<Address, Create2Error> {
+        let salt = salt.to_salt()?;
+        Ok(predict_from_code(*deployer, &self.init_code(implementation, &salt), &salt))
+    }
+
+    // 与Create2Template::search相同；含 {salt} 时每个salt需要两次Keccak256，init code只分配一次
+    pub fn search<S, P, F, E>(
+        &self,
+        implementation: &Address,
+        deployer: &Address,
+        salt_source: S,
+        cancel: &CancellationToken,
+        predicate: P,
+        on_match: F,
+    ) -> Result<usize, E>
+    where
+        S: IntoIterator,
+        S::Item: IntoSalt,
+        P: FnMut(&Address) -> bool,
+        F: FnMut(&S::Item, &Address, usize) -> Result<ControlFlow<()>, E>,
+        E: From<Create2Error>,
+    {
+        let mut code = self.init_code(implementation, &[0u8; 32]);
+        if !self.has_salt() {
+            let template = Create2Template::from_init_code_hash(*deployer, Keccak256::digest(&code).into());
+            return template.search(salt_source, cancel, predicate, on_match);
+        }
+        let predict = |salt: &S::Item| -> Result<Address, Create2Error> {
+            let salt = salt.to_salt()?;
+            splice(&mut code, &self.salt_offsets, &salt);
+            Ok(predict_from_code(*deployer, &code, &salt))
+        };
+        search_with(predict, salt_source, cancel, predicate, on_match)
+    }
+}
+
+fn splice(code: &mut [u8], offsets: &[usize], value: &[u8]) {
+    for &offset in offsets {
+        code[offset..offset + value.len()].copy_from_slice(value);
+    }
+}
+
+fn predict_from_code(deployer: Address, init_code: &[u8], salt: &[u8; 32]) -> Address {
+    Create2Template::from_init_code_hash(deployer, Keccak256::digest(init_code).into())
+        .predict_bytes(salt)
+        .into()
+}
+
@@ -0,0 +1,60 @@
+use crate::salt::IntoSalt;
+use crate::{Address, Create2Error, Create2Template};
+
+// 惰性地逐个计算salt对应的地址，产出(salt, 地址)；salt来源可以是任意IntoSalt的迭代器，
+// 调用方可以直接take(n)、filter，或放进自己的循环
+#[derive(Debug, Clone)]
+pub struct PredictIter<I> {
+    template: Create2Template,
+    salts: I,
+}
+
+impl<I> PredictIter<I> {
+    pub fn new(template: Create2Template, salts: I) -> Self {
+        PredictIter { template, salts }
+    }
+}
+
+impl<I> Iterator for PredictIter<I>
+where
+    I: Iterator,
+    I::Item: IntoSalt,
+{
+    type Item = Result<(I::Item, Address), Create2Error>;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        let salt = self.salts.next()?;
+        Some(self.template.predict(&salt).map(|address| (salt, address)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.salts.size_hint()
+    }
+}
+
+impl<I> ExactSizeIterator for PredictIter<I>
+where
+    I: ExactSizeIterator,
+    I::Item: IntoSalt,
+{
+}
+
+impl Create2Template {
+    pub fn predict_iter<S>(&self, salts: S) -> PredictIter<S::IntoIter>
+    where
+        S: IntoIterator,
+        S::Item: IntoSalt,
+    {
+        PredictIter::new(*self, salts.into_iter())
+    }
+}
+
+// 地址格式错误时立即返回错误；每个salt的错误（超过32字节）在迭代时逐个返回
+pub fn predict_iter<S>(implementation: &str, deployer: &str, salts: S) -> Result<PredictIter<S::IntoIter>, Create2Error>
+where
+    S: IntoIterator,
+    S::Item: IntoSalt,
+{
+    Ok(Create2Template::new(implementation, deployer)?.predict_iter(salts))
+}
@@ -0,0 +1,234 @@
+// 所有benchmark共用的CREATE2地址预测：EIP-1167最小代理的bytecode、hex、checksum和salt处理
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::string::{String, ToString};
+use sha3::{Digest, Keccak256};
+
+mod address;
+mod cancel;
+mod chain;
+pub mod checksum;
+mod create;
+mod create3;
+mod createx;
+mod erc6551;
+mod error;
+mod factory;
+pub mod hex;
+mod init_code;
+mod iter;
+mod mask;
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "std")]
+mod predictor;
+mod proxy;
+pub mod salt;
+mod safe;
+mod search;
+#[cfg(feature = "starknet")]
+mod starknet;
+mod template;
+#[cfg(any(test, feature = "test-vectors"))]
+pub mod test_vectors;
+mod uniswap;
+mod zksync;
+
+pub use address::Address;
+pub use cancel::CancellationToken;
+pub use chain::{
+    chain_preset, AddressFormat, ChainPreset, Create2Flavor, CHAIN_PRESETS, EVM_CREATE2_PREFIX, TRON_CREATE2_PREFIX,
+};
+pub use checksum::{checksum_address, checksum_address_into};
+pub use create::predict_create;
+pub use create3::{predict_create3, CREATE3_PROXY_INIT_CODE_HASH};
+pub use createx::{createx_guarded_salt, createx_salt, SaltGuard, CREATEX_ADDRESS};
+pub use erc6551::{Erc6551Account, ERC6551_REGISTRY};
+pub use error::Create2Error;
+pub use factory::{
+    factory_preset, DETERMINISTIC_DEPLOYMENT_PROXY, FACTORY_PRESETS, IMMUTABLE_CREATE2_FACTORY, SAFE_SINGLETON_FACTORY,
+    SINGLETON_FACTORY,
+};
+pub use init_code::InitCodeTemplate;
+pub use iter::{predict_iter, PredictIter};
+pub use mask::NibbleMask;
+#[cfg(feature = "rayon")]
+pub use parallel::{PredictWith, RayonPredictor};
+pub use safe::{safe_salt, SafeSalt};
+pub use search::{search, search_templates, Vanity};
+#[cfg(feature = "starknet")]
+pub use starknet::{pedersen_hash_on_elements, starknet_contract_address, StarknetTemplate};
+#[cfg(feature = "std")]
+pub use predictor::{random_salt, AddressPredictor, CpuPredictor, Salt, RANDOM_SALT_WORDS};
+pub use proxy::{
+    clones_with_immutable_args_init_code, erc1967_proxy_constructor_args, meta_proxy_init_code,
+    minimal_proxy_push0_init_code, parse_proxy_args, simple_account_initializer, ProxyScheme, MAX_IMMUTABLE_ARGS_LEN,
+};
+pub use template::Create2Template;
+pub use uniswap::{
+    uniswap_v2_template, uniswap_v3_template, V2PairKey, V3PoolKey, UNISWAP_V2_FACTORY, UNISWAP_V2_PAIR_INIT_CODE_HASH,
+    UNISWAP_V3_FACTORY, UNISWAP_V3_POOL_INIT_CODE_HASH,
+};
+pub use zksync::{predict_zksync_create2, ZKSYNC_CREATE2_PREFIX};
+
+use checksum::to_checksum_address;
+use salt::{salt_to_bytes, IntoSalt};
+
+// // Minimal Proxy (EIP-1167)
+// 预编译的常量字节数组
+
+// 3d602d80600a3d3981f3363d3d373d3d3d363d73
+pub const PREFIX_BYTES: &[u8] = &[
+    0x3d, 0x60, 0x2d, 0x80, 0x60, 0x0a, 0x3d, 0x39, 0x81, 0xf3,
+    0x36, 0x3d, 0x3d, 0x37, 0x3d, 0x3d, 0x3d, 0x36, 0x3d, 0x73
+];
+// 5af43d82803e903d91602b57fd5bf3ff，最后一个字节是CREATE2的0xff前缀
+pub const SUFFIX_BYTES: &[u8] = &[
+    0x5a, 0xf4, 0x3d, 0x82, 0x80, 0x3e, 0x90, 0x3d,
+    0x91, 0x60, 0x2b, 0x57, 0xfd, 0x5b, 0xf3, 0xff
+];
+
+// 指向implementation的EIP-1167最小代理init code（PREFIX + implementation + SUFFIX，不含最后的0xff），
+// 即实际部署时传给CREATE2的字节
+pub fn minimal_proxy_init_code(implementation: &Address) -> [u8; 55] {
+    let mut init_code = [0u8; 55];
+    init_code[..20].copy_from_slice(PREFIX_BYTES);
+    init_code[20..40].copy_from_slice(implementation.as_bytes());
+    init_code[40..].copy_from_slice(&SUFFIX_BYTES[..15]);
+    init_code
+}
+
+// keccak256(init code)，CREATE2哈希输入的最后32字节
+pub fn init_code_hash(implementation: &Address) -> [u8; 32] {
+    Keccak256::digest(minimal_proxy_init_code(implementation)).into()
+}
+
+// 热路径只检查长度和0x前缀
+#[inline(always)]
+fn validate_address(address: &str) -> Result<(), Create2Error> {
+    if address.len() != 42 || !address.starts_with("0x") {
+        return Err(Create2Error::InvalidAddress("Invalid address format".to_string()));
+    }
+    Ok(())
+}
+
+// 除长度和0x前缀外，还检查每一位都是十六进制字符
+pub fn check_address(address: &str) -> Result<(), Create2Error> {
+    address.parse::<Address>().map(|_| ())
+}
+
+pub fn predict_deterministic_address(
+    implementation: &str,
+    deployer: &str,
+    salt: &str,
+) -> Result<String, Create2Error> {
+    validate_address(implementation)?;
+    validate_address(deployer)?;
+
+    // 使用栈上的固定大小缓冲区
+    let mut bytecode = [0u8; 140]; // 20 + 20 + 16 + 20 + 32 + 32
+    let mut bytecode_hex = [0u8; 280];
+    let mut salt_bytes = [0u8; 32];
+
+    salt_to_bytes(salt, &mut salt_bytes)?;
+
+    // 构建bytecode
+    let mut pos = 0;
+
+    // PREFIX
+    bytecode[pos..pos + 20].copy_from_slice(PREFIX_BYTES);
+    pos += 20;
+
+    // implementation address (去掉0x，转小写)
+    let impl_lower = implementation[2..].to_lowercase();
+    hex::decode_into(&impl_lower, &mut bytecode[pos..pos + 20]);
+    pos += 20;
+
+    // SUFFIX
+    bytecode[pos..pos + 16].copy_from_slice(SUFFIX_BYTES);
+    pos += 16;
+
+    // deployer address (去掉0x，转小写)
+    let depl_lower = deployer[2..].to_lowercase();
+    hex::decode_into(&depl_lower, &mut bytecode[pos..pos + 20]);
+    pos += 20;
+
+    // salt
+    bytecode[pos..pos + 32].copy_from_slice(&salt_bytes);
+
+    // 第一次哈希 - 将前55字节转换为hex
+    hex::encode_into(&bytecode[0..55], &mut bytecode_hex[0..110]);
+
+    // 解码hex并计算第一次哈希
+    let mut first_part = [0u8; 55];
+    hex::decode_into(
+        unsafe { core::str::from_utf8_unchecked(&bytecode_hex[0..110]) },
+        &mut first_part
+    );
+
+    let first_hash = Keccak256::digest(first_part);
+
+    // 构建第二部分的hex
+    hex::encode_into(&bytecode[55..108], &mut bytecode_hex[110..216]);
+    hex::encode_into(&first_hash, &mut bytecode_hex[216..280]);
+
+    // 解码hex并计算第二次哈希
+    let mut second_part = [0u8; 85];
+    hex::decode_into(
+        unsafe { core::str::from_utf8_unchecked(&bytecode_hex[110..280]) },
+        &mut second_part
+    );
+
+    let second_hash = Keccak256::digest(second_part);
+
+    // 取最后20字节作为地址
+    let mut address_hex = [0u8; 40];
+    hex::encode_into(&second_hash[12..32], &mut address_hex);
+
+    let address_str = unsafe { core::str::from_utf8_unchecked(&address_hex) };
+    Ok(to_checksum_address(address_str))
+}
+
+// 直接在字节上计算EVM CREATE2地址，不经过hex字符串
+pub fn predict_deterministic_address_bytes(implementation: &[u8; 20], deployer: &[u8; 20], salt: &[u8; 32]) -> [u8; 20] {
+    predict_address_bytes_with_prefix(EVM_CREATE2_PREFIX, implementation, deployer, salt)
+}
+
+// prefix为CREATE2哈希输入的首字节：EVM为0xff，TRON为0x41
+pub fn predict_address_bytes_with_prefix(
+    prefix: u8,
+    implementation: &[u8; 20],
+    deployer: &[u8; 20],
+    salt: &[u8; 32],
+) -> [u8; 20] {
+    Create2Template::with_prefix(prefix, Address::new(*implementation), Address::new(*deployer)).predict_bytes(salt)
+}
+
+// 批量版本的predict_deterministic_address_bytes，结果写入out，不做堆分配；salts和out长度不同时panic
+pub fn predict_batch_into(implementation: &[u8; 20], deployer: &[u8; 20], salts: &[[u8; 32]], out: &mut [[u8; 20]]) {
+    Create2Template::from_addresses(Address::new(*implementation), Address::new(*deployer)).predict_batch_into(salts, out)
+}
+
+// 0x + 64位十六进制，不区分大小写
+pub fn parse_init_code_hash(hash: &str) -> Result<[u8; 32], Create2Error> {
+    match hash.strip_prefix("0x") {
+        Some(digits) if digits.len() == 64 && hex::is_hex(digits) => {
+            let mut bytes = [0u8; 32];
+            hex::decode_into(digits, &mut bytes);
+            Ok(bytes)
+        }
+        _ => Err(Create2Error::InvalidInitCodeHash(hash.to_string())),
+    }
+}
+
+// 通用CREATE2公式，init_code_hash为keccak256(init code)，适用于任意合约
+pub fn predict_create2(deployer: &Address, salt: impl IntoSalt, init_code_hash: &[u8; 32]) -> Result<Address, Create2Error> {
+    Create2Template::from_init_code_hash(*deployer, *init_code_hash).predict(salt)
+}
+
+// 地址已经解析成Address时使用，省去每次调用的地址校验和hex解码；同一组地址反复预测时用Create2Template
+pub fn predict_address(implementation: &Address, deployer: &Address, salt: impl IntoSalt) -> Result<Address, Create2Error> {
+    Create2Template::from_addresses(*implementation, *deployer).predict(salt)
+}
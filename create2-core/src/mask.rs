@@ -0,0 +1,97 @@
+use crate::{Address, Create2Error};
+use alloc::format;
+use core::fmt;
+use core::str::FromStr;
+
+// 地址的半字节掩码：每个十六进制位是固定值或 ? 通配符，例如 0x????dead 要求第5到8位为dead；
+// 比较时逐字节计算 (address & mask) == value，没有分支和字符串操作，GPU内核也能直接使用这两个数组
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NibbleMask {
+    mask: [u8; 20],
+    value: [u8; 20],
+}
+
+impl NibbleMask {
+    // value中mask为0的位会被清零
+    pub fn new(mask: [u8; 20], value: [u8; 20]) -> Self {
+        let mut masked = value;
+        for (byte, mask) in masked.iter_mut().zip(mask) {
+            *byte &= mask;
+        }
+        NibbleMask { mask, value: masked }
+    }
+
+    #[inline(always)]
+    pub fn matches(&self, address: &[u8; 20]) -> bool {
+        let diff = address
+            .iter()
+            .zip(&self.mask)
+            .zip(&self.value)
+            .fold(0, |diff, ((address, mask), value)| diff | ((address & mask) ^ value));
+        diff == 0
+    }
+
+    #[inline(always)]
+    pub fn matches_address(&self, address: &Address) -> bool {
+        self.matches(address.as_bytes())
+    }
+
+    pub fn mask(&self) -> &[u8; 20] {
+        &self.mask
+    }
+
+    pub fn value(&self) -> &[u8; 20] {
+        &self.value
+    }
+
+    // 固定的半字节个数，随机地址匹配的概率为 16^-n
+    pub fn fixed_nibbles(&self) -> u32 {
+        self.mask.iter().map(|byte| (byte & 0xf0 != 0) as u32 + (byte & 0x0f != 0) as u32).sum()
+    }
+}
+
+// 0x可选，最多40位，不足40位时后面都是通配符；十六进制位不区分大小写
+impl FromStr for NibbleMask {
+    type Err = Create2Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s.strip_prefix("0x").unwrap_or(s);
+        if digits.is_empty() || digits.len() > 40 {
+            return Err(Create2Error::InvalidPattern(format!(
+                "mask {} must have between 1 and 40 nibbles, got {}",
+                s,
+                digits.len()
+            )));
+        }
+        let mut mask = [0u8; 20];
+        let mut value = [0u8; 20];
+        for (i, c) in digits.chars().enumerate() {
+            let nibble = match c {
+                '?' => continue,
+                c => c.to_digit(16).ok_or_else(|| {
+                    Create2Error::InvalidPattern(format!("mask {} may only contain hex digits and ?, got {}", s, c))
+                })? as u8,
+            };
+            let shift = if i % 2 == 0 { 4 } else { 0 };
+            mask[i / 2] |= 0x0f << shift;
+            value[i / 2] |= nibble << shift;
+        }
+        Ok(NibbleMask { mask, value })
+    }
+}
+
+// 输出完整的40位，通配符写作 ?
+impl fmt::Display for NibbleMask {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("0x")?;
+        for i in 0..40 {
+            let shift = if i % 2 == 0 { 4 } else { 0 };
+            if (self.mask[i / 2] >> shift) & 0x0f == 0 {
+                f.write_str("?")?;
+            } else {
+                write!(f, "{:x}", (self.value[i / 2] >> shift) & 0x0f)?;
+            }
+        }
+        Ok(())
+    }
+}
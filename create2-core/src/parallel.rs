@@ -0,0 +1,148 @@
+use crate::salt::IntoSalt;
+use crate::{random_salt, Address, AddressPredictor, CancellationToken, Create2Error, Create2Template, Salt};
+use alloc::vec::Vec;
+use rand_core::RngCore;
+use rayon::prelude::*;
+use std::sync::Mutex;
+
+// rayon并行迭代器的扩展：salts.par_iter().predict_with(&template) 产出 (salt, 地址)，可以接进任意rayon流水线
+pub trait PredictWith: ParallelIterator {
+    fn predict_with(
+        self,
+        template: &Create2Template,
+    ) -> impl ParallelIterator<Item = Result<(Self::Item, Address), Create2Error>>;
+}
+
+impl<I> PredictWith for I
+where
+    I: ParallelIterator,
+    I::Item: IntoSalt,
+{
+    fn predict_with(
+        self,
+        template: &Create2Template,
+    ) -> impl ParallelIterator<Item = Result<(Self::Item, Address), Create2Error>> {
+        let template = *template;
+        self.map(move |salt| template.predict(&salt).map(|address| (salt, address)))
+    }
+}
+
+// AddressPredictor的rayon后端：salt按顺序由同一个随机数生成器生成，哈希在线程池中并行计算，
+// 因此传入带种子的生成器时结果与CpuPredictor相同，可按种子复现
+pub struct RayonPredictor<R> {
+    template: Create2Template,
+    rng: Mutex<R>,
+}
+
+impl<R: RngCore> RayonPredictor<R> {
+    pub fn new(template: Create2Template, rng: R) -> Self {
+        RayonPredictor {
+            template,
+            rng: Mutex::new(rng),
+        }
+    }
+
+    pub fn template(&self) -> &Create2Template {
+        &self.template
+    }
+}
+
+impl<R: RngCore> AddressPredictor for RayonPredictor<R> {
+    type Error = Create2Error;
+
+    // 取消后collect在第一个Err处短路，各线程不再处理新的salt
+    fn predict_batch(&self, salts: &[Salt], cancel: &CancellationToken) -> Result<Vec<Address>, Create2Error> {
+        salts
+            .par_iter()
+            .predict_with(&self.template)
+            .map(|result| {
+                cancel.check()?;
+                result.map(|(_, address)| address)
+            })
+            .collect()
+    }
+
+    fn predict_random(&self, n: usize, cancel: &CancellationToken) -> Result<Vec<(Salt, Address)>, Create2Error> {
+        let salts: Vec<Salt> = {
+            let mut rng = self.rng.lock().unwrap();
+            (0..n).map(|_| random_salt(&mut *rng)).collect()
+        };
+        let addresses = self.predict_batch(&salts, cancel)?;
+        Ok(salts.into_iter().zip(addresses).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CpuPredictor;
+    use alloc::format;
+    use alloc::string::String;
+    use rand_chacha::rand_core::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn template() -> Create2Template {
+        Create2Template::from_addresses([0x11; 20], [0x22; 20])
+    }
+
+    #[test]
+    fn predict_with_matches_template_predict() {
+        let template = template();
+        let salts: Vec<String> = (0..1000).map(|i| format!("salt-{}", i)).collect();
+        let results: Vec<(&String, Address)> = salts
+            .par_iter()
+            .predict_with(&template)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(results.len(), salts.len());
+        for (salt, (result_salt, address)) in salts.iter().zip(&results) {
+            assert_eq!(*result_salt, salt);
+            assert_eq!(*address, template.predict(salt).unwrap());
+        }
+    }
+
+    #[test]
+    fn predict_with_reports_bad_salts_per_item() {
+        let salts = ["short", "this salt is far longer than the thirty-two bytes a salt can hold"];
+        let results: Vec<_> = salts.par_iter().predict_with(&template()).collect();
+        assert_eq!(results[0].as_ref().unwrap().1, template().predict("short").unwrap());
+        assert!(matches!(results[1], Err(Create2Error::InvalidSalt(_))));
+    }
+
+    fn predict_through_trait(
+        predictor: &dyn AddressPredictor<Error = Create2Error>,
+        salts: &[Salt],
+    ) -> (Vec<Address>, Vec<(Salt, Address)>) {
+        let cancel = CancellationToken::new();
+        let batch = predictor.predict_batch(salts, &cancel).unwrap();
+        let random = predictor.predict_random(100, &cancel).unwrap();
+        (batch, random)
+    }
+
+    #[test]
+    fn rayon_predictor_matches_cpu_predictor() {
+        let salts: Vec<Salt> = (0..500).map(|i| format!("salt-{}", i)).collect();
+        let rayon = RayonPredictor::new(template(), ChaCha8Rng::seed_from_u64(7));
+        let cpu = CpuPredictor::new(template(), ChaCha8Rng::seed_from_u64(7));
+        let (batch, random) = predict_through_trait(&rayon, &salts);
+        assert_eq!((batch.clone(), random.clone()), predict_through_trait(&cpu, &salts));
+
+        for (salt, address) in salts.iter().zip(&batch) {
+            assert_eq!(*address, template().predict(salt).unwrap());
+        }
+        for (salt, address) in &random {
+            assert_eq!(salt.len(), 32);
+            assert_eq!(*address, template().predict(salt).unwrap());
+        }
+    }
+
+    #[test]
+    fn rayon_predictor_stops_when_cancelled() {
+        let predictor = RayonPredictor::new(template(), ChaCha8Rng::seed_from_u64(7));
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        let salts: Vec<Salt> = (0..100).map(|i| format!("salt-{}", i)).collect();
+        assert!(matches!(predictor.predict_batch(&salts, &cancel), Err(Create2Error::Cancelled)));
+        assert!(matches!(predictor.predict_random(100, &cancel), Err(Create2Error::Cancelled)));
+    }
+}
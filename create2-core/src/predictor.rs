@@ -0,0 +1,90 @@
+use crate::{hex, Address, CancellationToken, Create2Error, Create2Template};
+use rand_core::RngCore;
+use std::sync::Mutex;
+
+pub type Salt = String;
+
+// CPU和rayon后端共用的批量预测接口，调用方可以不关心具体用哪个后端；
+// 实现合约和部署者在创建后端时就固定下来；cancel被取消后尽快返回Create2Error::Cancelled
+pub trait AddressPredictor {
+    type Error: std::error::Error + From<Create2Error>;
+
+    // 按salt顺序返回地址
+    fn predict_batch(&self, salts: &[Salt], cancel: &CancellationToken) -> Result<Vec<Address>, Self::Error>;
+
+    // 随机生成n个salt（16个随机字节的hex，和benchmark中的salt格式相同）并返回 (salt, 地址)
+    fn predict_random(&self, n: usize, cancel: &CancellationToken) -> Result<Vec<(Salt, Address)>, Self::Error>;
+}
+
+// 随机salt的格式：16个随机字节编码成32个hex字符
+pub fn random_salt(rng: &mut impl RngCore) -> Salt {
+    let mut bytes = [0u8; 16];
+    rng.fill_bytes(&mut bytes);
+    hex::encode(&bytes)
+}
+
+// random_salt每次消耗的32位随机数个数；跳过n个salt时把ChaCha的word_pos直接后移 n·RANDOM_SALT_WORDS，不必逐个生成
+pub const RANDOM_SALT_WORDS: u128 = 4;
+
+// 单线程CPU后端；随机数生成器由调用方提供，传入带种子的生成器即可复现结果
+pub struct CpuPredictor<R> {
+    template: Create2Template,
+    rng: Mutex<R>,
+}
+
+impl<R: RngCore> CpuPredictor<R> {
+    pub fn new(template: Create2Template, rng: R) -> Self {
+        CpuPredictor {
+            template,
+            rng: Mutex::new(rng),
+        }
+    }
+
+    pub fn template(&self) -> &Create2Template {
+        &self.template
+    }
+}
+
+impl<R: RngCore> AddressPredictor for CpuPredictor<R> {
+    type Error = Create2Error;
+
+    fn predict_batch(&self, salts: &[Salt], cancel: &CancellationToken) -> Result<Vec<Address>, Create2Error> {
+        salts
+            .iter()
+            .map(|salt| {
+                cancel.check()?;
+                self.template.predict(salt)
+            })
+            .collect()
+    }
+
+    fn predict_random(&self, n: usize, cancel: &CancellationToken) -> Result<Vec<(Salt, Address)>, Create2Error> {
+        let mut rng = self.rng.lock().unwrap();
+        (0..n)
+            .map(|_| {
+                cancel.check()?;
+                let salt = random_salt(&mut *rng);
+                self.template.predict(&salt).map(|address| (salt, address))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_chacha::rand_core::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn skipping_word_pos_matches_generating_salts() {
+        let mut generated = ChaCha8Rng::seed_from_u64(7);
+        let mut skipped = ChaCha8Rng::seed_from_u64(7);
+        for _ in 0..1001 {
+            random_salt(&mut generated);
+        }
+        skipped.set_word_pos(1001 * RANDOM_SALT_WORDS);
+        assert_eq!(generated.get_word_pos(), skipped.get_word_pos());
+        assert_eq!(random_salt(&mut generated), random_salt(&mut skipped));
+    }
+}
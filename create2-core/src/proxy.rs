@@ -0,0 +1,264 @@
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use sha3::{Digest, Keccak256};
+use crate::{hex, minimal_proxy_init_code, Address, Create2Error, InitCodeTemplate};
+
+// ClonesWithImmutableArgs用PUSH2存runSize = 0x37 + 参数长度 + 2，所以参数最多 0xffff - 0x39 字节，超出直接拒绝而不是截断
+pub const MAX_IMMUTABLE_ARGS_LEN: usize = 0xffff - 0x39;
+
+// 代理合约的init code方案：EIP-1167最小代理，Solady使用PUSH0的最小代理，运行时代码后附加不可变参数的ClonesWithImmutableArgs，
+// 附加metadata的EIP-3448 MetaProxy，编译好的ERC-1967代理（creation code + ABI编码的构造参数），
+// Safe的SafeProxy（creation code + uint256(singleton)），或ERC-4337 SimpleAccountFactory部署的账户
+// （ERC1967Proxy(accountImplementation, initialize(owner))），或用户提供的带占位符的init code模板；
+// 除最小代理外init code哈希都随附加的数据变化
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ProxyScheme {
+    #[default]
+    MinimalProxy,
+    // Solady LibClone.clone_PUSH0，与EIP-1167功能相同但字节码不同，地址也不同；只能部署在支持PUSH0（Shanghai）的链上
+    MinimalProxyPush0,
+    ClonesWithImmutableArgs(Vec<u8>),
+    MetaProxy(Vec<u8>),
+    // constructor_args为None时按ERC1967Proxy(implementation, "")编码；
+    // TransparentUpgradeableProxy等需要更多参数的合约传入完整的构造参数，此时不使用implementation
+    Erc1967 {
+        creation_code: Vec<u8>,
+        constructor_args: Option<Vec<u8>>,
+    },
+    // creation code即SafeProxyFactory.proxyCreationCode()，随Safe版本不同；implementation为singleton
+    SafeProxy {
+        creation_code: Vec<u8>,
+    },
+    // creation code为ERC1967Proxy，implementation为SimpleAccountFactory.accountImplementation()，部署者为工厂；
+    // createAccount(owner, salt)的uint256 salt即CREATE2 salt
+    SimpleAccount {
+        creation_code: Vec<u8>,
+        owner: Address,
+    },
+    // 只能填入 {implementation}；含 {salt} 的模板每个salt的init code都不同，要直接用InitCodeTemplate的predict和search
+    Custom(InitCodeTemplate),
+}
+
+impl ProxyScheme {
+    // 实际部署时传给CREATE2的字节
+    pub fn init_code(&self, implementation: &Address) -> Result<Vec<u8>, Create2Error> {
+        match self {
+            ProxyScheme::MinimalProxy => Ok(minimal_proxy_init_code(implementation).to_vec()),
+            ProxyScheme::MinimalProxyPush0 => Ok(minimal_proxy_push0_init_code(implementation).to_vec()),
+            ProxyScheme::ClonesWithImmutableArgs(args) => clones_with_immutable_args_init_code(implementation, args),
+            ProxyScheme::MetaProxy(metadata) => Ok(meta_proxy_init_code(implementation, metadata)),
+            ProxyScheme::Erc1967 { creation_code, constructor_args } => {
+                let args = constructor_args
+                    .clone()
+                    .unwrap_or_else(|| erc1967_proxy_constructor_args(implementation, &[]));
+                Ok([creation_code.as_slice(), &args].concat())
+            }
+            ProxyScheme::SafeProxy { creation_code } => {
+                let mut singleton = [0u8; 32];
+                singleton[12..].copy_from_slice(implementation.as_bytes());
+                Ok([creation_code.as_slice(), &singleton].concat())
+            }
+            ProxyScheme::SimpleAccount { creation_code, owner } => {
+                let args = erc1967_proxy_constructor_args(implementation, &simple_account_initializer(owner));
+                Ok([creation_code.as_slice(), &args].concat())
+            }
+            ProxyScheme::Custom(template) if template.has_salt() => Err(Create2Error::InvalidProxyArgs(
+                "init code templates with a {salt} placeholder need InitCodeTemplate::predict or search".to_string(),
+            )),
+            ProxyScheme::Custom(template) => Ok(template.init_code(implementation, &[0u8; 32])),
+        }
+    }
+
+    pub fn init_code_hash(&self, implementation: &Address) -> Result<[u8; 32], Create2Error> {
+        Ok(Keccak256::digest(self.init_code(implementation)?).into())
+    }
+}
+
+// Solady的PUSH0最小代理：9字节创建代码 + 45字节运行时代码，用PUSH0代替RETURNDATASIZE压入0
+pub fn minimal_proxy_push0_init_code(implementation: &Address) -> [u8; 54] {
+    let mut init_code = [0u8; 54];
+    // 602d5f8160095f39f3 5f5f365f5f37365f73
+    init_code[..18].copy_from_slice(&[
+        0x60, 0x2d, 0x5f, 0x81, 0x60, 0x09, 0x5f, 0x39, 0xf3, 0x5f, 0x5f, 0x36, 0x5f, 0x5f, 0x37, 0x36, 0x5f, 0x73,
+    ]);
+    init_code[18..38].copy_from_slice(implementation.as_bytes());
+    // 5af43d5f5f3e6029573d5ffd5b3d5ff3
+    init_code[38..].copy_from_slice(&[
+        0x5a, 0xf4, 0x3d, 0x5f, 0x5f, 0x3e, 0x60, 0x29, 0x57, 0x3d, 0x5f, 0xfd, 0x5b, 0x3d, 0x5f, 0xf3,
+    ]);
+    init_code
+}
+
+// wighawag/clones-with-immutable-args的布局：10字节创建代码 + 55字节运行时代码 + 参数 + 2字节extraLength，
+// extraLength是参数长度再加上这2字节本身，运行时用 calldatasize - extraLength 定位参数
+pub fn clones_with_immutable_args_init_code(implementation: &Address, args: &[u8]) -> Result<Vec<u8>, Create2Error> {
+    if args.len() > MAX_IMMUTABLE_ARGS_LEN {
+        return Err(Create2Error::InvalidProxyArgs(format!(
+            "{} bytes of immutable args exceed the {} byte limit",
+            args.len(),
+            MAX_IMMUTABLE_ARGS_LEN
+        )));
+    }
+    // 参数加上末尾的2字节长度
+    let extra_len = (args.len() + 2) as u16;
+    let run_size = extra_len + 0x37;
+
+    let mut init_code = Vec::with_capacity(0x41 + args.len() + 2);
+    // 创建代码：PUSH2 runSize，把运行时代码复制到内存并返回
+    init_code.push(0x61);
+    init_code.extend_from_slice(&run_size.to_be_bytes());
+    init_code.extend_from_slice(&[0x3d, 0x81, 0x60, 0x0a, 0x3d, 0x39, 0xf3]);
+    // 运行时代码：复制calldata，再把附加数据复制到calldata之后
+    init_code.extend_from_slice(&[0x3d, 0x3d, 0x3d, 0x3d, 0x36, 0x3d, 0x3d, 0x37, 0x61]);
+    init_code.extend_from_slice(&extra_len.to_be_bytes());
+    init_code.extend_from_slice(&[0x60, 0x37, 0x36, 0x39, 0x36, 0x61]);
+    init_code.extend_from_slice(&extra_len.to_be_bytes());
+    // DELEGATECALL到implementation并原样返回
+    init_code.extend_from_slice(&[0x01, 0x3d, 0x73]);
+    init_code.extend_from_slice(implementation.as_bytes());
+    init_code.extend_from_slice(&[0x5a, 0xf4, 0x3d, 0x3d, 0x93, 0x80, 0x3e, 0x60, 0x35, 0x57, 0xfd, 0x5b, 0xf3]);
+    // 附加数据
+    init_code.extend_from_slice(args);
+    init_code.extend_from_slice(&extra_len.to_be_bytes());
+    Ok(init_code)
+}
+
+// EIP-3448参考实现的布局：65字节代码（含implementation） + metadata + 32字节大端的metadata长度，
+// 调用时metadata和长度附加在calldata后面转发给implementation
+pub fn meta_proxy_init_code(implementation: &Address, metadata: &[u8]) -> Vec<u8> {
+    let mut init_code = Vec::with_capacity(65 + metadata.len() + 32);
+    init_code.extend_from_slice(&[
+        0x60, 0x0b, 0x38, 0x03, 0x80, 0x60, 0x0b, 0x3d, 0x39, 0x3d, 0xf3, 0x36, 0x3d, 0x3d, 0x37, 0x3d,
+        0x3d, 0x3d, 0x3d, 0x60, 0x36, 0x80, 0x38, 0x03, 0x80, 0x91, 0x36, 0x39, 0x36, 0x01, 0x3d, 0x73,
+    ]);
+    init_code.extend_from_slice(implementation.as_bytes());
+    init_code.extend_from_slice(&[0x5a, 0xf4, 0x3d, 0x3d, 0x93, 0x80, 0x3e, 0x60, 0x34, 0x57, 0xfd, 0x5b, 0xf3]);
+    init_code.extend_from_slice(metadata);
+    // uint256长度
+    init_code.extend_from_slice(&[0u8; 24]);
+    init_code.extend_from_slice(&(metadata.len() as u64).to_be_bytes());
+    init_code
+}
+
+// ERC1967Proxy的构造参数abi.encode(address implementation, bytes data)，data通常是初始化函数的calldata
+pub fn erc1967_proxy_constructor_args(implementation: &Address, data: &[u8]) -> Vec<u8> {
+    let padded_len = data.len().div_ceil(32) * 32;
+    let mut args = vec![0u8; 96 + padded_len];
+    args[12..32].copy_from_slice(implementation.as_bytes());
+    // bytes参数的偏移量，接在两个head word之后
+    args[63] = 0x40;
+    args[88..96].copy_from_slice(&(data.len() as u64).to_be_bytes());
+    args[96..96 + data.len()].copy_from_slice(data);
+    args
+}
+
+// SimpleAccount.initialize(address owner)的calldata，selector为0xc4d66de8
+pub fn simple_account_initializer(owner: &Address) -> [u8; 36] {
+    let mut calldata = [0u8; 36];
+    calldata[..4].copy_from_slice(&[0xc4, 0xd6, 0x6d, 0xe8]);
+    calldata[16..].copy_from_slice(owner.as_bytes());
+    calldata
+}
+
+// 0x前缀的任意长度hex，"0x"表示空参数
+pub fn parse_proxy_args(args: &str) -> Result<Vec<u8>, Create2Error> {
+    match args.strip_prefix("0x") {
+        Some(digits) if digits.len() % 2 == 0 && hex::is_hex(digits) => {
+            let mut bytes = vec![0u8; digits.len() / 2];
+            hex::decode_into(digits, &mut bytes);
+            Ok(bytes)
+        }
+        _ => Err(Create2Error::InvalidProxyArgs(args.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::predict_create2;
+    use crate::salt::SaltPolicy;
+
+    const IMPLEMENTATION: Address = Address::new([0xbe; 20]);
+
+    #[test]
+    fn clones_with_immutable_args_layout() {
+        let implementation = Address::new([0x11; 20]);
+        let init_code = clones_with_immutable_args_init_code(&implementation, &[0xaa, 0xbb, 0xcc]).unwrap();
+        // runSize = 0x37 + 5，extraLength = 3 + 2，末尾的extraLength同样是5而不是参数长度3
+        let expected = format!(
+            "61003c3d81600a3d39f33d3d3d3d363d3d376100056037363936610005013d73{}5af43d3d93803e603557fd5bf3aabbcc0005",
+            "11".repeat(20)
+        );
+        assert_eq!(hex::encode(&init_code), expected);
+    }
+
+    #[test]
+    fn clones_with_immutable_args_rejects_long_args() {
+        let implementation = Address::new([0x11; 20]);
+        assert!(clones_with_immutable_args_init_code(&implementation, &vec![0u8; MAX_IMMUTABLE_ARGS_LEN]).is_ok());
+        assert!(clones_with_immutable_args_init_code(&implementation, &vec![0u8; MAX_IMMUTABLE_ARGS_LEN + 1]).is_err());
+        assert!(clones_with_immutable_args_init_code(&implementation, &vec![0u8; u16::MAX as usize - 1]).is_err());
+    }
+
+    #[test]
+    fn meta_proxy_matches_eip3448_reference() {
+        // EIP-3448参考实现中的字节码，implementation位置为占位的0xbebe…be
+        let reference = "600b380380600b3d393df3363d3d373d3d3d3d60368038038091363936013d73\
+                         bebebebebebebebebebebebebebebebebebebebe5af43d3d93803e603457fd5bf3";
+        assert_eq!(hex::encode(&meta_proxy_init_code(&IMPLEMENTATION, &[])), format!("{}{}", reference, "00".repeat(32)));
+        let init_code = meta_proxy_init_code(&IMPLEMENTATION, &[0xca, 0xfe]);
+        assert_eq!(hex::encode(&init_code), format!("{}cafe{}02", reference, "00".repeat(31)));
+    }
+
+    #[test]
+    fn erc1967_constructor_args_abi_encoding() {
+        let head = format!("{}{}{:064x}", "00".repeat(12), "be".repeat(20), 0x40);
+        // 空data只有长度word
+        assert_eq!(hex::encode(&erc1967_proxy_constructor_args(&IMPLEMENTATION, &[])), format!("{}{:064x}", head, 0));
+        // 36字节的data补零到64字节
+        let owner = Address::new([0x22; 20]);
+        let data = simple_account_initializer(&owner);
+        let expected = format!("{}{:064x}{}{}", head, 36, hex::encode(&data), "00".repeat(28));
+        assert_eq!(hex::encode(&erc1967_proxy_constructor_args(&IMPLEMENTATION, &data)), expected);
+    }
+
+    #[test]
+    fn simple_account_initializer_calldata() {
+        // initialize(address)的selector即keccak256("initialize(address)")的前4字节
+        let owner = Address::new([0x22; 20]);
+        let expected = format!("c4d66de8{}{}", "00".repeat(12), "22".repeat(20));
+        assert_eq!(hex::encode(&simple_account_initializer(&owner)), expected);
+    }
+
+    // 部署者为私钥1对应的地址，implementation为0xbebe…be；期望地址按OpenZeppelin Clones.predictDeterministicAddress
+    // 和viem getContractAddress（CREATE2，超过32字节的字符串salt先keccak256）的公式用alloy-primitives独立算出
+    fn clone_address(salt: [u8; 32]) -> Address {
+        let deployer: Address = "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf".parse().unwrap();
+        let init_code_hash = ProxyScheme::MinimalProxy.init_code_hash(&IMPLEMENTATION).unwrap();
+        predict_create2(&deployer, salt, &init_code_hash).unwrap()
+    }
+
+    #[test]
+    fn bytes32_salt_policy_predicts_clone_address() {
+        // keccak256("clone-1")
+        let salt = "0x58610e1a25c4a1e8db7e284d58fa1b50656e6f2b9b9eebd44d73935ad3ca3572";
+        let address = clone_address(SaltPolicy::Bytes32.to_salt(salt).unwrap());
+        assert_eq!(address, "0x5A35E3267c81ed51D297E17F2c9Fe503A3Ff2481".parse().unwrap());
+        assert!(SaltPolicy::Bytes32.to_salt("clone-1").is_err());
+        assert!(SaltPolicy::Bytes32.to_salt(&salt[..65]).is_err());
+    }
+
+    #[test]
+    fn hash_if_long_salt_policy_predicts_clone_address() {
+        let long = "a salt string that is longer than thirty-two bytes";
+        let address = clone_address(SaltPolicy::HashIfLong.to_salt(long).unwrap());
+        assert_eq!(address, "0x9619ac4F534A1cEDb236aFF89585c02D9532BBAb".parse().unwrap());
+        assert!(SaltPolicy::Pad.to_salt(long).is_err());
+        // 不超过32字节时与Pad相同，右侧补零
+        let address = clone_address(SaltPolicy::HashIfLong.to_salt("short").unwrap());
+        assert_eq!(address, "0xbaa6096eF64dC84aDd6191491e0772e4d7343f04".parse().unwrap());
+        assert_eq!(SaltPolicy::HashIfLong.to_salt("short").unwrap(), SaltPolicy::Pad.to_salt("short").unwrap());
+    }
+}
@@ -0,0 +1,99 @@
+use crate::salt::IntoSalt;
+use crate::Create2Error;
+use core::fmt;
+use sha3::{Digest, Keccak256};
+
+// SafeProxyFactory.createProxyWithNonce实际使用的CREATE2 salt：keccak256(keccak256(initializer) ++ uint256(saltNonce))
+pub fn safe_salt(initializer_hash: &[u8; 32], salt_nonce: &[u8; 32]) -> [u8; 32] {
+    let mut input = [0u8; 64];
+    input[..32].copy_from_slice(initializer_hash);
+    input[32..].copy_from_slice(salt_nonce);
+    Keccak256::digest(input).into()
+}
+
+// 一个Safe部署的(initializer, saltNonce)组合，可以直接传给predict、predict_iter和search：
+// 按上面的公式换算成salt，Display输出saltNonce，找到地址后把它传给createProxyWithNonce即可
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SafeSalt {
+    pub initializer_hash: [u8; 32],
+    pub salt_nonce: u64,
+}
+
+impl SafeSalt {
+    // initializer为Safe.setup的calldata
+    pub fn new(initializer: &[u8], salt_nonce: u64) -> Self {
+        SafeSalt {
+            initializer_hash: Keccak256::digest(initializer).into(),
+            salt_nonce,
+        }
+    }
+}
+
+impl IntoSalt for SafeSalt {
+    #[inline(always)]
+    fn to_salt(&self) -> Result<[u8; 32], Create2Error> {
+        Ok(safe_salt(&self.initializer_hash, &self.salt_nonce.to_salt()?))
+    }
+}
+
+impl fmt::Display for SafeSalt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.salt_nonce)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_init_code_hash, predict_create2, Address, ProxyScheme};
+    use alloc::string::ToString;
+
+    // 主网SafeProxyFactory v1.3.0和Safe v1.3.0 singleton；creation code用一段短字节代替proxyCreationCode()，
+    // initializer为Safe.setup的selector加一个空word。期望值按createProxyWithNonce的公式用alloy-primitives独立算出
+    const FACTORY: &str = "0xa6B71E26C5e0845f74c812102Ca7114b6a896AB2";
+    const SINGLETON: &str = "0xd9Db270c1B5E3Bd161E8c8503c55cEABeE709552";
+    const CREATION_CODE: [u8; 5] = [0x60, 0x80, 0x60, 0x40, 0x52];
+
+    fn initializer() -> [u8; 36] {
+        let mut initializer = [0u8; 36];
+        initializer[..4].copy_from_slice(&[0xb6, 0x3e, 0x80, 0x0d]);
+        initializer
+    }
+
+    fn hash(hex: &str) -> [u8; 32] {
+        parse_init_code_hash(hex).unwrap()
+    }
+
+    #[test]
+    fn salt_hashes_initializer_hash_and_nonce() {
+        let salt = SafeSalt::new(&initializer(), 1);
+        assert_eq!(salt.initializer_hash, hash("0x1c68225766fbad1c91c6c846a878b4b574a1d91def150b53dd743bf63e9df914"));
+        let mut nonce = [0u8; 32];
+        nonce[31] = 1;
+        assert_eq!(
+            safe_salt(&salt.initializer_hash, &nonce),
+            hash("0xfd3a8d0af1432708584ad079628e7247563b9e0059f4d2eab868db21012db403")
+        );
+        assert_eq!(salt.to_salt().unwrap(), safe_salt(&salt.initializer_hash, &nonce));
+        assert_eq!(salt.to_string(), "1");
+    }
+
+    #[test]
+    fn predicts_proxy_address() {
+        let factory: Address = FACTORY.parse().unwrap();
+        let scheme = ProxyScheme::SafeProxy {
+            creation_code: CREATION_CODE.to_vec(),
+        };
+        let init_code_hash = scheme.init_code_hash(&SINGLETON.parse().unwrap()).unwrap();
+        assert_eq!(init_code_hash, hash("0x839ad826dc2b98e9d28a37947c973f8c4fb464c82841de056d6e9c7911267cd5"));
+        for (salt_nonce, expected) in [
+            (0, "0xF4a6B5559A4266aC09a5ff0489827C6B727976B5"),
+            (1, "0xf57EAc8EBfB7D12eA1fF7Da7B206690A67ab87a7"),
+            (1_700_000_000_000, "0x5283CdD59acef380522F5972a2d0560739f46D1c"),
+        ] {
+            let salt = SafeSalt::new(&initializer(), salt_nonce);
+            let proxy = predict_create2(&factory, salt, &init_code_hash).unwrap();
+            assert_eq!(proxy, expected.parse().unwrap(), "saltNonce {}", salt_nonce);
+        }
+    }
+}
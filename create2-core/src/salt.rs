@@ -0,0 +1,132 @@
+use crate::{hex, Create2Error};
+use alloc::format;
+use alloc::string::String;
+use core::fmt;
+use core::str::FromStr;
+use sha3::{Digest, Keccak256};
+
+// salt按原始字节填充到32字节，超出部分无法放进CREATE2的salt
+pub const MAX_SALT_LEN: usize = 32;
+
+#[inline(always)]
+pub fn salt_to_bytes(salt: &str, output: &mut [u8; 32]) -> Result<(), Create2Error> {
+    if salt.len() > MAX_SALT_LEN {
+        return Err(Create2Error::InvalidSalt(format!(
+            "Salt length should not exceed {} characters, got {}",
+            MAX_SALT_LEN,
+            salt.len()
+        )));
+    }
+
+    output.fill(0);
+    let salt_data = salt.as_bytes();
+    output[..salt_data.len()].copy_from_slice(salt_data);
+    Ok(())
+}
+
+// 预测接口接受的salt类型：字符串按原始字节右侧补零（最多32字节，与原有行为相同），
+// [u8; 32]和B256原样使用，u64计数器按大端写入最后8字节（同uint256(n)）。
+// 按引用转换，迭代和搜索接口可以把原来的salt值交还给调用方
+pub trait IntoSalt {
+    fn to_salt(&self) -> Result<[u8; 32], Create2Error>;
+}
+
+impl IntoSalt for str {
+    #[inline(always)]
+    fn to_salt(&self) -> Result<[u8; 32], Create2Error> {
+        let mut bytes = [0u8; 32];
+        salt_to_bytes(self, &mut bytes)?;
+        Ok(bytes)
+    }
+}
+
+impl IntoSalt for String {
+    #[inline(always)]
+    fn to_salt(&self) -> Result<[u8; 32], Create2Error> {
+        self.as_str().to_salt()
+    }
+}
+
+impl IntoSalt for [u8; 32] {
+    #[inline(always)]
+    fn to_salt(&self) -> Result<[u8; 32], Create2Error> {
+        Ok(*self)
+    }
+}
+
+impl IntoSalt for u64 {
+    #[inline(always)]
+    fn to_salt(&self) -> Result<[u8; 32], Create2Error> {
+        let mut bytes = [0u8; 32];
+        bytes[24..].copy_from_slice(&self.to_be_bytes());
+        Ok(bytes)
+    }
+}
+
+#[cfg(feature = "alloy")]
+impl IntoSalt for alloy_primitives::B256 {
+    #[inline(always)]
+    fn to_salt(&self) -> Result<[u8; 32], Create2Error> {
+        Ok(self.0)
+    }
+}
+
+impl<T: IntoSalt + ?Sized> IntoSalt for &T {
+    #[inline(always)]
+    fn to_salt(&self) -> Result<[u8; 32], Create2Error> {
+        (**self).to_salt()
+    }
+}
+
+// 字符串salt的解释方式：Pad为原有行为（原始字节右侧补零，最多32字节）；Bytes32要求0x + 64位hex并原样使用，
+// 与链上用哈希生成bytes32 salt的合约（例如OpenZeppelin Clones）一致；HashIfLong与viem/ethers相同，
+// 不超过32字节时补零，更长的salt取keccak256而不是报错
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "kebab-case"))]
+pub enum SaltPolicy {
+    #[default]
+    Pad,
+    Bytes32,
+    HashIfLong,
+}
+
+impl SaltPolicy {
+    pub fn to_salt(self, salt: &str) -> Result<[u8; 32], Create2Error> {
+        match self {
+            SaltPolicy::Pad => salt.to_salt(),
+            SaltPolicy::Bytes32 => match salt.strip_prefix("0x") {
+                Some(digits) if digits.len() == 64 && hex::is_hex(digits) => {
+                    let mut bytes = [0u8; 32];
+                    hex::decode_into(digits, &mut bytes);
+                    Ok(bytes)
+                }
+                _ => Err(Create2Error::InvalidSalt(format!("{} is not a 0x-prefixed 32-byte hex value", salt))),
+            },
+            SaltPolicy::HashIfLong if salt.len() > MAX_SALT_LEN => Ok(Keccak256::digest(salt.as_bytes()).into()),
+            SaltPolicy::HashIfLong => salt.to_salt(),
+        }
+    }
+}
+
+impl FromStr for SaltPolicy {
+    type Err = Create2Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pad" => Ok(SaltPolicy::Pad),
+            "bytes32" => Ok(SaltPolicy::Bytes32),
+            "hash-if-long" => Ok(SaltPolicy::HashIfLong),
+            _ => Err(Create2Error::InvalidSalt(format!("unknown salt policy {} (expected pad, bytes32 or hash-if-long)", s))),
+        }
+    }
+}
+
+impl fmt::Display for SaltPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SaltPolicy::Pad => write!(f, "pad"),
+            SaltPolicy::Bytes32 => write!(f, "bytes32"),
+            SaltPolicy::HashIfLong => write!(f, "hash-if-long"),
+        }
+    }
+}
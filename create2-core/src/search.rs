@@ -0,0 +1,173 @@
+use crate::salt::IntoSalt;
+use crate::{Address, CancellationToken, Create2Error, Create2Template};
+use alloc::format;
+use core::fmt;
+use core::ops::ControlFlow;
+use core::str::FromStr;
+
+impl Create2Template {
+    // 依次计算salt_source中每个salt的地址，predicate命中时调用on_match(salt, 地址, 第几次尝试)；
+    // on_match返回Break、cancel被取消或salt用完时结束，返回实际尝试的次数（取消时不报错，调用方自己查看token）。
+    // 每个salt之前检查一次cancel，取消后不会再从salt_source取salt。停止条件、进度和检查点由调用方在
+    // salt_source和on_match里处理，库里不做任何输出
+    pub fn search<S, P, F, E>(
+        &self,
+        salt_source: S,
+        cancel: &CancellationToken,
+        predicate: P,
+        on_match: F,
+    ) -> Result<usize, E>
+    where
+        S: IntoIterator,
+        S::Item: IntoSalt,
+        P: FnMut(&Address) -> bool,
+        F: FnMut(&S::Item, &Address, usize) -> Result<ControlFlow<()>, E>,
+        E: From<Create2Error>,
+    {
+        search_with(|salt| self.predict(salt), salt_source, cancel, predicate, on_match)
+    }
+}
+
+// search的循环本身，地址由predict计算；init code随salt变化的方案（例如ERC-6551）也用它
+pub(crate) fn search_with<S, Q, P, F, E>(
+    mut predict: Q,
+    salt_source: S,
+    cancel: &CancellationToken,
+    mut predicate: P,
+    mut on_match: F,
+) -> Result<usize, E>
+where
+    S: IntoIterator,
+    Q: FnMut(&S::Item) -> Result<Address, Create2Error>,
+    P: FnMut(&Address) -> bool,
+    F: FnMut(&S::Item, &Address, usize) -> Result<ControlFlow<()>, E>,
+    E: From<Create2Error>,
+{
+    let mut attempts = 0;
+    let mut salts = salt_source.into_iter();
+    while !cancel.is_cancelled() {
+        let Some(salt) = salts.next() else {
+            break;
+        };
+        let address = predict(&salt)?;
+        attempts += 1;
+        if predicate(&address) && on_match(&salt, &address, attempts)?.is_break() {
+            break;
+        }
+    }
+    Ok(attempts)
+}
+
+pub fn search<S, P, F, E>(
+    implementation: &str,
+    deployer: &str,
+    salt_source: S,
+    cancel: &CancellationToken,
+    predicate: P,
+    on_match: F,
+) -> Result<usize, E>
+where
+    S: IntoIterator,
+    S::Item: IntoSalt,
+    P: FnMut(&Address) -> bool,
+    F: FnMut(&S::Item, &Address, usize) -> Result<ControlFlow<()>, E>,
+    E: From<Create2Error>,
+{
+    Create2Template::new(implementation, deployer)?.search(salt_source, cancel, predicate, on_match)
+}
+
+// 每个salt依次用templates中的每个模板计算地址（例如同一个实现合约的多个部署者），salt只转换一次；
+// on_match额外收到命中模板的下标，同一个salt在多个模板下命中时各报告一次。返回的尝试次数按salt计
+pub fn search_templates<S, P, F, E>(
+    templates: &[Create2Template],
+    salt_source: S,
+    cancel: &CancellationToken,
+    mut predicate: P,
+    mut on_match: F,
+) -> Result<usize, E>
+where
+    S: IntoIterator,
+    S::Item: IntoSalt,
+    P: FnMut(&Address) -> bool,
+    F: FnMut(usize, &S::Item, &Address, usize) -> Result<ControlFlow<()>, E>,
+    E: From<Create2Error>,
+{
+    let mut attempts = 0;
+    let mut salts = salt_source.into_iter();
+    while !cancel.is_cancelled() {
+        let Some(salt) = salts.next() else {
+            break;
+        };
+        let bytes = salt.to_salt()?;
+        attempts += 1;
+        for (index, template) in templates.iter().enumerate() {
+            let address = Address::new(template.predict_bytes(&bytes));
+            if predicate(&address) && on_match(index, &salt, &address, attempts)?.is_break() {
+                return Ok(attempts);
+            }
+        }
+    }
+    Ok(attempts)
+}
+
+// 内置的靓号规则，可直接作为search的predicate：repeated:n 要求末尾n个半字节相同，palindrome:n 要求末尾n个半字节
+// 构成回文；都在地址的原始字节上比较，与checksum大小写无关
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Vanity {
+    Repeated(usize),
+    Palindrome(usize),
+}
+
+impl Vanity {
+    #[inline(always)]
+    pub fn matches(&self, address: &[u8; 20]) -> bool {
+        let nibble = |i: usize| (address[i / 2] >> (4 - 4 * (i % 2))) & 0x0f;
+        match *self {
+            Vanity::Repeated(n) => (40 - n..39).all(|i| nibble(i) == nibble(i + 1)),
+            Vanity::Palindrome(n) => (0..n / 2).all(|i| nibble(40 - n + i) == nibble(39 - i)),
+        }
+    }
+
+    #[inline(always)]
+    pub fn matches_address(&self, address: &Address) -> bool {
+        self.matches(address.as_bytes())
+    }
+
+    // 随机地址匹配的概率：相同的n位只有第一位自由，回文只有前一半（奇数时加上中间一位）自由
+    pub fn probability(&self) -> f64 {
+        let fixed = match *self {
+            Vanity::Repeated(n) => n - 1,
+            Vanity::Palindrome(n) => n / 2,
+        };
+        // no_std下没有f64::powi
+        (0..fixed).fold(1.0, |probability, _| probability / 16.0)
+    }
+}
+
+// repeated:<n> 或 palindrome:<n>，n为2到40个半字节
+impl FromStr for Vanity {
+    type Err = Create2Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Create2Error::InvalidPattern(format!("vanity {} (expected repeated:<n> or palindrome:<n>)", s));
+        let (name, n) = s.split_once(':').ok_or_else(invalid)?;
+        let n: usize = n.parse().map_err(|_| invalid())?;
+        if !(2..=40).contains(&n) {
+            return Err(Create2Error::InvalidPattern(format!("vanity {} must cover between 2 and 40 nibbles", s)));
+        }
+        match name {
+            "repeated" => Ok(Vanity::Repeated(n)),
+            "palindrome" => Ok(Vanity::Palindrome(n)),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl fmt::Display for Vanity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Vanity::Repeated(n) => write!(f, "repeated:{}", n),
+            Vanity::Palindrome(n) => write!(f, "palindrome:{}", n),
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use starknet_crypto::{pedersen_hash, Felt};
+
+// Starknet不使用CREATE2，合约地址由Pedersen哈希得到：
+// pedersen_hash_on_elements([PREFIX, deployer, salt, class_hash, pedersen_hash_on_elements(calldata)]) mod 2^251 - 256
+
+// Cairo短字符串"STARKNET_CONTRACT_ADDRESS"
+const CONTRACT_ADDRESS_PREFIX: Felt = Felt::from_hex_unchecked("0x535441524b4e45545f434f4e54524143545f41444452455353");
+// 地址取模的上界 2^251 - 256
+const ADDR_BOUND: Felt = Felt::from_hex_unchecked("0x7ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff00");
+
+// cairo-lang的compute_hash_on_elements：从0开始依次与每个元素做pedersen，最后再哈希元素个数
+pub fn pedersen_hash_on_elements(elements: &[Felt]) -> Felt {
+    let hash = elements.iter().fold(Felt::ZERO, |hash, element| pedersen_hash(&hash, element));
+    pedersen_hash(&hash, &Felt::from(elements.len() as u64))
+}
+
+// deployer为部署交易里的deployer_address：deploy_account交易为0，UDC部署时为UDC地址（unique时salt还要再换算）
+pub fn starknet_contract_address(deployer: &Felt, salt: &Felt, class_hash: &Felt, constructor_calldata: &[Felt]) -> Felt {
+    StarknetTemplate::new(*deployer, *class_hash, constructor_calldata).predict(salt)
+}
+
+// 热循环使用的模板：前缀和deployer两次哈希以及calldata的哈希只算一次，之后每个salt只需4次pedersen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StarknetTemplate {
+    deployer_hash: Felt,
+    class_hash: Felt,
+    calldata_hash: Felt,
+}
+
+impl StarknetTemplate {
+    pub fn new(deployer: Felt, class_hash: Felt, constructor_calldata: &[Felt]) -> Self {
+        StarknetTemplate {
+            deployer_hash: pedersen_hash(&pedersen_hash(&Felt::ZERO, &CONTRACT_ADDRESS_PREFIX), &deployer),
+            class_hash,
+            calldata_hash: pedersen_hash_on_elements(constructor_calldata),
+        }
+    }
+
+    pub fn predict(&self, salt: &Felt) -> Felt {
+        let hash = pedersen_hash(&self.deployer_hash, salt);
+        let hash = pedersen_hash(&hash, &self.class_hash);
+        let hash = pedersen_hash(&hash, &self.calldata_hash);
+        let address = pedersen_hash(&hash, &Felt::from(5u64));
+        // 哈希小于有限域的模数，而模数小于2倍上界，最多减一次
+        if address >= ADDR_BOUND {
+            address - ADDR_BOUND
+        } else {
+            address
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use crate::salt::IntoSalt;
+use crate::{init_code_hash, Address, Create2Error, ProxyScheme, EVM_CREATE2_PREFIX};
+use sha3::{Digest, Keccak256};
+
+// 固定implementation和deployer的CREATE2预测：地址只校验、解码一次，init code哈希也只算一次，
+// 之后每个salt只需要一次Keccak256。也可以直接用任意合约的init code哈希创建，此时没有implementation
+#[derive(Debug, Clone, Copy)]
+pub struct Create2Template {
+    implementation: Option<Address>,
+    deployer: Address,
+    prefix: u8,
+    init_code_hash: [u8; 32],
+}
+
+impl Create2Template {
+    pub fn new(implementation: &str, deployer: &str) -> Result<Self, Create2Error> {
+        Ok(Self::from_addresses(implementation.parse::<Address>()?, deployer.parse::<Address>()?))
+    }
+
+    // 也接受alloy_primitives::Address（需要alloy feature）
+    pub fn from_addresses(implementation: impl Into<Address>, deployer: impl Into<Address>) -> Self {
+        Self::with_prefix(EVM_CREATE2_PREFIX, implementation.into(), deployer.into())
+    }
+
+    // prefix为CREATE2哈希输入的首字节：EVM为0xff，TRON为0x41
+    pub fn with_prefix(prefix: u8, implementation: Address, deployer: Address) -> Self {
+        Create2Template {
+            implementation: Some(implementation),
+            deployer,
+            prefix,
+            init_code_hash: init_code_hash(&implementation),
+        }
+    }
+
+    // 通用CREATE2：keccak256(0xff ++ deployer ++ salt ++ init_code_hash)[12..]，不限于最小代理
+    pub fn from_init_code_hash(deployer: impl Into<Address>, init_code_hash: [u8; 32]) -> Self {
+        Self::with_init_code_hash(EVM_CREATE2_PREFIX, deployer.into(), init_code_hash)
+    }
+
+    // 按代理方案计算init code哈希；只有最小代理保留implementation，其余方案的哈希随参数变化，按通用CREATE2计算
+    pub fn from_scheme(
+        scheme: &ProxyScheme,
+        implementation: impl Into<Address>,
+        deployer: impl Into<Address>,
+    ) -> Result<Self, Create2Error> {
+        Self::with_scheme(EVM_CREATE2_PREFIX, scheme, implementation.into(), deployer.into())
+    }
+
+    pub fn with_scheme(prefix: u8, scheme: &ProxyScheme, implementation: Address, deployer: Address) -> Result<Self, Create2Error> {
+        Ok(match scheme {
+            ProxyScheme::MinimalProxy => Self::with_prefix(prefix, implementation, deployer),
+            _ => Self::with_init_code_hash(prefix, deployer, scheme.init_code_hash(&implementation)?),
+        })
+    }
+
+    pub fn with_init_code_hash(prefix: u8, deployer: Address, init_code_hash: [u8; 32]) -> Self {
+        Create2Template {
+            implementation: None,
+            deployer,
+            prefix,
+            init_code_hash,
+        }
+    }
+
+    // 只有EIP-1167最小代理模板才有implementation
+    pub fn implementation(&self) -> Option<&Address> {
+        self.implementation.as_ref()
+    }
+
+    pub fn deployer(&self) -> &Address {
+        &self.deployer
+    }
+
+    pub fn init_code_hash(&self) -> &[u8; 32] {
+        &self.init_code_hash
+    }
+
+    #[inline(always)]
+    pub fn predict(&self, salt: impl IntoSalt) -> Result<Address, Create2Error> {
+        Ok(Address::new(self.predict_bytes(&salt.to_salt()?)))
+    }
+
+    #[inline(always)]
+    pub fn predict_bytes(&self, salt: &[u8; 32]) -> [u8; 20] {
+        // prefix + deployer + salt + keccak256(init code)
+        let mut input = [0u8; 85];
+        input[0] = self.prefix;
+        input[1..21].copy_from_slice(self.deployer.as_bytes());
+        input[21..53].copy_from_slice(salt);
+        input[53..].copy_from_slice(&self.init_code_hash);
+        let hash = Keccak256::digest(input);
+
+        let mut address = [0u8; 20];
+        address.copy_from_slice(&hash[12..32]);
+        address
+    }
+
+    // 批量写入调用方提供的缓冲区，不做任何堆分配；salts和out长度必须相同
+    pub fn predict_batch_into(&self, salts: &[[u8; 32]], out: &mut [[u8; 20]]) {
+        assert_eq!(salts.len(), out.len(), "salts and out must have the same length");
+        for (salt, address) in salts.iter().zip(out.iter_mut()) {
+            *address = self.predict_bytes(salt);
+        }
+    }
+}
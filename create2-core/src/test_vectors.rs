@@ -0,0 +1,200 @@
+use crate::{parse_init_code_hash, Address, Create2Template, EVM_CREATE2_PREFIX, TRON_CREATE2_PREFIX};
+
+// 一条CREATE2向量：prefix为哈希输入的首字节（EVM 0xff，TRON 0x41）；有implementation时
+// 按EIP-1167最小代理构造模板，init_code_hash即它的最小代理哈希，否则按通用CREATE2直接用init_code_hash
+#[derive(Debug, Clone, Copy)]
+pub struct Create2Vector {
+    pub name: &'static str,
+    pub prefix: u8,
+    pub deployer: &'static str,
+    pub implementation: Option<&'static str>,
+    pub salt: &'static str,
+    pub init_code_hash: &'static str,
+    pub expected: &'static str,
+}
+
+impl Create2Vector {
+    pub fn template(&self) -> Create2Template {
+        let deployer = parse_address(self.deployer);
+        match self.implementation {
+            Some(implementation) => Create2Template::with_prefix(self.prefix, parse_address(implementation), deployer),
+            None => Create2Template::with_init_code_hash(self.prefix, deployer, self.init_code_hash()),
+        }
+    }
+
+    pub fn salt(&self) -> [u8; 32] {
+        parse_init_code_hash(self.salt).expect("test vector salt is 0x + 64 hex digits")
+    }
+
+    pub fn init_code_hash(&self) -> [u8; 32] {
+        parse_init_code_hash(self.init_code_hash).expect("test vector init code hash is 0x + 64 hex digits")
+    }
+
+    pub fn expected(&self) -> Address {
+        parse_address(self.expected)
+    }
+}
+
+fn parse_address(address: &str) -> Address {
+    address.parse().expect("test vector address is 0x + 40 hex digits")
+}
+
+const ZERO: &str = "0x0000000000000000000000000000000000000000000000000000000000000000";
+// keccak256(0x00)、keccak256(0xdeadbeef)、keccak256(0xdeadbeef × 11)、keccak256(空)
+const HASH_00: &str = "0xbc36789e7a1e281436464229828f817d6612f7b477d66591ff96a9e064bcc98a";
+const HASH_DEADBEEF: &str = "0xd4fd4e189132273036449fc9e11198c739161b4c0116a9a2dccdfa1c492006f1";
+const HASH_DEADBEEF_X11: &str = "0xdba4863677690f1376cf73b7bcb3dee7f78ba4cceb9f1973dc10536d2f470ca8";
+const HASH_EMPTY: &str = "0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470";
+// 0x11…11作为implementation时的EIP-1167和PUSH0最小代理init code哈希
+const HASH_MINIMAL_PROXY: &str = "0xa2d88143eeea48efcc9f4a249ebe53d688d02897963ffc34032b89239ec11b5e";
+const HASH_MINIMAL_PROXY_PUSH0: &str = "0x5875eb64a6e3677551b192396aba7b3c3b314f51f334db782a3ccbb9ae502dec";
+
+// 前七条是EIP-1014规范里的例子，uniswap-v2是主网上的USDC/WETH交易对，这些期望地址都来自链外的权威来源；
+// 其余几条覆盖GPU kernel的最小代理模式和TRON前缀，期望地址由本crate的CPU实现算出后固定下来
+pub const CREATE2_VECTORS: &[Create2Vector] = &[
+    Create2Vector {
+        name: "eip1014-0",
+        prefix: EVM_CREATE2_PREFIX,
+        deployer: "0x0000000000000000000000000000000000000000",
+        implementation: None,
+        salt: ZERO,
+        init_code_hash: HASH_00,
+        expected: "0x4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38",
+    },
+    Create2Vector {
+        name: "eip1014-1",
+        prefix: EVM_CREATE2_PREFIX,
+        deployer: "0xdeadbeef00000000000000000000000000000000",
+        implementation: None,
+        salt: ZERO,
+        init_code_hash: HASH_00,
+        expected: "0xB928f69Bb1D91Cd65274e3c79d8986362984fDA3",
+    },
+    Create2Vector {
+        name: "eip1014-2",
+        prefix: EVM_CREATE2_PREFIX,
+        deployer: "0xdeadbeef00000000000000000000000000000000",
+        implementation: None,
+        salt: "0x000000000000000000000000feed000000000000000000000000000000000000",
+        init_code_hash: HASH_00,
+        expected: "0xD04116cDd17beBE565EB2422F2497E06cC1C9833",
+    },
+    Create2Vector {
+        name: "eip1014-3",
+        prefix: EVM_CREATE2_PREFIX,
+        deployer: "0x0000000000000000000000000000000000000000",
+        implementation: None,
+        salt: ZERO,
+        init_code_hash: HASH_DEADBEEF,
+        expected: "0x70f2b2914A2a4b783FaEFb75f459A580616Fcb5e",
+    },
+    Create2Vector {
+        name: "eip1014-4",
+        prefix: EVM_CREATE2_PREFIX,
+        deployer: "0x00000000000000000000000000000000deadbeef",
+        implementation: None,
+        salt: "0x00000000000000000000000000000000000000000000000000000000cafebabe",
+        init_code_hash: HASH_DEADBEEF,
+        expected: "0x60f3f640a8508fC6a86d45DF051962668E1e8AC7",
+    },
+    Create2Vector {
+        name: "eip1014-5",
+        prefix: EVM_CREATE2_PREFIX,
+        deployer: "0x00000000000000000000000000000000deadbeef",
+        implementation: None,
+        salt: "0x00000000000000000000000000000000000000000000000000000000cafebabe",
+        init_code_hash: HASH_DEADBEEF_X11,
+        expected: "0x1d8bfDC5D46DC4f61D6b6115972536eBE6A8854C",
+    },
+    Create2Vector {
+        name: "eip1014-6",
+        prefix: EVM_CREATE2_PREFIX,
+        deployer: "0x0000000000000000000000000000000000000000",
+        implementation: None,
+        salt: ZERO,
+        init_code_hash: HASH_EMPTY,
+        expected: "0xE33C0C7F7df4809055C3ebA6c09CFe4BaF1BD9e0",
+    },
+    Create2Vector {
+        name: "uniswap-v2-usdc-weth",
+        prefix: EVM_CREATE2_PREFIX,
+        deployer: "0x5C69bEe701ef814a2B6a3EDD4B1652CB9cc5aA6f",
+        implementation: None,
+        salt: "0x85053f65cd1ece2bb37b70c13d66eadebf2779df5ddd68cf12f3ccfdc6bfe760",
+        init_code_hash: "0x96e8ac4277198ff8b6f785478aa9a39f403cb768dd02cbee326c3e7da348845f",
+        expected: "0xB4e16d0168e52d35CaCD2c6185b44281Ec28C9Dc",
+    },
+    Create2Vector {
+        name: "minimal-proxy",
+        prefix: EVM_CREATE2_PREFIX,
+        deployer: "0x2222222222222222222222222222222222222222",
+        implementation: Some("0x1111111111111111111111111111111111111111"),
+        salt: "0x3333333333333333333333333333333333333333333333333333333333333333",
+        init_code_hash: HASH_MINIMAL_PROXY,
+        expected: "0xa0bd9a39224e7b5b445dbc5b80db3ee8c73a6abd",
+    },
+    Create2Vector {
+        name: "minimal-proxy-push0",
+        prefix: EVM_CREATE2_PREFIX,
+        deployer: "0x2222222222222222222222222222222222222222",
+        implementation: None,
+        salt: "0x4444444444444444444444444444444444444444444444444444444444444444",
+        init_code_hash: HASH_MINIMAL_PROXY_PUSH0,
+        expected: "0x088eba9d45fed39ab4d4fdfd5d1b55317dda3edb",
+    },
+    Create2Vector {
+        name: "tron-minimal-proxy",
+        prefix: TRON_CREATE2_PREFIX,
+        deployer: "0x2222222222222222222222222222222222222222",
+        implementation: Some("0x1111111111111111111111111111111111111111"),
+        salt: "0x3333333333333333333333333333333333333333333333333333333333333333",
+        init_code_hash: HASH_MINIMAL_PROXY,
+        expected: "0x8a529aa9d7e3a271b2448b3d1bb6a8d7b28d0d08",
+    },
+    Create2Vector {
+        name: "tron-minimal-proxy-push0",
+        prefix: TRON_CREATE2_PREFIX,
+        deployer: "0x2222222222222222222222222222222222222222",
+        implementation: None,
+        salt: "0x4444444444444444444444444444444444444444444444444444444444444444",
+        init_code_hash: HASH_MINIMAL_PROXY_PUSH0,
+        expected: "0xfb7f42ccee8632f44d83a5c832636fe6c3ed059e",
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::salt::IntoSalt;
+    use crate::{init_code_hash, uniswap_v2_template, ProxyScheme, V2PairKey, UNISWAP_V2_FACTORY};
+
+    #[test]
+    fn cpu_matches_every_vector() {
+        for vector in CREATE2_VECTORS {
+            let template = vector.template();
+            assert_eq!(template.init_code_hash(), &vector.init_code_hash(), "{}", vector.name);
+            assert_eq!(template.predict_bytes(&vector.salt()), *vector.expected().as_bytes(), "{}", vector.name);
+            assert_eq!(template.predict(vector.salt()).unwrap(), vector.expected(), "{}", vector.name);
+        }
+    }
+
+    #[test]
+    fn proxy_hashes_match_their_schemes() {
+        let implementation = parse_address("0x1111111111111111111111111111111111111111");
+        assert_eq!(init_code_hash(&implementation), parse_init_code_hash(HASH_MINIMAL_PROXY).unwrap());
+        let push0 = ProxyScheme::MinimalProxyPush0.init_code_hash(&implementation).unwrap();
+        assert_eq!(push0, parse_init_code_hash(HASH_MINIMAL_PROXY_PUSH0).unwrap());
+    }
+
+    #[test]
+    fn uniswap_vector_matches_pair_key() {
+        let vector = CREATE2_VECTORS.iter().find(|vector| vector.name == "uniswap-v2-usdc-weth").unwrap();
+        let key = V2PairKey::new(
+            parse_address("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"),
+            parse_address("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"),
+        )
+        .unwrap();
+        assert_eq!(key.to_salt().unwrap(), vector.salt());
+        assert_eq!(uniswap_v2_template(UNISWAP_V2_FACTORY).predict(key).unwrap(), vector.expected());
+    }
+}
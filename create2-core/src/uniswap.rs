@@ -0,0 +1,164 @@
+use crate::salt::IntoSalt;
+use crate::{Address, Create2Error, Create2Template};
+use alloc::format;
+use core::fmt;
+use sha3::{Digest, Keccak256};
+
+// Uniswap V2在主网的UniswapV2Factory 0x5C69bEe701ef814a2B6a3EDD4B1652CB9cc5aA6f
+pub const UNISWAP_V2_FACTORY: Address = Address::new([
+    0x5c, 0x69, 0xbe, 0xe7, 0x01, 0xef, 0x81, 0x4a, 0x2b, 0x6a,
+    0x3e, 0xdd, 0x4b, 0x16, 0x52, 0xcb, 0x9c, 0xc5, 0xaa, 0x6f,
+]);
+
+// v2-periphery UniswapV2Library.pairFor中的init code哈希，即UniswapV2Pair creation code的keccak256
+pub const UNISWAP_V2_PAIR_INIT_CODE_HASH: [u8; 32] = [
+    0x96, 0xe8, 0xac, 0x42, 0x77, 0x19, 0x8f, 0xf8, 0xb6, 0xf7, 0x85, 0x47, 0x8a, 0xa9, 0xa3, 0x9f,
+    0x40, 0x3c, 0xb7, 0x68, 0xdd, 0x02, 0xcb, 0xee, 0x32, 0x6c, 0x3e, 0x7d, 0xa3, 0x48, 0x84, 0x5f,
+];
+
+// Uniswap V3在主网和大多数链上的UniswapV3Factory 0x1F98431c8aD98523631AE4a59f267346ea31F984
+pub const UNISWAP_V3_FACTORY: Address = Address::new([
+    0x1f, 0x98, 0x43, 0x1c, 0x8a, 0xd9, 0x85, 0x23, 0x63, 0x1a,
+    0xe4, 0xa5, 0x9f, 0x26, 0x73, 0x46, 0xea, 0x31, 0xf9, 0x84,
+]);
+
+// v3-periphery PoolAddress.POOL_INIT_CODE_HASH，即UniswapV3Pool creation code的keccak256
+pub const UNISWAP_V3_POOL_INIT_CODE_HASH: [u8; 32] = [
+    0xe3, 0x4f, 0x19, 0x9b, 0x19, 0xb2, 0xb4, 0xf4, 0x7f, 0x68, 0x44, 0x26, 0x19, 0xd5, 0x55, 0x52,
+    0x7d, 0x24, 0x4f, 0x78, 0xa3, 0x29, 0x7e, 0xa8, 0x93, 0x25, 0xf8, 0x43, 0xf8, 0x7b, 0x8b, 0x54,
+];
+
+// 一个V2交易对：token按地址从小到大排序；作为salt时换算成keccak256(abi.encodePacked(token0, token1))，
+// Display输出 token0/token1
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct V2PairKey {
+    pub token0: Address,
+    pub token1: Address,
+}
+
+impl V2PairKey {
+    // 与UniswapV2Library.sortTokens一样接受任意顺序的两个token
+    pub fn new(token_a: Address, token_b: Address) -> Result<Self, Create2Error> {
+        let (token0, token1) = sort_tokens(token_a, token_b)?;
+        Ok(V2PairKey { token0, token1 })
+    }
+}
+
+impl IntoSalt for V2PairKey {
+    #[inline(always)]
+    fn to_salt(&self) -> Result<[u8; 32], Create2Error> {
+        let mut input = [0u8; 40];
+        input[..20].copy_from_slice(self.token0.as_bytes());
+        input[20..].copy_from_slice(self.token1.as_bytes());
+        Ok(Keccak256::digest(input).into())
+    }
+}
+
+impl fmt::Display for V2PairKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.token0, self.token1)
+    }
+}
+
+// 一个V3池子的PoolKey：token按地址从小到大排序，fee为uint24（例如500、3000、10000）；
+// 作为salt时换算成keccak256(abi.encode(token0, token1, fee))，Display输出 token0/token1/fee
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct V3PoolKey {
+    pub token0: Address,
+    pub token1: Address,
+    pub fee: u32,
+}
+
+impl V3PoolKey {
+    // 与PoolAddress.getPoolKey一样接受任意顺序的两个token
+    pub fn new(token_a: Address, token_b: Address, fee: u32) -> Result<Self, Create2Error> {
+        if fee > 0xff_ffff {
+            return Err(Create2Error::InvalidSalt(format!("fee {} does not fit in uint24", fee)));
+        }
+        let (token0, token1) = sort_tokens(token_a, token_b)?;
+        Ok(V3PoolKey { token0, token1, fee })
+    }
+}
+
+impl IntoSalt for V3PoolKey {
+    #[inline(always)]
+    fn to_salt(&self) -> Result<[u8; 32], Create2Error> {
+        let mut input = [0u8; 96];
+        input[12..32].copy_from_slice(self.token0.as_bytes());
+        input[44..64].copy_from_slice(self.token1.as_bytes());
+        input[92..].copy_from_slice(&self.fee.to_be_bytes());
+        Ok(Keccak256::digest(input).into())
+    }
+}
+
+impl fmt::Display for V3PoolKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}/{}", self.token0, self.token1, self.fee)
+    }
+}
+
+fn sort_tokens(token_a: Address, token_b: Address) -> Result<(Address, Address), Create2Error> {
+    if token_a == token_b {
+        return Err(Create2Error::InvalidSalt(format!("pool tokens must differ, got {} twice", token_a)));
+    }
+    if token_a.as_bytes() < token_b.as_bytes() {
+        Ok((token_a, token_b))
+    } else {
+        Ok((token_b, token_a))
+    }
+}
+
+// 以工厂为部署者、pair init code哈希为init code哈希的模板，predict(V2PairKey)即得到交易对地址；
+// 官方部署传UNISWAP_V2_FACTORY，pair字节码不同的fork直接用from_init_code_hash
+pub fn uniswap_v2_template(factory: Address) -> Create2Template {
+    Create2Template::from_init_code_hash(factory, UNISWAP_V2_PAIR_INIT_CODE_HASH)
+}
+
+// 以工厂为部署者、POOL_INIT_CODE_HASH为init code哈希的模板，predict(V3PoolKey)即得到池子地址；
+// 官方部署传UNISWAP_V3_FACTORY，pool字节码不同的fork直接用from_init_code_hash
+pub fn uniswap_v3_template(factory: Address) -> Create2Template {
+    Create2Template::from_init_code_hash(factory, UNISWAP_V3_POOL_INIT_CODE_HASH)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::str::FromStr;
+
+    fn addr(s: &str) -> Address {
+        Address::from_str(s).unwrap()
+    }
+
+    const USDC: &str = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+    const WETH: &str = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2";
+
+    #[test]
+    fn v2_usdc_weth_pair() {
+        let key = V2PairKey::new(addr(USDC), addr(WETH)).unwrap();
+        let pair = uniswap_v2_template(UNISWAP_V2_FACTORY).predict(key).unwrap();
+        assert_eq!(pair, addr("0xB4e16d0168e52d35CaCD2c6185b44281Ec28C9Dc"));
+    }
+
+    #[test]
+    fn v3_usdc_weth_500_pool() {
+        let key = V3PoolKey::new(addr(USDC), addr(WETH), 500).unwrap();
+        let pool = uniswap_v3_template(UNISWAP_V3_FACTORY).predict(key).unwrap();
+        assert_eq!(pool, addr("0x88e6A0c2dDD26FEEb64F039a2c41296FcB3f5640"));
+    }
+
+    #[test]
+    fn token_order_does_not_matter() {
+        assert_eq!(V2PairKey::new(addr(USDC), addr(WETH)).unwrap(), V2PairKey::new(addr(WETH), addr(USDC)).unwrap());
+        let forward = uniswap_v3_template(UNISWAP_V3_FACTORY).predict(V3PoolKey::new(addr(USDC), addr(WETH), 500).unwrap());
+        let reversed = uniswap_v3_template(UNISWAP_V3_FACTORY).predict(V3PoolKey::new(addr(WETH), addr(USDC), 500).unwrap());
+        assert_eq!(forward.unwrap(), reversed.unwrap());
+        let pair = uniswap_v2_template(UNISWAP_V2_FACTORY).predict(V2PairKey::new(addr(WETH), addr(USDC)).unwrap());
+        assert_eq!(pair.unwrap(), addr("0xB4e16d0168e52d35CaCD2c6185b44281Ec28C9Dc"));
+    }
+
+    #[test]
+    fn rejects_identical_tokens_and_wide_fee() {
+        assert!(V2PairKey::new(addr(USDC), addr(USDC)).is_err());
+        assert!(V3PoolKey::new(addr(USDC), addr(WETH), 0x100_0000).is_err());
+    }
+}
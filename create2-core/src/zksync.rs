@@ -0,0 +1,30 @@
+use crate::salt::IntoSalt;
+use crate::{Address, Create2Error};
+use sha3::{Digest, Keccak256};
+
+// keccak256("zksyncCreate2")，zkSync Era的ContractDeployer用它代替EVM的0xff前缀
+pub const ZKSYNC_CREATE2_PREFIX: [u8; 32] = [
+    0x20, 0x20, 0xdb, 0xa9, 0x1b, 0x30, 0xcc, 0x00, 0x06, 0x18, 0x8a, 0xf7, 0x94, 0xc2, 0xfb, 0x30,
+    0xdd, 0x85, 0x20, 0xdb, 0x7e, 0x2c, 0x08, 0x8b, 0x7f, 0xc7, 0xc1, 0x03, 0xc0, 0x0c, 0xa4, 0x94,
+];
+
+// zkSync Era的CREATE2：keccak256(PREFIX ++ bytes32(sender) ++ salt ++ bytecode_hash ++ keccak256(constructor_input))[12..]；
+// bytecode_hash是zkSync带版本号的字节码哈希（编译产物里的hash字段），不是keccak256(init code)
+pub fn predict_zksync_create2(
+    sender: &Address,
+    salt: impl IntoSalt,
+    bytecode_hash: &[u8; 32],
+    constructor_input: &[u8],
+) -> Result<Address, Create2Error> {
+    let mut input = [0u8; 160];
+    input[..32].copy_from_slice(&ZKSYNC_CREATE2_PREFIX);
+    input[44..64].copy_from_slice(sender.as_bytes());
+    input[64..96].copy_from_slice(&salt.to_salt()?);
+    input[96..128].copy_from_slice(bytecode_hash);
+    input[128..].copy_from_slice(&Keccak256::digest(constructor_input));
+
+    let hash = Keccak256::digest(input);
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&hash[12..]);
+    Ok(Address::new(address))
+}
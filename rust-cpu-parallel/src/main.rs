@@ -1,55 +1,77 @@
+#[macro_use]
+extern crate create2_cli;
 mod create2;
 
-use create2::predict_deterministic_address;
-use std::io::{self, Write};
+use create2_cli::{bloom, checkpoint, cli, compare, config, format_duration, nonces, notify, output, pattern, progress, prompt, salts, validate};
+use bloom::{SaltBloom, DEFAULT_BLOOM_CAPACITY, DEFAULT_BLOOM_FP_RATE};
+use checkpoint::Checkpoint;
+use cli::Command;
+use config::{GpuBackend, PatternPosition, Scheme, Settings, Verbosity, DEFAULT_BENCH_DIFFICULTY, DEFAULT_NONCE_COUNT};
+use rayon::prelude::*;
+use rayon::ThreadPool;
+use create2_core::salt::SaltPolicy;
+use create2_core::{
+    check_address, predict_deterministic_address, random_salt, search_templates, Address, CancellationToken, Create2Error,
+    Create2Template, PredictWith, RANDOM_SALT_WORDS,
+};
+use output::{BenchSummary, MatchRecord, MatchSink, Output, Prediction, ResultsFile};
+use notify::Notifier;
+use pattern::{AddressKind, PatternSet};
+use progress::ProgressReporter;
+use salts::SaltReader;
+use validate::Validation;
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+use std::hint::black_box;
+use std::ops::ControlFlow;
 use std::time::{Duration, Instant};
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 const TOTAL_OPERATIONS: usize = 50_000_000;
 const IMPLEMENTATION: &str = "0xa84c57e9966df7df79bff42f35c68aae71796f64";
 const DEPLOYER: &str = "0xfe15afcb5b9831b8af5fd984678250e95de8e312";
 const PROGRESS_INTERVAL: usize = 10000;
+// find模式每个线程每隔多少次尝试检查停止条件并同步检查点
+const CHECK_INTERVAL: usize = 1000;
+// predict模式每次从文件读取的salt数
+const PREDICT_BATCH_SIZE: usize = 65536;
+const DEFAULT_PATTERN: &str = "eAce1";
 
-fn format_duration(duration: Duration) -> String {
-    let total_secs = duration.as_secs_f64();
-    
-    if total_secs < 60.0 {
-        format!("{:.1}s", total_secs)
-    } else if total_secs < 3600.0 {
-        let mins = (total_secs / 60.0) as u32;
-        let secs = total_secs % 60.0;
-        format!("{}m{:.1}s", mins, secs)
-    } else {
-        let hours = (total_secs / 3600.0) as u32;
-        let mins = ((total_secs % 3600.0) / 60.0) as u32;
-        let secs = total_secs % 60.0;
-        format!("{}h{}m{:.1}s", hours, mins, secs)
-    }
+// 所有线程共用同一个种子，每个线程使用独立的ChaCha流，结果可按 (seed, 线程序号) 复现
+fn seeded_rng(seed: u64, thread_index: usize) -> ChaCha8Rng {
+    use rand::SeedableRng;
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    rng.set_stream(thread_index as u64);
+    rng
 }
 
-fn run_benchmark() -> Result<(), Box<dyn std::error::Error>> {
-    println!("🚀 Rust CREATE2地址预测benchmark (CPU并行版)");
-    println!("总计算量: {} 次", TOTAL_OPERATIONS);
-    println!("实现合约: {}", IMPLEMENTATION);
-    println!("部署者: {}", DEPLOYER);
-    println!("CPU线程数: {}", rayon::current_num_threads());
-    println!("--------------------------------------------------------------------------------");
+fn run_benchmark(settings: &Settings, pool: &ThreadPool) -> Result<(), Box<dyn std::error::Error>> {
+    let output = Output::open(settings.output.as_deref())?;
+    let seed = settings.seed.unwrap_or_else(rand::random);
+    
+    status!("🚀 Rust CREATE2地址预测benchmark (CPU并行版)");
+    status!("总计算量: {} 次", TOTAL_OPERATIONS);
+    status!("实现合约: {}", settings.implementation);
+    status!("部署者: {}", settings.deployer);
+    print_init_code_hash(settings);
+    status!("CPU线程数: {}", pool.current_num_threads());
+    status!("随机种子: {}", seed);
+    status!("--------------------------------------------------------------------------------");
     
     let counter = Arc::new(AtomicUsize::new(0));
     let start_time = Instant::now();
-    let last_report_time = Arc::new(std::sync::Mutex::new(Instant::now()));
-    let last_report_count = Arc::new(AtomicUsize::new(0));
+    let reporter = ProgressReporter::new(settings);
+    let template = settings.template()?;
     
-    rayon::scope(|s| {
-        for _ in 0..rayon::current_num_threads() {
+    pool.scope(|s| {
+        for thread_index in 0..pool.current_num_threads() {
             let counter = counter.clone();
-            let last_report_time = last_report_time.clone();
-            let last_report_count = last_report_count.clone();
+            let reporter = &reporter;
+            let template = &template;
             
             s.spawn(move |_| {
-                use rand::Rng;
-                let mut rng = rand::thread_rng();
+                let mut rng = seeded_rng(seed, thread_index);
                 let mut local_count = 0;
                 
                 // 预分配缓冲区
@@ -67,65 +89,29 @@ fn run_benchmark() -> Result<(), Box<dyn std::error::Error>> {
                         salt.push(hex_chars[(byte & 0x0f) as usize] as char);
                     }
                     
-                    if let Ok(_address) = predict_deterministic_address(IMPLEMENTATION, DEPLOYER, &salt) {
+                    if let Ok(_address) = template.predict(&salt).map(|address| address.to_string()) {
                         local_count += 1;
                         
                         if local_count >= 1000 {
                             let total = counter.fetch_add(local_count, Ordering::Relaxed) + local_count;
                             local_count = 0;
                             
-                            if total >= TOTAL_OPERATIONS {
-                                let now = Instant::now();
-                                let elapsed = now.duration_since(start_time);
-                                let avg_tps = total as f64 / elapsed.as_secs_f64();
-                                let percentage = (total.min(TOTAL_OPERATIONS) as f64 / TOTAL_OPERATIONS as f64) * 100.0;
+                            let finished = total >= TOTAL_OPERATIONS;
+                            let tick = if finished {
+                                Some(reporter.finish(total))
+                            } else {
+                                reporter.tick(total)
+                            };
+                            if let Some(tick) = tick {
+                                let count = tick.count.min(TOTAL_OPERATIONS);
+                                let percentage = (count as f64 / TOTAL_OPERATIONS as f64) * 100.0;
                                 
-                                print!("\r进度: {:.2}% ({}/{}) | 平均TPS: {:.0} | 当前TPS: {:.0} | 用时: {}",
-                                    percentage, total.min(TOTAL_OPERATIONS), TOTAL_OPERATIONS, avg_tps, avg_tps, 
-                                    format_duration(elapsed));
-                                io::stdout().flush().unwrap();
-                                break;
+                                progress!("\r进度: {:.2}% ({}/{}) | 平均TPS: {:.0} | 当前TPS: {:.0} | 用时: {}",
+                                    percentage, count, TOTAL_OPERATIONS, tick.avg_tps, tick.current_tps, 
+                                    format_duration(tick.elapsed));
                             }
-                            
-                            if total % PROGRESS_INTERVAL == 0 {
-                                let now = Instant::now();
-                                let should_report = {
-                                    let mut last_time = last_report_time.lock().unwrap();
-                                    if now.duration_since(*last_time).as_millis() >= 100 {
-                                        *last_time = now;
-                                        true
-                                    } else {
-                                        false
-                                    }
-                                };
-                                
-                                if should_report {
-                                    let elapsed = now.duration_since(start_time);
-                                    let avg_tps = total as f64 / elapsed.as_secs_f64();
-                                    
-                                    // 计算当前TPS（瞬时速度）
-                                    let last_count = last_report_count.load(Ordering::Relaxed);
-                                    let current_tps = if total > last_count {
-                                        let interval_count = total - last_count;
-                                        let interval_elapsed = now.duration_since(*last_report_time.lock().unwrap());
-                                        if interval_elapsed.as_secs_f64() > 0.0 {
-                                            interval_count as f64 / interval_elapsed.as_secs_f64()
-                                        } else {
-                                            avg_tps
-                                        }
-                                    } else {
-                                        avg_tps
-                                    };
-                                    
-                                    last_report_count.store(total, Ordering::Relaxed);
-                                    
-                                    let percentage = (total as f64 / TOTAL_OPERATIONS as f64) * 100.0;
-                                    
-                                    print!("\r进度: {:.2}% ({}/{}) | 平均TPS: {:.0} | 当前TPS: {:.0} | 用时: {}",
-                                        percentage, total, TOTAL_OPERATIONS, avg_tps, current_tps, 
-                                        format_duration(elapsed));
-                                    io::stdout().flush().unwrap();
-                                }
+                            if finished {
+                                break;
                             }
                         }
                     }
@@ -137,155 +123,483 @@ fn run_benchmark() -> Result<(), Box<dyn std::error::Error>> {
     let total_elapsed = start_time.elapsed();
     let final_count = counter.load(Ordering::Relaxed);
     
-    println!("\n--------------------------------------------------------------------------------");
-    println!("✅ 计算完成!");
-    println!();
-    println!("📊 Benchmark 结果:");
-    println!("==================================================");
-    println!("总操作数:     {}", final_count);
-    println!("总用时:       {:.1}s", total_elapsed.as_secs_f64());
-    println!("平均TPS:      {:.2} ops/sec", final_count as f64 / total_elapsed.as_secs_f64());
-    println!("每次操作耗时: {:.2} μs", total_elapsed.as_micros() as f64 / final_count as f64);
-    println!("并行线程数:   {}", rayon::current_num_threads());
+    status!("\n--------------------------------------------------------------------------------");
+    status!("✅ 计算完成!");
+    status!();
+    summary!("📊 Benchmark 结果:");
+    summary!("==================================================");
+    summary!("总操作数:     {}", final_count);
+    summary!("总用时:       {:.1}s", total_elapsed.as_secs_f64());
+    summary!("平均TPS:      {:.2} ops/sec", final_count as f64 / total_elapsed.as_secs_f64());
+    summary!("每次操作耗时: {:.2} μs", total_elapsed.as_micros() as f64 / final_count as f64);
+    summary!("并行线程数:   {}", pool.current_num_threads());
+    
+    let mut summary = BenchSummary::new("cpu-parallel", final_count, total_elapsed.as_secs_f64());
+    summary.threads = Some(pool.current_num_threads());
+    summary.seed = Some(seed);
+    output::emit_json(&summary)?;
+    output.record(&summary.to_string())?;
+    
+    Ok(())
+}
+
+// bench-find：以末尾 --bench-difficulty 个0为合成规则，按benchmark的计算量跑完整的 生成salt→哈希→匹配→输出 流程，
+// 报告每秒找到的地址数；用时包括find模式启动时的校准
+fn run_match_benchmark(settings: &Settings, pool: &ThreadPool) -> Result<(), Box<dyn std::error::Error>> {
+    let output = Output::open(settings.output.as_deref())?;
+    let seed = settings.seed.unwrap_or_else(rand::random);
+    let difficulty = settings.bench_difficulty;
+    let settings = settings.bench_find("0".repeat(difficulty), TOTAL_OPERATIONS, seed);
+    let expected = PatternSet::from_settings(&settings, AddressKind::Evm)?
+        .expected_attempts()
+        .ok_or("bench-find needs a pattern with a difficulty estimate")?;
+    
+    let start_time = Instant::now();
+    let matches = find_address(&settings, pool)?;
+    let total_elapsed = start_time.elapsed();
+    let matches_per_sec = matches as f64 / total_elapsed.as_secs_f64();
+    
+    status!();
+    summary!("📊 匹配Benchmark 结果:");
+    summary!("==================================================");
+    summary!("难度:         末尾 {} 个0，平均每 {:.0} 次尝试匹配一次", difficulty, expected);
+    summary!("总尝试次数:   {}", TOTAL_OPERATIONS);
+    summary!("总用时:       {:.1}s", total_elapsed.as_secs_f64());
+    summary!("平均TPS:      {:.2} ops/sec", TOTAL_OPERATIONS as f64 / total_elapsed.as_secs_f64());
+    summary!("找到地址数:   {}（期望 {:.1}）", matches, TOTAL_OPERATIONS as f64 / expected);
+    summary!("每秒匹配数:   {:.2} matches/sec", matches_per_sec);
+    
+    let mut summary = BenchSummary::new("cpu-parallel", TOTAL_OPERATIONS, total_elapsed.as_secs_f64());
+    summary.threads = Some(pool.current_num_threads());
+    summary.seed = Some(seed);
+    summary.difficulty = Some(difficulty);
+    summary.matches = Some(matches);
+    summary.matches_per_sec = Some(matches_per_sec);
+    output::emit_json(&summary)?;
+    output.record(&summary.to_string())?;
     
     Ok(())
 }
 
 fn run_single_test() -> Result<(), Box<dyn std::error::Error>> {
-    println!("运行单次测试验证...");
-    println!();
+    status!("运行单次测试验证...");
+    status!();
     
     let implementation = "0xa84c57e9966df7df79bff42f35c68aae71796f64";
     let deployer = "0xfe15afcb5b9831b8af5fd984678250e95de8e312";
     let salt = "test-salt-test";
     
-    println!("📝 测试参数:");
-    println!("  Implementation: {}", implementation);
-    println!("  Deployer: {}", deployer);
-    println!("  Salt: {}", salt);
+    status!("📝 测试参数:");
+    status!("  Implementation: {}", implementation);
+    status!("  Deployer: {}", deployer);
+    status!("  Salt: {}", salt);
     
     let result = predict_deterministic_address(implementation, deployer, salt)?;
     assert_eq!(result, "0x22FBFB2264B9Cd1ADe8ce5013012c817878D783C");
-    println!("\n✅ 结果: {}", result);
+    summary!("\n✅ 结果: {}", result);
     
     Ok(())
 }
 
-fn find_address() -> Result<(), Box<dyn std::error::Error>> {
-    println!("🔍 开始搜索以 eAce1 结尾的EVM CREATE2地址...");
-    println!("Implementation: {}", IMPLEMENTATION);
-    println!("Deployer: {}", DEPLOYER);
-    println!("CPU线程数: {}", rayon::current_num_threads());
-    println!("按Ctrl+C停止搜索");
-    println!("--------------------------------------------------------------------------------");
+// 返回找到的地址数，达到停止条件后所有线程退出
+fn find_address(settings: &Settings, pool: &ThreadPool) -> Result<usize, Box<dyn std::error::Error>> {
+    let targets = settings.find_targets();
+    let pattern = PatternSet::from_settings(settings, AddressKind::Evm)?.per_salt(targets.len());
+    let templates = settings.templates()?;
+    let output = Output::open(settings.output.as_deref())?;
+    let results = ResultsFile::open(settings.results.as_deref(), "cpu-parallel", pattern.spec())?;
+    let notifier = Notifier::new(settings.notify_url.as_deref(), "cpu-parallel", pattern.spec())?;
+    // --resume 恢复时沿用检查点里的种子和每个线程的随机数位置
+    let checkpoint = Checkpoint::open(settings, settings.seed.unwrap_or_else(rand::random), pool.current_num_threads())?;
+    pattern.restore(&checkpoint.matches);
+    let seed = checkpoint.seed;
+    let bloom = SaltBloom::open(settings)?;
     
-    let counter = Arc::new(AtomicUsize::new(0));
+    status!("🔍 开始搜索{}的EVM CREATE2地址...", pattern);
+    if let Some(attempts) = pattern.expected_attempts() {
+        status!("难度: 平均每 {:.0} 次尝试匹配一次", attempts);
+        let tps = calibrate_tps(&templates, pool);
+        status!("校准TPS: {:.0} | 预计平均每 {} 找到一个地址", tps, progress::format_eta(attempts, tps));
+    }
+    pattern.print_rules();
+    status!("Implementation: {}", settings.find_implementations().join(", "));
+    status!("Deployer: {}", settings.find_deployers().join(", "));
+    print_init_code_hash(settings);
+    status!("CPU线程数: {}", pool.current_num_threads());
+    status!("随机种子: {}", seed);
+    if let Some(ref bloom) = bloom {
+        status!("布隆过滤器: {}", bloom.describe());
+    }
+    print_find_limits(settings);
+    status!("按Ctrl+C停止搜索");
+    status!("--------------------------------------------------------------------------------");
+    
+    let counter = Arc::new(AtomicUsize::new(checkpoint.attempts));
     let start_time = Instant::now();
-    let last_report_time = Arc::new(std::sync::Mutex::new(Instant::now()));
-    let last_report_count = Arc::new(AtomicUsize::new(0));
+    let elapsed_offset = checkpoint.elapsed();
+    let reporter = ProgressReporter::resumed(settings, checkpoint.attempts, elapsed_offset);
+    let sink = MatchSink::resumed(settings, &targets, &pattern, &output, &results, &notifier, &checkpoint);
+    // 任一线程达到停止条件时取消，其他线程在下一个salt前停下
+    let stop = CancellationToken::new();
+    if settings.max_attempts.is_some_and(|max| checkpoint.attempts >= max) {
+        stop.cancel();
+    }
+    let checkpoint = Mutex::new(checkpoint);
+    let bloom = bloom.map(Mutex::new);
+    let skipped = AtomicUsize::new(0);
     
-    rayon::scope(|s| {
-        for _ in 0..rayon::current_num_threads() {
+    pool.scope(|s| {
+        for thread_index in 0..pool.current_num_threads() {
             let counter = counter.clone();
-            let last_report_time = last_report_time.clone();
-            let last_report_count = last_report_count.clone();
+            let reporter = &reporter;
+            let pattern = &pattern;
+            let sink = &sink;
+            let stop = &stop;
+            let checkpoint = &checkpoint;
+            let bloom = &bloom;
+            let skipped = &skipped;
+            let templates = &templates;
             
             s.spawn(move |_| {
-                use rand::Rng;
-                let mut rng = rand::thread_rng();
-                let mut local_count = 0;
-                
-                // 预分配缓冲区
-                let hex_chars = b"0123456789abcdef";
+                let mut rng = seeded_rng(seed, thread_index);
+                rng.set_word_pos(checkpoint.lock().unwrap().rng_positions[thread_index]);
                 
-                loop {
-                    // 生成随机salt（与GPU版本相同的优化方法）
-                    let mut salt = String::with_capacity(32);
-                    let mut bytes = [0u8; 16];
-                    rng.fill(&mut bytes);
+                // 每次search CHECK_INTERVAL个salt；命中的地址立即输出，和尝试次数、随机数位置一起写入检查点
+                while !stop.is_cancelled() {
+                    let mut flushed = 0;
+                    let mut records = Vec::new();
+                    let chunk_position = rng.get_word_pos();
                     
-                    // 直接将字节转换为十六进制字符串
-                    for byte in bytes.iter() {
-                        salt.push(hex_chars[(byte >> 4) as usize] as char);
-                        salt.push(hex_chars[(byte & 0x0f) as usize] as char);
+                    // 布隆过滤器中已有的批次直接把随机数位置移到批次末尾，不生成salt，也不计入尝试次数
+                    if bloom.as_ref().is_some_and(|bloom| bloom.lock().unwrap().contains(seed, thread_index, chunk_position)) {
+                        rng.set_word_pos(chunk_position + CHECK_INTERVAL as u128 * RANDOM_SALT_WORDS);
+                        skipped.fetch_add(1, Ordering::Relaxed);
+                        continue;
                     }
                     
-                    if let Ok(address) = predict_deterministic_address(IMPLEMENTATION, DEPLOYER, &salt) {
-                        local_count += 1;
-                        
-                        if address.ends_with("eAce1") {
-                            let total = counter.fetch_add(local_count, Ordering::Relaxed) + local_count;
-                            local_count = 0;
-                            let elapsed = start_time.elapsed();
-                            println!("\n✨ 找到目标地址!");
-                            println!("  Salt: {}", salt);
-                            println!("  Address: {}", address);
-                            println!("  尝试次数: {}", total);
-                            println!("  用时: {}", format_duration(elapsed));
-                            println!("--------------------------------------------------------------------------------");
-                        }
-                        
-                        if local_count >= 1000 {
-                            let total = counter.fetch_add(local_count, Ordering::Relaxed) + local_count;
-                            local_count = 0;
+                    let salts = std::iter::repeat_with(|| random_salt(&mut rng)).take(CHECK_INTERVAL);
+                    
+                    let searched = search_templates(
+                        templates,
+                        salts,
+                        stop,
+                        |address| pattern.matches(&address.to_string()),
+                        |index, salt, address, count| -> Result<ControlFlow<()>, Create2Error> {
+                            let total = counter.fetch_add(count - flushed, Ordering::Relaxed) + count - flushed;
+                            flushed = count;
+                            // 其他线程可能同时命中，超出 --max-matches 的结果不再记录
+                            let record = sink.report(index, salt.to_string(), &address.to_string(), total).unwrap_or_else(|e| {
+                                eprintln!("⚠️  {}", output::error_chain(&e));
+                                None
+                            });
+                            let Some(record) = record else {
+                                return Ok(ControlFlow::Break(()));
+                            };
+                            records.push(record);
                             
-                            if total % PROGRESS_INTERVAL == 0 {
-                                let now = Instant::now();
-                                let should_report = {
-                                    let mut last_time = last_report_time.lock().unwrap();
-                                    if now.duration_since(*last_time).as_millis() >= 100 {
-                                        *last_time = now;
-                                        true
-                                    } else {
-                                        false
-                                    }
-                                };
-                                
-                                if should_report {
-                                    let elapsed = now.duration_since(start_time);
-                                    let avg_tps = total as f64 / elapsed.as_secs_f64();
-                                    
-                                    // 计算当前TPS（瞬时速度）
-                                    let last_count = last_report_count.load(Ordering::Relaxed);
-                                    let current_tps = if total > last_count {
-                                        let interval_count = total - last_count;
-                                        let interval_elapsed = now.duration_since(*last_report_time.lock().unwrap());
-                                        if interval_elapsed.as_secs_f64() > 0.0 {
-                                            interval_count as f64 / interval_elapsed.as_secs_f64()
-                                        } else {
-                                            avg_tps
-                                        }
-                                    } else {
-                                        avg_tps
-                                    };
-                                    
-                                    last_report_count.store(total, Ordering::Relaxed);
-                                    
-                                    print!("\r已尝试: {} | 平均TPS: {:.0} | 当前TPS: {:.0} | 用时: {}     ", 
-                                        total, avg_tps, current_tps, format_duration(elapsed));
-                                    io::stdout().flush().unwrap();
-                                }
+                            if settings.find_limit_reached(total, sink.count(), elapsed_offset + start_time.elapsed()) {
+                                stop.cancel();
+                                return Ok(ControlFlow::Break(()));
                             }
+                            Ok(ControlFlow::Continue(()))
+                        },
+                    );
+                    // 随机salt都是32个字符，不会出错
+                    let searched = searched.unwrap_or_else(|e| {
+                        eprintln!("⚠️  {}", output::error_chain(&e));
+                        stop.cancel();
+                        flushed
+                    });
+                    
+                    let total = counter.fetch_add(searched - flushed, Ordering::Relaxed) + searched - flushed;
+                    let elapsed = elapsed_offset + start_time.elapsed();
+                    sync_checkpoint(checkpoint, settings, thread_index, searched, rng.get_word_pos(), elapsed, records);
+                    
+                    // 只记录完整搜索过的批次，中途停止的批次下次仍会搜索
+                    if let Some(bloom) = bloom {
+                        let mut bloom = bloom.lock().unwrap();
+                        if searched == CHECK_INTERVAL {
+                            bloom.insert(seed, thread_index, chunk_position);
+                        }
+                        if let Err(e) = bloom.save_if_due() {
+                            eprintln!("⚠️  {}", output::error_chain(&e));
                         }
                     }
+                    
+                    // 停止条件每CHECK_INTERVAL次检查一次，--max-attempts 最多超出 线程数×CHECK_INTERVAL 次
+                    if settings.find_limit_reached(total, sink.count(), elapsed) {
+                        stop.cancel();
+                    }
+                    
+                    if let Some(tick) = reporter.tick(total) {
+                        progress!("\r已尝试: {} | 平均TPS: {:.0} | 当前TPS: {:.0} | 用时: {}{}{}     ", 
+                            tick.count, tick.avg_tps, tick.current_tps, format_duration(tick.elapsed),
+                            tick.eta(pattern.expected_attempts()), pattern.best(&checkpoint.lock().unwrap().matches));
+                    }
                 }
             });
         }
     });
     
+    let elapsed = elapsed_offset + start_time.elapsed();
+    let mut checkpoint = checkpoint.into_inner().unwrap();
+    if let Some(ref path) = settings.resume {
+        checkpoint.elapsed_secs = elapsed.as_secs_f64();
+        checkpoint.save(path)?;
+    }
+    if let Some(bloom) = bloom {
+        bloom.into_inner().unwrap().save()?;
+        summary!("\n♻️  跳过了 {} 个已搜索过的批次", skipped.into_inner());
+    }
+    
+    let found = checkpoint.matches.len();
+    summary!("\n🏁 搜索结束: 已尝试 {} 次, 找到 {} 个地址, 用时 {}",
+        checkpoint.attempts, found, format_duration(elapsed));
+    pattern.print_hits();
+    pattern.print_top(&checkpoint.matches, &output)?;
+    Ok(found)
+}
+
+// 每个线程用与搜索相同的方式生成salt并格式化地址，只是不做匹配；使用单独的随机数流，不影响检查点
+fn calibrate_tps(templates: &[Create2Template], pool: &ThreadPool) -> f64 {
+    progress::calibrate(|| {
+        pool.install(|| {
+            (0..pool.current_num_threads())
+                .into_par_iter()
+                .map(|thread_index| {
+                    let mut rng = seeded_rng(0, thread_index);
+                    let cancel = CancellationToken::new();
+                    let salts = std::iter::repeat_with(|| random_salt(&mut rng)).take(CHECK_INTERVAL);
+                    search_templates(templates, salts, &cancel, |address| black_box(address.to_string()).is_empty(), |_, _, _, _| {
+                        Ok::<_, Create2Error>(ControlFlow::Continue(()))
+                    })
+                    .unwrap_or(0)
+                })
+                .sum()
+        })
+    })
+}
+
+// 把一个线程新完成的尝试次数、随机数位置和找到的地址在同一把锁内合并进检查点，恢复时不会重复或跳过salt
+fn sync_checkpoint(
+    checkpoint: &Mutex<Checkpoint>,
+    settings: &Settings,
+    thread_index: usize,
+    count: usize,
+    rng_position: u128,
+    elapsed: Duration,
+    records: Vec<MatchRecord>,
+) {
+    let mut checkpoint = checkpoint.lock().unwrap();
+    checkpoint.attempts += count;
+    checkpoint.rng_positions[thread_index] = rng_position;
+    checkpoint.elapsed_secs = checkpoint.elapsed_secs.max(elapsed.as_secs_f64());
+    checkpoint.matches.extend(records);
+    if let Err(e) = checkpoint.save_if_due(settings.resume.as_deref()) {
+        eprintln!("⚠️  {}", output::error_chain(&e));
+    }
+}
+
+
+fn print_init_code_hash(settings: &Settings) {
+    if let Some(ref hash) = settings.init_code_hash {
+        status!("Init code hash: {}（通用CREATE2，忽略实现合约）", hash);
+    } else if settings.scheme != Scheme::Minimal {
+        status!("代理方案: {}", settings.scheme);
+        if let Some(ref path) = settings.proxy_bytecode {
+            status!("Creation code: {}", path.display());
+        }
+        if let Some(ref owner) = settings.account_owner {
+            status!("账户owner: {}", owner);
+        }
+        if let Some(ref args) = settings.proxy_args {
+            status!("代理参数: {}", args);
+        }
+    }
+}
+
+fn print_find_limits(settings: &Settings) {
+    if let Some(max) = settings.max_attempts {
+        status!("最大尝试次数: {}", max);
+    }
+    if let Some(max) = settings.max_time {
+        status!("最长搜索时间: {}秒", max);
+    }
+    if let Some(max) = settings.max_matches {
+        status!("最多找到地址数: {}", max);
+    }
+}
+
+// 逐批读取salt文件并行计算，每批结果按输入顺序流式输出
+fn run_predict(settings: &Settings, pool: &ThreadPool) -> Result<(), Box<dyn std::error::Error>> {
+    let path = settings.salts_file.as_deref().ok_or("predict requires --salts-file")?;
+    let mut reader = SaltReader::open(path, settings.salt_policy)?;
+    let template = settings.template()?;
+    let output = Output::open(settings.output.as_deref())?;
+    
+    status!("📄 从 {} 读取salt", path.display());
+    status!("实现合约: {}", settings.implementation);
+    status!("部署者: {}", settings.deployer);
+    print_init_code_hash(settings);
+    status!("CPU线程数: {}", pool.current_num_threads());
+    status!("--------------------------------------------------------------------------------");
+    
+    let start_time = Instant::now();
+    let mut total = 0;
+    
+    loop {
+        let salts = reader.read_batch(PREDICT_BATCH_SIZE)?;
+        if salts.is_empty() {
+            break;
+        }
+        total += salts.len();
+        
+        let addresses = pool.install(|| {
+            salts
+                .par_iter()
+                .map(|(_, bytes)| *bytes)
+                .predict_with(&template)
+                .map(|result| result.map(|(_, address)| address.to_string()))
+                .collect::<Result<Vec<_>, _>>()
+        })?;
+        for ((salt, _), address) in salts.into_iter().zip(addresses) {
+            output::emit_prediction(&output, &Prediction { salt, address })?;
+        }
+    }
+    
+    status!("--------------------------------------------------------------------------------");
+    status!("✅ 共计算 {} 个地址, 用时 {}", total, format_duration(start_time.elapsed()));
     Ok(())
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let args: Vec<String> = std::env::args().collect();
+// 检查运行参数并打印各模式会做什么，不执行任何哈希计算
+fn run_validate(settings: &Settings, pool: &ThreadPool) -> Result<(), Box<dyn std::error::Error>> {
+    let mut validation = Validation::new();
+    let deployers = settings.deployers.iter().map(|deployer| ("find模式部署者", deployer));
+    let implementations = settings.implementations.iter().map(|implementation| ("find模式实现合约", implementation));
+    for (label, address) in [("实现合约", &settings.implementation), ("部署者", &settings.deployer)]
+        .into_iter()
+        .chain(implementations)
+        .chain(deployers)
+    {
+        validation.check(label, address.parse::<Address>().map(|address| address.to_string()));
+    }
+    validation.check("计算后端", Ok(format!("CPU并行，{} 个线程", pool.current_num_threads())));
+    validation.check("benchmark模式", Ok(format!("计算 {} 个随机salt的地址", TOTAL_OPERATIONS)));
+    validation.check_common(settings, AddressKind::Evm);
+    validation.check_checkpoint(settings, pool.current_num_threads());
+    print_find_limits(settings);
+    validation.finish()?;
+    Ok(())
+}
+
+fn main() {
+    if let Err(e) = run() {
+        output::report_error(e.as_ref());
+        std::process::exit(1);
+    }
+}
+
+fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let defaults = Settings {
+        implementation: IMPLEMENTATION.to_string(),
+        implementations: Vec::new(),
+        deployer: DEPLOYER.to_string(),
+        deployers: Vec::new(),
+        chain: None,
+        init_code_hash: None,
+        scheme: Scheme::Minimal,
+        proxy_args: None,
+        proxy_bytecode: None,
+        account_owner: None,
+        batch_size: 1,
+        backend: GpuBackend::Auto,
+        threads: None,
+        hybrid: false,
+        batches_per_dispatch: 1,
+        addresses_per_thread: 4,
+        command_queues: 1,
+        pattern: DEFAULT_PATTERN.to_string(),
+        position: PatternPosition::Suffix,
+        patterns: Vec::new(),
+        suffix: None,
+        ignore_case: false,
+        regex: None,
+        mask: None,
+        objective: None,
+        vanity: None,
+        score: None,
+        top: 10,
+        output: None,
+        results: None,
+        notify_url: None,
+        json: false,
+        seed: None,
+        max_attempts: None,
+        max_time: None,
+        max_matches: None,
+        first_match: false,
+        bench_difficulty: DEFAULT_BENCH_DIFFICULTY,
+        nonce_start: 0,
+        nonce_count: DEFAULT_NONCE_COUNT,
+        verbosity: Verbosity::Normal,
+        salts_file: None,
+        salt_policy: SaltPolicy::Pad,
+        resume: None,
+        bloom: None,
+        bloom_capacity: DEFAULT_BLOOM_CAPACITY,
+        bloom_fp_rate: DEFAULT_BLOOM_FP_RATE,
+        tune_cache: None,
+        tuned: None,
+        profile: None,
+        progress_interval: PROGRESS_INTERVAL,
+        progress_every: Duration::from_millis(100),
+    };
+    let args = cli::parse_args(std::env::args())?;
+    args.reject_gpu_flags(env!("CARGO_BIN_NAME"))?;
+    if args.help {
+        cli::print_usage(env!("CARGO_BIN_NAME"));
+        return Ok(());
+    }
+    let mut settings = config::load_settings(args.config_path, args.overrides, defaults)?;
+    let mut command = args.command;
+    if prompt::interactive() {
+        command = prompt::ask(&mut settings, check_address)?;
+    }
+    output::set_json_mode(settings.json);
+    output::set_first_match_mode(settings.first_match);
+    output::set_verbosity(settings.verbosity);
+    verbose!("运行参数: {:?}", settings);
+    
+    // 在独立线程池中运行，0 表示使用rayon默认线程数（RAYON_NUM_THREADS或CPU核心数）
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(settings.threads.unwrap_or(0))
+        .build()?;
     
-    if args.len() > 1 {
-        match args[1].as_str() {
-            "test" => run_single_test(),
-            "find" => find_address(),
-            _ => run_benchmark(),
+    // validate把 --chain 的问题和其他检查一起报告
+    if command != Command::Validate {
+        settings.check_chain(AddressKind::Evm)?;
+    }
+
+    match command {
+        Command::Test => run_single_test(),
+        Command::Find => {
+            // 停止时一个地址都没找到，以非零退出码结束；--first-match 用2与出错的1区分
+            if find_address(&settings, &pool)? == 0 {
+                std::process::exit(if settings.first_match { 2 } else { 1 });
+            }
+            Ok(())
+        }
+        Command::Predict => run_predict(&settings, &pool),
+        Command::Nonces => nonces::run_nonce_sweep(&settings),
+        Command::Compare => compare::run_scheme_comparison(&settings),
+        Command::Validate => run_validate(&settings, &pool),
+        Command::Tune => Err("tune (GPU autotuning) is only supported on the Metal builds".into()),
+        Command::Completions(shell) => {
+            cli::print_completions(shell, env!("CARGO_BIN_NAME"));
+            Ok(())
         }
-    } else {
-        run_benchmark()
+        Command::Bench => run_benchmark(&settings, &pool),
+        Command::BenchFind => run_match_benchmark(&settings, &pool),
     }
 }
\ No newline at end of file
@@ -0,0 +1,142 @@
+use crate::config::GpuBackend;
+use crate::gpu_compute::{Candidate, GpuAccelerator, GpuFilter};
+use create2_core::{Create2Error, Create2Template};
+use create2_core::salt::IntoSalt;
+use std::sync::Arc;
+
+// Cheap to clone: clones share one CUDA or OpenCL device, kernel and buffer pool, and every
+// launch gets its own params and count buffer, so threads can submit batches concurrently
+#[derive(Clone)]
+pub struct Create2Predictor {
+    gpu_accelerator: Option<Arc<GpuAccelerator>>,
+}
+
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Create2Predictor>();
+};
+
+impl Create2Predictor {
+    pub fn new(use_gpu: bool, backend: GpuBackend, batch_size: usize, seed: u64) -> Result<Self, Create2Error> {
+        if use_gpu {
+            match GpuAccelerator::new(backend, batch_size, seed) {
+                Ok(accelerator) => {
+                    status!("✅ GPU acceleration enabled ({}) with batch size: {}", accelerator.backend(), batch_size);
+                    Ok(Create2Predictor {
+                        gpu_accelerator: Some(Arc::new(accelerator)),
+                    })
+                }
+                Err(e) => {
+                    eprintln!("⚠️  GPU initialization failed: {}. Falling back to CPU.", e);
+                    Ok(Create2Predictor {
+                        gpu_accelerator: None,
+                    })
+                }
+            }
+        } else {
+            Ok(Create2Predictor {
+                gpu_accelerator: None,
+            })
+        }
+    }
+    
+    // For embedding applications; bench uses predict_batch_address_into
+    #[allow(dead_code)]
+    pub fn predict_batch_address(
+        &self,
+        template: &Create2Template,
+        batch_size: usize,
+    ) -> Result<Vec<String>, Create2Error> {
+        if let Some(ref gpu) = self.gpu_accelerator {
+            let results = gpu.process_batch_gpu_random(template, batch_size)?;
+            Ok(results.into_iter().map(|(addr, _)| addr).collect())
+        } else {
+            Err(Create2Error::GpuInit("GPU not available".to_string()))
+        }
+    }
+    
+    // predict_batch_address into a vector the caller keeps between batches: its Strings are
+    // overwritten in place, so a run of equal-sized batches stops allocating after the first
+    pub fn predict_batch_address_into(
+        &self,
+        template: &Create2Template,
+        batch_size: usize,
+        results: &mut Vec<(String, u32)>,
+    ) -> Result<(), Create2Error> {
+        if let Some(ref gpu) = self.gpu_accelerator {
+            gpu.process_batch_gpu_random_into(template, batch_size, results)
+        } else {
+            Err(Create2Error::GpuInit("GPU not available".to_string()))
+        }
+    }
+    
+    // One find batch over every template; with a filter only the candidates the kernel kept come back, in batch order
+    pub fn search_batch(
+        &self,
+        templates: &[Create2Template],
+        batch_size: usize,
+        filter: Option<&GpuFilter>,
+    ) -> Result<Vec<Candidate>, Create2Error> {
+        if let Some(ref gpu) = self.gpu_accelerator {
+            gpu.process_batch_filtered(templates, batch_size, filter)
+        } else {
+            Err(Create2Error::GpuInit("GPU not available".to_string()))
+        }
+    }
+    
+    pub fn predict_batch_with_salt<S: IntoSalt>(
+        &self,
+        template: &Create2Template,
+        salts: &[S],
+    ) -> Result<Vec<String>, Create2Error> {
+        let mut results = Vec::new();
+        self.predict_batch_with_salt_into(template, salts, &mut results)?;
+        Ok(results.into_iter().map(|(addr, _)| addr).collect())
+    }
+    
+    // predict_batch_with_salt into a vector the caller keeps between batches, as predict_batch_address_into
+    pub fn predict_batch_with_salt_into<S: IntoSalt>(
+        &self,
+        template: &Create2Template,
+        salts: &[S],
+        results: &mut Vec<(String, u32)>,
+    ) -> Result<(), Create2Error> {
+        if let Some(ref gpu) = self.gpu_accelerator {
+            gpu.process_batch_with_salt_into(template, salts, results)
+        } else {
+            Err(Create2Error::GpuInit("GPU not available".to_string()))
+        }
+    }
+    
+    pub fn is_gpu_enabled(&self) -> bool {
+        self.gpu_accelerator.is_some()
+    }
+    
+    pub fn device_name(&self) -> Option<String> {
+        self.gpu_accelerator.as_ref().map(|gpu| gpu.device_name().to_string())
+    }
+    
+    pub fn backend(&self) -> Option<GpuBackend> {
+        self.gpu_accelerator.as_ref().map(|gpu| gpu.backend())
+    }
+    
+    pub fn buffer_pool_stats(&self) -> Option<(usize, usize)> {
+        self.gpu_accelerator.as_ref().map(|gpu| gpu.buffer_pool_stats())
+    }
+    
+    pub fn rng_position(&self) -> Option<u128> {
+        self.gpu_accelerator.as_ref().map(|gpu| gpu.rng_position())
+    }
+    
+    pub fn set_rng_position(&self, position: u128) {
+        if let Some(ref gpu) = self.gpu_accelerator {
+            gpu.set_rng_position(position);
+        }
+    }
+    
+    pub fn skip_batch(&self) {
+        if let Some(ref gpu) = self.gpu_accelerator {
+            gpu.skip_batch();
+        }
+    }
+}
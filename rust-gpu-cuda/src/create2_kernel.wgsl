@@ -0,0 +1,369 @@
+// WGSL port of create2_kernel.cu for the wgpu backend, so one shader runs on Metal, Vulkan and DX12.
+// WGSL has neither 64-bit integers nor bytes: Keccak lanes and the PCG32 state are vec2<u32>
+// (low, high) pairs, byte strings are arrays holding one byte per u32, and the buffers are u32 words
+// read and written in little-endian order, so params and results keep the CUDA layout byte for byte.
+
+const WORKGROUP_SIZE: u32 = 256u;
+
+// Byte offsets of the character arrays in Create2Params
+const IMPLEMENTATION: u32 = 0u;
+const DEPLOYER: u32 = 20u;
+const INIT_CODE_HASH: u32 = 40u;
+const FILTER: u32 = 72u;
+
+// Word indexes of the u32 fields; THREADS_PER_GROUP is appended by the host, see WgpuCompute::params_bytes
+const BATCH_SIZE: u32 = 28u;
+const ADDRESSES_PER_THREAD: u32 = 29u;
+const RANDOM_SEED: u32 = 30u;
+const USE_GPU_RANDOM: u32 = 31u;
+const USE_INIT_CODE_HASH: u32 = 32u;
+const USE_FILTER: u32 = 33u;
+const FILTER_IGNORE_CASE: u32 = 34u;
+const THREADS_PER_GROUP: u32 = 35u;
+
+// Create2Result is 76 bytes: address[40], salt_index, salt[32]
+const RESULT_WORDS: u32 = 19u;
+
+@group(0) @binding(0) var<storage, read> params: array<u32, 36>;
+@group(0) @binding(1) var<storage, read> salts: array<u32>;  // 32 bytes per salt
+@group(0) @binding(2) var<storage, read_write> results: array<u32>;
+@group(0) @binding(3) var<storage, read_write> result_count: atomic<u32>;  // Next free result slot when filtering
+
+// Keccak256 constants as (low, high) words
+var<private> RC: array<vec2<u32>, 24> = array<vec2<u32>, 24>(
+    vec2<u32>(0x00000001u, 0x00000000u), vec2<u32>(0x00008082u, 0x00000000u), vec2<u32>(0x0000808au, 0x80000000u),
+    vec2<u32>(0x80008000u, 0x80000000u), vec2<u32>(0x0000808bu, 0x00000000u), vec2<u32>(0x80000001u, 0x00000000u),
+    vec2<u32>(0x80008081u, 0x80000000u), vec2<u32>(0x00008009u, 0x80000000u), vec2<u32>(0x0000008au, 0x00000000u),
+    vec2<u32>(0x00000088u, 0x00000000u), vec2<u32>(0x80008009u, 0x00000000u), vec2<u32>(0x8000000au, 0x00000000u),
+    vec2<u32>(0x8000808bu, 0x00000000u), vec2<u32>(0x0000008bu, 0x80000000u), vec2<u32>(0x00008089u, 0x80000000u),
+    vec2<u32>(0x00008003u, 0x80000000u), vec2<u32>(0x00008002u, 0x80000000u), vec2<u32>(0x00000080u, 0x80000000u),
+    vec2<u32>(0x0000800au, 0x00000000u), vec2<u32>(0x8000000au, 0x80000000u), vec2<u32>(0x80008081u, 0x80000000u),
+    vec2<u32>(0x00008080u, 0x80000000u), vec2<u32>(0x80000001u, 0x00000000u), vec2<u32>(0x80008008u, 0x80000000u)
+);
+
+var<private> RHO: array<u32, 24> = array<u32, 24>(
+    1u,  3u,  6u,  10u, 15u, 21u, 28u, 36u, 45u, 55u, 2u,  14u,
+    27u, 41u, 56u, 8u,  25u, 43u, 62u, 18u, 39u, 61u, 20u, 44u
+);
+
+var<private> PREFIX: array<u32, 20> = array<u32, 20>(
+    0x3du, 0x60u, 0x2du, 0x80u, 0x60u, 0x0au, 0x3du, 0x39u, 0x81u, 0xf3u,
+    0x36u, 0x3du, 0x3du, 0x37u, 0x3du, 0x3du, 0x3du, 0x36u, 0x3du, 0x73u
+);
+
+var<private> SUFFIX: array<u32, 16> = array<u32, 16>(
+    0x5au, 0xf4u, 0x3du, 0x82u, 0x80u, 0x3eu, 0x90u, 0x3du,
+    0x91u, 0x60u, 0x2bu, 0x57u, 0xfdu, 0x5bu, 0xf3u, 0xffu
+);
+
+// 64-bit rotate left; n is never 0 or 32 (theta rotates by 1 and no rho offset is 0 or 32)
+fn rotl64(v: vec2<u32>, n: u32) -> vec2<u32> {
+    if (n < 32u) {
+        return vec2<u32>((v.x << n) | (v.y >> (32u - n)), (v.y << n) | (v.x >> (32u - n)));
+    }
+    let m = n - 32u;
+    return vec2<u32>((v.y << m) | (v.x >> (32u - m)), (v.x << m) | (v.y >> (32u - m)));
+}
+
+// Keccak-f[1600] permutation
+fn keccak_f(state: ptr<function, array<vec2<u32>, 25>>) {
+    var c: array<vec2<u32>, 5>;
+    var b: array<vec2<u32>, 25>;
+
+    for (var round_index = 0u; round_index < 24u; round_index++) {
+        // Theta
+        for (var i = 0u; i < 5u; i++) {
+            c[i] = (*state)[i] ^ (*state)[i + 5u] ^ (*state)[i + 10u] ^ (*state)[i + 15u] ^ (*state)[i + 20u];
+        }
+        for (var i = 0u; i < 5u; i++) {
+            let d = c[(i + 4u) % 5u] ^ rotl64(c[(i + 1u) % 5u], 1u);
+            for (var j = 0u; j < 25u; j += 5u) {
+                (*state)[j + i] ^= d;
+            }
+        }
+
+        // Rho and Pi
+        b[0] = (*state)[0];
+        var x = 1u;
+        var y = 0u;
+        for (var t = 0u; t < 24u; t++) {
+            b[y + 5u * ((2u * x + 3u * y) % 5u)] = rotl64((*state)[x + 5u * y], RHO[t]);
+            let temp = x;
+            x = y;
+            y = (2u * temp + 3u * y) % 5u;
+        }
+
+        // Chi
+        for (var j = 0u; j < 25u; j += 5u) {
+            for (var i = 0u; i < 5u; i++) {
+                (*state)[j + i] = b[j + i] ^ (~b[j + (i + 1u) % 5u] & b[j + (i + 2u) % 5u]);
+            }
+        }
+
+        // Iota
+        (*state)[0] ^= RC[round_index];
+    }
+}
+
+fn xor_state_byte(state: ptr<function, array<vec2<u32>, 25>>, i: u32, value: u32) {
+    let shift = (i % 4u) * 8u;
+    if (i % 8u < 4u) {
+        (*state)[i / 8u].x ^= value << shift;
+    } else {
+        (*state)[i / 8u].y ^= value << shift;
+    }
+}
+
+// Keccak256 of the first len bytes of input; every message here (55, 85 or 40 bytes) fits in
+// one 136-byte rate block, so there is a single absorb and no block loop
+fn keccak256(input: ptr<function, array<u32, 136>>, len: u32) -> array<u32, 32> {
+    var state: array<vec2<u32>, 25>;
+    for (var i = 0u; i < len; i++) {
+        xor_state_byte(&state, i, (*input)[i]);
+    }
+
+    // Padding
+    xor_state_byte(&state, len, 0x01u);
+    xor_state_byte(&state, 135u, 0x80u);
+    keccak_f(&state);
+
+    // Squeeze phase
+    var output: array<u32, 32>;
+    for (var i = 0u; i < 32u; i++) {
+        let lane = state[i / 8u];
+        let word = select(lane.y, lane.x, i % 8u < 4u);
+        output[i] = (word >> ((i % 4u) * 8u)) & 0xffu;
+    }
+    return output;
+}
+
+fn param_byte(offset: u32) -> u32 {
+    return (params[offset / 4u] >> ((offset % 4u) * 8u)) & 0xffu;
+}
+
+fn hex_char(value: u32) -> u32 {
+    if (value < 10u) {
+        return 48u + value;  // '0'
+    }
+    return 87u + value;  // 'a' - 10
+}
+
+// 64-bit arithmetic for PCG32 on (low, high) pairs
+fn add64(a: vec2<u32>, b: vec2<u32>) -> vec2<u32> {
+    let lo = a.x + b.x;
+    return vec2<u32>(lo, a.y + b.y + select(0u, 1u, lo < a.x));
+}
+
+// High 32 bits of a 32x32 product, from 16-bit halves
+fn mul_hi32(a: u32, b: u32) -> u32 {
+    let a0 = a & 0xffffu;
+    let a1 = a >> 16u;
+    let b0 = b & 0xffffu;
+    let b1 = b >> 16u;
+    let p01 = a0 * b1;
+    let p10 = a1 * b0;
+    let mid = ((a0 * b0) >> 16u) + (p01 & 0xffffu) + (p10 & 0xffffu);
+    return a1 * b1 + (p01 >> 16u) + (p10 >> 16u) + (mid >> 16u);
+}
+
+// Low 64 bits of a 64x64 product
+fn mul64(a: vec2<u32>, b: vec2<u32>) -> vec2<u32> {
+    return vec2<u32>(a.x * b.x, mul_hi32(a.x, b.x) + a.x * b.y + a.y * b.x);
+}
+
+// 64-bit shift right by 0 < n < 32
+fn shr64(v: vec2<u32>, n: u32) -> vec2<u32> {
+    return vec2<u32>((v.x >> n) | (v.y << (32u - n)), v.y >> n);
+}
+
+// PCG32 Random Number Generator, same sequence as the CUDA and Metal kernels
+struct PcgState {
+    state: vec2<u32>,
+    inc: vec2<u32>,
+}
+
+// 6364136223846793005
+const PCG_MULTIPLIER: vec2<u32> = vec2<u32>(0x4c957f2du, 0x5851f42du);
+// 1099511628211, large prime for stream separation
+const STREAM_PRIME: vec2<u32> = vec2<u32>(0x000001b3u, 0x00000100u);
+
+fn pcg32_random(rng: ptr<function, PcgState>) -> u32 {
+    let oldstate = (*rng).state;
+    (*rng).state = add64(mul64(oldstate, PCG_MULTIPLIER), (*rng).inc);
+    let shifted = shr64(shr64(oldstate, 18u) ^ oldstate, 27u);
+    let xorshifted = shifted.x;
+    let rot = oldstate.y >> 27u;
+    return (xorshifted >> rot) | (xorshifted << ((32u - rot) & 31u));
+}
+
+fn pcg32_init(rng: ptr<function, PcgState>, seed: vec2<u32>, stream: vec2<u32>) {
+    (*rng).state = vec2<u32>(0u, 0u);
+    (*rng).inc = vec2<u32>((stream.x << 1u) | 1u, (stream.y << 1u) | (stream.x >> 31u));
+    pcg32_random(rng);
+    (*rng).state = add64((*rng).state, seed);
+    pcg32_random(rng);
+}
+
+// Generate random hex string using GPU RNG
+fn generate_random_salt(rng: ptr<function, PcgState>, salt: ptr<function, array<u32, 32>>) {
+    // Generate 32 hex characters from the top 16 bits of 8 random numbers
+    for (var i = 0u; i < 8u; i++) {
+        let value = pcg32_random(rng);
+        (*salt)[i * 4u] = hex_char((value >> 28u) & 0xfu);
+        (*salt)[i * 4u + 1u] = hex_char((value >> 24u) & 0xfu);
+        (*salt)[i * 4u + 2u] = hex_char((value >> 20u) & 0xfu);
+        (*salt)[i * 4u + 3u] = hex_char((value >> 16u) & 0xfu);
+    }
+}
+
+// Compares an address against the filter; with exact false the filter character is
+// lowercased first, for the check against the lowercase hex before the checksum is applied
+fn matches_filter(address: ptr<function, array<u32, 40>>, exact: bool) -> bool {
+    for (var i = 0u; i < 40u; i++) {
+        var f = param_byte(FILTER + i);
+        if (f == 0u) {
+            continue;
+        }
+        if (!exact && f >= 65u && f <= 70u) {  // 'A'..'F'
+            f += 32u;
+        }
+        if ((*address)[i] != f) {
+            return false;
+        }
+    }
+    return true;
+}
+
+// Threads are numbered as if the dispatch used THREADS_PER_GROUP per group like the CUDA and Metal
+// kernels, so GPU random salts do not depend on the fixed WORKGROUP_SIZE; large batches use a 2D grid
+@compute @workgroup_size(256)
+fn compute_create2_batch(
+    @builtin(global_invocation_id) global_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    let gid = global_id.x + global_id.y * num_workgroups.x * WORKGROUP_SIZE;
+    let tid = gid % params[THREADS_PER_GROUP];
+
+    // Thread coarsening: each thread processes multiple addresses
+    let batch_size = params[BATCH_SIZE];
+    let start_idx = gid * params[ADDRESSES_PER_THREAD];
+    let end_idx = min(start_idx + params[ADDRESSES_PER_THREAD], batch_size);
+
+    if (start_idx >= batch_size) {
+        return;
+    }
+
+    // Initialize GPU RNG if needed
+    let use_gpu_random = params[USE_GPU_RANDOM] == 1u;
+    var rng: PcgState;
+    if (use_gpu_random) {
+        // The seed wraps in 32 bits before it is widened, as in the CUDA kernel
+        pcg32_init(&rng, vec2<u32>(params[RANDOM_SEED] + gid, 0u), mul64(vec2<u32>(tid, 0u), STREAM_PRIME));
+    }
+
+    // Bytecode without the salt: PREFIX, implementation, SUFFIX, deployer (76 bytes)
+    var bytecode: array<u32, 108>;
+    for (var i = 0u; i < 20u; i++) {
+        bytecode[i] = PREFIX[i];
+        bytecode[20u + i] = param_byte(IMPLEMENTATION + i);
+        bytecode[56u + i] = param_byte(DEPLOYER + i);
+    }
+    for (var i = 0u; i < 16u; i++) {
+        bytecode[40u + i] = SUFFIX[i];
+    }
+
+    let filtering = params[USE_FILTER] == 1u;
+
+    // Process multiple addresses per thread
+    for (var idx = start_idx; idx < end_idx; idx++) {
+        // Get salt for this iteration
+        var salt: array<u32, 32>;
+        if (use_gpu_random) {
+            generate_random_salt(&rng, &salt);
+        } else {
+            for (var i = 0u; i < 32u; i++) {
+                let offset = idx * 32u + i;
+                salt[i] = (salts[offset / 4u] >> ((offset % 4u) * 8u)) & 0xffu;
+            }
+        }
+        for (var i = 0u; i < 32u; i++) {
+            bytecode[76u + i] = salt[i];
+        }
+
+        // First hash - computed from the first 55 bytes of the bytecode
+        var first_hash: array<u32, 32>;
+        if (params[USE_INIT_CODE_HASH] == 1u) {
+            for (var i = 0u; i < 32u; i++) {
+                first_hash[i] = param_byte(INIT_CODE_HASH + i);
+            }
+        } else {
+            var first_part: array<u32, 136>;
+            for (var i = 0u; i < 55u; i++) {
+                first_part[i] = bytecode[i];
+            }
+            first_hash = keccak256(&first_part, 55u);
+        }
+
+        // Second part: the remaining bytecode followed by the first hash
+        var second_part: array<u32, 136>;
+        for (var i = 0u; i < 53u; i++) {
+            second_part[i] = bytecode[55u + i];
+        }
+        for (var i = 0u; i < 32u; i++) {
+            second_part[53u + i] = first_hash[i];
+        }
+        let second_hash = keccak256(&second_part, 85u);
+
+        // Last 20 bytes as a lowercase hex address
+        var address: array<u32, 40>;
+        for (var i = 0u; i < 20u; i++) {
+            let b = second_hash[12u + i];
+            address[i * 2u] = hex_char(b >> 4u);
+            address[i * 2u + 1u] = hex_char(b & 0xfu);
+        }
+
+        // Most addresses fail the lowercase check, which skips the checksum hash and the write back
+        if (filtering && !matches_filter(&address, false)) {
+            continue;
+        }
+
+        // Compute checksum
+        var address_input: array<u32, 136>;
+        for (var i = 0u; i < 40u; i++) {
+            address_input[i] = address[i];
+        }
+        let address_hash = keccak256(&address_input, 40u);
+
+        // Apply checksum
+        for (var i = 0u; i < 40u; i++) {
+            let c = address[i];
+            if (c >= 97u && c <= 102u) {  // 'a'..'f'
+                let byte_value = address_hash[i / 2u];
+                let nibble_value = select(byte_value & 0x0fu, byte_value >> 4u, i % 2u == 0u);
+                if (nibble_value >= 8u) {
+                    address[i] = c - 32u;  // Convert to uppercase
+                }
+            }
+        }
+
+        if (filtering && params[FILTER_IGNORE_CASE] == 0u && !matches_filter(&address, true)) {
+            continue;
+        }
+
+        // Store result; filtered candidates are packed at the front of the buffer
+        var slot = idx;
+        if (filtering) {
+            slot = atomicAdd(&result_count, 1u);
+        }
+        let base = slot * RESULT_WORDS;
+        for (var w = 0u; w < 10u; w++) {
+            results[base + w] = address[w * 4u] | (address[w * 4u + 1u] << 8u)
+                | (address[w * 4u + 2u] << 16u) | (address[w * 4u + 3u] << 24u);
+        }
+        results[base + 10u] = idx;
+        for (var w = 0u; w < 8u; w++) {
+            results[base + 11u + w] = salt[w * 4u] | (salt[w * 4u + 1u] << 8u)
+                | (salt[w * 4u + 2u] << 16u) | (salt[w * 4u + 3u] << 24u);
+        }
+    }
+}
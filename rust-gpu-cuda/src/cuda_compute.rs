@@ -0,0 +1,301 @@
+use crate::gpu_compute::{
+    decode_addresses_into, decode_candidates, group_size, kernel_params, BufferPool, Candidate, Create2Params,
+    Create2Result, GpuFilter,
+};
+use cudarc::driver::{result, sys, CudaDevice, CudaFunction, CudaSlice, DeviceRepr, DriverError, LaunchAsync, LaunchConfig, ValidAsZeroBits};
+use cudarc::nvrtc::compile_ptx;
+use create2_core::{Create2Error, Create2Template};
+use std::mem;
+use std::sync::Arc;
+
+const MODULE_NAME: &str = "create2";
+const KERNEL_NAME: &str = "compute_create2_batch";
+
+// Passed to the kernel by value
+unsafe impl DeviceRepr for Create2Params {}
+
+unsafe impl DeviceRepr for Create2Result {}
+unsafe impl ValidAsZeroBits for Create2Result {}
+
+fn alloc_error(e: DriverError) -> Create2Error {
+    Create2Error::GpuDispatch(format!("Failed to allocate CUDA buffer: {}", e))
+}
+
+fn dispatch_error(e: DriverError) -> Create2Error {
+    Create2Error::GpuDispatch(format!("CUDA dispatch failed: {}", e))
+}
+
+pub struct CudaCompute {
+    device: Arc<CudaDevice>,
+    device_name: String,
+    kernel: CudaFunction,
+    buffer_pool: BufferPool<CudaSlice<u8>, CudaSlice<Create2Result>>,
+    batch_size: usize,
+    max_threads_per_group: usize,
+    addresses_per_thread: u32,
+}
+
+impl CudaCompute {
+    pub fn new(batch_size: usize) -> Result<Self, Create2Error> {
+        // First visible device; select another one with CUDA_VISIBLE_DEVICES
+        let device = CudaDevice::new(0)
+            .map_err(|e| {
+                Create2Error::GpuInit(format!("CUDA device not found ({}). Ensure an NVIDIA GPU and driver are installed.", e))
+            })?;
+        let device_name = device.name()
+            .map_err(|e| Create2Error::GpuInit(format!("Failed to query CUDA device: {}", e)))?;
+        
+        status!("Using CUDA device: {}", device_name);
+        // Every pooled buffer holds batch_size entries, so one results buffer must fit on the device
+        let total_memory = unsafe { result::device::total_mem(*device.cu_device()) }
+            .map_err(|e| Create2Error::GpuInit(format!("Failed to query CUDA device memory: {}", e)))? as u64;
+        let results_size = (mem::size_of::<Create2Result>() as u64).saturating_mul(batch_size as u64);
+        if batch_size == 0 || batch_size > u32::MAX as usize || results_size > total_memory {
+            return Err(Create2Error::GpuInit(format!(
+                "Batch size {} is out of range for this device (total memory: {} bytes)",
+                batch_size,
+                total_memory
+            )));
+        }
+        
+        let max_threads = device
+            .attribute(sys::CUdevice_attribute::CU_DEVICE_ATTRIBUTE_MAX_THREADS_PER_BLOCK)
+            .map_err(|e| Create2Error::GpuInit(format!("Failed to query CUDA device: {}", e)))?;
+        verbose!("Max threads per block: {}", max_threads);
+        verbose!("Total memory: {} bytes", total_memory);
+        
+        let max_threads_per_group = max_threads as usize;
+        
+        // Thread coarsening: each thread processes 4 addresses for better instruction-level parallelism
+        let addresses_per_thread = 4u32;
+        
+        // Load kernel source and compile it with NVRTC for the installed driver
+        let kernel_source = include_str!("create2_kernel.cu");
+        let ptx = compile_ptx(kernel_source)
+            .map_err(|e| Create2Error::GpuInit(format!("Failed to compile CUDA kernel: {:?}", e)))?;
+        device
+            .load_ptx(ptx, MODULE_NAME, &[KERNEL_NAME])
+            .map_err(|e| Create2Error::GpuInit(format!("Failed to load CUDA module: {}", e)))?;
+        
+        // Get compute function
+        let kernel = device
+            .get_func(MODULE_NAME, KERNEL_NAME)
+            .ok_or_else(|| Create2Error::GpuInit(format!("Failed to get compute function: {}", KERNEL_NAME)))?;
+        
+        verbose!(
+            "Kernel: max {} threads per block, {} addresses per thread",
+            max_threads_per_group,
+            addresses_per_thread
+        );
+        
+        Ok(CudaCompute {
+            device,
+            device_name,
+            kernel,
+            // Create buffer pool for reuse
+            buffer_pool: BufferPool::new(),
+            batch_size,
+            max_threads_per_group,
+            addresses_per_thread,
+        })
+    }
+    
+    // With a filter only matching candidates are written back, compacted through an atomic
+    // counter, so the host no longer copies and scans the whole batch; without one every address is returned
+    pub fn compute_batch_filtered(
+        &self,
+        template: &Create2Template,
+        batch_size: usize,
+        random_seed: u32,
+        filter: Option<&GpuFilter>,
+    ) -> Result<Vec<Candidate>, Create2Error> {
+        decode_candidates(&self.gpu_random_results(template, batch_size, random_seed, filter)?)
+    }
+    
+    // An unfiltered GPU random batch written over out, which the caller keeps between batches
+    pub fn compute_batch_gpu_random_into(
+        &self,
+        template: &Create2Template,
+        batch_size: usize,
+        random_seed: u32,
+        out: &mut Vec<(String, u32)>,
+    ) -> Result<(), Create2Error> {
+        decode_addresses_into(&self.gpu_random_results(template, batch_size, random_seed, None)?, out)
+    }
+    
+    // A batch of provided salts written over out, which the caller keeps between batches
+    pub fn compute_batch_with_salts_into(
+        &self,
+        template: &Create2Template,
+        salts: &[[u8; 32]],
+        out: &mut Vec<(String, u32)>,
+    ) -> Result<(), Create2Error> {
+        decode_addresses_into(&self.salts_results(template, salts)?, out)
+    }
+    
+    fn gpu_random_results(
+        &self,
+        template: &Create2Template,
+        batch_size: usize,
+        random_seed: u32,
+        filter: Option<&GpuFilter>,
+    ) -> Result<Vec<Create2Result>, Create2Error> {
+        self.check_batch_size(batch_size)?;
+        
+        // Get buffers from pool
+        let salts_buffer = self.buffer_pool.get_salts_buffer(|| self.alloc_salts_buffer())?;
+        let mut results_buffer = self.buffer_pool.get_results_buffer(|| self.alloc_results_buffer())?;
+        
+        // Ensure buffers are returned to pool even on error
+        let result = self.compute_batch_gpu_random_internal(
+            template,
+            batch_size,
+            random_seed,
+            filter,
+            &salts_buffer,
+            &mut results_buffer,
+        );
+        
+        // Always return buffers to pool
+        self.buffer_pool.return_salts_buffer(salts_buffer);
+        self.buffer_pool.return_results_buffer(results_buffer);
+        
+        result
+    }
+    
+    fn salts_results(&self, template: &Create2Template, salts: &[[u8; 32]]) -> Result<Vec<Create2Result>, Create2Error> {
+        self.check_batch_size(salts.len())?;
+        
+        let mut salts_buffer = self.buffer_pool.get_salts_buffer(|| self.alloc_salts_buffer())?;
+        let mut results_buffer = self.buffer_pool.get_results_buffer(|| self.alloc_results_buffer())?;
+        
+        // Ensure buffers are returned to pool even on error
+        let result = self.compute_batch_with_salts_internal(
+            template,
+            salts,
+            &mut salts_buffer,
+            &mut results_buffer,
+        );
+        
+        // Always return buffers to pool
+        self.buffer_pool.return_salts_buffer(salts_buffer);
+        self.buffer_pool.return_results_buffer(results_buffer);
+        
+        result
+    }
+    
+    pub fn device_name(&self) -> &str {
+        &self.device_name
+    }
+    
+    // (新分配的buffer数, 复用次数)
+    pub fn buffer_pool_stats(&self) -> (usize, usize) {
+        self.buffer_pool.stats()
+    }
+    
+    // The pooled buffers are sized for batch_size entries; larger batches would overrun them
+    fn check_batch_size(&self, count: usize) -> Result<(), Create2Error> {
+        if count == 0 || count > self.batch_size {
+            return Err(Create2Error::GpuDispatch(format!(
+                "Batch of {} addresses does not fit the buffer pool (1..={})",
+                count, self.batch_size
+            )));
+        }
+        Ok(())
+    }
+    
+    fn alloc_salts_buffer(&self) -> Result<CudaSlice<u8>, Create2Error> {
+        self.device.alloc_zeros::<u8>(32 * self.batch_size).map_err(alloc_error)
+    }
+    
+    fn alloc_results_buffer(&self) -> Result<CudaSlice<Create2Result>, Create2Error> {
+        self.device.alloc_zeros::<Create2Result>(self.batch_size).map_err(alloc_error)
+    }
+    
+    fn launch_config(&self, count: usize) -> LaunchConfig {
+        let (num_threads_needed, threads_per_group) =
+            group_size(count, self.addresses_per_thread, self.max_threads_per_group);
+        LaunchConfig {
+            grid_dim: (num_threads_needed.div_ceil(threads_per_group) as u32, 1, 1),
+            block_dim: (threads_per_group as u32, 1, 1),
+            shared_mem_bytes: 0,
+        }
+    }
+    
+    fn compute_batch_gpu_random_internal(
+        &self,
+        template: &Create2Template,
+        batch_size: usize,
+        random_seed: u32,
+        filter: Option<&GpuFilter>,
+        salts_buffer: &CudaSlice<u8>,
+        results_buffer: &mut CudaSlice<Create2Result>,
+    ) -> Result<Vec<Create2Result>, Create2Error> {
+        
+        // Prepare parameters for GPU random generation
+        let params = Create2Params {
+            filter: filter.map_or([0u8; 40], |filter| filter.chars),
+            random_seed,
+            use_gpu_random: 1,  // Always use GPU random
+            use_filter: filter.is_some() as u32,
+            filter_ignore_case: filter.is_some_and(|filter| filter.ignore_case) as u32,
+            ..kernel_params(template, batch_size, self.addresses_per_thread)
+        };
+        
+        // No salt copying needed for GPU random generation
+        
+        // Filtered candidates are appended at the slot this counter hands out; one per launch
+        let mut count_buffer = self.device.alloc_zeros::<u32>(1).map_err(alloc_error)?;
+        
+        // Launch kernel; the copies below run on the same stream and wait for it
+        unsafe {
+            self.kernel.clone().launch(
+                self.launch_config(batch_size),
+                (params, salts_buffer, &mut *results_buffer, &mut count_buffer),  // Salts unused in GPU random mode
+            )
+        }
+        .map_err(dispatch_error)?;
+        
+        // Read results: the first count slots when filtering, otherwise the whole batch in order
+        let count = match filter {
+            Some(_) => self.device.dtoh_sync_copy(&count_buffer).map_err(dispatch_error)?[0] as usize,
+            None => batch_size,
+        }
+        .min(batch_size);
+        if count == 0 {
+            return Ok(Vec::new());
+        }
+        self.device.dtoh_sync_copy(&results_buffer.slice(0..count)).map_err(dispatch_error)
+    }
+    
+    fn compute_batch_with_salts_internal(
+        &self,
+        template: &Create2Template,
+        salts: &[[u8; 32]],
+        salts_buffer: &mut CudaSlice<u8>,
+        results_buffer: &mut CudaSlice<Create2Result>,
+    ) -> Result<Vec<Create2Result>, Create2Error> {
+        
+        // Prepare parameters for salt-based computation
+        let params = kernel_params(template, salts.len(), self.addresses_per_thread);
+        
+        // Salts are already padded to 32 bytes, so the whole batch is one copy
+        let salt_bytes = salts.as_flattened();
+        self.device
+            .htod_sync_copy_into(salt_bytes, &mut salts_buffer.slice_mut(0..salt_bytes.len()))
+            .map_err(dispatch_error)?;
+        let mut count_buffer = self.device.alloc_zeros::<u32>(1).map_err(alloc_error)?;  // Unused without a filter
+        
+        // Launch kernel
+        unsafe {
+            self.kernel.clone().launch(
+                self.launch_config(salts.len()),
+                (params, &*salts_buffer, &mut *results_buffer, &mut count_buffer),
+            )
+        }
+        .map_err(dispatch_error)?;
+        
+        // Read results
+        self.device.dtoh_sync_copy(&results_buffer.slice(0..salts.len())).map_err(dispatch_error)
+    }
+}
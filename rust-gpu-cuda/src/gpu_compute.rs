@@ -0,0 +1,510 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::collections::VecDeque;
+use crate::config::GpuBackend;
+#[cfg(feature = "cuda")]
+use crate::cuda_compute::CudaCompute;
+#[cfg(feature = "opencl")]
+use crate::opencl_compute::OpenClCompute;
+#[cfg(feature = "wgpu")]
+use crate::wgpu_compute::WgpuCompute;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use create2_core::Create2Template;
+use create2_core::salt::IntoSalt;
+use create2_core::Create2Error;
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct Create2Params {
+    // Raw address bytes, decoded once on the host
+    pub implementation: [u8; 20],
+    pub deployer: [u8; 20],
+    pub init_code_hash: [u8; 32],
+    // Required checksum characters per position, 0 for any; only read when use_filter is 1
+    pub filter: [u8; 40],
+    pub batch_size: u32,
+    pub addresses_per_thread: u32,
+    pub random_seed: u32,
+    pub use_gpu_random: u32,
+    pub use_init_code_hash: u32,
+    pub use_filter: u32,
+    pub filter_ignore_case: u32,
+}
+
+#[repr(C, packed)]
+#[derive(Debug, Clone, Copy)]
+pub struct Create2Result {
+    pub address: [u8; 40],
+    pub salt_index: u32,
+    // Salt string the kernel generated in GPU random mode
+    pub salt: [u8; 32],
+}
+
+// Per-position filter evaluated on the device before anything is written back;
+// built from PatternSet::char_filter, so a candidate still needs the exact CPU check
+#[derive(Debug, Clone, Copy)]
+pub struct GpuFilter {
+    pub chars: [u8; 40],
+    pub ignore_case: bool,
+}
+
+impl GpuFilter {
+    pub fn new(chars: &[u8], ignore_case: bool) -> Option<Self> {
+        Some(GpuFilter {
+            chars: chars.try_into().ok()?,
+            ignore_case,
+        })
+    }
+}
+
+// One address read back in find mode, with the position in its batch, the GPU salt and
+// the index of the template (deployer) that produced it
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    pub index: u32,
+    pub template: usize,
+    pub address: String,
+    pub salt: [u8; 32],
+}
+
+impl Candidate {
+    // GPU salts are 32 lowercase hex characters used as a string salt
+    pub fn salt(&self) -> String {
+        String::from_utf8_lossy(&self.salt).into_owned()
+    }
+}
+
+// Pools the salts and results buffers of one backend; S and R are that backend's buffer types,
+// and a buffer is only allocated when the pool is empty
+pub(crate) struct BufferPool<S, R> {
+    salts_buffers: Mutex<VecDeque<S>>,
+    results_buffers: Mutex<VecDeque<R>>,
+    // -vv 输出的统计：新分配的buffer数和从池中复用的次数
+    allocated: AtomicUsize,
+    reused: AtomicUsize,
+}
+
+impl<S, R> BufferPool<S, R> {
+    pub(crate) fn new() -> Self {
+        BufferPool {
+            salts_buffers: Mutex::new(VecDeque::new()),
+            results_buffers: Mutex::new(VecDeque::new()),
+            allocated: AtomicUsize::new(0),
+            reused: AtomicUsize::new(0),
+        }
+    }
+    
+    fn take<T>(&self, pool: &Mutex<VecDeque<T>>, allocate: impl FnOnce() -> Result<T, Create2Error>) -> Result<T, Create2Error> {
+        let buffer = pool.lock().unwrap().pop_front();
+        let counter = if buffer.is_some() { &self.reused } else { &self.allocated };
+        counter.fetch_add(1, Ordering::Relaxed);
+        buffer.map_or_else(allocate, Ok)
+    }
+    
+    pub(crate) fn stats(&self) -> (usize, usize) {
+        (self.allocated.load(Ordering::Relaxed), self.reused.load(Ordering::Relaxed))
+    }
+    
+    pub(crate) fn get_salts_buffer(&self, allocate: impl FnOnce() -> Result<S, Create2Error>) -> Result<S, Create2Error> {
+        self.take(&self.salts_buffers, allocate)
+    }
+    
+    // New results buffers must be zeroed by allocate
+    pub(crate) fn get_results_buffer(&self, allocate: impl FnOnce() -> Result<R, Create2Error>) -> Result<R, Create2Error> {
+        self.take(&self.results_buffers, allocate)
+    }
+    
+    pub(crate) fn return_salts_buffer(&self, buffer: S) {
+        let mut pool = self.salts_buffers.lock().unwrap();
+        if pool.len() < 16 {  // Increased pool size for better concurrency
+            pool.push_back(buffer);
+        }
+    }
+    
+    pub(crate) fn return_results_buffer(&self, buffer: R) {
+        let mut pool = self.results_buffers.lock().unwrap();
+        if pool.len() < 16 {  // Increased pool size for better concurrency
+            // Skip clearing for performance - will be overwritten anyway
+            pool.push_back(buffer);
+        }
+    }
+}
+
+// Kernel parameters for a batch of provided salts; GPU random mode overrides the seed and filter fields
+pub(crate) fn kernel_params(template: &Create2Template, batch_size: usize, addresses_per_thread: u32) -> Create2Params {
+    Create2Params {
+        implementation: template.implementation().map_or([0u8; 20], |implementation| *implementation.as_bytes()),
+        deployer: *template.deployer().as_bytes(),
+        init_code_hash: *template.init_code_hash(),
+        filter: [0u8; 40],
+        batch_size: batch_size as u32,
+        addresses_per_thread,
+        random_seed: 0,
+        use_gpu_random: 0,
+        // Templates built from an init code hash have no implementation to hash on the device
+        use_init_code_hash: template.implementation().is_none() as u32,
+        use_filter: 0,
+        filter_ignore_case: 0,
+    }
+}
+
+// (threads needed, threads per group) for count addresses. Every backend sizes groups like the
+// Metal one, since the per-thread PCG32 stream depends on the thread's index in its group
+pub(crate) fn group_size(count: usize, addresses_per_thread: u32, max_threads_per_group: usize) -> (usize, usize) {
+    // Optimize thread group size with thread coarsening
+    let num_threads_needed = (count as u32).div_ceil(addresses_per_thread) as usize;
+    
+    // Dynamic thread group sizing based on device capability and workload
+    let optimal_threads = match num_threads_needed {
+        n if n >= max_threads_per_group * 16 => max_threads_per_group,
+        n if n >= max_threads_per_group * 4 => max_threads_per_group / 2,
+        n if n >= max_threads_per_group => max_threads_per_group / 4,
+        n if n >= 256 => 256,
+        n if n >= 64 => 64,
+        _ => 32,
+    };
+    
+    (num_threads_needed, max_threads_per_group.min(optimal_threads).min(num_threads_needed))
+}
+
+pub(crate) fn decode_address(result: &Create2Result, i: usize) -> Result<String, Create2Error> {
+    Ok(format!("0x{}", address_hex(result, i)?))
+}
+
+fn address_hex(result: &Create2Result, i: usize) -> Result<&str, Create2Error> {
+    std::str::from_utf8(&result.address[..40])
+        .map_err(|e| Create2Error::GpuDispatch(format!("Failed to decode address at index {}: {}", i, e)))
+}
+
+// Unfiltered results in batch order as (address, index), written over out: its Vec and every
+// String it already holds are reused, so a caller that keeps out between batches of the same
+// size allocates nothing per address after the first batch
+pub(crate) fn decode_addresses_into(results: &[Create2Result], out: &mut Vec<(String, u32)>) -> Result<(), Create2Error> {
+    out.truncate(results.len());
+    for (i, result) in results.iter().enumerate() {
+        let hex = address_hex(result, i)?;
+        match out.get_mut(i) {
+            Some((address, index)) => {
+                address.clear();
+                address.push_str("0x");
+                address.push_str(hex);
+                *index = i as u32;
+            }
+            None => out.push((format!("0x{}", hex), i as u32)),
+        }
+    }
+    Ok(())
+}
+
+// Filtered results arrive in completion order; sorting keeps matches in batch order for checkpoints
+pub(crate) fn decode_candidates(results: &[Create2Result]) -> Result<Vec<Candidate>, Create2Error> {
+    let mut candidates = Vec::with_capacity(results.len());
+    for (i, result) in results.iter().enumerate() {
+        candidates.push(Candidate {
+            index: result.salt_index,
+            template: 0,
+            address: decode_address(result, i)?,
+            salt: result.salt,
+        });
+    }
+    candidates.sort_unstable_by_key(|candidate| candidate.index);
+    Ok(candidates)
+}
+
+// Salts checked against create2-core when a device is set up
+#[cfg(any(feature = "opencl", feature = "wgpu"))]
+const SELF_CHECK_SALTS: usize = 64;
+
+// Predicts a fixed batch on the device and on the CPU (create2-core) and refuses the device on any
+// difference; run by the backends whose kernel is compiled by a driver (OpenCL) or a shader translator (wgpu)
+#[cfg(any(feature = "opencl", feature = "wgpu"))]
+pub(crate) fn self_check(
+    name: &str,
+    batch_size: usize,
+    compute_batch_with_salts: impl FnOnce(&Create2Template, &[[u8; 32]]) -> Result<Vec<(String, u32)>, Create2Error>,
+) -> Result<(), Create2Error> {
+    let template = Create2Template::from_addresses([0x11; 20], [0x22; 20]);
+    let count = SELF_CHECK_SALTS.min(batch_size);
+    let salts: Vec<[u8; 32]> = (0..count).map(|i| [i as u8; 32]).collect();
+    let addresses = compute_batch_with_salts(&template, &salts)?;
+    for (salt, (address, _)) in salts.iter().zip(&addresses) {
+        let expected = template.predict(*salt)?.to_string();
+        if *address != expected {
+            return Err(Create2Error::GpuInit(format!(
+                "{} kernel disagrees with the CPU implementation: got {}, expected {}",
+                name, address, expected
+            )));
+        }
+    }
+    verbose!("{} self-check: {} addresses match the CPU implementation", name, count);
+    Ok(())
+}
+
+// The backend GpuAccelerator dispatches to, chosen once by --backend
+enum Compute {
+    #[cfg(feature = "cuda")]
+    Cuda(CudaCompute),
+    #[cfg(feature = "opencl")]
+    OpenCl(OpenClCompute),
+    #[cfg(feature = "wgpu")]
+    Wgpu(WgpuCompute),
+}
+
+impl Compute {
+    fn new(backend: GpuBackend, batch_size: usize) -> Result<Self, Create2Error> {
+        match backend {
+            #[cfg(feature = "cuda")]
+            GpuBackend::Cuda => Ok(Compute::Cuda(CudaCompute::new(batch_size)?)),
+            #[cfg(feature = "opencl")]
+            GpuBackend::Opencl => Ok(Compute::OpenCl(OpenClCompute::new(batch_size)?)),
+            #[cfg(feature = "wgpu")]
+            GpuBackend::Wgpu | GpuBackend::Vulkan => Ok(Compute::Wgpu(WgpuCompute::new(backend, batch_size)?)),
+            // auto (unavailable backends are rejected by Settings::check_backend): the first of
+            // BACKENDS that initializes, reporting the error of the last one when none does
+            _ => {
+                let (last, fallbacks) = crate::BACKENDS.split_last().expect("at least one backend is compiled");
+                for backend in fallbacks {
+                    match Self::new(*backend, batch_size) {
+                        Ok(compute) => return Ok(compute),
+                        Err(e) => status!("{} unavailable ({}), trying the next backend", backend, e),
+                    }
+                }
+                Self::new(*last, batch_size)
+            }
+        }
+    }
+    
+    fn compute_batch_filtered(
+        &self,
+        template: &Create2Template,
+        batch_size: usize,
+        random_seed: u32,
+        filter: Option<&GpuFilter>,
+    ) -> Result<Vec<Candidate>, Create2Error> {
+        match self {
+            #[cfg(feature = "cuda")]
+            Compute::Cuda(compute) => compute.compute_batch_filtered(template, batch_size, random_seed, filter),
+            #[cfg(feature = "opencl")]
+            Compute::OpenCl(compute) => compute.compute_batch_filtered(template, batch_size, random_seed, filter),
+            #[cfg(feature = "wgpu")]
+            Compute::Wgpu(compute) => compute.compute_batch_filtered(template, batch_size, random_seed, filter),
+        }
+    }
+    
+    fn compute_batch_gpu_random_into(
+        &self,
+        template: &Create2Template,
+        batch_size: usize,
+        random_seed: u32,
+        out: &mut Vec<(String, u32)>,
+    ) -> Result<(), Create2Error> {
+        match self {
+            #[cfg(feature = "cuda")]
+            Compute::Cuda(compute) => compute.compute_batch_gpu_random_into(template, batch_size, random_seed, out),
+            #[cfg(feature = "opencl")]
+            Compute::OpenCl(compute) => compute.compute_batch_gpu_random_into(template, batch_size, random_seed, out),
+            #[cfg(feature = "wgpu")]
+            Compute::Wgpu(compute) => compute.compute_batch_gpu_random_into(template, batch_size, random_seed, out),
+        }
+    }
+    
+    fn compute_batch_with_salts_into(
+        &self,
+        template: &Create2Template,
+        salts: &[[u8; 32]],
+        out: &mut Vec<(String, u32)>,
+    ) -> Result<(), Create2Error> {
+        match self {
+            #[cfg(feature = "cuda")]
+            Compute::Cuda(compute) => compute.compute_batch_with_salts_into(template, salts, out),
+            #[cfg(feature = "opencl")]
+            Compute::OpenCl(compute) => compute.compute_batch_with_salts_into(template, salts, out),
+            #[cfg(feature = "wgpu")]
+            Compute::Wgpu(compute) => compute.compute_batch_with_salts_into(template, salts, out),
+        }
+    }
+    
+    fn device_name(&self) -> &str {
+        match self {
+            #[cfg(feature = "cuda")]
+            Compute::Cuda(compute) => compute.device_name(),
+            #[cfg(feature = "opencl")]
+            Compute::OpenCl(compute) => compute.device_name(),
+            #[cfg(feature = "wgpu")]
+            Compute::Wgpu(compute) => compute.device_name(),
+        }
+    }
+    
+    fn backend(&self) -> GpuBackend {
+        match self {
+            #[cfg(feature = "cuda")]
+            Compute::Cuda(_) => GpuBackend::Cuda,
+            #[cfg(feature = "opencl")]
+            Compute::OpenCl(_) => GpuBackend::Opencl,
+            #[cfg(feature = "wgpu")]
+            Compute::Wgpu(compute) => compute.backend(),
+        }
+    }
+    
+    fn buffer_pool_stats(&self) -> (usize, usize) {
+        match self {
+            #[cfg(feature = "cuda")]
+            Compute::Cuda(compute) => compute.buffer_pool_stats(),
+            #[cfg(feature = "opencl")]
+            Compute::OpenCl(compute) => compute.buffer_pool_stats(),
+            #[cfg(feature = "wgpu")]
+            Compute::Wgpu(compute) => compute.buffer_pool_stats(),
+        }
+    }
+}
+
+pub struct GpuAccelerator {
+    compute: Compute,
+    // Host-side generator for the per-batch kernel seeds; seeding it makes GPU random runs replayable
+    rng: Mutex<ChaCha8Rng>,
+}
+
+impl GpuAccelerator {
+    pub fn new(backend: GpuBackend, batch_size: usize, seed: u64) -> Result<Self, Create2Error> {
+        let compute = Compute::new(backend, batch_size)?;
+        Ok(GpuAccelerator {
+            compute,
+            rng: Mutex::new(ChaCha8Rng::seed_from_u64(seed)),
+        })
+    }
+    
+    pub fn device_name(&self) -> &str {
+        self.compute.device_name()
+    }
+    
+    // The backend actually in use; auto resolves to cuda, opencl, wgpu or vulkan
+    pub fn backend(&self) -> GpuBackend {
+        self.compute.backend()
+    }
+    
+    pub fn buffer_pool_stats(&self) -> (usize, usize) {
+        self.compute.buffer_pool_stats()
+    }
+    
+    // Position of the batch seed generator, saved in --resume checkpoints
+    pub fn rng_position(&self) -> u128 {
+        self.rng.lock().unwrap().get_word_pos()
+    }
+    
+    pub fn set_rng_position(&self, position: u128) {
+        self.rng.lock().unwrap().set_word_pos(position);
+    }
+    
+    // Draws the next batch seed without dispatching it, for batches the caller already searched
+    pub fn skip_batch(&self) {
+        self.rng.lock().unwrap().gen::<u32>();
+    }
+    
+    pub fn process_batch_gpu_random(
+        &self,
+        template: &Create2Template,
+        batch_size: usize,
+    ) -> Result<Vec<(String, u32)>, Create2Error> {
+        let mut results = Vec::new();
+        self.process_batch_gpu_random_into(template, batch_size, &mut results)?;
+        Ok(results)
+    }
+    
+    // Like process_batch_gpu_random, but overwrites the caller's vector instead of allocating one per batch
+    pub fn process_batch_gpu_random_into(
+        &self,
+        template: &Create2Template,
+        batch_size: usize,
+        out: &mut Vec<(String, u32)>,
+    ) -> Result<(), Create2Error> {
+        let random_seed = self.rng.lock().unwrap().gen::<u32>();
+        
+        self.compute.compute_batch_gpu_random_into(template, batch_size, random_seed, out)
+    }
+    
+    // Every template is dispatched with the same batch seed, so each GPU salt is checked against
+    // all of them; candidates come back in batch order, then template order
+    pub fn process_batch_filtered(
+        &self,
+        templates: &[Create2Template],
+        batch_size: usize,
+        filter: Option<&GpuFilter>,
+    ) -> Result<Vec<Candidate>, Create2Error> {
+        let random_seed = self.rng.lock().unwrap().gen::<u32>();
+        
+        let mut candidates = Vec::new();
+        for (index, template) in templates.iter().enumerate() {
+            let batch = self.compute.compute_batch_filtered(template, batch_size, random_seed, filter)?;
+            candidates.extend(batch.into_iter().map(|candidate| Candidate { template: index, ..candidate }));
+        }
+        candidates.sort_by_key(|candidate| (candidate.index, candidate.template));
+        Ok(candidates)
+    }
+    
+    // Overwrites the caller's vector instead of allocating one per batch
+    pub fn process_batch_with_salt_into<S: IntoSalt>(
+        &self,
+        template: &Create2Template,
+        salts: &[S],
+        out: &mut Vec<(String, u32)>,
+    ) -> Result<(), Create2Error> {
+        // Each salt is converted up front and copied into a fixed 32-byte slot of the salts buffer
+        let salts = salts.iter().map(IntoSalt::to_salt).collect::<Result<Vec<_>, _>>()?;
+        self.compute.compute_batch_with_salts_into(template, &salts, out)
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use create2_core::test_vectors::{Create2Vector, CREATE2_VECTORS};
+    use create2_core::EVM_CREATE2_PREFIX;
+    
+    const RANDOM_BATCH: usize = 256;
+    
+    // Every compiled backend that initializes here; on a machine without a GPU this is empty
+    // and the tests below pass without checking anything
+    fn accelerators() -> Vec<GpuAccelerator> {
+        crate::BACKENDS
+            .iter()
+            .filter_map(|backend| match GpuAccelerator::new(*backend, RANDOM_BATCH, 0) {
+                Ok(accelerator) => Some(accelerator),
+                Err(e) => {
+                    eprintln!("skipping {}: {}", backend, e);
+                    None
+                }
+            })
+            .collect()
+    }
+    
+    // The kernels only hash with the EVM 0xff prefix
+    fn evm_vectors() -> impl Iterator<Item = &'static Create2Vector> {
+        CREATE2_VECTORS.iter().filter(|vector| vector.prefix == EVM_CREATE2_PREFIX)
+    }
+    
+    #[test]
+    fn salts_kernel_matches_test_vectors() {
+        for gpu in accelerators() {
+            let mut results = Vec::new();
+            for vector in evm_vectors() {
+                gpu.process_batch_with_salt_into(&vector.template(), &[vector.salt()], &mut results).unwrap();
+                assert_eq!(results[0].0, vector.expected().to_string(), "{} on {}", vector.name, gpu.backend());
+            }
+        }
+    }
+    
+    #[test]
+    fn random_kernel_matches_cpu() {
+        for gpu in accelerators() {
+            for vector in evm_vectors() {
+                let template = vector.template();
+                let candidates = gpu.process_batch_filtered(std::slice::from_ref(&template), RANDOM_BATCH, None).unwrap();
+                assert_eq!(candidates.len(), RANDOM_BATCH, "{} on {}", vector.name, gpu.backend());
+                for candidate in candidates {
+                    let expected = template.predict(candidate.salt).unwrap().to_string();
+                    assert_eq!(candidate.address, expected, "{} on {}, salt {}", vector.name, gpu.backend(), candidate.salt());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,586 @@
+#[macro_use]
+extern crate create2_cli;
+mod create2;
+mod gpu_compute;
+#[cfg(feature = "cuda")]
+mod cuda_compute;
+#[cfg(feature = "opencl")]
+mod opencl_compute;
+#[cfg(feature = "wgpu")]
+mod wgpu_compute;
+
+use create2_cli::{bloom, checkpoint, cli, compare, config, format_duration, nonces, notify, output, pattern, progress, prompt, salts, validate};
+use bloom::{SaltBloom, DEFAULT_BLOOM_CAPACITY, DEFAULT_BLOOM_FP_RATE};
+use checkpoint::Checkpoint;
+use cli::Command;
+use config::{GpuBackend, PatternPosition, Scheme, Settings, Verbosity, DEFAULT_BENCH_DIFFICULTY, DEFAULT_NONCE_COUNT};
+use create2::Create2Predictor;
+use gpu_compute::GpuFilter;
+use create2_core::salt::SaltPolicy;
+use create2_core::{check_address, Address, Create2Error, Create2Template};
+use notify::Notifier;
+use pattern::{AddressKind, PatternSet};
+use progress::ProgressReporter;
+use salts::SaltReader;
+use validate::Validation;
+use output::{BenchSummary, MatchSink, Output, Prediction, ResultsFile};
+use std::time::{Duration, Instant};
+
+const TOTAL_OPERATIONS: usize = 50_000_000;
+const IMPLEMENTATION: &str = "0xa84c57e9966df7df79bff42f35c68aae71796f64";
+const DEPLOYER: &str = "0xfe15afcb5b9831b8af5fd984678250e95de8e312";
+const PROGRESS_INTERVAL: usize = 10000;
+const GPU_BATCH_SIZE: usize = 262144; // 256K - Quadrupled batch size with thread coarsening
+const DEFAULT_PATTERN: &str = "eAce1";
+// auto按此顺序尝试：CUDA，没有NVIDIA设备或驱动时用OpenCL，再不行用wgpu；每个后端都可用同名feature关闭。
+// vulkan与wgpu是同一个后端，只是限定Vulkan API，放在最后
+#[cfg(not(any(feature = "cuda", feature = "opencl", feature = "wgpu")))]
+compile_error!("enable at least one of the cuda, opencl and wgpu features");
+const BACKENDS: &[GpuBackend] = &[
+    #[cfg(feature = "cuda")]
+    GpuBackend::Cuda,
+    #[cfg(feature = "opencl")]
+    GpuBackend::Opencl,
+    #[cfg(feature = "wgpu")]
+    GpuBackend::Wgpu,
+    #[cfg(feature = "wgpu")]
+    GpuBackend::Vulkan,
+];
+
+fn run_benchmark(settings: &Settings) -> Result<(), Box<dyn std::error::Error>> {
+    let template = settings.template()?;
+    let output = Output::open(settings.output.as_deref())?;
+    // 未指定--seed时随机生成并打印，每个批次的GPU种子都由它派生
+    let seed = settings.seed.unwrap_or_else(rand::random);
+    
+    status!("🚀 Rust CREATE2地址预测benchmark (CUDA GPU加速版)");
+    status!("总计算量: {} 次", TOTAL_OPERATIONS);
+    status!("实现合约: {}", settings.implementation);
+    status!("部署者: {}", settings.deployer);
+    print_init_code_hash(settings);
+    status!("GPU批处理大小: {}", settings.batch_size);
+    status!("随机数生成: GPU上生成 (PCG32算法)");
+    status!("随机种子: {}", seed);
+    status!("--------------------------------------------------------------------------------");
+    
+    let predictor = Create2Predictor::new(true, settings.backend, settings.batch_size, seed)?;
+    
+    if !predictor.is_gpu_enabled() {
+        eprintln!("❌ GPU不可用，请检查CUDA或OpenCL驱动");
+        return Err("GPU initialization failed".into());
+    }
+    
+    let start_time = Instant::now();
+    let reporter = ProgressReporter::new(settings);
+    let mut processed = 0;
+    // 每个批次覆盖写入同一个结果Vec，不再为每个地址重新分配String
+    let mut results = Vec::with_capacity(settings.batch_size);
+    
+    while processed < TOTAL_OPERATIONS {
+        let batch_size = std::cmp::min(settings.batch_size, TOTAL_OPERATIONS - processed);
+        
+        match predictor.predict_batch_address_into(&template, batch_size, &mut results) {
+            Ok(()) => {
+                processed += batch_size;
+                
+                let tick = if processed >= TOTAL_OPERATIONS {
+                    Some(reporter.finish(processed))
+                } else {
+                    reporter.tick(processed)
+                };
+                if let Some(tick) = tick {
+                    let percentage = (tick.count as f64 / TOTAL_OPERATIONS as f64) * 100.0;
+                    
+                    progress!("\r进度: {:.2}% ({}/{}) | 平均TPS: {:.0} | 当前TPS: {:.0} | 用时: {}",
+                        percentage, tick.count, TOTAL_OPERATIONS, tick.avg_tps, tick.current_tps, 
+                        format_duration(tick.elapsed));
+                }
+            }
+            Err(e) => {
+                eprintln!("\n错误: GPU处理失败 - {}", e);
+                return Err(Box::new(e));
+            }
+        }
+    }
+    
+    let total_elapsed = start_time.elapsed();
+    let avg_tps = TOTAL_OPERATIONS as f64 / total_elapsed.as_secs_f64();
+    let us_per_op = total_elapsed.as_micros() as f64 / TOTAL_OPERATIONS as f64;
+    
+    status!("\n--------------------------------------------------------------------------------");
+    status!("✅ 计算完成! (GPU加速)");
+    status!();
+    summary!("📊 Benchmark 结果:");
+    summary!("==================================================");
+    summary!("总操作数:     {}", TOTAL_OPERATIONS);
+    summary!("总用时:       {}", format_duration(total_elapsed));
+    summary!("平均TPS:      {:.2} ops/sec", avg_tps);
+    summary!("每次操作耗时: {:.2} μs", us_per_op);
+    
+    let mut summary = BenchSummary::new("gpu-cuda", TOTAL_OPERATIONS, total_elapsed.as_secs_f64());
+    summary.batch_size = Some(settings.batch_size);
+    summary.device = predictor.device_name();
+    summary.seed = Some(seed);
+    print_buffer_pool_stats(&predictor);
+    output::emit_json(&summary)?;
+    output.record(&summary.to_string())?;
+    
+    Ok(())
+}
+
+// bench-find：以末尾 --bench-difficulty 个0为合成规则，按benchmark的计算量跑完整的 生成salt→哈希→匹配→输出 流程，
+// 报告每秒找到的地址数；用时包括find模式启动时的校准
+fn run_match_benchmark(settings: &Settings) -> Result<(), Box<dyn std::error::Error>> {
+    let output = Output::open(settings.output.as_deref())?;
+    let seed = settings.seed.unwrap_or_else(rand::random);
+    let difficulty = settings.bench_difficulty;
+    let settings = settings.bench_find("0".repeat(difficulty), TOTAL_OPERATIONS, seed);
+    let expected = PatternSet::from_settings(&settings, AddressKind::Evm)?
+        .expected_attempts()
+        .ok_or("bench-find needs a pattern with a difficulty estimate")?;
+    
+    let start_time = Instant::now();
+    let matches = find_address(&settings)?;
+    let total_elapsed = start_time.elapsed();
+    let matches_per_sec = matches as f64 / total_elapsed.as_secs_f64();
+    
+    status!();
+    summary!("📊 匹配Benchmark 结果:");
+    summary!("==================================================");
+    summary!("难度:         末尾 {} 个0，平均每 {:.0} 次尝试匹配一次", difficulty, expected);
+    summary!("总尝试次数:   {}", TOTAL_OPERATIONS);
+    summary!("总用时:       {:.1}s", total_elapsed.as_secs_f64());
+    summary!("平均TPS:      {:.2} ops/sec", TOTAL_OPERATIONS as f64 / total_elapsed.as_secs_f64());
+    summary!("找到地址数:   {}（期望 {:.1}）", matches, TOTAL_OPERATIONS as f64 / expected);
+    summary!("每秒匹配数:   {:.2} matches/sec", matches_per_sec);
+    
+    let mut summary = BenchSummary::new("gpu-cuda", TOTAL_OPERATIONS, total_elapsed.as_secs_f64());
+    summary.batch_size = Some(settings.batch_size);
+    summary.seed = Some(seed);
+    summary.difficulty = Some(difficulty);
+    summary.matches = Some(matches);
+    summary.matches_per_sec = Some(matches_per_sec);
+    output::emit_json(&summary)?;
+    output.record(&summary.to_string())?;
+    
+    Ok(())
+}
+
+fn run_single_test(settings: &Settings) -> Result<(), Box<dyn std::error::Error>> {
+    status!("Running single test for verification...");
+    let implementation: Address = "0xa84c57e9966df7df79bff42f35c68aae71796f64".parse()?;
+    let deployer: Address = "0xfe15afcb5b9831b8af5fd984678250e95de8e312".parse()?;
+    let template = Create2Template::from_addresses(implementation, deployer);
+    // 使用原始的salt格式以匹配预期地址
+    let salt = "test-salt-test";
+
+    let predictor = Create2Predictor::new(true, settings.backend, 1, 0)?;
+    if !predictor.is_gpu_enabled() {
+        eprintln!("❌ GPU不可用，请检查CUDA或OpenCL驱动");
+        return Err("GPU initialization failed".into());
+    }
+    
+    status!("\n📝 测试参数:");
+    status!("  Implementation: {}", implementation);
+    status!("  Deployer: {}", deployer);
+    status!("  Salt: {}", salt);
+    
+    // 使用固定的salt进行单次测试
+    let salts = vec![salt.to_string()];
+    match predictor.predict_batch_with_salt(&template, &salts) {
+        Ok(results) => {
+            let expected = "0x22FBFB2264B9Cd1ADe8ce5013012c817878D783C";
+            
+            if results[0] == expected {
+                summary!("✅ 结果: {}", results[0]);
+            } else {
+                summary!("⚠️  地址不匹配!");
+            }
+        }
+        Err(e) => {
+            eprintln!("❌ 测试失败: {}", e);
+            return Err(Box::new(e));
+        }
+    }
+    
+    Ok(())
+}
+
+// 返回找到的地址数，达到停止条件后正常退出
+fn find_address(settings: &Settings) -> Result<usize, Box<dyn std::error::Error>> {
+    let targets = settings.find_targets();
+    let pattern = PatternSet::from_settings(settings, AddressKind::Evm)?.per_salt(targets.len());
+    let templates = settings.templates()?;
+    let output = Output::open(settings.output.as_deref())?;
+    let results_file = ResultsFile::open(settings.results.as_deref(), "gpu-cuda", pattern.spec())?;
+    let notifier = Notifier::new(settings.notify_url.as_deref(), "gpu-cuda", pattern.spec())?;
+    // --resume 恢复时沿用检查点里的种子和批次种子位置
+    let mut checkpoint = Checkpoint::open(settings, settings.seed.unwrap_or_else(rand::random), 1)?;
+    pattern.restore(&checkpoint.matches);
+    checkpoint.check_batch_size(settings.batch_size)?;
+    let resume_path = settings.resume.as_deref();
+    let seed = checkpoint.seed;
+    let mut bloom = SaltBloom::open(settings)?;
+    
+    status!("🔍 开始搜索{}的EVM CREATE2地址...", pattern);
+    if let Some(attempts) = pattern.expected_attempts() {
+        status!("难度: 平均每 {:.0} 次尝试匹配一次", attempts);
+    }
+    pattern.print_rules();
+    status!("Implementation: {}", settings.find_implementations().join(", "));
+    status!("Deployer: {}", settings.find_deployers().join(", "));
+    print_init_code_hash(settings);
+    status!("GPU批处理大小: {}", settings.batch_size);
+    status!("随机数生成: GPU上生成 (PCG32算法)");
+    status!("随机种子: {}", seed);
+    if let Some(ref bloom) = bloom {
+        status!("布隆过滤器: {}", bloom.describe());
+    }
+    print_find_limits(settings);
+    status!("按Ctrl+C停止搜索");
+    status!("--------------------------------------------------------------------------------");
+    
+    let predictor = Create2Predictor::new(true, settings.backend, settings.batch_size, seed)?;
+    
+    if !predictor.is_gpu_enabled() {
+        eprintln!("❌ GPU不可用，请检查CUDA或OpenCL驱动");
+        return Err("GPU initialization failed".into());
+    }
+    
+    // 单个前缀、后缀或掩码规则在GPU上先筛选，只拷回候选地址；其他规则拷回整个批次在CPU上比较
+    let filter = pattern
+        .char_filter()
+        .and_then(|filter| GpuFilter::new(&filter.chars, filter.ignore_case));
+    if filter.is_some() {
+        status!("GPU端过滤: 只拷回可能匹配的地址");
+    }
+    
+    // 校准用的批次不计入搜索，随后把随机数位置恢复到检查点
+    if let Some(attempts) = pattern.expected_attempts() {
+        let tps = progress::calibrate(|| {
+            predictor.search_batch(&templates, settings.batch_size, filter.as_ref()).map_or(0, |_| settings.batch_size)
+        });
+        status!("校准TPS: {:.0} | 预计平均每 {} 找到一个地址", tps, progress::format_eta(attempts, tps));
+    }
+    predictor.set_rng_position(checkpoint.rng_positions[0]);
+    
+    let start_time = Instant::now();
+    let elapsed_offset = checkpoint.elapsed();
+    let reporter = ProgressReporter::resumed(settings, checkpoint.attempts, elapsed_offset);
+    let mut total_processed = checkpoint.attempts;
+    let sink = MatchSink::resumed(settings, &targets, &pattern, &output, &results_file, &notifier, &checkpoint);
+    let mut batch_num = 0;
+    let mut batch_offset = checkpoint.batch_offset;
+    let mut skipped = 0;
+    
+    while settings.max_attempts.is_none_or(|max| total_processed < max) {
+        let batch_position = predictor.rng_position().unwrap_or(0);
+        
+        // 布隆过滤器中已有的批次只推进批次种子，不调度GPU，也不计入尝试次数
+        if bloom.as_ref().is_some_and(|bloom| bloom.contains(seed, 0, batch_position)) {
+            predictor.skip_batch();
+            skipped += 1;
+            continue;
+        }
+        batch_num += 1;
+        
+        match predictor.search_batch(&templates, settings.batch_size, filter.as_ref()) {
+            Ok(candidates) => {
+                // 从检查点恢复的第一个批次跳过已经检查过的地址，最后一个批次只统计到 --max-attempts 为止；
+                // 过滤后只有候选地址，尝试次数按候选在批次中的位置计算
+                let start = std::mem::take(&mut batch_offset);
+                let end = settings
+                    .max_attempts
+                    .map_or(settings.batch_size, |max| start.saturating_add(max.saturating_sub(total_processed)))
+                    .min(settings.batch_size);
+                let attempts_before = total_processed;
+                let mut checked = end;
+                let mut done = false;
+                
+                for candidate in candidates.iter().filter(|candidate| (start..end).contains(&(candidate.index as usize))) {
+                    let address = &candidate.address;
+                    total_processed = attempts_before + candidate.index as usize + 1 - start;
+                    
+                    if pattern.matches(address) {
+                        checkpoint.matches.extend(sink.report(candidate.template, candidate.salt(), address, total_processed)?);
+                        
+                        if settings.max_matches.is_some_and(|max| sink.count() >= max) {
+                            checked = candidate.index as usize + 1;
+                            done = true;
+                            break;
+                        }
+                    }
+                }
+                total_processed = attempts_before + checked - start;
+                
+                // 批次检查完时记录下一个批次的位置，中途停止时记录本批次起点和批内偏移
+                if checked == settings.batch_size {
+                    checkpoint.rng_positions[0] = predictor.rng_position().unwrap_or(0);
+                    checkpoint.batch_offset = 0;
+                    // 只记录完整搜索过的批次
+                    if let Some(ref mut bloom) = bloom {
+                        bloom.insert(seed, 0, batch_position);
+                    }
+                } else {
+                    checkpoint.rng_positions[0] = batch_position;
+                    checkpoint.batch_offset = checked;
+                }
+                
+                let elapsed = elapsed_offset + start_time.elapsed();
+                checkpoint.attempts = total_processed;
+                checkpoint.elapsed_secs = elapsed.as_secs_f64();
+                checkpoint.save_if_due(resume_path)?;
+                if let Some(ref mut bloom) = bloom {
+                    bloom.save_if_due()?;
+                }
+                
+                if let Some(tick) = reporter.tick(total_processed) {
+                    progress!("\r已尝试: {} | 批次: {} | 平均TPS: {:.0} | 当前TPS: {:.0} | 用时: {}{}{}     ", 
+                        tick.count, batch_num, tick.avg_tps, tick.current_tps, format_duration(tick.elapsed),
+                        tick.eta(pattern.expected_attempts()), pattern.best(&checkpoint.matches));
+                }
+                
+                if done || settings.find_limit_reached(total_processed, sink.count(), elapsed) {
+                    break;
+                }
+            }
+            Err(e) => {
+                eprintln!("\n错误: GPU处理失败 - {}", e);
+                eprintln!("批次: {}, 已处理: {}", batch_num, total_processed);
+                return Err(Box::new(e));
+            }
+        }
+    }
+    
+    let elapsed = elapsed_offset + start_time.elapsed();
+    if let Some(path) = resume_path {
+        checkpoint.elapsed_secs = elapsed.as_secs_f64();
+        checkpoint.save(path)?;
+    }
+    if let Some(ref bloom) = bloom {
+        bloom.save()?;
+        summary!("\n♻️  跳过了 {} 个已搜索过的批次", skipped);
+    }
+    
+    print_buffer_pool_stats(&predictor);
+    summary!("\n🏁 搜索结束: 已尝试 {} 次, 找到 {} 个地址, 用时 {}",
+        total_processed, sink.count(), format_duration(elapsed));
+    pattern.print_hits();
+    pattern.print_top(&checkpoint.matches, &output)?;
+    Ok(sink.count())
+}
+
+fn print_buffer_pool_stats(predictor: &Create2Predictor) {
+    if let Some((allocated, reused)) = predictor.buffer_pool_stats() {
+        trace!("Buffer pool: {} buffers allocated, {} reused", allocated, reused);
+    }
+}
+
+fn print_init_code_hash(settings: &Settings) {
+    if let Some(ref hash) = settings.init_code_hash {
+        status!("Init code hash: {}（通用CREATE2，忽略实现合约）", hash);
+    } else if settings.scheme != Scheme::Minimal {
+        status!("代理方案: {}", settings.scheme);
+        if let Some(ref path) = settings.proxy_bytecode {
+            status!("Creation code: {}", path.display());
+        }
+        if let Some(ref owner) = settings.account_owner {
+            status!("账户owner: {}", owner);
+        }
+        if let Some(ref args) = settings.proxy_args {
+            status!("代理参数: {}", args);
+        }
+    }
+}
+
+fn print_find_limits(settings: &Settings) {
+    if let Some(max) = settings.max_attempts {
+        status!("最大尝试次数: {}", max);
+    }
+    if let Some(max) = settings.max_time {
+        status!("最长搜索时间: {}秒", max);
+    }
+    if let Some(max) = settings.max_matches {
+        status!("最多找到地址数: {}", max);
+    }
+}
+
+// 按批处理大小分批读取salt文件交给GPU计算，结果按输入顺序流式输出
+fn run_predict(settings: &Settings) -> Result<(), Box<dyn std::error::Error>> {
+    let path = settings.salts_file.as_deref().ok_or("predict requires --salts-file")?;
+    let mut reader = SaltReader::open(path, settings.salt_policy)?;
+    let template = settings.template()?;
+    let output = Output::open(settings.output.as_deref())?;
+    
+    status!("📄 从 {} 读取salt", path.display());
+    status!("实现合约: {}", settings.implementation);
+    status!("部署者: {}", settings.deployer);
+    print_init_code_hash(settings);
+    status!("GPU批处理大小: {}", settings.batch_size);
+    status!("--------------------------------------------------------------------------------");
+    
+    let predictor = Create2Predictor::new(true, settings.backend, settings.batch_size, 0)?;
+    
+    if !predictor.is_gpu_enabled() {
+        eprintln!("❌ GPU不可用，请检查CUDA或OpenCL驱动");
+        return Err("GPU initialization failed".into());
+    }
+    
+    let start_time = Instant::now();
+    let mut total = 0;
+    
+    loop {
+        let salts = reader.read_batch(settings.batch_size)?;
+        if salts.is_empty() {
+            break;
+        }
+        total += salts.len();
+        
+        let salt_bytes: Vec<[u8; 32]> = salts.iter().map(|(_, bytes)| *bytes).collect();
+        let addresses = predictor.predict_batch_with_salt(&template, &salt_bytes)?;
+        for ((salt, _), address) in salts.into_iter().zip(addresses) {
+            output::emit_prediction(&output, &Prediction { salt, address })?;
+        }
+    }
+    print_buffer_pool_stats(&predictor);
+    
+    status!("--------------------------------------------------------------------------------");
+    status!("✅ 共计算 {} 个地址, 用时 {}", total, format_duration(start_time.elapsed()));
+    Ok(())
+}
+
+// 检查运行参数并打印各模式会做什么：会初始化CUDA或OpenCL设备并编译内核（OpenCL还会做一次与CPU对照的自检），不提交任何批次
+fn run_validate(settings: &Settings) -> Result<(), Box<dyn std::error::Error>> {
+    let mut validation = Validation::new();
+    let deployers = settings.deployers.iter().map(|deployer| ("find模式部署者", deployer));
+    let implementations = settings.implementations.iter().map(|implementation| ("find模式实现合约", implementation));
+    for (label, address) in [("实现合约", &settings.implementation), ("部署者", &settings.deployer)]
+        .into_iter()
+        .chain(implementations)
+        .chain(deployers)
+    {
+        validation.check(label, address.parse::<Address>().map(|address| address.to_string()));
+    }
+    validation.check("GPU后端", settings.check_backend(BACKENDS).map(|_| settings.backend.to_string()));
+    let predictor = Create2Predictor::new(true, settings.backend, settings.batch_size, 0)?;
+    let device = predictor
+        .device_name()
+        .zip(predictor.backend())
+        .map(|(name, backend)| format!("{}（{}），GPU批处理大小 {}", name, backend, settings.batch_size))
+        .ok_or_else(|| Create2Error::GpuInit("CUDA和OpenCL都不可用，已回退到CPU；bench、find和predict需要GPU".to_string()));
+    validation.check("GPU设备", device);
+    validation.check("benchmark模式", Ok(format!("计算 {} 个随机salt的地址", TOTAL_OPERATIONS)));
+    validation.check_common(settings, AddressKind::Evm);
+    if let Some(checkpoint) = validation.check_checkpoint(settings, 1) {
+        validation.check("检查点批处理大小", checkpoint.check_batch_size(settings.batch_size).map(|_| settings.batch_size.to_string()));
+    }
+    print_find_limits(settings);
+    validation.finish()?;
+    Ok(())
+}
+
+fn main() {
+    if let Err(e) = run() {
+        output::report_error(e.as_ref());
+        std::process::exit(1);
+    }
+}
+
+fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let defaults = Settings {
+        implementation: IMPLEMENTATION.to_string(),
+        implementations: Vec::new(),
+        deployer: DEPLOYER.to_string(),
+        deployers: Vec::new(),
+        chain: None,
+        init_code_hash: None,
+        scheme: Scheme::Minimal,
+        proxy_args: None,
+        proxy_bytecode: None,
+        account_owner: None,
+        batch_size: GPU_BATCH_SIZE,
+        backend: GpuBackend::Auto,
+        threads: None,
+        hybrid: false,
+        batches_per_dispatch: 1,
+        addresses_per_thread: 4,
+        command_queues: 1,
+        pattern: DEFAULT_PATTERN.to_string(),
+        position: PatternPosition::Suffix,
+        patterns: Vec::new(),
+        suffix: None,
+        ignore_case: false,
+        regex: None,
+        mask: None,
+        objective: None,
+        vanity: None,
+        score: None,
+        top: 10,
+        output: None,
+        results: None,
+        notify_url: None,
+        json: false,
+        seed: None,
+        max_attempts: None,
+        max_time: None,
+        max_matches: None,
+        first_match: false,
+        bench_difficulty: DEFAULT_BENCH_DIFFICULTY,
+        nonce_start: 0,
+        nonce_count: DEFAULT_NONCE_COUNT,
+        verbosity: Verbosity::Normal,
+        salts_file: None,
+        salt_policy: SaltPolicy::Pad,
+        resume: None,
+        bloom: None,
+        bloom_capacity: DEFAULT_BLOOM_CAPACITY,
+        bloom_fp_rate: DEFAULT_BLOOM_FP_RATE,
+        tune_cache: None,
+        tuned: None,
+        profile: None,
+        progress_interval: PROGRESS_INTERVAL,
+        progress_every: Duration::from_millis(100),
+    };
+    let args = cli::parse_args(std::env::args())?;
+    if args.help {
+        cli::print_usage(env!("CARGO_BIN_NAME"));
+        return Ok(());
+    }
+    let mut settings = config::load_settings(args.config_path, args.overrides, defaults)?;
+    let mut command = args.command;
+    if prompt::interactive() {
+        command = prompt::ask(&mut settings, check_address)?;
+    }
+    output::set_json_mode(settings.json);
+    output::set_first_match_mode(settings.first_match);
+    output::set_verbosity(settings.verbosity);
+    verbose!("运行参数: {:?}", settings);
+    
+    // validate把 --chain 和 --backend 的问题和其他检查一起报告
+    if command != Command::Validate {
+        settings.check_chain(AddressKind::Evm)?;
+        settings.check_backend(BACKENDS)?;
+    }
+
+    match command {
+        Command::Test => run_single_test(&settings),
+        Command::Find => {
+            // 停止时一个地址都没找到，以非零退出码结束；--first-match 用2与出错的1区分
+            if find_address(&settings)? == 0 {
+                std::process::exit(if settings.first_match { 2 } else { 1 });
+            }
+            Ok(())
+        }
+        Command::Predict => run_predict(&settings),
+        Command::Nonces => nonces::run_nonce_sweep(&settings),
+        Command::Compare => compare::run_scheme_comparison(&settings),
+        Command::Validate => run_validate(&settings),
+        Command::Tune => Err("tune (GPU autotuning) is only supported on the Metal builds".into()),
+        Command::Completions(shell) => {
+            cli::print_completions(shell, env!("CARGO_BIN_NAME"));
+            Ok(())
+        }
+        Command::Bench => run_benchmark(&settings),
+        Command::BenchFind => run_match_benchmark(&settings),
+    }
+}
\ No newline at end of file
@@ -0,0 +1,335 @@
+use crate::gpu_compute::{
+    decode_addresses_into, decode_candidates, group_size, kernel_params, self_check, BufferPool, Candidate,
+    Create2Params, Create2Result, GpuFilter,
+};
+use opencl3::command_queue::CommandQueue;
+use opencl3::context::Context;
+use opencl3::device::{get_all_devices, Device, CL_DEVICE_TYPE_GPU};
+use opencl3::error_codes::ClError;
+use opencl3::kernel::{ExecuteKernel, Kernel};
+use opencl3::memory::{Buffer, CL_MEM_COPY_HOST_PTR, CL_MEM_READ_ONLY, CL_MEM_READ_WRITE};
+use opencl3::program::Program;
+use opencl3::types::{cl_uint, CL_BLOCKING};
+use create2_core::{Create2Error, Create2Template};
+use std::mem;
+use std::ptr;
+use std::sync::Mutex;
+
+const KERNEL_NAME: &str = "compute_create2_batch";
+
+const EMPTY_RESULT: Create2Result = Create2Result { address: [0; 40], salt_index: 0, salt: [0; 32] };
+
+fn alloc_error(e: ClError) -> Create2Error {
+    Create2Error::GpuDispatch(format!("Failed to allocate OpenCL buffer: {}", e))
+}
+
+fn dispatch_error(e: ClError) -> Create2Error {
+    Create2Error::GpuDispatch(format!("OpenCL dispatch failed: {}", e))
+}
+
+// Portable fallback for AMD and Intel GPUs: the same kernel as CUDA, built from create2_kernel.cl
+// by the platform's OpenCL compiler. Driver compilers differ more than NVRTC does, so a new device
+// is only used after its addresses for a fixed set of salts match the CPU implementation
+pub struct OpenClCompute {
+    context: Context,
+    queue: CommandQueue,
+    // Arguments are set on the kernel object itself, so concurrent launches take turns
+    kernel: Mutex<Kernel>,
+    device_name: String,
+    buffer_pool: BufferPool<Buffer<u8>, Buffer<Create2Result>>,
+    batch_size: usize,
+    max_threads_per_group: usize,
+    addresses_per_thread: u32,
+}
+
+impl OpenClCompute {
+    pub fn new(batch_size: usize) -> Result<Self, Create2Error> {
+        // First GPU of the first platform that has one
+        let device_id = get_all_devices(CL_DEVICE_TYPE_GPU)
+            .ok()
+            .and_then(|devices| devices.first().copied())
+            .ok_or_else(|| {
+                Create2Error::GpuInit("OpenCL GPU not found. Ensure an OpenCL driver (ICD) is installed.".to_string())
+            })?;
+        let device = Device::new(device_id);
+        let query_error = |e: ClError| Create2Error::GpuInit(format!("Failed to query OpenCL device: {}", e));
+        let device_name = device.name().map_err(query_error)?;
+
+        status!("Using OpenCL device: {}", device_name);
+        // Every pooled buffer holds batch_size entries, so one results buffer must fit in a single allocation
+        let max_alloc = device.max_mem_alloc_size().map_err(query_error)?;
+        let results_size = (mem::size_of::<Create2Result>() as u64).saturating_mul(batch_size as u64);
+        if batch_size == 0 || batch_size > u32::MAX as usize || results_size > max_alloc {
+            return Err(Create2Error::GpuInit(format!(
+                "Batch size {} is out of range for this device (max allocation: {} bytes)",
+                batch_size,
+                max_alloc
+            )));
+        }
+
+        let max_threads_per_group = device.max_work_group_size().map_err(query_error)?;
+        verbose!("Max work group size: {}", max_threads_per_group);
+        verbose!("Max allocation: {} bytes", max_alloc);
+
+        // Thread coarsening: each thread processes 4 addresses, as on CUDA and Metal
+        let addresses_per_thread = 4u32;
+
+        let context = Context::from_device(&device)
+            .map_err(|e| Create2Error::GpuInit(format!("Failed to create OpenCL context: {}", e)))?;
+        let queue = CommandQueue::create_default_with_properties(&context, 0, 0)
+            .map_err(|e| Create2Error::GpuInit(format!("Failed to create OpenCL command queue: {}", e)))?;
+
+        // Build the kernel for this device
+        let kernel_source = include_str!("create2_kernel.cl");
+        let program = Program::create_and_build_from_source(&context, kernel_source, "")
+            .map_err(|e| Create2Error::GpuInit(format!("Failed to compile OpenCL kernel: {}", e)))?;
+        let kernel = Kernel::create(&program, KERNEL_NAME)
+            .map_err(|e| Create2Error::GpuInit(format!("Failed to get compute function: {}", e)))?;
+
+        let compute = OpenClCompute {
+            context,
+            queue,
+            kernel: Mutex::new(kernel),
+            device_name,
+            buffer_pool: BufferPool::new(),
+            batch_size,
+            max_threads_per_group,
+            addresses_per_thread,
+        };
+        self_check("OpenCL", batch_size, |template, salts| compute.compute_batch_with_salts(template, salts))?;
+        Ok(compute)
+    }
+
+    // With a filter only matching candidates are written back, compacted through an atomic
+    // counter, so the host no longer copies and scans the whole batch; without one every address is returned
+    pub fn compute_batch_filtered(
+        &self,
+        template: &Create2Template,
+        batch_size: usize,
+        random_seed: u32,
+        filter: Option<&GpuFilter>,
+    ) -> Result<Vec<Candidate>, Create2Error> {
+        decode_candidates(&self.gpu_random_results(template, batch_size, random_seed, filter)?)
+    }
+
+    // An unfiltered GPU random batch written over out, which the caller keeps between batches
+    pub fn compute_batch_gpu_random_into(
+        &self,
+        template: &Create2Template,
+        batch_size: usize,
+        random_seed: u32,
+        out: &mut Vec<(String, u32)>,
+    ) -> Result<(), Create2Error> {
+        decode_addresses_into(&self.gpu_random_results(template, batch_size, random_seed, None)?, out)
+    }
+
+    pub fn compute_batch_with_salts(
+        &self,
+        template: &Create2Template,
+        salts: &[[u8; 32]],
+    ) -> Result<Vec<(String, u32)>, Create2Error> {
+        let mut out = Vec::new();
+        self.compute_batch_with_salts_into(template, salts, &mut out)?;
+        Ok(out)
+    }
+
+    // Like compute_batch_with_salts, written over out, which the caller keeps between batches
+    pub fn compute_batch_with_salts_into(
+        &self,
+        template: &Create2Template,
+        salts: &[[u8; 32]],
+        out: &mut Vec<(String, u32)>,
+    ) -> Result<(), Create2Error> {
+        decode_addresses_into(&self.salts_results(template, salts)?, out)
+    }
+
+    fn gpu_random_results(
+        &self,
+        template: &Create2Template,
+        batch_size: usize,
+        random_seed: u32,
+        filter: Option<&GpuFilter>,
+    ) -> Result<Vec<Create2Result>, Create2Error> {
+        self.check_batch_size(batch_size)?;
+
+        let salts_buffer = self.buffer_pool.get_salts_buffer(|| self.alloc_salts_buffer())?;
+        let mut results_buffer = self.buffer_pool.get_results_buffer(|| self.alloc_results_buffer())?;
+
+        // Ensure buffers are returned to pool even on error
+        let result = self.compute_batch_gpu_random_internal(
+            template,
+            batch_size,
+            random_seed,
+            filter,
+            &salts_buffer,
+            &mut results_buffer,
+        );
+
+        self.buffer_pool.return_salts_buffer(salts_buffer);
+        self.buffer_pool.return_results_buffer(results_buffer);
+
+        result
+    }
+
+    fn salts_results(&self, template: &Create2Template, salts: &[[u8; 32]]) -> Result<Vec<Create2Result>, Create2Error> {
+        self.check_batch_size(salts.len())?;
+
+        let mut salts_buffer = self.buffer_pool.get_salts_buffer(|| self.alloc_salts_buffer())?;
+        let mut results_buffer = self.buffer_pool.get_results_buffer(|| self.alloc_results_buffer())?;
+
+        // Ensure buffers are returned to pool even on error
+        let result = self.compute_batch_with_salts_internal(
+            template,
+            salts,
+            &mut salts_buffer,
+            &mut results_buffer,
+        );
+
+        self.buffer_pool.return_salts_buffer(salts_buffer);
+        self.buffer_pool.return_results_buffer(results_buffer);
+
+        result
+    }
+
+    pub fn device_name(&self) -> &str {
+        &self.device_name
+    }
+
+    // (新分配的buffer数, 复用次数)
+    pub fn buffer_pool_stats(&self) -> (usize, usize) {
+        self.buffer_pool.stats()
+    }
+
+    // The pooled buffers are sized for batch_size entries; larger batches would overrun them
+    fn check_batch_size(&self, count: usize) -> Result<(), Create2Error> {
+        if count == 0 || count > self.batch_size {
+            return Err(Create2Error::GpuDispatch(format!(
+                "Batch of {} addresses does not fit the buffer pool (1..={})",
+                count, self.batch_size
+            )));
+        }
+        Ok(())
+    }
+
+    fn alloc_salts_buffer(&self) -> Result<Buffer<u8>, Create2Error> {
+        unsafe { Buffer::create(&self.context, CL_MEM_READ_ONLY, 32 * self.batch_size, ptr::null_mut()) }.map_err(alloc_error)
+    }
+
+    fn alloc_results_buffer(&self) -> Result<Buffer<Create2Result>, Create2Error> {
+        let mut buffer = unsafe { Buffer::create(&self.context, CL_MEM_READ_WRITE, self.batch_size, ptr::null_mut()) }
+            .map_err(alloc_error)?;
+        // Initialize with zeros; fill patterns must be a power of two in size, which Create2Result is not
+        unsafe { self.queue.enqueue_write_buffer(&mut buffer, CL_BLOCKING, 0, &vec![EMPTY_RESULT; self.batch_size], &[]) }
+            .map_err(alloc_error)?;
+        Ok(buffer)
+    }
+
+    // Filtered candidates are appended at the slot this counter hands out; one per launch
+    fn new_count_buffer(&self) -> Result<Buffer<cl_uint>, Create2Error> {
+        let mut count: cl_uint = 0;
+        unsafe {
+            Buffer::create(
+                &self.context,
+                CL_MEM_READ_WRITE | CL_MEM_COPY_HOST_PTR,
+                1,
+                &mut count as *mut cl_uint as *mut std::ffi::c_void,
+            )
+        }
+        .map_err(alloc_error)
+    }
+
+    // Launches one batch and waits for it; the global size is rounded up to whole work groups,
+    // and the kernel returns early for threads past the end of the batch
+    fn launch(
+        &self,
+        params: &Create2Params,
+        count: usize,
+        salts_buffer: &Buffer<u8>,
+        results_buffer: &mut Buffer<Create2Result>,
+        count_buffer: &mut Buffer<cl_uint>,
+    ) -> Result<(), Create2Error> {
+        let (num_threads_needed, threads_per_group) =
+            group_size(count, self.addresses_per_thread, self.max_threads_per_group);
+        let event = {
+            let kernel = self.kernel.lock().unwrap();
+            unsafe {
+                ExecuteKernel::new(&kernel)
+                    .set_arg(params)
+                    .set_arg(salts_buffer)
+                    .set_arg(results_buffer)
+                    .set_arg(count_buffer)
+                    .set_global_work_size(num_threads_needed.div_ceil(threads_per_group) * threads_per_group)
+                    .set_local_work_size(threads_per_group)
+                    .enqueue_nd_range(&self.queue)
+            }
+            .map_err(dispatch_error)?
+        };
+        event.wait().map_err(dispatch_error)
+    }
+
+    fn read_results(&self, results_buffer: &Buffer<Create2Result>, count: usize) -> Result<Vec<Create2Result>, Create2Error> {
+        let mut results = vec![EMPTY_RESULT; count];
+        unsafe { self.queue.enqueue_read_buffer(results_buffer, CL_BLOCKING, 0, &mut results, &[]) }
+            .map_err(dispatch_error)?;
+        Ok(results)
+    }
+
+    fn compute_batch_gpu_random_internal(
+        &self,
+        template: &Create2Template,
+        batch_size: usize,
+        random_seed: u32,
+        filter: Option<&GpuFilter>,
+        salts_buffer: &Buffer<u8>,
+        results_buffer: &mut Buffer<Create2Result>,
+    ) -> Result<Vec<Create2Result>, Create2Error> {
+        // Prepare parameters for GPU random generation
+        let params = Create2Params {
+            filter: filter.map_or([0u8; 40], |filter| filter.chars),
+            random_seed,
+            use_gpu_random: 1,  // Always use GPU random
+            use_filter: filter.is_some() as u32,
+            filter_ignore_case: filter.is_some_and(|filter| filter.ignore_case) as u32,
+            ..kernel_params(template, batch_size, self.addresses_per_thread)
+        };
+
+        let mut count_buffer = self.new_count_buffer()?;
+        // Salts unused in GPU random mode
+        self.launch(&params, batch_size, salts_buffer, results_buffer, &mut count_buffer)?;
+
+        // Read results: the first count slots when filtering, otherwise the whole batch in order
+        let count = match filter {
+            Some(_) => {
+                let mut count: [cl_uint; 1] = [0];
+                unsafe { self.queue.enqueue_read_buffer(&count_buffer, CL_BLOCKING, 0, &mut count, &[]) }
+                    .map_err(dispatch_error)?;
+                count[0] as usize
+            }
+            None => batch_size,
+        }
+        .min(batch_size);
+        if count == 0 {
+            return Ok(Vec::new());
+        }
+        self.read_results(results_buffer, count)
+    }
+
+    fn compute_batch_with_salts_internal(
+        &self,
+        template: &Create2Template,
+        salts: &[[u8; 32]],
+        salts_buffer: &mut Buffer<u8>,
+        results_buffer: &mut Buffer<Create2Result>,
+    ) -> Result<Vec<Create2Result>, Create2Error> {
+        // Prepare parameters for salt-based computation
+        let params = kernel_params(template, salts.len(), self.addresses_per_thread);
+
+        // Salts are already padded to 32 bytes, so the whole batch is one copy
+        unsafe { self.queue.enqueue_write_buffer(salts_buffer, CL_BLOCKING, 0, salts.as_flattened(), &[]) }
+            .map_err(dispatch_error)?;
+        let mut count_buffer = self.new_count_buffer()?;  // Unused without a filter
+        self.launch(&params, salts.len(), salts_buffer, results_buffer, &mut count_buffer)?;
+
+        self.read_results(results_buffer, salts.len())
+    }
+}
@@ -0,0 +1,431 @@
+use crate::config::GpuBackend;
+use crate::gpu_compute::{
+    decode_addresses_into, decode_candidates, group_size, kernel_params, self_check, BufferPool, Candidate,
+    Create2Params, Create2Result, GpuFilter,
+};
+use create2_core::{Create2Error, Create2Template};
+use std::mem;
+use std::sync::mpsc;
+use wgpu::util::{BufferInitDescriptor, DeviceExt};
+
+const KERNEL_NAME: &str = "compute_create2_batch";
+
+// Must match @workgroup_size in create2_kernel.wgsl
+const WORKGROUP_SIZE: usize = 256;
+
+// Threads per group the kernel numbers its PCG32 streams by, whatever WORKGROUP_SIZE is;
+// 1024 is what CUDA and Metal report on current GPUs
+const LOGICAL_MAX_THREADS_PER_GROUP: usize = 1024;
+
+// Create2Params followed by the logical threads per group, as 32-bit words
+const PARAMS_SIZE: usize = mem::size_of::<Create2Params>() + 4;
+
+const RESULT_SIZE: usize = mem::size_of::<Create2Result>();
+
+fn dispatch_error(e: impl std::fmt::Display) -> Create2Error {
+    Create2Error::GpuDispatch(format!("wgpu dispatch failed: {}", e))
+}
+
+// The results storage buffer and the mappable buffer it is copied into for reading back;
+// pooled together so concurrent batches never share a staging buffer
+struct ResultsBuffers {
+    storage: wgpu::Buffer,
+    staging: wgpu::Buffer,
+}
+
+// One WGSL kernel (create2_kernel.wgsl) run through wgpu on Metal, Vulkan or DX12, whichever the
+// platform provides; --backend vulkan restricts it to Vulkan, the compute path for Linux without CUDA.
+// The shader emulates 64-bit arithmetic and is translated per platform by naga, so like OpenCL a
+// device is only used after its addresses match the CPU implementation
+pub struct WgpuCompute {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    pipeline: wgpu::ComputePipeline,
+    device_name: String,
+    // wgpu or vulkan, as requested
+    backend: GpuBackend,
+    buffer_pool: BufferPool<wgpu::Buffer, ResultsBuffers>,
+    batch_size: usize,
+    max_workgroups: u32,
+    addresses_per_thread: u32,
+}
+
+impl WgpuCompute {
+    pub fn new(backend: GpuBackend, batch_size: usize) -> Result<Self, Create2Error> {
+        let (backends, missing) = match backend {
+            GpuBackend::Vulkan => (wgpu::Backends::VULKAN, "Vulkan adapter not found. Ensure a Vulkan driver (ICD) is installed."),
+            _ => (wgpu::Backends::all(), "wgpu adapter not found. Ensure a Metal, Vulkan or DX12 driver is installed."),
+        };
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor { backends, ..Default::default() });
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::HighPerformance,
+            force_fallback_adapter: false,
+            compatible_surface: None,
+        }))
+        .ok_or_else(|| Create2Error::GpuInit(missing.to_string()))?;
+        let info = adapter.get_info();
+        let device_name = format!("{} ({:?})", info.name, info.backend);
+
+        status!("Using wgpu device: {}", device_name);
+        // Every pooled buffer holds batch_size entries, so one results buffer must fit in a single binding
+        let limits = adapter.limits();
+        let max_binding = (limits.max_storage_buffer_binding_size as u64).min(limits.max_buffer_size);
+        let results_size = (RESULT_SIZE as u64).saturating_mul(batch_size as u64);
+        if batch_size == 0 || batch_size > u32::MAX as usize || results_size > max_binding {
+            return Err(Create2Error::GpuInit(format!(
+                "Batch size {} is out of range for this device (max storage binding: {} bytes)",
+                batch_size,
+                max_binding
+            )));
+        }
+        verbose!("Max storage binding: {} bytes", max_binding);
+        verbose!("Max workgroups per dimension: {}", limits.max_compute_workgroups_per_dimension);
+
+        // Thread coarsening: each thread processes 4 addresses, as on CUDA and Metal
+        let addresses_per_thread = 4u32;
+
+        let (device, queue) = pollster::block_on(adapter.request_device(
+            &wgpu::DeviceDescriptor {
+                label: Some("create2"),
+                required_features: wgpu::Features::empty(),
+                required_limits: limits.clone(),
+                memory_hints: wgpu::MemoryHints::Performance,
+            },
+            None,
+        ))
+        .map_err(|e| Create2Error::GpuInit(format!("Failed to create wgpu device: {}", e)))?;
+
+        // Shader and pipeline errors are reported through the error scope instead of panicking
+        device.push_error_scope(wgpu::ErrorFilter::Validation);
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("create2_kernel"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("create2_kernel.wgsl").into()),
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some(KERNEL_NAME),
+            layout: None,
+            module: &module,
+            entry_point: KERNEL_NAME,
+            compilation_options: Default::default(),
+            cache: None,
+        });
+        if let Some(e) = pollster::block_on(device.pop_error_scope()) {
+            return Err(Create2Error::GpuInit(format!("Failed to compile WGSL kernel: {}", e)));
+        }
+
+        let compute = WgpuCompute {
+            device,
+            queue,
+            pipeline,
+            device_name,
+            backend,
+            buffer_pool: BufferPool::new(),
+            batch_size,
+            max_workgroups: limits.max_compute_workgroups_per_dimension,
+            addresses_per_thread,
+        };
+        self_check("wgpu", batch_size, |template, salts| compute.compute_batch_with_salts(template, salts))?;
+        Ok(compute)
+    }
+
+    // With a filter only matching candidates are written back, compacted through an atomic
+    // counter, so the host no longer copies and scans the whole batch; without one every address is returned
+    pub fn compute_batch_filtered(
+        &self,
+        template: &Create2Template,
+        batch_size: usize,
+        random_seed: u32,
+        filter: Option<&GpuFilter>,
+    ) -> Result<Vec<Candidate>, Create2Error> {
+        decode_candidates(&self.gpu_random_results(template, batch_size, random_seed, filter)?)
+    }
+
+    // An unfiltered GPU random batch written over out, which the caller keeps between batches
+    pub fn compute_batch_gpu_random_into(
+        &self,
+        template: &Create2Template,
+        batch_size: usize,
+        random_seed: u32,
+        out: &mut Vec<(String, u32)>,
+    ) -> Result<(), Create2Error> {
+        decode_addresses_into(&self.gpu_random_results(template, batch_size, random_seed, None)?, out)
+    }
+
+    pub fn compute_batch_with_salts(
+        &self,
+        template: &Create2Template,
+        salts: &[[u8; 32]],
+    ) -> Result<Vec<(String, u32)>, Create2Error> {
+        let mut out = Vec::new();
+        self.compute_batch_with_salts_into(template, salts, &mut out)?;
+        Ok(out)
+    }
+
+    // Like compute_batch_with_salts, written over out, which the caller keeps between batches
+    pub fn compute_batch_with_salts_into(
+        &self,
+        template: &Create2Template,
+        salts: &[[u8; 32]],
+        out: &mut Vec<(String, u32)>,
+    ) -> Result<(), Create2Error> {
+        decode_addresses_into(&self.salts_results(template, salts)?, out)
+    }
+
+    fn gpu_random_results(
+        &self,
+        template: &Create2Template,
+        batch_size: usize,
+        random_seed: u32,
+        filter: Option<&GpuFilter>,
+    ) -> Result<Vec<Create2Result>, Create2Error> {
+        self.check_batch_size(batch_size)?;
+
+        let salts_buffer = self.buffer_pool.get_salts_buffer(|| Ok(self.alloc_salts_buffer()))?;
+        let results_buffers = self.buffer_pool.get_results_buffer(|| Ok(self.alloc_results_buffers()))?;
+
+        // Ensure buffers are returned to pool even on error
+        let result = self.compute_batch_gpu_random_internal(
+            template,
+            batch_size,
+            random_seed,
+            filter,
+            &salts_buffer,
+            &results_buffers,
+        );
+
+        self.buffer_pool.return_salts_buffer(salts_buffer);
+        self.buffer_pool.return_results_buffer(results_buffers);
+
+        result
+    }
+
+    fn salts_results(&self, template: &Create2Template, salts: &[[u8; 32]]) -> Result<Vec<Create2Result>, Create2Error> {
+        self.check_batch_size(salts.len())?;
+
+        let salts_buffer = self.buffer_pool.get_salts_buffer(|| Ok(self.alloc_salts_buffer()))?;
+        let results_buffers = self.buffer_pool.get_results_buffer(|| Ok(self.alloc_results_buffers()))?;
+
+        // Ensure buffers are returned to pool even on error
+        let result = self.compute_batch_with_salts_internal(template, salts, &salts_buffer, &results_buffers);
+
+        self.buffer_pool.return_salts_buffer(salts_buffer);
+        self.buffer_pool.return_results_buffer(results_buffers);
+
+        result
+    }
+
+    pub fn device_name(&self) -> &str {
+        &self.device_name
+    }
+
+    pub fn backend(&self) -> GpuBackend {
+        self.backend
+    }
+
+    // (新分配的buffer数, 复用次数)
+    pub fn buffer_pool_stats(&self) -> (usize, usize) {
+        self.buffer_pool.stats()
+    }
+
+    // The pooled buffers are sized for batch_size entries; larger batches would overrun them
+    fn check_batch_size(&self, count: usize) -> Result<(), Create2Error> {
+        if count == 0 || count > self.batch_size {
+            return Err(Create2Error::GpuDispatch(format!(
+                "Batch of {} addresses does not fit the buffer pool (1..={})",
+                count, self.batch_size
+            )));
+        }
+        Ok(())
+    }
+
+    fn alloc_salts_buffer(&self) -> wgpu::Buffer {
+        self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("salts"),
+            size: (32 * self.batch_size) as u64,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        })
+    }
+
+    // wgpu zero-initializes new buffers, so there is nothing to clear
+    fn alloc_results_buffers(&self) -> ResultsBuffers {
+        let size = (RESULT_SIZE * self.batch_size) as u64;
+        ResultsBuffers {
+            storage: self.device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("results"),
+                size,
+                usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+                mapped_at_creation: false,
+            }),
+            staging: self.device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("results staging"),
+                size,
+                usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            }),
+        }
+    }
+
+    // Create2Params as the little-endian words the shader reads, followed by the logical threads per group
+    fn params_bytes(params: &Create2Params, threads_per_group: u32) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(PARAMS_SIZE);
+        bytes.extend_from_slice(&params.implementation);
+        bytes.extend_from_slice(&params.deployer);
+        bytes.extend_from_slice(&params.init_code_hash);
+        bytes.extend_from_slice(&params.filter);
+        for word in [
+            params.batch_size,
+            params.addresses_per_thread,
+            params.random_seed,
+            params.use_gpu_random,
+            params.use_init_code_hash,
+            params.use_filter,
+            params.filter_ignore_case,
+            threads_per_group,
+        ] {
+            bytes.extend_from_slice(&word.to_le_bytes());
+        }
+        bytes
+    }
+
+    // Launches one batch and waits for it, returning the filtered result count. Params and the
+    // counter get fresh buffers per launch, so concurrent launches never share them
+    fn launch(
+        &self,
+        params: &Create2Params,
+        count: usize,
+        salts_buffer: &wgpu::Buffer,
+        results_buffers: &ResultsBuffers,
+    ) -> Result<usize, Create2Error> {
+        let (num_threads_needed, threads_per_group) =
+            group_size(count, self.addresses_per_thread, LOGICAL_MAX_THREADS_PER_GROUP);
+        // Batches wider than one grid dimension are spread over a second one
+        let workgroups = num_threads_needed.div_ceil(WORKGROUP_SIZE) as u32;
+        let workgroups_x = workgroups.min(self.max_workgroups);
+        let workgroups_y = workgroups.div_ceil(workgroups_x);
+        if workgroups_y > self.max_workgroups {
+            return Err(dispatch_error(format!("{} workgroups exceed the device grid", workgroups)));
+        }
+
+        let params_buffer = self.device.create_buffer_init(&BufferInitDescriptor {
+            label: Some("params"),
+            contents: &Self::params_bytes(params, threads_per_group as u32),
+            usage: wgpu::BufferUsages::STORAGE,
+        });
+        let count_buffer = self.device.create_buffer_init(&BufferInitDescriptor {
+            label: Some("result count"),
+            contents: &0u32.to_le_bytes(),
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+        });
+        let count_staging = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("result count staging"),
+            size: 4,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some(KERNEL_NAME),
+            layout: &self.pipeline.get_bind_group_layout(0),
+            entries: &[
+                wgpu::BindGroupEntry { binding: 0, resource: params_buffer.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 1, resource: salts_buffer.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 2, resource: results_buffers.storage.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 3, resource: count_buffer.as_entire_binding() },
+            ],
+        });
+
+        let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some(KERNEL_NAME) });
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor { label: Some(KERNEL_NAME), timestamp_writes: None });
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(workgroups_x, workgroups_y, 1);
+        }
+        encoder.copy_buffer_to_buffer(&count_buffer, 0, &count_staging, 0, 4);
+        self.queue.submit(Some(encoder.finish()));
+
+        self.read_mapped(&count_staging, 4, |data| u32::from_le_bytes(data.try_into().unwrap()) as usize)
+    }
+
+    // Maps the first size bytes of a staging buffer, waiting for the queue, and unmaps it again
+    fn read_mapped<T>(&self, buffer: &wgpu::Buffer, size: u64, read: impl FnOnce(&[u8]) -> T) -> Result<T, Create2Error> {
+        let slice = buffer.slice(..size);
+        let (sender, receiver) = mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        self.device.poll(wgpu::Maintain::Wait);
+        receiver.recv().map_err(dispatch_error)?.map_err(dispatch_error)?;
+        let value = read(&slice.get_mapped_range()[..]);
+        buffer.unmap();
+        Ok(value)
+    }
+
+    fn read_results(&self, results_buffers: &ResultsBuffers, count: usize) -> Result<Vec<Create2Result>, Create2Error> {
+        let size = (RESULT_SIZE * count) as u64;
+        let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some("read results") });
+        encoder.copy_buffer_to_buffer(&results_buffers.storage, 0, &results_buffers.staging, 0, size);
+        self.queue.submit(Some(encoder.finish()));
+
+        self.read_mapped(&results_buffers.staging, size, |data| {
+            data.chunks_exact(RESULT_SIZE)
+                .map(|chunk| Create2Result {
+                    address: chunk[..40].try_into().unwrap(),
+                    salt_index: u32::from_le_bytes(chunk[40..44].try_into().unwrap()),
+                    salt: chunk[44..76].try_into().unwrap(),
+                })
+                .collect()
+        })
+    }
+
+    fn compute_batch_gpu_random_internal(
+        &self,
+        template: &Create2Template,
+        batch_size: usize,
+        random_seed: u32,
+        filter: Option<&GpuFilter>,
+        salts_buffer: &wgpu::Buffer,
+        results_buffers: &ResultsBuffers,
+    ) -> Result<Vec<Create2Result>, Create2Error> {
+        // Prepare parameters for GPU random generation
+        let params = Create2Params {
+            filter: filter.map_or([0u8; 40], |filter| filter.chars),
+            random_seed,
+            use_gpu_random: 1,  // Always use GPU random
+            use_filter: filter.is_some() as u32,
+            filter_ignore_case: filter.is_some_and(|filter| filter.ignore_case) as u32,
+            ..kernel_params(template, batch_size, self.addresses_per_thread)
+        };
+
+        // Salts unused in GPU random mode
+        let filtered = self.launch(&params, batch_size, salts_buffer, results_buffers)?;
+
+        // Read results: the first count slots when filtering, otherwise the whole batch in order
+        let count = match filter {
+            Some(_) => filtered,
+            None => batch_size,
+        }
+        .min(batch_size);
+        if count == 0 {
+            return Ok(Vec::new());
+        }
+        self.read_results(results_buffers, count)
+    }
+
+    fn compute_batch_with_salts_internal(
+        &self,
+        template: &Create2Template,
+        salts: &[[u8; 32]],
+        salts_buffer: &wgpu::Buffer,
+        results_buffers: &ResultsBuffers,
+    ) -> Result<Vec<Create2Result>, Create2Error> {
+        // Prepare parameters for salt-based computation
+        let params = kernel_params(template, salts.len(), self.addresses_per_thread);
+
+        // Salts are already padded to 32 bytes, so the whole batch is one write, ordered before the dispatch
+        self.queue.write_buffer(salts_buffer, 0, salts.as_flattened());
+        self.launch(&params, salts.len(), salts_buffer, results_buffers)?;  // Count unused without a filter
+
+        self.read_results(results_buffers, salts.len())
+    }
+}
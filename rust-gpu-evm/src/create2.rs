@@ -1,32 +1,37 @@
-use crate::gpu_compute::GpuAccelerator;
-
-#[derive(Debug)]
-pub enum Create2Error {
-    GpuError(String),
-}
-
-impl std::fmt::Display for Create2Error {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        match self {
-            Create2Error::GpuError(err) => write!(f, "GPU error: {}", err),
-        }
-    }
-}
-
-impl std::error::Error for Create2Error {}
+use crate::gpu_compute::{Candidate, DeviceStats, GpuAccelerator, GpuFilter, PendingBatch, MAX_COMMAND_QUEUES};
+use crate::output::PipelineStats;
+use crate::tune::TunedParams;
+use create2_core::{Create2Error, Create2Template};
+use create2_core::salt::IntoSalt;
+use std::sync::Arc;
 
+// Cheap to clone: clones share the Metal devices, pipelines and buffer pools, and every
+// dispatch gets its own command buffer, so threads can submit batches concurrently
+#[derive(Clone)]
 pub struct Create2Predictor {
-    gpu_accelerator: Option<GpuAccelerator>,
+    gpu_accelerator: Option<Arc<GpuAccelerator>>,
 }
 
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Create2Predictor>();
+};
+
 impl Create2Predictor {
-    pub fn new(use_gpu: bool, batch_size: usize) -> Result<Self, String> {
+    // command_queues is --command-queues; out of range is a usage error, not a reason to fall back to the CPU
+    pub fn new(use_gpu: bool, batch_size: usize, seed: u64, command_queues: usize) -> Result<Self, Create2Error> {
+        if command_queues == 0 || command_queues > MAX_COMMAND_QUEUES {
+            return Err(Create2Error::GpuInit(format!(
+                "Command queues must be between 1 and {}, got {}",
+                MAX_COMMAND_QUEUES, command_queues
+            )));
+        }
         if use_gpu {
-            match GpuAccelerator::new(batch_size) {
+            match GpuAccelerator::new(batch_size, seed, command_queues) {
                 Ok(accelerator) => {
-                    println!("✅ GPU acceleration enabled with batch size: {}", batch_size);
+                    status!("✅ GPU acceleration enabled with batch size: {}", batch_size);
                     Ok(Create2Predictor {
-                        gpu_accelerator: Some(accelerator),
+                        gpu_accelerator: Some(Arc::new(accelerator)),
                     })
                 }
                 Err(e) => {
@@ -45,43 +50,153 @@ impl Create2Predictor {
     
     pub fn predict_batch_address(
         &self,
-        implementation: &str,
-        deployer: &str,
+        template: &Create2Template,
         batch_size: usize,
     ) -> Result<Vec<String>, Create2Error> {
+        let mut results = Vec::new();
+        self.predict_batch_address_into(template, batch_size, &mut results)?;
+        Ok(results.into_iter().map(|(addr, _)| addr).collect())
+    }
+    
+    // predict_batch_address into a vector the caller keeps between batches: its Strings are
+    // overwritten in place, so a run of equal-sized batches stops allocating after the first
+    pub fn predict_batch_address_into(
+        &self,
+        template: &Create2Template,
+        batch_size: usize,
+        results: &mut Vec<(String, u32)>,
+    ) -> Result<(), Create2Error> {
         if let Some(ref gpu) = self.gpu_accelerator {
-            match gpu.process_batch_gpu_random(implementation, deployer, batch_size) {
-                Ok(results) => {
-                    let addresses: Vec<String> = results.into_iter().map(|(addr, _)| addr).collect();
-                    Ok(addresses)
-                }
-                Err(e) => Err(Create2Error::GpuError(e)),
-            }
+            gpu.process_batch_gpu_random_into(template, batch_size, results)
+        } else {
+            Err(Create2Error::GpuInit("GPU not available".to_string()))
+        }
+    }
+    
+    // One find batch over every template; with a filter only the candidates the kernel kept come back, in batch order
+    pub fn search_batch(
+        &self,
+        templates: &[Create2Template],
+        batch_size: usize,
+        filter: Option<&GpuFilter>,
+    ) -> Result<Vec<Candidate>, Create2Error> {
+        if let Some(ref gpu) = self.gpu_accelerator {
+            gpu.process_batch_filtered(templates, batch_size, filter)
         } else {
-            Err(Create2Error::GpuError("GPU not available".to_string()))
+            Err(Create2Error::GpuInit("GPU not available".to_string()))
         }
     }
     
-    pub fn predict_batch_with_salt(
+    // One find batch per seed from next_seed, all in one dispatch that returns once it is committed:
+    // the kernel loops over the batches, the caller checks the previous dispatch while the GPU runs,
+    // then wait()s for this one or awaits it as a future. Candidates are tagged with their batch
+    pub fn submit_search_batches(
         &self,
-        implementation: &str,
-        deployer: &str,
-        salts: &[String],
+        templates: &[Create2Template],
+        batch_size: usize,
+        filter: Option<&GpuFilter>,
+        seeds: &[u32],
+    ) -> Result<PendingBatch<'_>, Create2Error> {
+        if let Some(ref gpu) = self.gpu_accelerator {
+            gpu.submit_batches_filtered(templates, batch_size, filter, seeds)
+        } else {
+            Err(Create2Error::GpuInit("GPU not available".to_string()))
+        }
+    }
+    
+    pub fn predict_batch_with_salt<S: IntoSalt>(
+        &self,
+        template: &Create2Template,
+        salts: &[S],
     ) -> Result<Vec<String>, Create2Error> {
+        let mut results = Vec::new();
+        self.predict_batch_with_salt_into(template, salts, &mut results)?;
+        Ok(results.into_iter().map(|(addr, _)| addr).collect())
+    }
+    
+    // predict_batch_with_salt into a vector the caller keeps between batches, as predict_batch_address_into
+    pub fn predict_batch_with_salt_into<S: IntoSalt>(
+        &self,
+        template: &Create2Template,
+        salts: &[S],
+        results: &mut Vec<(String, u32)>,
+    ) -> Result<(), Create2Error> {
         if let Some(ref gpu) = self.gpu_accelerator {
-            match gpu.process_batch_with_salt(implementation, deployer, salts) {
-                Ok(results) => {
-                    let addresses: Vec<String> = results.into_iter().map(|(addr, _)| addr).collect();
-                    Ok(addresses)
-                }
-                Err(e) => Err(Create2Error::GpuError(e)),
-            }
+            gpu.process_batch_with_salt_into(template, salts, results)
         } else {
-            Err(Create2Error::GpuError("GPU not available".to_string()))
+            Err(Create2Error::GpuInit("GPU not available".to_string()))
         }
     }
     
     pub fn is_gpu_enabled(&self) -> bool {
         self.gpu_accelerator.is_some()
     }
-}
\ No newline at end of file
+    
+    pub fn device_name(&self) -> Option<String> {
+        self.gpu_accelerator.as_ref().map(|gpu| gpu.device_name().to_string())
+    }
+    
+    pub fn buffer_pool_stats(&self) -> Option<(usize, usize)> {
+        self.gpu_accelerator.as_ref().map(|gpu| gpu.buffer_pool_stats())
+    }
+    
+    pub fn device_stats(&self) -> Vec<DeviceStats> {
+        self.gpu_accelerator.as_ref().map_or_else(Vec::new, |gpu| gpu.device_stats())
+    }
+    
+    // Pipeline limits and dispatch geometry of a batch of batch_size addresses on every device; empty on the CPU
+    pub fn pipeline_stats(&self, batch_size: usize) -> Vec<PipelineStats> {
+        self.gpu_accelerator.as_ref().map_or_else(Vec::new, |gpu| gpu.pipeline_stats(batch_size))
+    }
+    
+    pub fn max_threads_per_group(&self) -> Option<usize> {
+        self.gpu_accelerator.as_ref().map(|gpu| gpu.max_threads_per_group())
+    }
+    
+    pub fn set_threads_per_group(&self, threads: Option<usize>) {
+        if let Some(ref gpu) = self.gpu_accelerator {
+            gpu.set_threads_per_group(threads);
+        }
+    }
+    
+    // Coarsening factor of the kernel; the CPU fallback has none, so any value is accepted there
+    pub fn set_addresses_per_thread(&self, addresses: usize) -> Result<(), Create2Error> {
+        match self.gpu_accelerator {
+            Some(ref gpu) => gpu.set_addresses_per_thread(addresses),
+            None => Ok(()),
+        }
+    }
+    
+    // Uses the thread group size `tune` measured; a cache measured on other devices is ignored
+    pub fn use_tuned(&self, tuned: Option<&TunedParams>) {
+        let (Some(gpu), Some(tuned)) = (self.gpu_accelerator.as_ref(), tuned) else {
+            return;
+        };
+        if tuned.device != gpu.device_name() {
+            eprintln!("⚠️  Tune cache was measured on {}, not {}; rerun tune", tuned.device, gpu.device_name());
+            return;
+        }
+        gpu.set_threads_per_group(Some(tuned.threads_per_group));
+        status!("Tuned threads per group: {}", tuned.threads_per_group);
+    }
+    
+    pub fn rng_position(&self) -> Option<u128> {
+        self.gpu_accelerator.as_ref().map(|gpu| gpu.rng_position())
+    }
+    
+    pub fn set_rng_position(&self, position: u128) {
+        if let Some(ref gpu) = self.gpu_accelerator {
+            gpu.set_rng_position(position);
+        }
+    }
+    
+    pub fn skip_batch(&self) {
+        if let Some(ref gpu) = self.gpu_accelerator {
+            gpu.skip_batch();
+        }
+    }
+    
+    pub fn next_seed(&self) -> Option<u32> {
+        self.gpu_accelerator.as_ref().map(|gpu| gpu.next_seed())
+    }
+}
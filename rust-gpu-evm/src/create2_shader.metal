@@ -109,27 +109,6 @@ inline void keccak256_thread(const thread uchar* input, uint32_t input_len, thre
     }
 }
 
-// Optimized hex character to value conversion
-inline uchar hex_to_value(uchar c) {
-    return (c <= '9') ? (c - '0') : ((c & 0xDF) - 'A' + 10);
-}
-
-// Optimized hex decode with vectorization hints
-inline void hex_decode_device(const device uchar* hex, thread uchar* bytes, uint32_t len) {
-    #pragma unroll 4
-    for (uint32_t i = 0; i < len; i++) {
-        uint32_t idx = i * 2;
-        bytes[i] = (hex_to_value(hex[idx]) << 4) | hex_to_value(hex[idx + 1]);
-    }
-}
-
-// Helper function to decode hex string from thread memory
-void hex_decode_thread(const thread uchar* hex, thread uchar* bytes, uint32_t len) {
-    for (uint32_t i = 0; i < len; i++) {
-        bytes[i] = (hex_to_value(hex[i * 2]) << 4) | hex_to_value(hex[i * 2 + 1]);
-    }
-}
-
 // Optimized hex encode with inline conversion
 inline void hex_encode(const thread uchar* bytes, thread uchar* hex, uint32_t len) {
     #pragma unroll 4
@@ -182,42 +161,59 @@ inline void generate_random_salt(thread PCGState* rng, thread uchar* salt) {
 }
 
 struct Create2Params {
-    uchar implementation[40];  // hex string without 0x
-    uchar deployer[40];        // hex string without 0x
+    uchar implementation[20];  // raw address bytes
+    uchar deployer[20];        // raw address bytes
+    uchar init_code_hash[32];  // used instead of hashing the proxy init code when use_init_code_hash is 1
+    uchar filter[40];          // required checksum character per position, 0 for any
     uint32_t batch_size;       // number of addresses to compute
     uint32_t addresses_per_thread; // number of addresses each thread processes
-    uint32_t random_seed;      // seed for GPU random number generation
     uint32_t use_gpu_random;   // 1 to use GPU random, 0 to use provided salts
+    uint32_t use_init_code_hash; // 1 for generic CREATE2 with a host-provided init code hash
+    uint32_t use_filter;       // 1 to write back only addresses passing the filter
+    uint32_t filter_ignore_case; // 1 to compare the filter against the lowercase address only
+    uint32_t first_thread;     // grid index of this dispatch's first thread when a batch is split across devices
+    uint32_t batches;          // GPU random batches searched in this dispatch, one seed each; 1 without a filter
+    uint32_t result_capacity;  // result slots in the results buffer; filtered candidates past it are only counted
 };
 
 struct Create2Result {
     uchar address[40];         // resulting address in hex
     uint32_t salt_index;       // which salt produced this address
+    uint32_t batch;            // which of the dispatch's batches it belongs to
+    uchar salt[32];            // salt string that produced this address
 };
 
+// Compares an address against the filter; with exact false the filter character is
+// lowercased first, for the check against the lowercase hex before the checksum is applied
+inline bool matches_filter(thread const uchar* address, device const uchar* filter, bool exact) {
+    for (int i = 0; i < 40; i++) {
+        uchar f = filter[i];
+        if (f == 0) continue;
+        if (!exact && f >= 'A' && f <= 'F') f += 32;
+        if (address[i] != f) return false;
+    }
+    return true;
+}
+
 kernel void compute_create2_batch(
     device const Create2Params* params [[buffer(0)]],
     device const uchar* salts [[buffer(1)]],  // Array of salts (32 bytes each)
     device Create2Result* results [[buffer(2)]],
+    device atomic_uint* result_count [[buffer(3)]],  // Next free result slot when filtering
+    constant uint32_t* seeds [[buffer(4)]],  // Per-batch seeds for GPU random generation
     uint gid [[thread_position_in_grid]],
     uint tid [[thread_index_in_threadgroup]]
 ) {
+    // Index of this thread in the whole batch; batch_size is the end of this device's shard
+    uint32_t thread_index = params->first_thread + gid;
+    
     // Thread coarsening: each thread processes multiple addresses
     uint32_t addresses_per_thread = params->addresses_per_thread;
-    uint32_t start_idx = gid * addresses_per_thread;
+    uint32_t start_idx = thread_index * addresses_per_thread;
     uint32_t end_idx = min(start_idx + addresses_per_thread, params->batch_size);
     
     if (start_idx >= params->batch_size) return;
     
-    // Initialize GPU RNG if needed
-    PCGState rng;
-    if (params->use_gpu_random == 1) {
-        // Use global thread ID and seed to create unique RNG per thread
-        uint64_t unique_seed = params->random_seed + gid;
-        uint64_t stream = (uint64_t)tid * 1099511628211ULL; // Large prime for stream separation
-        pcg32_init(&rng, unique_seed, stream);
-    }
-    
     // Constants - shared across all iterations
     const uchar PREFIX[20] = {
         0x3d, 0x60, 0x2d, 0x80, 0x60, 0x0a, 0x3d, 0x39, 0x81, 0xf3,
@@ -228,13 +224,6 @@ kernel void compute_create2_batch(
         0x91, 0x60, 0x2b, 0x57, 0xfd, 0x5b, 0xf3, 0xff
     };
     
-    // Pre-decode addresses once (reuse across iterations)
-    uchar impl_bytes[20];
-    hex_decode_device(params->implementation, impl_bytes, 20);
-    
-    uchar depl_bytes[20];
-    hex_decode_device(params->deployer, depl_bytes, 20);
-    
     // Pre-build common bytecode parts
     uchar bytecode_template[76];  // Without salt: 20 + 20 + 16 + 20 = 76
     uint32_t pos = 0;
@@ -246,7 +235,7 @@ kernel void compute_create2_batch(
     
     // Add implementation
     for (int i = 0; i < 20; i++) {
-        bytecode_template[pos++] = impl_bytes[i];
+        bytecode_template[pos++] = params->implementation[i];
     }
     
     // Add SUFFIX
@@ -256,101 +245,133 @@ kernel void compute_create2_batch(
     
     // Add deployer
     for (int i = 0; i < 20; i++) {
-        bytecode_template[pos++] = depl_bytes[i];
+        bytecode_template[pos++] = params->deployer[i];
     }
     
-    // Process multiple addresses per thread
-    for (uint32_t idx = start_idx; idx < end_idx; idx++) {
-        // Get salt for this iteration
-        uchar salt_str[32];
-        
+    // Each batch restarts the generators from its own seed, so a batch produces the same
+    // salts whether it is dispatched alone or as one of several batches
+    for (uint32_t batch = 0; batch < params->batches; batch++) {
+        PCGState rng;
         if (params->use_gpu_random == 1) {
-            // Generate random salt on GPU
-            generate_random_salt(&rng, salt_str);
-        } else {
-            // Use provided salt
-            device const uchar* salt_ptr = salts + (idx * 32);
+            // Use global thread ID and seed to create unique RNG per thread
+            uint64_t unique_seed = seeds[batch] + thread_index;
+            uint64_t stream = (uint64_t)tid * 1099511628211ULL; // Large prime for stream separation
+            pcg32_init(&rng, unique_seed, stream);
+        }
+        
+        // Process multiple addresses per thread
+        for (uint32_t idx = start_idx; idx < end_idx; idx++) {
+            // Get salt for this iteration
+            uchar salt_str[32];
+            
+            if (params->use_gpu_random == 1) {
+                // Generate random salt on GPU
+                generate_random_salt(&rng, salt_str);
+            } else {
+                // Use provided salt
+                device const uchar* salt_ptr = salts + (idx * 32);
+                
+                // Vectorized salt copy
+                #pragma unroll 8
+                for (int i = 0; i < 32; i++) {
+                    salt_str[i] = salt_ptr[i];
+                }
+            }
+            
+            // Build complete bytecode by adding salt to template
+            uchar bytecode[108];
+            
+            // Copy template
+            #pragma unroll 8
+            for (int i = 0; i < 76; i++) {
+                bytecode[i] = bytecode_template[i];
+            }
             
-            // Vectorized salt copy
+            // Add salt
             #pragma unroll 8
             for (int i = 0; i < 32; i++) {
-                salt_str[i] = salt_ptr[i];
+                bytecode[76 + i] = salt_str[i];
             }
-        }
-        
-        // Build complete bytecode by adding salt to template
-        uchar bytecode[108];
-        
-        // Copy template
-        #pragma unroll 8
-        for (int i = 0; i < 76; i++) {
-            bytecode[i] = bytecode_template[i];
-        }
-        
-        // Add salt
-        #pragma unroll 8
-        for (int i = 0; i < 32; i++) {
-            bytecode[76 + i] = salt_str[i];
-        }
-        
-        // First hash - compute directly from bytecode (first 55 bytes)
-        uchar first_hash[32];
-        keccak256_thread(bytecode, 55, first_hash);
-        
-        // Build second part for hashing
-        uchar second_part[85];
-        
-        // Copy remaining bytecode
-        #pragma unroll 8
-        for (int i = 0; i < 53; i++) {
-            second_part[i] = bytecode[55 + i];
-        }
-        
-        // Add first hash
-        #pragma unroll 8
-        for (int i = 0; i < 32; i++) {
-            second_part[53 + i] = first_hash[i];
-        }
-        
-        uchar second_hash[32];
-        keccak256_thread(second_part, 85, second_hash);
-        
-        // Take last 20 bytes as address
-        uchar address_bytes[20];
-        #pragma unroll 4
-        for (int i = 0; i < 20; i++) {
-            address_bytes[i] = second_hash[12 + i];
-        }
-        
-        // Convert to checksum address
-        uchar address_hex[40];
-        hex_encode(address_bytes, address_hex, 20);
-        
-        // Compute checksum
-        uchar address_hash[32];
-        keccak256_thread(address_hex, 40, address_hash);
-        
-        // Apply checksum
-        #pragma unroll 8
-        for (int i = 0; i < 40; i++) {
-            uchar c = address_hex[i];
-            if (c >= 'a' && c <= 'f') {
-                uint32_t byte_index = i / 2;
-                uint32_t nibble_index = i % 2;
-                uchar byte_value = address_hash[byte_index];
-                uchar nibble_value = (nibble_index == 0) ? (byte_value >> 4) : (byte_value & 0x0f);
-                
-                if (nibble_value >= 8) {
-                    address_hex[i] = c - 32; // Convert to uppercase
+            
+            // First hash - compute directly from bytecode (first 55 bytes)
+            uchar first_hash[32];
+            if (params->use_init_code_hash == 1) {
+                for (int i = 0; i < 32; i++) {
+                    first_hash[i] = params->init_code_hash[i];
                 }
+            } else {
+                keccak256_thread(bytecode, 55, first_hash);
+            }
+            
+            // Build second part for hashing
+            uchar second_part[85];
+            
+            // Copy remaining bytecode
+            #pragma unroll 8
+            for (int i = 0; i < 53; i++) {
+                second_part[i] = bytecode[55 + i];
+            }
+            
+            // Add first hash
+            #pragma unroll 8
+            for (int i = 0; i < 32; i++) {
+                second_part[53 + i] = first_hash[i];
+            }
+            
+            uchar second_hash[32];
+            keccak256_thread(second_part, 85, second_hash);
+            
+            // Take last 20 bytes as address
+            uchar address_bytes[20];
+            #pragma unroll 4
+            for (int i = 0; i < 20; i++) {
+                address_bytes[i] = second_hash[12 + i];
+            }
+            
+            // Convert to checksum address
+            uchar address_hex[40];
+            hex_encode(address_bytes, address_hex, 20);
+            
+            // Most addresses fail the lowercase check, which skips the checksum hash and the write back
+            bool filtering = params->use_filter == 1;
+            if (filtering && !matches_filter(address_hex, params->filter, false)) continue;
+            
+            // Compute checksum
+            uchar address_hash[32];
+            keccak256_thread(address_hex, 40, address_hash);
+            
+            // Apply checksum
+            #pragma unroll 8
+            for (int i = 0; i < 40; i++) {
+                uchar c = address_hex[i];
+                if (c >= 'a' && c <= 'f') {
+                    uint32_t byte_index = i / 2;
+                    uint32_t nibble_index = i % 2;
+                    uchar byte_value = address_hash[byte_index];
+                    uchar nibble_value = (nibble_index == 0) ? (byte_value >> 4) : (byte_value & 0x0f);
+                    
+                    if (nibble_value >= 8) {
+                        address_hex[i] = c - 32; // Convert to uppercase
+                    }
+                }
+            }
+            
+            if (filtering && params->filter_ignore_case == 0 && !matches_filter(address_hex, params->filter, true)) continue;
+            
+            // Store result; filtered candidates are packed at the front of the buffer, and the host
+            // reports an overflow from the count when more matched than there are slots
+            uint32_t slot = filtering ? atomic_fetch_add_explicit(result_count, 1, memory_order_relaxed) : idx;
+            if (slot >= params->result_capacity) continue;
+            #pragma unroll 8
+            for (int i = 0; i < 40; i++) {
+                results[slot].address[i] = address_hex[i];
+            }
+            results[slot].salt_index = idx;
+            results[slot].batch = batch;
+            #pragma unroll 8
+            for (int i = 0; i < 32; i++) {
+                results[slot].salt[i] = salt_str[i];
             }
         }
-        
-        // Store result
-        #pragma unroll 8
-        for (int i = 0; i < 40; i++) {
-            results[idx].address[i] = address_hex[i];
-        }
-        results[idx].salt_index = idx;
     }
 }
\ No newline at end of file
@@ -1,17 +1,59 @@
 use metal::*;
+use block::ConcreteBlock;
+use std::fs;
+use std::future::Future;
 use std::mem;
-use std::sync::{Arc, Mutex};
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::time::{Duration, Instant};
 use std::collections::VecDeque;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use create2_core::Create2Template;
+use create2_core::salt::IntoSalt;
+use create2_core::Create2Error;
+use crate::output::PipelineStats;
+
+// Thread coarsening: addresses each kernel thread computes unless --addresses-per-thread or the
+// tune cache sets another factor; it is the same on every device
+pub const DEFAULT_ADDRESSES_PER_THREAD: u32 = 4;
+pub const MAX_ADDRESSES_PER_THREAD: u32 = 64;
+
+// Command queues per device for --command-queues. Dispatches take them in turn, so independent
+// batches submitted together run concurrently instead of waiting behind each other on one queue
+pub const MAX_COMMAND_QUEUES: usize = 16;
+
+// Batches one filtered find dispatch can search; their seeds are passed with set_bytes, which takes at most 4 KB
+pub const MAX_BATCHES_PER_DISPATCH: usize = 1024;
+
+// Batches at least this large keep their salts and results in private GPU memory on devices
+// without unified memory (discrete GPUs, eGPUs), where shared buffers are read over the bus
+const PRIVATE_BUFFERS_MIN_BATCH: usize = 1 << 16;
 
 #[repr(C)]
 #[derive(Debug, Clone, Copy)]
 pub struct Create2Params {
-    pub implementation: [u8; 40],
-    pub deployer: [u8; 40],
+    // Raw address bytes, decoded once on the host
+    pub implementation: [u8; 20],
+    pub deployer: [u8; 20],
+    pub init_code_hash: [u8; 32],
+    // Required checksum characters per position, 0 for any; only read when use_filter is 1
+    pub filter: [u8; 40],
     pub batch_size: u32,
     pub addresses_per_thread: u32,
-    pub random_seed: u32,
     pub use_gpu_random: u32,
+    pub use_init_code_hash: u32,
+    pub use_filter: u32,
+    pub filter_ignore_case: u32,
+    // Grid index of the dispatch's first thread when a batch is split across devices
+    pub first_thread: u32,
+    // GPU random batches in the dispatch, each with its own seed; only filtered dispatches search more than one
+    pub batches: u32,
+    // Result slots in the results buffer, so filtered candidates beyond it are counted but not written
+    pub result_capacity: u32,
 }
 
 #[repr(C, packed)]
@@ -19,37 +61,107 @@ pub struct Create2Params {
 pub struct Create2Result {
     pub address: [u8; 40],
     pub salt_index: u32,
+    // Which of the dispatch's batches the address belongs to
+    pub batch: u32,
+    // Salt string the kernel generated in GPU random mode
+    pub salt: [u8; 32],
+}
+
+// Per-position filter evaluated on the device before anything is written back;
+// built from PatternSet::char_filter, so a candidate still needs the exact CPU check
+#[derive(Debug, Clone, Copy)]
+pub struct GpuFilter {
+    pub chars: [u8; 40],
+    pub ignore_case: bool,
+}
+
+impl GpuFilter {
+    pub fn new(chars: &[u8], ignore_case: bool) -> Option<Self> {
+        Some(GpuFilter {
+            chars: chars.try_into().ok()?,
+            ignore_case,
+        })
+    }
+}
+
+// One address read back in find mode, with the position in its batch, the batch within the
+// dispatch, the GPU salt and the index of the template (deployer) that produced it
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    pub index: u32,
+    pub batch: usize,
+    pub template: usize,
+    pub address: String,
+    pub salt: [u8; 32],
+}
+
+impl Candidate {
+    // GPU salts are 32 lowercase hex characters used as a string salt
+    pub fn salt(&self) -> String {
+        String::from_utf8_lossy(&self.salt).into_owned()
+    }
 }
 
 struct BufferPool {
     device: Device,
     salts_buffers: Mutex<VecDeque<Buffer>>,
     results_buffers: Mutex<VecDeque<Buffer>>,
+    // Shared buffers the host fills and reads when the salts and results buffers are private;
+    // blit encoders copy between them in the dispatch's command buffer
+    staging_buffers: Mutex<VecDeque<Buffer>>,
     buffer_size: usize,
+    private: bool,
+    // -vv 输出的统计：新分配的buffer数和从池中复用的次数
+    allocated: AtomicUsize,
+    reused: AtomicUsize,
 }
 
 impl BufferPool {
-    fn new(device: Device, batch_size: usize) -> Self {
+    fn new(device: Device, batch_size: usize, private: bool) -> Self {
         BufferPool {
             device,
             salts_buffers: Mutex::new(VecDeque::new()),
             results_buffers: Mutex::new(VecDeque::new()),
+            staging_buffers: Mutex::new(VecDeque::new()),
             buffer_size: batch_size,
+            private,
+            allocated: AtomicUsize::new(0),
+            reused: AtomicUsize::new(0),
+        }
+    }
+    
+    fn take(&self, pool: &Mutex<VecDeque<Buffer>>) -> Option<Buffer> {
+        let buffer = pool.lock().unwrap().pop_front();
+        let counter = if buffer.is_some() { &self.reused } else { &self.allocated };
+        counter.fetch_add(1, Ordering::Relaxed);
+        buffer
+    }
+    
+    fn stats(&self) -> (usize, usize) {
+        (self.allocated.load(Ordering::Relaxed), self.reused.load(Ordering::Relaxed))
+    }
+    
+    fn storage_mode(&self) -> MTLResourceOptions {
+        if self.private {
+            MTLResourceOptions::StorageModePrivate
+        } else {
+            MTLResourceOptions::StorageModeShared
         }
     }
     
     fn get_salts_buffer(&self) -> Buffer {
-        let mut pool = self.salts_buffers.lock().unwrap();
-        pool.pop_front().unwrap_or_else(|| {
+        self.take(&self.salts_buffers).unwrap_or_else(|| {
             let size = (32 * self.buffer_size) as u64;
-            self.device.new_buffer(size, MTLResourceOptions::StorageModeShared)
+            self.device.new_buffer(size, self.storage_mode())
         })
     }
     
     fn get_results_buffer(&self) -> Buffer {
-        let mut pool = self.results_buffers.lock().unwrap();
-        pool.pop_front().unwrap_or_else(|| {
+        self.take(&self.results_buffers).unwrap_or_else(|| {
             let size = (mem::size_of::<Create2Result>() * self.buffer_size) as u64;
+            if self.private {
+                return self.device.new_buffer(size, MTLResourceOptions::StorageModePrivate);
+            }
             let buffer = self.device.new_buffer(size, MTLResourceOptions::StorageModeShared);
             // Initialize with zeros
             unsafe {
@@ -74,39 +186,425 @@ impl BufferPool {
             pool.push_back(buffer);
         }
     }
+    
+    // Large enough for the salts uploaded to and the results read back from any shard
+    fn get_staging_buffer(&self) -> Buffer {
+        self.take(&self.staging_buffers).unwrap_or_else(|| {
+            let size = (mem::size_of::<Create2Result>().max(32) * self.buffer_size) as u64;
+            self.device.new_buffer(size, MTLResourceOptions::StorageModeShared)
+        })
+    }
+    
+    fn return_staging_buffer(&self, buffer: Buffer) {
+        let mut pool = self.staging_buffers.lock().unwrap();
+        if pool.len() < 16 {
+            pool.push_back(buffer);
+        }
+    }
 }
 
-pub struct MetalCompute {
+// The part of a batch one device computes: salt indexes start..end of a batch dispatched with
+// threads_per_group threads per group. Shards start on a thread group boundary, so every thread
+// gets the index, seed and PCG32 stream it would have if one device ran the whole batch
+#[derive(Debug, Clone, Copy)]
+pub struct Shard {
+    pub start: usize,
+    pub end: usize,
+    pub threads_per_group: usize,
+    pub addresses_per_thread: u32,
+}
+
+impl Shard {
+    fn len(&self) -> usize {
+        self.end - self.start
+    }
+}
+
+// Addresses a device has computed and the time its dispatches took, for the per-device TPS
+// and for splitting later batches in proportion to it
+#[derive(Debug, Clone)]
+pub struct DeviceStats {
+    pub name: String,
+    pub addresses: u64,
+    pub busy: Duration,
+}
+
+impl DeviceStats {
+    pub fn tps(&self) -> f64 {
+        self.addresses as f64 / self.busy.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+// Updated from command buffer completion handlers, which run on a Metal thread
+struct DeviceCounters {
+    epoch: Instant,
+    computed: AtomicU64,
+    busy_nanos: AtomicU64,
+    // When the queue's last command buffer completed, in nanoseconds since epoch; a dispatch
+    // committed while another was still running is only busy from then on
+    idle_since: AtomicU64,
+}
+
+impl DeviceCounters {
+    fn new() -> Self {
+        DeviceCounters {
+            epoch: Instant::now(),
+            computed: AtomicU64::new(0),
+            busy_nanos: AtomicU64::new(0),
+            idle_since: AtomicU64::new(0),
+        }
+    }
+    
+    fn now(&self) -> u64 {
+        self.epoch.elapsed().as_nanos() as u64
+    }
+    
+    fn record(&self, addresses: u64, committed: u64) {
+        let now = self.now();
+        let started = committed.max(self.idle_since.swap(now, Ordering::Relaxed));
+        self.computed.fetch_add(addresses, Ordering::Relaxed);
+        self.busy_nanos.fetch_add(now.saturating_sub(started), Ordering::Relaxed);
+    }
+}
+
+// Completion of a committed command buffer, signalled by its completed handler instead of
+// blocking in wait_until_completed: wait() blocks the calling thread, is_complete() polls, and
+// awaiting it as a future wakes the task from the handler
+#[derive(Clone)]
+pub struct Completion {
+    state: Arc<(Mutex<CompletionState>, Condvar)>,
+}
+
+#[derive(Default)]
+struct CompletionState {
+    // None until the handler runs, then whether the command buffer completed without an error
+    succeeded: Option<bool>,
+    waker: Option<Waker>,
+}
+
+impl Completion {
+    fn new() -> Self {
+        Completion {
+            state: Arc::new((Mutex::new(CompletionState::default()), Condvar::new())),
+        }
+    }
+    
+    fn signal(&self, succeeded: bool) {
+        let (lock, condvar) = &*self.state;
+        let mut state = lock.lock().unwrap();
+        state.succeeded = Some(succeeded);
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+        condvar.notify_all();
+    }
+    
+    pub fn is_complete(&self) -> bool {
+        self.state.0.lock().unwrap().succeeded.is_some()
+    }
+    
+    pub fn wait(&self) -> Result<(), Create2Error> {
+        let (lock, condvar) = &*self.state;
+        let state = condvar.wait_while(lock.lock().unwrap(), |state| state.succeeded.is_none()).unwrap();
+        Self::result(state.succeeded == Some(true))
+    }
+    
+    fn result(succeeded: bool) -> Result<(), Create2Error> {
+        if succeeded {
+            Ok(())
+        } else {
+            Err(Create2Error::GpuDispatch("Metal command buffer failed".to_string()))
+        }
+    }
+}
+
+impl Future for Completion {
+    type Output = Result<(), Create2Error>;
+    
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.0.lock().unwrap();
+        match state.succeeded {
+            Some(succeeded) => Poll::Ready(Self::result(succeeded)),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+// One committed shard of one template. The buffers go back to the pool once the dispatch has
+// completed, even if its results are never read
+pub struct InFlight<'a> {
+    compute: &'a MetalCompute,
+    shard: Shard,
+    template: usize,
+    filtered: bool,
+    completion: Completion,
+    salts_buffer: Option<Buffer>,
+    results_buffer: Option<Buffer>,
+    // The shared buffer the results are read from when the pool's buffers are private
+    staging_buffer: Option<Buffer>,
+    count_buffer: Buffer,
+}
+
+impl InFlight<'_> {
+    fn finish(self) -> Result<Vec<Candidate>, Create2Error> {
+        self.completion.wait()?;
+        self.read_results()
+    }
+    
+    // The first count slots when filtering, otherwise the shard's slots in order. A filtered
+    // dispatch of several batches can match more addresses than the buffer holds; the ones
+    // past the end were never written, so the whole dispatch is reported as failed
+    fn results(&self) -> Result<&[Create2Result], Create2Error> {
+        let capacity = self.compute.batch_size;
+        let range = if self.filtered {
+            let count = unsafe { *(self.count_buffer.contents() as *const u32) as usize };
+            if count > capacity {
+                return Err(Create2Error::GpuDispatch(format!(
+                    "{} candidates matched the GPU filter but the results buffer holds {}; lower --batches-per-dispatch",
+                    count, capacity
+                )));
+            }
+            0..count
+        } else {
+            self.shard.start..self.shard.end
+        };
+        let results_buffer = self.staging_buffer.as_ref().or(self.results_buffer.as_ref()).unwrap();
+        unsafe {
+            let ptr = results_buffer.contents() as *const Create2Result;
+            Ok(&std::slice::from_raw_parts(ptr, capacity)[range])
+        }
+    }
+    
+    fn read_results(&self) -> Result<Vec<Candidate>, Create2Error> {
+        let results = self.results()?;
+        let mut candidates = Vec::with_capacity(results.len());
+        for (i, result) in results.iter().enumerate() {
+            candidates.push(Candidate {
+                index: result.salt_index,
+                batch: result.batch as usize,
+                template: self.template,
+                address: format!("0x{}", address_hex(result, i)?),
+                salt: result.salt,
+            });
+        }
+        Ok(candidates)
+    }
+    
+    // Writes this shard's (address, index) pairs over out from slot start on, reusing the Strings
+    // already there; returns the slot after the last one written
+    fn read_addresses_into(&self, out: &mut Vec<(String, u32)>, start: usize) -> Result<usize, Create2Error> {
+        let results = self.results()?;
+        for (i, result) in results.iter().enumerate() {
+            let hex = address_hex(result, i)?;
+            match out.get_mut(start + i) {
+                Some((address, index)) => {
+                    address.clear();
+                    address.push_str("0x");
+                    address.push_str(hex);
+                    *index = result.salt_index;
+                }
+                None => out.push((format!("0x{}", hex), result.salt_index)),
+            }
+        }
+        Ok(start + results.len())
+    }
+}
+
+fn address_hex(result: &Create2Result, i: usize) -> Result<&str, Create2Error> {
+    std::str::from_utf8(&result.address[..40])
+        .map_err(|e| Create2Error::GpuDispatch(format!("Failed to decode address at index {}: {}", i, e)))
+}
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        // The GPU may still be writing to the buffers of a batch dropped before it completed
+        let _ = self.completion.wait();
+        if let Some(buffer) = self.salts_buffer.take() {
+            self.compute.buffer_pool.return_salts_buffer(buffer);
+        }
+        if let Some(buffer) = self.results_buffer.take() {
+            self.compute.buffer_pool.return_results_buffer(buffer);
+        }
+        if let Some(buffer) = self.staging_buffer.take() {
+            self.compute.buffer_pool.return_staging_buffer(buffer);
+        }
+    }
+}
+
+// A committed batch: every shard on every device for every template. The caller can prepare
+// the next batch meanwhile and then wait() for it, or await it as a future
+pub struct PendingBatch<'a> {
+    dispatches: Vec<InFlight<'a>>,
+}
+
+impl PendingBatch<'_> {
+    // For embedding applications that poll from an event loop instead of blocking
     #[allow(dead_code)]
+    pub fn is_ready(&self) -> bool {
+        self.dispatches.iter().all(|dispatch| dispatch.completion.is_complete())
+    }
+    
+    // Candidates in batch order, then template order
+    pub fn wait(self) -> Result<Vec<Candidate>, Create2Error> {
+        let mut candidates = Vec::new();
+        self.wait_into(&mut candidates)?;
+        Ok(candidates)
+    }
+    
+    // wait() into a vector the caller keeps between dispatches, so the find loop reuses its allocation
+    pub fn wait_into(self, candidates: &mut Vec<Candidate>) -> Result<(), Create2Error> {
+        candidates.clear();
+        for dispatch in self.dispatches {
+            candidates.extend(dispatch.finish()?);
+        }
+        // Filtering threads append in completion order; sorting keeps matches in batch order for checkpoints
+        candidates.sort_by_key(|candidate| (candidate.batch, candidate.index, candidate.template));
+        Ok(())
+    }
+    
+    // An unfiltered batch of one template as (address, index) in batch order, written over out:
+    // the shards cover the batch in order, so no sort is needed and out's Strings are reused
+    fn wait_addresses_into(self, out: &mut Vec<(String, u32)>) -> Result<(), Create2Error> {
+        let mut len = 0;
+        for dispatch in &self.dispatches {
+            dispatch.completion.wait()?;
+            len = dispatch.read_addresses_into(out, len)?;
+        }
+        out.truncate(len);
+        Ok(())
+    }
+}
+
+impl Future for PendingBatch<'_> {
+    type Output = Result<Vec<Candidate>, Create2Error>;
+    
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        for dispatch in &mut self.dispatches {
+            if Pin::new(&mut dispatch.completion).poll(cx).is_pending() {
+                return Poll::Pending;
+            }
+        }
+        let dispatches = mem::take(&mut self.dispatches);
+        Poll::Ready(PendingBatch { dispatches }.wait())
+    }
+}
+
+// MTLBinaryArchive holding the compiled compute pipeline, one file per device and shader source
+// under ~/Library/Caches/create2-benchmark. Later runs still compile the source to a library, but
+// the pipeline's GPU binary comes from the archive instead of being compiled again
+struct PipelineArchive {
+    archive: BinaryArchive,
+    path: PathBuf,
+    loaded: bool,
+}
+
+impl PipelineArchive {
+    // None without a home directory or when Metal cannot create an archive; the pipeline is then compiled as before
+    fn open(device: &DeviceRef, shader_source: &str) -> Option<Self> {
+        let path = Self::path(device, shader_source)?;
+        let loaded = path.exists();
+        let descriptor = BinaryArchiveDescriptor::new();
+        if loaded {
+            descriptor.set_url(&file_url(&path));
+        }
+        match device.new_binary_archive_with_descriptor(&descriptor) {
+            Ok(archive) => {
+                if loaded {
+                    verbose!("Loaded pipeline archive {}", path.display());
+                }
+                Some(PipelineArchive { archive, path, loaded })
+            }
+            // An archive written for another OS or driver version is replaced by a fresh one
+            Err(e) if loaded => {
+                verbose!("Discarding pipeline archive {}: {}", path.display(), e);
+                let _ = fs::remove_file(&path);
+                Self::open(device, shader_source)
+            }
+            Err(e) => {
+                verbose!("Pipeline archive unavailable: {}", e);
+                None
+            }
+        }
+    }
+    
+    // FNV-1a of the source keys the file, so an edited shader never loads a stale binary
+    fn path(device: &DeviceRef, shader_source: &str) -> Option<PathBuf> {
+        let hash = shader_source
+            .bytes()
+            .fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3));
+        let device_name: String = device
+            .name()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+            .collect();
+        let home = PathBuf::from(std::env::var_os("HOME")?);
+        Some(home.join("Library/Caches/create2-benchmark").join(format!("{}-{:016x}.metallib", device_name, hash)))
+    }
+    
+    // Adds the pipeline built with this archive to it and writes it out; a loaded archive already has it
+    fn store(&self, descriptor: &ComputePipelineDescriptorRef) {
+        if self.loaded {
+            return;
+        }
+        let result = self
+            .path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .map_err(|e| e.to_string())
+            .and_then(|_| self.archive.add_compute_pipeline_functions_with_descriptor(descriptor).map(|_| ()))
+            .and_then(|_| self.archive.serialize_to_url(&file_url(&self.path)).map(|_| ()));
+        match result {
+            Ok(()) => verbose!("Saved pipeline archive {}", self.path.display()),
+            Err(e) => verbose!("Failed to save pipeline archive {}: {}", self.path.display(), e),
+        }
+    }
+}
+
+fn file_url(path: &Path) -> URL {
+    URL::new_with_string(&format!("file://{}", path.display().to_string().replace('%', "%25").replace(' ', "%20")))
+}
+
+pub struct MetalCompute {
     device: Device,
-    command_queue: CommandQueue,
+    command_queues: Vec<CommandQueue>,
+    // Queue of the next dispatch, round robin
+    next_queue: AtomicUsize,
     pipeline_state: ComputePipelineState,
-    params_buffer: Buffer,
     buffer_pool: Arc<BufferPool>,
-    #[allow(dead_code)]
     batch_size: usize,
     max_threads_per_group: usize,
-    addresses_per_thread: u32,
+    counters: Arc<DeviceCounters>,
 }
 
 impl MetalCompute {
-    pub fn new(batch_size: usize) -> Result<Self, String> {
-        // Get default Metal device
-        let device = Device::system_default()
-            .ok_or_else(|| "Metal device not found. Ensure you're running on macOS with Metal support.".to_string())?;
+    pub fn new(device: Device, batch_size: usize, command_queues: usize) -> Result<Self, Create2Error> {
+        status!("Using Metal device: {}", device.name());
+        // Every pooled buffer holds batch_size entries, so one results buffer must fit on the device. The
+        // kernel computes a thread's first index as thread_index * addresses_per_thread in 32 bits; the
+        // padding threads of the last thread group start past batch_size, so a whole group must fit too
+        let margin = MAX_ADDRESSES_PER_THREAD as u64 * device.max_threads_per_threadgroup().width;
+        let results_size = (mem::size_of::<Create2Result>() as u64).saturating_mul(batch_size as u64);
+        if batch_size == 0 || batch_size as u64 > u32::MAX as u64 - margin || results_size > device.max_buffer_length() {
+            return Err(Create2Error::GpuInit(format!(
+                "Batch size {} is out of range for this device (max buffer length: {} bytes)",
+                batch_size,
+                device.max_buffer_length()
+            )));
+        }
         
-        println!("Using Metal device: {}", device.name());
         let max_threads = device.max_threads_per_threadgroup();
-        println!("Max threads per threadgroup: {:?}", max_threads);
+        verbose!("Max threads per threadgroup: {:?}", max_threads);
+        verbose!("Max buffer length: {} bytes", device.max_buffer_length());
         
         // Use full capacity of M4 Pro GPU
         let max_threads_per_group = max_threads.width as usize;  // M4 Pro supports 1024
         
-        // Thread coarsening: each thread processes 4 addresses for better instruction-level parallelism
-        let addresses_per_thread = 4u32;
-        
-        // Create command queue
-        let command_queue = device.new_command_queue();
+        // Create command queues
+        let command_queues = (0..command_queues).map(|_| device.new_command_queue()).collect::<Vec<_>>();
+        verbose!("Command queues: {}", command_queues.len());
         
         // Load shader source
         let shader_source = include_str!("create2_shader.metal");
@@ -115,267 +613,241 @@ impl MetalCompute {
         let options = CompileOptions::new();
         let library = device
             .new_library_with_source(shader_source, &options)
-            .map_err(|e| format!("Failed to compile Metal shader: {}", e))?;
+            .map_err(|e| Create2Error::GpuInit(format!("Failed to compile Metal shader: {}", e)))?;
         
         // Get compute function
         let kernel = library
             .get_function("compute_create2_batch", None)
-            .map_err(|e| format!("Failed to get compute function: {}", e))?;
+            .map_err(|e| Create2Error::GpuInit(format!("Failed to get compute function: {}", e)))?;
         
         // Create compute pipeline
+        let descriptor = ComputePipelineDescriptor::new();
+        descriptor.set_compute_function(Some(&kernel));
+        let archive = PipelineArchive::open(&device, shader_source);
+        if let Some(ref archive) = archive {
+            descriptor.set_binary_archives(&[&archive.archive]);
+        }
         let pipeline_state = device
-            .new_compute_pipeline_state_with_function(&kernel)
-            .map_err(|e| format!("Failed to create compute pipeline: {}", e))?;
-        
-        // Allocate params buffer (shared across all operations)
-        let params_size = mem::size_of::<Create2Params>() as u64;
-        let params_buffer = device.new_buffer(params_size, MTLResourceOptions::StorageModeShared);
+            .new_compute_pipeline_state(&descriptor)
+            .map_err(|e| Create2Error::GpuInit(format!("Failed to create compute pipeline: {}", e)))?;
+        if let Some(ref archive) = archive {
+            archive.store(&descriptor);
+        }
         
         // Create buffer pool for reuse
-        let buffer_pool = Arc::new(BufferPool::new(device.clone(), batch_size));
+        let private_buffers = !device.has_unified_memory() && batch_size >= PRIVATE_BUFFERS_MIN_BATCH;
+        if private_buffers {
+            verbose!("Using private buffers with blit staging for batches of {}", batch_size);
+        }
+        let buffer_pool = Arc::new(BufferPool::new(device.clone(), batch_size, private_buffers));
+        
+        verbose!(
+            "Pipeline: thread execution width {}, max {} threads per threadgroup, {} bytes of static threadgroup memory",
+            pipeline_state.thread_execution_width(),
+            pipeline_state.max_total_threads_per_threadgroup(),
+            pipeline_state.static_threadgroup_memory_length()
+        );
+        // The pipeline's limit depends on the kernel's register use and can be below the device's
+        let max_threads_per_group = max_threads_per_group.min(pipeline_state.max_total_threads_per_threadgroup() as usize);
         
         Ok(MetalCompute {
             device,
-            command_queue,
+            command_queues,
+            next_queue: AtomicUsize::new(0),
             pipeline_state,
-            params_buffer,
             buffer_pool,
             batch_size,
             max_threads_per_group,
-            addresses_per_thread,
+            counters: Arc::new(DeviceCounters::new()),
         })
     }
     
-    pub fn compute_batch_gpu_random(
-        &self,
-        implementation: &str,
-        deployer: &str,
-        batch_size: usize,
-        random_seed: u32,
-    ) -> Result<Vec<(String, u32)>, String> {
-        // Get buffers from pool
-        let salts_buffer = self.buffer_pool.get_salts_buffer();
-        let results_buffer = self.buffer_pool.get_results_buffer();
-        
-        // Ensure buffers are returned to pool even on error
-        let result = self.compute_batch_gpu_random_internal(
-            implementation,
-            deployer,
-            batch_size,
-            random_seed,
-            &salts_buffer,
-            &results_buffer,
-        );
-        
-        // Always return buffers to pool
-        self.buffer_pool.return_salts_buffer(salts_buffer);
-        self.buffer_pool.return_results_buffer(results_buffer);
-        
-        result
+    // Thread group size for a batch of count addresses: the tuned size when `tune` measured one,
+    // otherwise sized dynamically from device capability and workload
+    pub fn threads_per_group(&self, count: usize, addresses_per_thread: u32, tuned: Option<usize>) -> usize {
+        let num_threads_needed = (count as u32).div_ceil(addresses_per_thread) as usize;
+        let optimal_threads = tuned.unwrap_or(match num_threads_needed {
+            n if n >= self.max_threads_per_group * 16 => self.max_threads_per_group,
+            n if n >= self.max_threads_per_group * 4 => self.max_threads_per_group / 2,
+            n if n >= self.max_threads_per_group => self.max_threads_per_group / 4,
+            n if n >= 256 => 256,
+            n if n >= 64 => 64,
+            _ => 32,
+        });
+        self.max_threads_per_group.min(optimal_threads).min(num_threads_needed)
     }
     
-    pub fn compute_batch_with_salts(
-        &self,
-        implementation: &str,
-        deployer: &str,
-        salts: &[String],
-    ) -> Result<Vec<(String, u32)>, String> {
-        // Get buffers from pool
-        let salts_buffer = self.buffer_pool.get_salts_buffer();
-        let results_buffer = self.buffer_pool.get_results_buffer();
-        
-        // Ensure buffers are returned to pool even on error
-        let result = self.compute_batch_with_salts_internal(
-            implementation,
-            deployer,
-            salts,
-            &salts_buffer,
-            &results_buffer,
-        );
-        
-        // Always return buffers to pool
-        self.buffer_pool.return_salts_buffer(salts_buffer);
-        self.buffer_pool.return_results_buffer(results_buffer);
-        
-        result
+    pub fn max_threads_per_group(&self) -> usize {
+        self.max_threads_per_group
     }
     
-    fn compute_batch_gpu_random_internal(
+    // With a filter only matching candidates are written back, compacted through an atomic
+    // counter, so the host no longer copies and scans the whole batch; without one every address is returned.
+    // The kernel loops over one batch per seed before completing, which only a filtered dispatch can hold
+    pub fn submit_batch_filtered(
         &self,
-        implementation: &str,
-        deployer: &str,
-        batch_size: usize,
-        random_seed: u32,
-        salts_buffer: &Buffer,
-        results_buffer: &Buffer,
-    ) -> Result<Vec<(String, u32)>, String> {
+        template: &Create2Template,
+        template_index: usize,
+        shard: Shard,
+        seeds: &[u32],
+        filter: Option<&GpuFilter>,
+    ) -> Result<InFlight<'_>, Create2Error> {
+        self.check_batch_size(shard.end)?;
+        if seeds.is_empty() || seeds.len() > MAX_BATCHES_PER_DISPATCH || (filter.is_none() && seeds.len() > 1) {
+            return Err(Create2Error::GpuDispatch(format!(
+                "Cannot dispatch {} batches at once (1..={} with a GPU filter, otherwise 1)",
+                seeds.len(),
+                MAX_BATCHES_PER_DISPATCH
+            )));
+        }
         
         // Prepare parameters for GPU random generation
-        let mut params = Create2Params {
-            implementation: [0u8; 40],
-            deployer: [0u8; 40],
-            batch_size: batch_size as u32,
-            addresses_per_thread: self.addresses_per_thread,
-            random_seed,
+        let params = Create2Params {
+            filter: filter.map_or([0u8; 40], |filter| filter.chars),
             use_gpu_random: 1,  // Always use GPU random
+            use_filter: filter.is_some() as u32,
+            filter_ignore_case: filter.is_some_and(|filter| filter.ignore_case) as u32,
+            batches: seeds.len() as u32,
+            ..self.kernel_params(template, shard)
         };
         
-        // Copy implementation address (without 0x prefix)
-        let impl_bytes = implementation[2..].as_bytes();
-        params.implementation[..impl_bytes.len()].copy_from_slice(impl_bytes);
-        
-        // Copy deployer address (without 0x prefix)
-        let depl_bytes = deployer[2..].as_bytes();
-        params.deployer[..depl_bytes.len()].copy_from_slice(depl_bytes);
-        
-        // Copy params to buffer
-        unsafe {
-            let ptr = self.params_buffer.contents() as *mut Create2Params;
-            *ptr = params;
-        }
-        
         // No salt copying needed for GPU random generation
-        
-        // Create command buffer and encoder
-        let command_buffer = self.command_queue.new_command_buffer();
-        let encoder = command_buffer.new_compute_command_encoder();
-        
-        // Set pipeline and buffers
-        encoder.set_compute_pipeline_state(&self.pipeline_state);
-        encoder.set_buffer(0, Some(&self.params_buffer), 0);
-        encoder.set_buffer(1, Some(&salts_buffer), 0);  // Still need to pass buffer even if unused
-        encoder.set_buffer(2, Some(&results_buffer), 0);
-        
-        // Optimize thread group size with thread coarsening
-        let num_threads_needed = ((batch_size as u32 + self.addresses_per_thread - 1) / self.addresses_per_thread) as usize;
-        
-        // Dynamic thread group sizing based on device capability and workload
-        let optimal_threads = match num_threads_needed {
-            n if n >= self.max_threads_per_group * 16 => self.max_threads_per_group,
-            n if n >= self.max_threads_per_group * 4 => self.max_threads_per_group / 2,
-            n if n >= self.max_threads_per_group => self.max_threads_per_group / 4,
-            n if n >= 256 => 256,
-            n if n >= 64 => 64,
-            _ => 32,
-        };
-        
-        let threads_per_group = (self.max_threads_per_group.min(optimal_threads).min(num_threads_needed)) as u64;
-        let thread_group_size = MTLSize {
-            width: threads_per_group,
-            height: 1,
-            depth: 1,
-        };
-        
-        let thread_groups = MTLSize {
-            width: (num_threads_needed as u64 + threads_per_group - 1) / threads_per_group,
-            height: 1,
-            depth: 1,
-        };
-        
-        // Dispatch compute kernel
-        encoder.dispatch_thread_groups(thread_groups, thread_group_size);
-        encoder.end_encoding();
-        
-        // Commit and wait
-        command_buffer.commit();
-        command_buffer.wait_until_completed();
-        
-        // Read results
-        let mut results = Vec::with_capacity(batch_size);
-        unsafe {
-            let ptr = results_buffer.contents() as *const Create2Result;
-            let slice = std::slice::from_raw_parts(ptr, batch_size);
-            
-            for (i, result) in slice.iter().enumerate() {
-                let address_bytes = &result.address[..40];
-                let address_str = std::str::from_utf8(address_bytes)
-                    .map_err(|e| format!("Failed to decode address at index {}: {}", i, e))?;
-                results.push((format!("0x{}", address_str), i as u32));
-            }
-        }
-        
-        Ok(results)
+        Ok(self.submit(&params, seeds, shard, template_index, filter.is_some(), None))
     }
     
-    fn compute_batch_with_salts_internal(
+    // salts is the whole batch; only the shard's part is copied to this device
+    pub fn submit_batch_with_salts(
         &self,
-        implementation: &str,
-        deployer: &str,
-        salts: &[String],
-        salts_buffer: &Buffer,
-        results_buffer: &Buffer,
-    ) -> Result<Vec<(String, u32)>, String> {
+        template: &Create2Template,
+        salts: &[[u8; 32]],
+        shard: Shard,
+    ) -> Result<InFlight<'_>, Create2Error> {
+        self.check_batch_size(shard.end)?;
         
         // Prepare parameters for salt-based computation
-        let mut params = Create2Params {
-            implementation: [0u8; 40],
-            deployer: [0u8; 40],
-            batch_size: salts.len() as u32,
-            addresses_per_thread: self.addresses_per_thread,
-            random_seed: 0,  // Not used for salt-based computation
-            use_gpu_random: 0,  // Always use provided salts
-        };
+        let params = self.kernel_params(template, shard);
         
-        // Copy implementation address (without 0x prefix)
-        let impl_bytes = implementation[2..].as_bytes();
-        params.implementation[..impl_bytes.len()].copy_from_slice(impl_bytes);
-        
-        // Copy deployer address (without 0x prefix)
-        let depl_bytes = deployer[2..].as_bytes();
-        params.deployer[..depl_bytes.len()].copy_from_slice(depl_bytes);
-        
-        // Copy params to buffer
-        unsafe {
-            let ptr = self.params_buffer.contents() as *mut Create2Params;
-            *ptr = params;
+        Ok(self.submit(&params, &[0], shard, 0, false, Some(salts)))
+    }
+    
+    pub fn device_name(&self) -> &str {
+        self.device.name()
+    }
+    
+    // (新分配的buffer数, 复用次数)
+    pub fn buffer_pool_stats(&self) -> (usize, usize) {
+        self.buffer_pool.stats()
+    }
+    
+    // Limits of the compiled pipeline and the geometry the shard is dispatched with
+    pub fn pipeline_stats(&self, shard: Shard) -> PipelineStats {
+        let threads_per_group = shard.threads_per_group.min(self.max_threads_per_group);
+        let threads = shard.len().div_ceil(shard.addresses_per_thread as usize);
+        PipelineStats {
+            device: self.device_name().to_string(),
+            max_threads_per_group: self.pipeline_state.max_total_threads_per_threadgroup() as usize,
+            thread_execution_width: self.pipeline_state.thread_execution_width() as usize,
+            static_threadgroup_memory: self.pipeline_state.static_threadgroup_memory_length() as usize,
+            thread_groups: threads.div_ceil(threads_per_group),
+            threads_per_group,
+            addresses_per_thread: shard.addresses_per_thread as usize,
+        }
+    }
+    
+    pub fn stats(&self) -> DeviceStats {
+        DeviceStats {
+            name: self.device_name().to_string(),
+            addresses: self.counters.computed.load(Ordering::Relaxed),
+            busy: Duration::from_nanos(self.counters.busy_nanos.load(Ordering::Relaxed)),
+        }
+    }
+    
+    // The pooled buffers are sized for batch_size entries; larger batches would overrun them
+    fn check_batch_size(&self, count: usize) -> Result<(), Create2Error> {
+        if count == 0 || count > self.batch_size {
+            return Err(Create2Error::GpuDispatch(format!(
+                "Batch of {} addresses does not fit the buffer pool (1..={})",
+                count, self.batch_size
+            )));
+        }
+        Ok(())
+    }
+    
+    // Parameters for the shard of a batch; GPU random mode overrides the seed and filter fields
+    fn kernel_params(&self, template: &Create2Template, shard: Shard) -> Create2Params {
+        Create2Params {
+            implementation: template.implementation().map_or([0u8; 20], |implementation| *implementation.as_bytes()),
+            deployer: *template.deployer().as_bytes(),
+            init_code_hash: *template.init_code_hash(),
+            filter: [0u8; 40],
+            // The kernel stops at the end of the shard, not of the whole batch
+            batch_size: shard.end as u32,
+            addresses_per_thread: shard.addresses_per_thread,
+            use_gpu_random: 0,  // Always use provided salts
+            // Templates built from an init code hash have no implementation to hash on the device
+            use_init_code_hash: template.implementation().is_none() as u32,
+            use_filter: 0,
+            filter_ignore_case: 0,
+            first_thread: (shard.start as u32) / shard.addresses_per_thread,
+            batches: 1,
+            result_capacity: self.batch_size as u32,
         }
+    }
+    
+    // Encodes one dispatch of the shard and commits it without waiting. The completed handler adds
+    // its time to the device stats and signals the completion; salts is the whole batch in salt mode.
+    // With private buffers the salts are uploaded and the results read back through a staging buffer
+    // by blit encoders around the dispatch; filtered results are few, so the kernel writes them to it directly
+    fn submit(
+        &self,
+        params: &Create2Params,
+        seeds: &[u32],
+        shard: Shard,
+        template: usize,
+        filtered: bool,
+        salts: Option<&[[u8; 32]]>,
+    ) -> InFlight<'_> {
+        let salts_buffer = self.buffer_pool.get_salts_buffer();
+        let staging_buffer = self.buffer_pool.private.then(|| self.buffer_pool.get_staging_buffer());
+        let results_buffer = (staging_buffer.is_none() || !filtered).then(|| self.buffer_pool.get_results_buffer());
+        let output_buffer = results_buffer.as_ref().or(staging_buffer.as_ref()).unwrap();
+        // Filtered candidates are appended at the slot this counter hands out; one per dispatch
+        let count_buffer = self.new_count_buffer();
         
-        // Optimized salt copying with memcpy
-        unsafe {
-            let ptr = salts_buffer.contents() as *mut u8;
-            let base_ptr = ptr;
-            
-            // Process salts in chunks for better cache usage
-            for (i, salt) in salts.iter().enumerate() {
-                let salt_bytes = salt.as_bytes();
-                let dest = base_ptr.add(i * 32);
-                
-                // Direct copy without clearing (GPU will read exact bytes needed)
-                if salt_bytes.len() == 32 {
-                    // Fast path for full-length salts
-                    std::ptr::copy_nonoverlapping(salt_bytes.as_ptr(), dest, 32);
-                } else {
-                    // Handle shorter salts
-                    std::ptr::write_bytes(dest, 0, 32);
-                    std::ptr::copy_nonoverlapping(salt_bytes.as_ptr(), dest, salt_bytes.len());
-                }
+        // Create command buffer on the next queue in turn
+        let queue = self.next_queue.fetch_add(1, Ordering::Relaxed) % self.command_queues.len();
+        let command_buffer = self.command_queues[queue].new_command_buffer();
+        
+        // Salts are already padded to 32 bytes, so the shard is one copy to the same offset it has in the batch
+        if let Some(salts) = salts {
+            let offset = shard.start * 32;
+            let size = shard.len() * 32;
+            let upload_buffer = staging_buffer.as_ref().unwrap_or(&salts_buffer);
+            unsafe {
+                let ptr = (upload_buffer.contents() as *mut u8).add(offset);
+                std::ptr::copy_nonoverlapping(salts[shard.start..shard.end].as_ptr() as *const u8, ptr, size);
+            }
+            if let Some(ref staging_buffer) = staging_buffer {
+                let blit = command_buffer.new_blit_command_encoder();
+                blit.copy_from_buffer(staging_buffer, offset as u64, &salts_buffer, offset as u64, size as u64);
+                blit.end_encoding();
             }
         }
         
-        // Create command buffer and encoder
-        let command_buffer = self.command_queue.new_command_buffer();
         let encoder = command_buffer.new_compute_command_encoder();
         
         // Set pipeline and buffers
         encoder.set_compute_pipeline_state(&self.pipeline_state);
-        encoder.set_buffer(0, Some(&self.params_buffer), 0);
-        encoder.set_buffer(1, Some(&salts_buffer), 0);
-        encoder.set_buffer(2, Some(&results_buffer), 0);
+        // Params are copied into the command buffer, so concurrent dispatches never share them
+        encoder.set_bytes(0, mem::size_of::<Create2Params>() as u64, params as *const Create2Params as *const std::ffi::c_void);
+        encoder.set_buffer(1, Some(&salts_buffer), 0);  // Still need to pass buffer even if unused
+        encoder.set_buffer(2, Some(output_buffer), 0);
+        encoder.set_buffer(3, Some(&count_buffer), 0);
+        encoder.set_bytes(4, mem::size_of_val(seeds) as u64, seeds.as_ptr() as *const std::ffi::c_void);
         
         // Optimize thread group size with thread coarsening
-        let num_threads_needed = ((salts.len() as u32 + self.addresses_per_thread - 1) / self.addresses_per_thread) as usize;
-        
-        // Dynamic thread group sizing based on device capability and workload
-        let optimal_threads = match num_threads_needed {
-            n if n >= self.max_threads_per_group * 16 => self.max_threads_per_group,
-            n if n >= self.max_threads_per_group * 4 => self.max_threads_per_group / 2,
-            n if n >= self.max_threads_per_group => self.max_threads_per_group / 4,
-            n if n >= 256 => 256,
-            n if n >= 64 => 64,
-            _ => 32,
-        };
-        
-        let threads_per_group = (self.max_threads_per_group.min(optimal_threads).min(num_threads_needed)) as u64;
+        let num_threads_needed = (shard.len() as u32).div_ceil(shard.addresses_per_thread) as u64;
+        let threads_per_group = shard.threads_per_group.min(self.max_threads_per_group) as u64;
         let thread_group_size = MTLSize {
             width: threads_per_group,
             height: 1,
@@ -383,7 +855,7 @@ impl MetalCompute {
         };
         
         let thread_groups = MTLSize {
-            width: (num_threads_needed as u64 + threads_per_group - 1) / threads_per_group,
+            width: num_threads_needed.div_ceil(threads_per_group),
             height: 1,
             depth: 1,
         };
@@ -392,59 +864,351 @@ impl MetalCompute {
         encoder.dispatch_thread_groups(thread_groups, thread_group_size);
         encoder.end_encoding();
         
-        // Commit and wait
+        // Unfiltered results fill the shard's slots, which are copied back to the same offset
+        if let (Some(results_buffer), Some(staging_buffer)) = (&results_buffer, &staging_buffer) {
+            let result_size = mem::size_of::<Create2Result>();
+            let blit = command_buffer.new_blit_command_encoder();
+            blit.copy_from_buffer(
+                results_buffer,
+                (shard.start * result_size) as u64,
+                staging_buffer,
+                (shard.start * result_size) as u64,
+                (shard.len() * result_size) as u64,
+            );
+            blit.end_encoding();
+        }
+        
+        // Commit; the handler runs on a Metal thread once the GPU is done
+        let completion = Completion::new();
+        let signal = completion.clone();
+        let counters = self.counters.clone();
+        let addresses = shard.len() as u64 * params.batches as u64;
+        let committed = counters.now();
+        let handler = ConcreteBlock::new(move |command_buffer: &CommandBufferRef| {
+            let succeeded = matches!(command_buffer.status(), MTLCommandBufferStatus::Completed);
+            if succeeded {
+                counters.record(addresses, committed);
+            }
+            signal.signal(succeeded);
+        })
+        .copy();
+        command_buffer.add_completed_handler(&handler);
         command_buffer.commit();
-        command_buffer.wait_until_completed();
         
-        // Read results
-        let mut results = Vec::with_capacity(salts.len());
+        InFlight {
+            compute: self,
+            shard,
+            template,
+            filtered,
+            completion,
+            salts_buffer: Some(salts_buffer),
+            results_buffer,
+            staging_buffer,
+            count_buffer,
+        }
+    }
+    
+    fn new_count_buffer(&self) -> Buffer {
+        let buffer = self.device.new_buffer(mem::size_of::<u32>() as u64, MTLResourceOptions::StorageModeShared);
         unsafe {
-            let ptr = results_buffer.contents() as *const Create2Result;
-            let slice = std::slice::from_raw_parts(ptr, salts.len());
-            
-            for (i, result) in slice.iter().enumerate() {
-                let address_bytes = &result.address[..40];
-                let address_str = std::str::from_utf8(address_bytes)
-                    .map_err(|e| format!("Failed to decode address at index {}: {}", i, e))?;
-                results.push((format!("0x{}", address_str), i as u32));
-            }
+            *(buffer.contents() as *mut u32) = 0;
         }
-        
-        Ok(results)
+        buffer
     }
 }
 
 pub struct GpuAccelerator {
-    compute: MetalCompute,
+    // Every Metal device, the system default first; each batch is split across all of them
+    devices: Vec<MetalCompute>,
+    device_name: String,
+    // Host-side generator for the per-batch kernel seeds; seeding it makes GPU random runs replayable
+    rng: Mutex<ChaCha8Rng>,
+    // Thread group size from the tune cache or the tune sweep, 0 for the dynamic sizing
+    threads_per_group: AtomicUsize,
+    addresses_per_thread: AtomicUsize,
 }
 
 impl GpuAccelerator {
-    pub fn new(batch_size: usize) -> Result<Self, String> {
-        let compute = MetalCompute::new(batch_size)?;
+    pub fn new(batch_size: usize, seed: u64, command_queues: usize) -> Result<Self, Create2Error> {
+        let mut candidates = Device::all();
+        if candidates.is_empty() {
+            return Err(Create2Error::GpuInit(
+                "Metal device not found. Ensure you're running on macOS with Metal support.".to_string(),
+            ));
+        }
+        if let Some(default) = Device::system_default() {
+            candidates.sort_by_key(|device| device.registry_id() != default.registry_id());
+        }
+        
+        // A device that cannot run the batch is left out rather than failing the whole run
+        let mut devices = Vec::with_capacity(candidates.len());
+        let mut last_error = None;
+        for device in candidates {
+            let name = device.name().to_string();
+            match MetalCompute::new(device, batch_size, command_queues) {
+                Ok(compute) => devices.push(compute),
+                Err(e) => {
+                    eprintln!("⚠️  Skipping Metal device {}: {}", name, e);
+                    last_error = Some(e);
+                }
+            }
+        }
+        if let Some(e) = last_error.filter(|_| devices.is_empty()) {
+            return Err(e);
+        }
+        if devices.len() > 1 {
+            status!("Splitting each batch across {} Metal devices", devices.len());
+        }
+        
+        let device_name = devices.iter().map(MetalCompute::device_name).collect::<Vec<_>>().join(" + ");
         Ok(GpuAccelerator {
-            compute,
+            devices,
+            device_name,
+            rng: Mutex::new(ChaCha8Rng::seed_from_u64(seed)),
+            threads_per_group: AtomicUsize::new(0),
+            addresses_per_thread: AtomicUsize::new(DEFAULT_ADDRESSES_PER_THREAD as usize),
         })
     }
     
+    pub fn device_name(&self) -> &str {
+        &self.device_name
+    }
+    
+    pub fn buffer_pool_stats(&self) -> (usize, usize) {
+        self.devices.iter().map(MetalCompute::buffer_pool_stats).fold((0, 0), |(a, r), (da, dr)| (a + da, r + dr))
+    }
+    
+    pub fn device_stats(&self) -> Vec<DeviceStats> {
+        self.devices.iter().map(MetalCompute::stats).collect()
+    }
+    
+    // One entry per device that a batch of count addresses would be dispatched to, split as it is now
+    pub fn pipeline_stats(&self, count: usize) -> Vec<PipelineStats> {
+        self.shards(count).into_iter().map(|(compute, shard)| compute.pipeline_stats(shard)).collect()
+    }
+    
+    // Largest thread group every device can run
+    pub fn max_threads_per_group(&self) -> usize {
+        self.devices.iter().map(MetalCompute::max_threads_per_group).min().unwrap_or(0)
+    }
+    
+    // Fixes the thread group size of later batches; None restores the dynamic sizing
+    pub fn set_threads_per_group(&self, threads: Option<usize>) {
+        self.threads_per_group.store(threads.unwrap_or(0), Ordering::Relaxed);
+    }
+    
+    // Sets the coarsening factor of later batches; GPU salts depend on it, like on the batch size
+    pub fn set_addresses_per_thread(&self, addresses: usize) -> Result<(), Create2Error> {
+        if addresses == 0 || addresses > MAX_ADDRESSES_PER_THREAD as usize {
+            return Err(Create2Error::GpuDispatch(format!(
+                "Addresses per thread must be between 1 and {}, got {}",
+                MAX_ADDRESSES_PER_THREAD, addresses
+            )));
+        }
+        self.addresses_per_thread.store(addresses, Ordering::Relaxed);
+        Ok(())
+    }
+    
+    // Position of the batch seed generator, saved in --resume checkpoints
+    pub fn rng_position(&self) -> u128 {
+        self.rng.lock().unwrap().get_word_pos()
+    }
+    
+    pub fn set_rng_position(&self, position: u128) {
+        self.rng.lock().unwrap().set_word_pos(position);
+    }
+    
+    // Draws the next batch seed without dispatching it, for batches the caller already searched
+    pub fn skip_batch(&self) {
+        self.next_seed();
+    }
+    
+    // The kernel seed of the next batch; drawing it moves the generator to the batch after
+    pub fn next_seed(&self) -> u32 {
+        self.rng.lock().unwrap().gen::<u32>()
+    }
+    
+    // Splits count addresses into one shard per device, in proportion to each device's measured
+    // TPS (equal until every device has run a batch), in whole thread groups sized for the
+    // whole batch on the first device so the salts do not depend on how many devices there are
+    fn shards(&self, count: usize) -> Vec<(&MetalCompute, Shard)> {
+        let tuned = Some(self.threads_per_group.load(Ordering::Relaxed)).filter(|&threads| threads > 0);
+        let addresses_per_thread = self.addresses_per_thread.load(Ordering::Relaxed) as u32;
+        let threads_per_group = self.devices[0].threads_per_group(count, addresses_per_thread, tuned);
+        let unit = threads_per_group * addresses_per_thread as usize;
+        let units = count.div_ceil(unit);
+        
+        let stats = self.device_stats();
+        let weights: Vec<f64> = if stats.iter().all(|stats| stats.addresses > 0) {
+            stats.iter().map(DeviceStats::tps).collect()
+        } else {
+            vec![1.0; stats.len()]
+        };
+        let total: f64 = weights.iter().sum();
+        
+        let mut shards = Vec::with_capacity(self.devices.len());
+        let mut assigned = 0;
+        for (i, (compute, weight)) in self.devices.iter().zip(&weights).enumerate() {
+            let device_units = if i + 1 == self.devices.len() {
+                units - assigned
+            } else {
+                ((units as f64 * weight / total).round() as usize).min(units - assigned)
+            };
+            if device_units == 0 {
+                continue;
+            }
+            let start = assigned * unit;
+            assigned += device_units;
+            shards.push((
+                compute,
+                Shard {
+                    start,
+                    end: (assigned * unit).min(count),
+                    threads_per_group,
+                    addresses_per_thread,
+                },
+            ));
+        }
+        shards
+    }
+    
+    // Commits every shard without waiting; each device works through its own queue, so the
+    // devices run concurrently and the host is free until it waits for the batch
+    fn submit_shards<'a>(
+        &'a self,
+        count: usize,
+        submit: impl Fn(&'a MetalCompute, Shard) -> Result<InFlight<'a>, Create2Error>,
+    ) -> Result<Vec<InFlight<'a>>, Create2Error> {
+        self.shards(count).into_iter().map(|(compute, shard)| submit(compute, shard)).collect()
+    }
+    
+    // For embedding applications; Create2Predictor goes through process_batch_gpu_random_into
+    #[allow(dead_code)]
     pub fn process_batch_gpu_random(
         &self,
-        implementation: &str,
-        deployer: &str,
+        template: &Create2Template,
         batch_size: usize,
-    ) -> Result<Vec<(String, u32)>, String> {
-        use rand::Rng;
-        let mut rng = rand::thread_rng();
-        let random_seed = rng.gen::<u32>();
-        
-        self.compute.compute_batch_gpu_random(implementation, deployer, batch_size, random_seed)
+    ) -> Result<Vec<(String, u32)>, Create2Error> {
+        let mut results = Vec::new();
+        self.process_batch_gpu_random_into(template, batch_size, &mut results)?;
+        Ok(results)
+    }
+    
+    // Like process_batch_gpu_random, but overwrites the caller's vector instead of allocating one per batch
+    pub fn process_batch_gpu_random_into(
+        &self,
+        template: &Create2Template,
+        batch_size: usize,
+        out: &mut Vec<(String, u32)>,
+    ) -> Result<(), Create2Error> {
+        self.submit_batch_filtered(std::slice::from_ref(template), batch_size, None)?.wait_addresses_into(out)
+    }
+    
+    pub fn process_batch_filtered(
+        &self,
+        templates: &[Create2Template],
+        batch_size: usize,
+        filter: Option<&GpuFilter>,
+    ) -> Result<Vec<Candidate>, Create2Error> {
+        self.submit_batch_filtered(templates, batch_size, filter)?.wait()
+    }
+    
+    // Every template is dispatched with the same batch seed, so each GPU salt is checked against
+    // all of them; the batch is committed and returned without waiting for the GPU
+    pub fn submit_batch_filtered(
+        &self,
+        templates: &[Create2Template],
+        batch_size: usize,
+        filter: Option<&GpuFilter>,
+    ) -> Result<PendingBatch<'_>, Create2Error> {
+        let random_seed = self.next_seed();
+        self.submit_batches_filtered(templates, batch_size, filter, &[random_seed])
+    }
+    
+    // One batch per seed, all searched in the same dispatch: each thread loops over the batches
+    // on the GPU, and the host only hears back once, with the candidates of every batch
+    pub fn submit_batches_filtered(
+        &self,
+        templates: &[Create2Template],
+        batch_size: usize,
+        filter: Option<&GpuFilter>,
+        seeds: &[u32],
+    ) -> Result<PendingBatch<'_>, Create2Error> {
+        let mut dispatches = Vec::new();
+        for (index, template) in templates.iter().enumerate() {
+            dispatches.extend(self.submit_shards(batch_size, |compute, shard| {
+                compute.submit_batch_filtered(template, index, shard, seeds, filter)
+            })?);
+        }
+        Ok(PendingBatch { dispatches })
     }
     
-    pub fn process_batch_with_salt(
+    // For embedding applications; Create2Predictor goes through process_batch_with_salt_into
+    #[allow(dead_code)]
+    pub fn process_batch_with_salt<S: IntoSalt>(
         &self,
-        implementation: &str,
-        deployer: &str,
-        salts: &[String],
-    ) -> Result<Vec<(String, u32)>, String> {
-        self.compute.compute_batch_with_salts(implementation, deployer, salts)
+        template: &Create2Template,
+        salts: &[S],
+    ) -> Result<Vec<(String, u32)>, Create2Error> {
+        let mut results = Vec::new();
+        self.process_batch_with_salt_into(template, salts, &mut results)?;
+        Ok(results)
+    }
+    
+    // Like process_batch_with_salt, but overwrites the caller's vector instead of allocating one per batch
+    pub fn process_batch_with_salt_into<S: IntoSalt>(
+        &self,
+        template: &Create2Template,
+        salts: &[S],
+        out: &mut Vec<(String, u32)>,
+    ) -> Result<(), Create2Error> {
+        // Each salt is converted up front and copied into a fixed 32-byte slot of the salts buffer
+        let salts = salts.iter().map(IntoSalt::to_salt).collect::<Result<Vec<_>, _>>()?;
+        let dispatches = self.submit_shards(salts.len(), |compute, shard| compute.submit_batch_with_salts(template, &salts, shard))?;
+        PendingBatch { dispatches }.wait_addresses_into(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use create2_core::test_vectors::{Create2Vector, CREATE2_VECTORS};
+    use create2_core::EVM_CREATE2_PREFIX;
+    
+    const RANDOM_BATCH: usize = 256;
+    
+    // None on a machine without a Metal device, where the tests below pass without checking anything
+    fn accelerator() -> Option<GpuAccelerator> {
+        GpuAccelerator::new(RANDOM_BATCH, 0, 1)
+            .inspect_err(|e| eprintln!("skipping the Metal kernel tests: {}", e))
+            .ok()
+    }
+    
+    // The kernel only hashes with the EVM 0xff prefix
+    fn evm_vectors() -> impl Iterator<Item = &'static Create2Vector> {
+        CREATE2_VECTORS.iter().filter(|vector| vector.prefix == EVM_CREATE2_PREFIX)
+    }
+    
+    #[test]
+    fn salts_kernel_matches_test_vectors() {
+        let Some(gpu) = accelerator() else { return };
+        for vector in evm_vectors() {
+            let results = gpu.process_batch_with_salt(&vector.template(), &[vector.salt()]).unwrap();
+            assert_eq!(results[0].0, vector.expected().to_string(), "{}", vector.name);
+        }
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn random_kernel_matches_cpu() {
+        let Some(gpu) = accelerator() else { return };
+        for vector in evm_vectors() {
+            let template = vector.template();
+            let candidates = gpu.process_batch_filtered(std::slice::from_ref(&template), RANDOM_BATCH, None).unwrap();
+            assert_eq!(candidates.len(), RANDOM_BATCH, "{}", vector.name);
+            for candidate in candidates {
+                let expected = template.predict(candidate.salt).unwrap().to_string();
+                assert_eq!(candidate.address, expected, "{}, salt {}", vector.name, candidate.salt());
+            }
+        }
+    }
+}
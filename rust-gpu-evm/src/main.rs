@@ -1,88 +1,100 @@
+#[macro_use]
+extern crate create2_cli;
 mod create2;
 mod gpu_compute;
 
+use create2_cli::{bloom, checkpoint, cli, compare, config, format_duration, nonces, notify, output, pattern, progress, prompt, salts, tune, validate};
+use bloom::{SaltBloom, DEFAULT_BLOOM_CAPACITY, DEFAULT_BLOOM_FP_RATE};
+use checkpoint::Checkpoint;
+use cli::Command;
+use config::{GpuBackend, PatternPosition, Scheme, Settings, Verbosity, DEFAULT_BENCH_DIFFICULTY, DEFAULT_NONCE_COUNT};
 use create2::Create2Predictor;
-use std::io::{self, Write};
+use gpu_compute::{GpuFilter, PendingBatch, DEFAULT_ADDRESSES_PER_THREAD, MAX_BATCHES_PER_DISPATCH, MAX_COMMAND_QUEUES};
+use create2_core::salt::SaltPolicy;
+use create2_core::{check_address, random_salt, search_templates, Address, CancellationToken, Create2Error, Create2Template, RANDOM_SALT_WORDS};
+use notify::Notifier;
+use pattern::{AddressKind, PatternSet};
+use progress::ProgressReporter;
+use salts::SaltReader;
+use tune::TunedParams;
+use validate::Validation;
+use output::{BenchSummary, MatchRecord, MatchSink, Output, PipelineStats, Prediction, ResultsFile};
+use rand_chacha::ChaCha8Rng;
+use rayon::prelude::*;
+use rayon::ThreadPool;
+use std::hint::black_box;
+use std::ops::ControlFlow;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
 
 const TOTAL_OPERATIONS: usize = 50_000_000;
 const IMPLEMENTATION: &str = "0xa84c57e9966df7df79bff42f35c68aae71796f64";
 const DEPLOYER: &str = "0xfe15afcb5b9831b8af5fd984678250e95de8e312";
 const PROGRESS_INTERVAL: usize = 10000;
+// --hybrid 的CPU线程每隔多少次尝试检查停止条件并同步检查点
+const CHECK_INTERVAL: usize = 1000;
 const GPU_BATCH_SIZE: usize = 262144; // 256K - Quadrupled batch size with thread coarsening
+// tune扫描的批处理大小、每组线程数和每线程地址数，超出设备上限的组合跳过
+const TUNE_BATCH_SIZES: &[usize] = &[32768, 65536, 131072, 262144, 524288, 1048576];
+const TUNE_THREADS_PER_GROUP: &[usize] = &[32, 64, 128, 256, 512, 1024];
+const TUNE_ADDRESSES_PER_THREAD: &[usize] = &[1, 2, 4, 8, 16];
+const DEFAULT_TUNE_CACHE: &str = "create2-tune-gpu.json";
+const DEFAULT_PATTERN: &str = "eAce1";
+// 只编译了Metal后端
+const BACKENDS: &[GpuBackend] = &[GpuBackend::Metal];
 
-fn format_duration(duration: Duration) -> String {
-    let total_secs = duration.as_secs_f64();
+fn run_benchmark(settings: &Settings) -> Result<(), Box<dyn std::error::Error>> {
+    let template = settings.template()?;
+    let output = Output::open(settings.output.as_deref())?;
+    // 未指定--seed时随机生成并打印，每个批次的GPU种子都由它派生
+    let seed = settings.seed.unwrap_or_else(rand::random);
     
-    if total_secs < 60.0 {
-        format!("{:.1}s", total_secs)
-    } else if total_secs < 3600.0 {
-        let mins = (total_secs / 60.0) as u32;
-        let secs = total_secs % 60.0;
-        format!("{}m{:.1}s", mins, secs)
-    } else {
-        let hours = (total_secs / 3600.0) as u32;
-        let mins = ((total_secs % 3600.0) / 60.0) as u32;
-        let secs = total_secs % 60.0;
-        format!("{}h{}m{:.1}s", hours, mins, secs)
-    }
-}
-
-fn run_benchmark() -> Result<(), Box<dyn std::error::Error>> {
-    println!("🚀 Rust CREATE2地址预测benchmark (GPU加速版)");
-    println!("总计算量: {} 次", TOTAL_OPERATIONS);
-    println!("实现合约: {}", IMPLEMENTATION);
-    println!("部署者: {}", DEPLOYER);
-    println!("GPU批处理大小: {}", GPU_BATCH_SIZE);
-    println!("随机数生成: GPU上生成 (PCG32算法)");
-    println!("--------------------------------------------------------------------------------");
+    status!("🚀 Rust CREATE2地址预测benchmark (GPU加速版)");
+    status!("总计算量: {} 次", TOTAL_OPERATIONS);
+    status!("实现合约: {}", settings.implementation);
+    status!("部署者: {}", settings.deployer);
+    print_init_code_hash(settings);
+    status!("GPU批处理大小: {}", settings.batch_size);
+    status!("随机数生成: GPU上生成 (PCG32算法)");
+    status!("随机种子: {}", seed);
+    status!("--------------------------------------------------------------------------------");
     
-    let predictor = Create2Predictor::new(true, GPU_BATCH_SIZE)?;
+    let predictor = Create2Predictor::new(true, settings.batch_size, seed, settings.command_queues)?;
     
     if !predictor.is_gpu_enabled() {
         eprintln!("❌ GPU不可用，请检查Metal支持");
         return Err("GPU initialization failed".into());
     }
     
+    predictor.use_tuned(settings.tuned.as_ref());
+    predictor.set_addresses_per_thread(settings.addresses_per_thread)?;
+    
     let start_time = Instant::now();
-    let mut last_report_time = start_time;
-    let mut last_report_count = 0;
+    let reporter = ProgressReporter::new(settings);
     let mut processed = 0;
+    // 每个批次覆盖写入同一个结果Vec，不再为每个地址重新分配String
+    let mut results = Vec::with_capacity(settings.batch_size);
     
     while processed < TOTAL_OPERATIONS {
-        let batch_size = std::cmp::min(GPU_BATCH_SIZE, TOTAL_OPERATIONS - processed);
+        let batch_size = std::cmp::min(settings.batch_size, TOTAL_OPERATIONS - processed);
         
-        match predictor.predict_batch_address(IMPLEMENTATION, DEPLOYER, batch_size) {
-            Ok(_results) => {
+        match predictor.predict_batch_address_into(&template, batch_size, &mut results) {
+            Ok(()) => {
                 processed += batch_size;
                 
-                if processed % PROGRESS_INTERVAL <= batch_size || processed >= TOTAL_OPERATIONS {
-                    let current_time = Instant::now();
-                    let elapsed = current_time.duration_since(start_time);
-                    
-                    let avg_tps = processed as f64 / elapsed.as_secs_f64();
-                    
-                    let current_tps = if processed > last_report_count {
-                        let interval_elapsed = current_time.duration_since(last_report_time);
-                        let interval_count = processed - last_report_count;
-                        if interval_elapsed.as_secs_f64() > 0.0 {
-                            interval_count as f64 / interval_elapsed.as_secs_f64()
-                        } else {
-                            0.0
-                        }
-                    } else {
-                        avg_tps
-                    };
+                let tick = if processed >= TOTAL_OPERATIONS {
+                    Some(reporter.finish(processed))
+                } else {
+                    reporter.tick(processed)
+                };
+                if let Some(tick) = tick {
+                    let percentage = (tick.count as f64 / TOTAL_OPERATIONS as f64) * 100.0;
                     
-                    let percentage = (processed as f64 / TOTAL_OPERATIONS as f64) * 100.0;
-                    
-                    print!("\r进度: {:.2}% ({}/{}) | 平均TPS: {:.0} | 当前TPS: {:.0} | 用时: {}",
-                        percentage, processed, TOTAL_OPERATIONS, avg_tps, current_tps, 
-                        format_duration(elapsed));
-                    io::stdout().flush().unwrap();
-                    
-                    last_report_time = current_time;
-                    last_report_count = processed;
+                    progress!("\r进度: {:.2}% ({}/{}) | 平均TPS: {:.0} | 当前TPS: {:.0} | 用时: {}",
+                        percentage, tick.count, TOTAL_OPERATIONS, tick.avg_tps, tick.current_tps, 
+                        format_duration(tick.elapsed));
                 }
             }
             Err(e) => {
@@ -96,47 +108,97 @@ fn run_benchmark() -> Result<(), Box<dyn std::error::Error>> {
     let avg_tps = TOTAL_OPERATIONS as f64 / total_elapsed.as_secs_f64();
     let us_per_op = total_elapsed.as_micros() as f64 / TOTAL_OPERATIONS as f64;
     
-    println!("\n--------------------------------------------------------------------------------");
-    println!("✅ 计算完成! (GPU加速)");
-    println!();
-    println!("📊 Benchmark 结果:");
-    println!("==================================================");
-    println!("总操作数:     {}", TOTAL_OPERATIONS);
-    println!("总用时:       {}", format_duration(total_elapsed));
-    println!("平均TPS:      {:.2} ops/sec", avg_tps);
-    println!("每次操作耗时: {:.2} μs", us_per_op);
+    status!("\n--------------------------------------------------------------------------------");
+    status!("✅ 计算完成! (GPU加速)");
+    status!();
+    summary!("📊 Benchmark 结果:");
+    summary!("==================================================");
+    summary!("总操作数:     {}", TOTAL_OPERATIONS);
+    summary!("总用时:       {}", format_duration(total_elapsed));
+    summary!("平均TPS:      {:.2} ops/sec", avg_tps);
+    summary!("每次操作耗时: {:.2} μs", us_per_op);
+    
+    let mut summary = BenchSummary::new("gpu-evm", TOTAL_OPERATIONS, total_elapsed.as_secs_f64());
+    summary.batch_size = Some(settings.batch_size);
+    summary.device = predictor.device_name();
+    summary.seed = Some(seed);
+    summary.pipelines = predictor.pipeline_stats(settings.batch_size);
+    print_device_stats(&predictor);
+    print_pipeline_stats(&summary.pipelines);
+    print_buffer_pool_stats(&predictor);
+    output::emit_json(&summary)?;
+    output.record(&summary.to_string())?;
+    
+    Ok(())
+}
+
+// bench-find：以末尾 --bench-difficulty 个0为合成规则，按benchmark的计算量跑完整的 生成salt→哈希→匹配→输出 流程，
+// 报告每秒找到的地址数；用时包括find模式启动时的校准
+fn run_match_benchmark(settings: &Settings) -> Result<(), Box<dyn std::error::Error>> {
+    let output = Output::open(settings.output.as_deref())?;
+    let seed = settings.seed.unwrap_or_else(rand::random);
+    let difficulty = settings.bench_difficulty;
+    let settings = settings.bench_find("0".repeat(difficulty), TOTAL_OPERATIONS, seed);
+    let expected = PatternSet::from_settings(&settings, AddressKind::Evm)?
+        .expected_attempts()
+        .ok_or("bench-find needs a pattern with a difficulty estimate")?;
+    
+    let start_time = Instant::now();
+    let matches = find_address(&settings)?;
+    let total_elapsed = start_time.elapsed();
+    let matches_per_sec = matches as f64 / total_elapsed.as_secs_f64();
+    
+    status!();
+    summary!("📊 匹配Benchmark 结果:");
+    summary!("==================================================");
+    summary!("难度:         末尾 {} 个0，平均每 {:.0} 次尝试匹配一次", difficulty, expected);
+    summary!("总尝试次数:   {}", TOTAL_OPERATIONS);
+    summary!("总用时:       {:.1}s", total_elapsed.as_secs_f64());
+    summary!("平均TPS:      {:.2} ops/sec", TOTAL_OPERATIONS as f64 / total_elapsed.as_secs_f64());
+    summary!("找到地址数:   {}（期望 {:.1}）", matches, TOTAL_OPERATIONS as f64 / expected);
+    summary!("每秒匹配数:   {:.2} matches/sec", matches_per_sec);
+    
+    let mut summary = BenchSummary::new("gpu-evm", TOTAL_OPERATIONS, total_elapsed.as_secs_f64());
+    summary.batch_size = Some(settings.batch_size);
+    summary.seed = Some(seed);
+    summary.difficulty = Some(difficulty);
+    summary.matches = Some(matches);
+    summary.matches_per_sec = Some(matches_per_sec);
+    output::emit_json(&summary)?;
+    output.record(&summary.to_string())?;
     
     Ok(())
 }
 
 fn run_single_test() -> Result<(), Box<dyn std::error::Error>> {
-    println!("Running single test for verification...");
-    let implementation = "0xa84c57e9966df7df79bff42f35c68aae71796f64";
-    let deployer = "0xfe15afcb5b9831b8af5fd984678250e95de8e312";
+    status!("Running single test for verification...");
+    let implementation: Address = "0xa84c57e9966df7df79bff42f35c68aae71796f64".parse()?;
+    let deployer: Address = "0xfe15afcb5b9831b8af5fd984678250e95de8e312".parse()?;
+    let template = Create2Template::from_addresses(implementation, deployer);
     // 使用原始的salt格式以匹配预期地址
     let salt = "test-salt-test";
 
-    let predictor = Create2Predictor::new(true, 1)?;
+    let predictor = Create2Predictor::new(true, 1, 0, 1)?;
     if !predictor.is_gpu_enabled() {
         eprintln!("❌ GPU不可用，请检查Metal支持");
         return Err("GPU initialization failed".into());
     }
     
-    println!("\n📝 测试参数:");
-    println!("  Implementation: {}", implementation);
-    println!("  Deployer: {}", deployer);
-    println!("  Salt: {}", salt);
+    status!("\n📝 测试参数:");
+    status!("  Implementation: {}", implementation);
+    status!("  Deployer: {}", deployer);
+    status!("  Salt: {}", salt);
     
     // 使用固定的salt进行单次测试
     let salts = vec![salt.to_string()];
-    match predictor.predict_batch_with_salt(implementation, deployer, &salts) {
+    match predictor.predict_batch_with_salt(&template, &salts) {
         Ok(results) => {
             let expected = "0x22FBFB2264B9Cd1ADe8ce5013012c817878D783C";
             
             if results[0] == expected {
-                println!("✅ 结果: {}", results[0]);
+                summary!("✅ 结果: {}", results[0]);
             } else {
-                println!("⚠️  地址不匹配!");
+                summary!("⚠️  地址不匹配!");
             }
         }
         Err(e) => {
@@ -148,77 +210,719 @@ fn run_single_test() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-fn find_address() -> Result<(), Box<dyn std::error::Error>> {
-    println!("🔍 开始搜索以 eAce1 结尾的EVM CREATE2地址...");
-    println!("Implementation: {}", IMPLEMENTATION);
-    println!("Deployer: {}", DEPLOYER);
-    println!("GPU批处理大小: {}", GPU_BATCH_SIZE);
-    println!("随机数生成: GPU上生成 (PCG32算法)");
-    println!("按Ctrl+C停止搜索");
-    println!("--------------------------------------------------------------------------------");
+// 返回找到的地址数，达到停止条件后正常退出
+fn find_address(settings: &Settings) -> Result<usize, Box<dyn std::error::Error>> {
+    let targets = settings.find_targets();
+    let pattern = PatternSet::from_settings(settings, AddressKind::Evm)?.per_salt(targets.len());
+    let templates = settings.templates()?;
+    if settings.batches_per_dispatch > MAX_BATCHES_PER_DISPATCH {
+        return Err(format!("--batches-per-dispatch must be at most {}, got {}", MAX_BATCHES_PER_DISPATCH, settings.batches_per_dispatch).into());
+    }
+    let output = Output::open(settings.output.as_deref())?;
+    let results_file = ResultsFile::open(settings.results.as_deref(), "gpu-evm", pattern.spec())?;
+    let notifier = Notifier::new(settings.notify_url.as_deref(), "gpu-evm", pattern.spec())?;
+    // 随机数流0是GPU的批次种子，--hybrid 时1..=n依次是各CPU线程的ChaCha流
+    let pool = hybrid_pool(settings)?;
+    let cpu_threads = pool.as_ref().map_or(0, |pool| pool.current_num_threads());
+    // --resume 恢复时沿用检查点里的种子和批次种子位置
+    let checkpoint = Checkpoint::open(settings, settings.seed.unwrap_or_else(rand::random), 1 + cpu_threads)?;
+    pattern.restore(&checkpoint.matches);
+    checkpoint.check_batch_size(settings.batch_size)?;
+    checkpoint.check_addresses_per_thread(settings.addresses_per_thread)?;
+    let resume_path = settings.resume.as_deref();
+    let seed = checkpoint.seed;
+    let bloom = SaltBloom::open(settings)?;
     
-    let predictor = Create2Predictor::new(true, GPU_BATCH_SIZE)?;
+    status!("🔍 开始搜索{}的EVM CREATE2地址...", pattern);
+    if let Some(attempts) = pattern.expected_attempts() {
+        status!("难度: 平均每 {:.0} 次尝试匹配一次", attempts);
+    }
+    pattern.print_rules();
+    status!("Implementation: {}", settings.find_implementations().join(", "));
+    status!("Deployer: {}", settings.find_deployers().join(", "));
+    print_init_code_hash(settings);
+    status!("GPU批处理大小: {}", settings.batch_size);
+    status!("随机数生成: GPU上生成 (PCG32算法)");
+    if cpu_threads > 0 {
+        status!("CPU线程数: {}（与GPU同时搜索）", cpu_threads);
+    }
+    status!("随机种子: {}", seed);
+    if let Some(ref bloom) = bloom {
+        status!("布隆过滤器: {}", bloom.describe());
+    }
+    print_find_limits(settings);
+    status!("按Ctrl+C停止搜索");
+    status!("--------------------------------------------------------------------------------");
+    
+    let predictor = Create2Predictor::new(true, settings.batch_size, seed, settings.command_queues)?;
     
     if !predictor.is_gpu_enabled() {
         eprintln!("❌ GPU不可用，请检查Metal支持");
         return Err("GPU initialization failed".into());
     }
     
+    predictor.use_tuned(settings.tuned.as_ref());
+    predictor.set_addresses_per_thread(settings.addresses_per_thread)?;
+    
+    // 单个前缀、后缀或掩码规则在GPU上先筛选，只拷回候选地址；其他规则拷回整个批次在CPU上比较
+    let filter = pattern
+        .char_filter()
+        .and_then(|filter| GpuFilter::new(&filter.chars, filter.ignore_case));
+    if filter.is_some() {
+        status!("GPU端过滤: 只拷回可能匹配的地址");
+    }
+    // 内核在一次调度里连续搜索多个批次时只拷回候选地址，没有GPU端过滤时每个批次都要整批拷回
+    let batches_per_dispatch = if filter.is_some() { settings.batches_per_dispatch } else { 1 };
+    if batches_per_dispatch > 1 {
+        status!("每次GPU调度搜索 {} 个批次", batches_per_dispatch);
+    } else if settings.batches_per_dispatch > 1 {
+        eprintln!("⚠️  --batches-per-dispatch 需要GPU端过滤（单个前缀、后缀或掩码规则），每次调度只搜索1个批次");
+    }
+    
+    // 校准用的批次不计入搜索，随后把随机数位置恢复到检查点；--hybrid 时加上CPU线程的TPS
+    if let Some(attempts) = pattern.expected_attempts() {
+        let mut tps = progress::calibrate(|| {
+            predictor.search_batch(&templates, settings.batch_size, filter.as_ref()).map_or(0, |_| settings.batch_size)
+        });
+        if let Some(ref pool) = pool {
+            tps += calibrate_cpu_tps(&templates, pool);
+        }
+        status!("校准TPS: {:.0} | 预计平均每 {} 找到一个地址", tps, progress::format_eta(attempts, tps));
+    }
+    predictor.set_rng_position(checkpoint.rng_positions[0]);
+    
     let start_time = Instant::now();
-    let mut last_report_time = start_time;
-    let mut total_processed = 0;
+    let elapsed_offset = checkpoint.elapsed();
+    let reporter = ProgressReporter::resumed(settings, checkpoint.attempts, elapsed_offset);
+    // GPU和CPU线程共用的总尝试次数，检查点和进度行都按它计算
+    let counter = AtomicUsize::new(checkpoint.attempts);
+    let gpu_attempts = AtomicUsize::new(0);
+    let cpu_attempts = AtomicUsize::new(0);
+    let sink = MatchSink::resumed(settings, &targets, &pattern, &output, &results_file, &notifier, &checkpoint);
+    // GPU或任一CPU线程达到停止条件时取消，其他一方在下一个批次或salt前停下
+    let stop = CancellationToken::new();
+    let mut batch_offset = checkpoint.batch_offset;
+    let checkpoint = Mutex::new(checkpoint);
+    let bloom = bloom.map(Mutex::new);
+    let skipped = AtomicUsize::new(0);
     let mut batch_num = 0;
     
-    loop {
-        batch_num += 1;
-        
-        match predictor.predict_batch_address(IMPLEMENTATION, DEPLOYER, GPU_BATCH_SIZE) {
-            Ok(results) => {
-                total_processed += results.len();
+    let mut search_gpu = || -> Result<(), Box<dyn std::error::Error>> {
+        // 检查一次调度的候选地址时GPU已经在计算下一次调度；停止时还没检查的批次直接丢弃，
+        // 检查点只记录检查过的批次。剩余的 --max-attempts 不够整次调度时只提交用得到的批次
+        let remaining = || settings.max_attempts.map(|max| max.saturating_sub(counter.load(Ordering::Relaxed)));
+        let submit = |remaining: Option<usize>| {
+            let batches = remaining.map_or(batches_per_dispatch, |remaining| {
+                remaining.div_ceil(settings.batch_size).clamp(1, batches_per_dispatch)
+            });
+            submit_batches(&predictor, &templates, settings.batch_size, filter.as_ref(), batches, bloom.as_ref(), seed, &skipped)
+        };
+        let mut next = None;
+        // 每次调度的候选地址写入同一个Vec，复用它的容量
+        let mut candidates = Vec::new();
+        'search: while !stop.is_cancelled() && settings.max_attempts.is_none_or(|max| counter.load(Ordering::Relaxed) < max) {
+            let (positions, dispatch) = match next.take() {
+                Some(submitted) => submitted,
+                None => submit(remaining().map(|remaining| remaining + batch_offset)).map_err(|e| gpu_failed(e, batch_num, &counter))?,
+            };
+            // 剩余的 --max-attempts 在这次调度内就会用完时不再提交下一次调度
+            let covered = positions.len() * settings.batch_size - batch_offset;
+            let remaining_after = remaining().map(|remaining| remaining.saturating_sub(covered));
+            if remaining_after.is_none_or(|remaining| remaining > 0) {
+                next = Some(submit(remaining_after).map_err(|e| gpu_failed(e, batch_num, &counter))?);
+            }
+            dispatch.wait_into(&mut candidates).map_err(|e| gpu_failed(e, batch_num, &counter))?;
+            
+            // 一次调度的各个批次按顺序检查，检查点和布隆过滤器仍按批次记录
+            for (batch, &(batch_position, next_position)) in positions.iter().enumerate() {
+                batch_num += 1;
+                
+                // 从检查点恢复的第一个批次跳过已经检查过的地址，最后一个批次只统计到 --max-attempts 为止；
+                // 过滤后只有候选地址，尝试次数按候选在批次中的位置计算
+                let start = std::mem::take(&mut batch_offset);
+                let attempts_before = counter.load(Ordering::Relaxed);
+                let end = settings
+                    .max_attempts
+                    .map_or(settings.batch_size, |max| start.saturating_add(max.saturating_sub(attempts_before)))
+                    .min(settings.batch_size);
+                let mut checked = end;
+                let mut done = false;
+                let mut records = Vec::new();
                 
-                for address in results.iter() {
-                    if address.ends_with("eAce1") {
-                        let elapsed = start_time.elapsed();
-                        println!("\n✨ 找到目标地址!");
-                        println!("  Address: {}", address);
-                        println!("  尝试次数: {}", total_processed);
-                        println!("  用时: {}", format_duration(elapsed));
-                        println!("--------------------------------------------------------------------------------");
+                let batch_candidates = candidates.iter().filter(|candidate| candidate.batch == batch);
+                for candidate in batch_candidates.filter(|candidate| (start..end).contains(&(candidate.index as usize))) {
+                    if pattern.matches(&candidate.address) {
+                        let attempts = attempts_before + candidate.index as usize + 1 - start;
+                        let record = sink.report(candidate.template, candidate.salt(), &candidate.address, attempts)?;
+                        records.extend(record);
+                        if settings.max_matches.is_some_and(|max| sink.count() >= max) {
+                            checked = candidate.index as usize + 1;
+                            done = true;
+                            break;
+                        }
                     }
                 }
+                let count = checked - start;
+                let total = counter.fetch_add(count, Ordering::Relaxed) + count;
+                gpu_attempts.fetch_add(count, Ordering::Relaxed);
+                let elapsed = elapsed_offset + start_time.elapsed();
                 
-                let current_time = Instant::now();
-                let elapsed = current_time.duration_since(start_time);
+                // 批次检查完时记录下一个批次的位置，中途停止时记录本批次起点和批内偏移
+                {
+                    let mut checkpoint = checkpoint.lock().unwrap();
+                    if checked == settings.batch_size {
+                        checkpoint.rng_positions[0] = next_position;
+                        checkpoint.batch_offset = 0;
+                        // 只记录完整搜索过的批次
+                        if let Some(ref bloom) = bloom {
+                            bloom.lock().unwrap().insert(seed, 0, batch_position);
+                        }
+                    } else {
+                        checkpoint.rng_positions[0] = batch_position;
+                        checkpoint.batch_offset = checked;
+                    }
+                    checkpoint.attempts += count;
+                    checkpoint.elapsed_secs = checkpoint.elapsed_secs.max(elapsed.as_secs_f64());
+                    checkpoint.matches.extend(records);
+                    checkpoint.save_if_due(resume_path)?;
+                }
+                if let Some(ref bloom) = bloom {
+                    bloom.lock().unwrap().save_if_due()?;
+                }
                 
-                if current_time.duration_since(last_report_time).as_millis() >= 100 {
-                    let avg_tps = total_processed as f64 / elapsed.as_secs_f64();
-                    
-                    print!("\r已尝试: {} | 批次: {} | 平均TPS: {:.0} | 用时: {}     ", 
-                        total_processed, batch_num, avg_tps, format_duration(elapsed));
-                    io::stdout().flush().unwrap();
-                    
-                    last_report_time = current_time;
+                if let Some(tick) = reporter.tick(total) {
+                    progress!("\r已尝试: {} | 批次: {} | 平均TPS: {:.0} | 当前TPS: {:.0} | 用时: {}{}{}     ", 
+                        tick.count, batch_num, tick.avg_tps, tick.current_tps, format_duration(tick.elapsed),
+                        tick.eta(pattern.expected_attempts()), pattern.best(&checkpoint.lock().unwrap().matches));
+                }
+                
+                if done || settings.find_limit_reached(total, sink.count(), elapsed) {
+                    break 'search;
                 }
-            }
-            Err(e) => {
-                eprintln!("\n错误: GPU处理失败 - {}", e);
-                eprintln!("批次: {}, 已处理: {}", batch_num, total_processed);
-                return Err(Box::new(e));
             }
         }
+        Ok(())
+    };
+    
+    let searched = std::thread::scope(|s| {
+        if let Some(ref pool) = pool {
+            s.spawn(|| {
+                pool.scope(|ps| {
+                    for thread_index in 0..cpu_threads {
+                        let (counter, cpu_attempts, sink, stop) = (&counter, &cpu_attempts, &sink, &stop);
+                        let (checkpoint, bloom, skipped, templates) = (&checkpoint, &bloom, &skipped, &templates);
+                        let pattern = &pattern;
+                        
+                        ps.spawn(move |_| {
+                            let stream = thread_index + 1;
+                            let mut rng = seeded_rng(seed, stream);
+                            rng.set_word_pos(checkpoint.lock().unwrap().rng_positions[stream]);
+                            
+                            // 与cpu-parallel相同：每次search CHECK_INTERVAL个salt，命中的地址交给同一个出口
+                            while !stop.is_cancelled() {
+                                let mut flushed = 0;
+                                let mut records = Vec::new();
+                                let chunk_position = rng.get_word_pos();
+                                
+                                if bloom.as_ref().is_some_and(|bloom| bloom.lock().unwrap().contains(seed, stream, chunk_position)) {
+                                    rng.set_word_pos(chunk_position + CHECK_INTERVAL as u128 * RANDOM_SALT_WORDS);
+                                    skipped.fetch_add(1, Ordering::Relaxed);
+                                    continue;
+                                }
+                                
+                                let salts = std::iter::repeat_with(|| random_salt(&mut rng)).take(CHECK_INTERVAL);
+                                let searched = search_templates(
+                                    templates,
+                                    salts,
+                                    stop,
+                                    |address| pattern.matches(&address.to_string()),
+                                    |index, salt, address, count| -> Result<ControlFlow<()>, Create2Error> {
+                                        let total = counter.fetch_add(count - flushed, Ordering::Relaxed) + count - flushed;
+                                        cpu_attempts.fetch_add(count - flushed, Ordering::Relaxed);
+                                        flushed = count;
+                                        let record = sink.report(index, salt.to_string(), &address.to_string(), total).unwrap_or_else(|e| {
+                                            eprintln!("⚠️  {}", output::error_chain(&e));
+                                            None
+                                        });
+                                        let Some(record) = record else {
+                                            return Ok(ControlFlow::Break(()));
+                                        };
+                                        records.push(record);
+                                        if settings.find_limit_reached(total, sink.count(), elapsed_offset + start_time.elapsed()) {
+                                            stop.cancel();
+                                            return Ok(ControlFlow::Break(()));
+                                        }
+                                        Ok(ControlFlow::Continue(()))
+                                    },
+                                );
+                                // 随机salt都是32个字符，不会出错
+                                let searched = searched.unwrap_or_else(|e| {
+                                    eprintln!("⚠️  {}", output::error_chain(&e));
+                                    stop.cancel();
+                                    flushed
+                                });
+                                
+                                let total = counter.fetch_add(searched - flushed, Ordering::Relaxed) + searched - flushed;
+                                cpu_attempts.fetch_add(searched - flushed, Ordering::Relaxed);
+                                let elapsed = elapsed_offset + start_time.elapsed();
+                                sync_checkpoint(checkpoint, settings, stream, searched, rng.get_word_pos(), elapsed, records);
+                                
+                                // 只记录完整搜索过的批次，中途停止的批次下次仍会搜索
+                                if let Some(bloom) = bloom {
+                                    if searched == CHECK_INTERVAL {
+                                        bloom.lock().unwrap().insert(seed, stream, chunk_position);
+                                    }
+                                }
+                                
+                                if settings.find_limit_reached(total, sink.count(), elapsed) {
+                                    stop.cancel();
+                                }
+                            }
+                        });
+                    }
+                });
+            });
+        }
+        let searched = search_gpu();
+        // GPU出错或停止后CPU线程也在下一个salt前停下
+        stop.cancel();
+        searched
+    });
+    
+    let elapsed = elapsed_offset + start_time.elapsed();
+    let mut checkpoint = checkpoint.into_inner().unwrap();
+    if let Some(path) = resume_path {
+        checkpoint.elapsed_secs = elapsed.as_secs_f64();
+        checkpoint.save(path)?;
+    }
+    if let Some(bloom) = bloom {
+        bloom.into_inner().unwrap().save()?;
+        summary!("\n♻️  跳过了 {} 个已搜索过的批次", skipped.into_inner());
+    }
+    searched?;
+    
+    print_device_stats(&predictor);
+    print_buffer_pool_stats(&predictor);
+    // --hybrid：分别列出GPU和CPU本次运行的尝试次数和TPS
+    if cpu_threads > 0 {
+        let secs = start_time.elapsed().as_secs_f64();
+        let (gpu, cpu) = (gpu_attempts.into_inner(), cpu_attempts.into_inner());
+        summary!("\nGPU: 已尝试 {} 次, {:.0} TPS", gpu, gpu as f64 / secs);
+        summary!("CPU ({} 线程): 已尝试 {} 次, {:.0} TPS", cpu_threads, cpu, cpu as f64 / secs);
+    }
+    let found = checkpoint.matches.len();
+    summary!("\n🏁 搜索结束: 已尝试 {} 次, 找到 {} 个地址, 用时 {}",
+        checkpoint.attempts, found, format_duration(elapsed));
+    pattern.print_hits();
+    pattern.print_top(&checkpoint.matches, &output)?;
+    Ok(found)
+}
+
+// 跳过布隆过滤器中已有的批次（只推进批次种子，不调度GPU，也不计入尝试次数），把接下来的batches个批次
+// 放进同一次调度提交，不等待GPU；返回每个批次起点和下一个批次的随机数位置，以及已提交的调度
+#[allow(clippy::too_many_arguments)]
+fn submit_batches<'a>(
+    predictor: &'a Create2Predictor,
+    templates: &[Create2Template],
+    batch_size: usize,
+    filter: Option<&GpuFilter>,
+    batches: usize,
+    bloom: Option<&Mutex<SaltBloom>>,
+    seed: u64,
+    skipped: &AtomicUsize,
+) -> Result<(Vec<(u128, u128)>, PendingBatch<'a>), Create2Error> {
+    let mut positions = Vec::with_capacity(batches);
+    let mut seeds = Vec::with_capacity(batches);
+    while seeds.len() < batches {
+        let batch_position = predictor.rng_position().unwrap_or(0);
+        if bloom.is_some_and(|bloom| bloom.lock().unwrap().contains(seed, 0, batch_position)) {
+            predictor.skip_batch();
+            skipped.fetch_add(1, Ordering::Relaxed);
+            continue;
+        }
+        seeds.push(predictor.next_seed().unwrap_or(0));
+        positions.push((batch_position, predictor.rng_position().unwrap_or(0)));
     }
+    let dispatch = predictor.submit_search_batches(templates, batch_size, filter, &seeds)?;
+    Ok((positions, dispatch))
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let args: Vec<String> = std::env::args().collect();
+fn gpu_failed(e: Create2Error, batch_num: usize, counter: &AtomicUsize) -> Box<dyn std::error::Error> {
+    eprintln!("\n错误: GPU处理失败 - {}", e);
+    eprintln!("批次: {}, 已处理: {}", batch_num, counter.load(Ordering::Relaxed));
+    Box::new(e)
+}
+
+// --hybrid 时与GPU同时搜索的CPU线程池，0 表示使用rayon默认线程数（RAYON_NUM_THREADS或CPU核心数）
+fn hybrid_pool(settings: &Settings) -> Result<Option<ThreadPool>, rayon::ThreadPoolBuildError> {
+    if !settings.hybrid {
+        return Ok(None);
+    }
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(settings.threads.unwrap_or(0))
+        .build()
+        .map(Some)
+}
+
+// 每个CPU线程使用独立的ChaCha流，结果可按 (seed, 随机数流) 复现
+fn seeded_rng(seed: u64, stream: usize) -> ChaCha8Rng {
+    use rand::SeedableRng;
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    rng.set_stream(stream as u64);
+    rng
+}
+
+// 与搜索相同的salt生成和地址格式化，只是不做匹配；使用种子0的随机数流，不影响检查点
+fn calibrate_cpu_tps(templates: &[Create2Template], pool: &ThreadPool) -> f64 {
+    progress::calibrate(|| {
+        pool.install(|| {
+            (0..pool.current_num_threads())
+                .into_par_iter()
+                .map(|thread_index| {
+                    let mut rng = seeded_rng(0, thread_index);
+                    let cancel = CancellationToken::new();
+                    let salts = std::iter::repeat_with(|| random_salt(&mut rng)).take(CHECK_INTERVAL);
+                    search_templates(templates, salts, &cancel, |address| black_box(address.to_string()).is_empty(), |_, _, _, _| {
+                        Ok::<_, Create2Error>(ControlFlow::Continue(()))
+                    })
+                    .unwrap_or(0)
+                })
+                .sum()
+        })
+    })
+}
+
+// 把一个CPU线程新完成的尝试次数、随机数位置和找到的地址在同一把锁内合并进检查点
+fn sync_checkpoint(
+    checkpoint: &Mutex<Checkpoint>,
+    settings: &Settings,
+    stream: usize,
+    count: usize,
+    rng_position: u128,
+    elapsed: Duration,
+    records: Vec<MatchRecord>,
+) {
+    let mut checkpoint = checkpoint.lock().unwrap();
+    checkpoint.attempts += count;
+    checkpoint.rng_positions[stream] = rng_position;
+    checkpoint.elapsed_secs = checkpoint.elapsed_secs.max(elapsed.as_secs_f64());
+    checkpoint.matches.extend(records);
+    if let Err(e) = checkpoint.save_if_due(settings.resume.as_deref()) {
+        eprintln!("⚠️  {}", output::error_chain(&e));
+    }
+}
+
+// 有多块GPU时每块的计算量和TPS，批次按这个TPS分配
+fn print_device_stats(predictor: &Create2Predictor) {
+    let stats = predictor.device_stats();
+    if stats.len() > 1 {
+        for (i, device) in stats.iter().enumerate() {
+            summary!("GPU {} ({}): {} 个地址，{:.0} TPS", i, device.name, device.addresses, device.tps());
+        }
+    }
+}
+
+// 每块设备的管线上限和一个批次的调度形状，与TPS一起解读
+fn print_pipeline_stats(pipelines: &[PipelineStats]) {
+    for pipeline in pipelines {
+        summary!(
+            "GPU管线 ({}): {} 组 × 每组 {} 线程 × 每线程 {} 个地址 | 每组最多 {} 线程，SIMD宽度 {}，静态线程组内存 {} 字节",
+            pipeline.device,
+            pipeline.thread_groups,
+            pipeline.threads_per_group,
+            pipeline.addresses_per_thread,
+            pipeline.max_threads_per_group,
+            pipeline.thread_execution_width,
+            pipeline.static_threadgroup_memory
+        );
+    }
+}
+
+fn print_buffer_pool_stats(predictor: &Create2Predictor) {
+    if let Some((allocated, reused)) = predictor.buffer_pool_stats() {
+        trace!("Buffer pool: {} buffers allocated, {} reused", allocated, reused);
+    }
+}
+
+fn print_init_code_hash(settings: &Settings) {
+    if let Some(ref hash) = settings.init_code_hash {
+        status!("Init code hash: {}（通用CREATE2，忽略实现合约）", hash);
+    } else if settings.scheme != Scheme::Minimal {
+        status!("代理方案: {}", settings.scheme);
+        if let Some(ref path) = settings.proxy_bytecode {
+            status!("Creation code: {}", path.display());
+        }
+        if let Some(ref owner) = settings.account_owner {
+            status!("账户owner: {}", owner);
+        }
+        if let Some(ref args) = settings.proxy_args {
+            status!("代理参数: {}", args);
+        }
+    }
+}
+
+fn print_find_limits(settings: &Settings) {
+    if let Some(max) = settings.max_attempts {
+        status!("最大尝试次数: {}", max);
+    }
+    if let Some(max) = settings.max_time {
+        status!("最长搜索时间: {}秒", max);
+    }
+    if let Some(max) = settings.max_matches {
+        status!("最多找到地址数: {}", max);
+    }
+}
+
+// 按批处理大小分批读取salt文件交给GPU计算，结果按输入顺序流式输出
+fn run_predict(settings: &Settings) -> Result<(), Box<dyn std::error::Error>> {
+    let path = settings.salts_file.as_deref().ok_or("predict requires --salts-file")?;
+    let mut reader = SaltReader::open(path, settings.salt_policy)?;
+    let template = settings.template()?;
+    let output = Output::open(settings.output.as_deref())?;
+    
+    status!("📄 从 {} 读取salt", path.display());
+    status!("实现合约: {}", settings.implementation);
+    status!("部署者: {}", settings.deployer);
+    print_init_code_hash(settings);
+    status!("GPU批处理大小: {}", settings.batch_size);
+    status!("--------------------------------------------------------------------------------");
+    
+    let predictor = Create2Predictor::new(true, settings.batch_size, 0, settings.command_queues)?;
+    
+    if !predictor.is_gpu_enabled() {
+        eprintln!("❌ GPU不可用，请检查Metal支持");
+        return Err("GPU initialization failed".into());
+    }
+    
+    predictor.use_tuned(settings.tuned.as_ref());
+    predictor.set_addresses_per_thread(settings.addresses_per_thread)?;
+    
+    let start_time = Instant::now();
+    let mut total = 0;
     
-    if args.len() > 1 {
-        match args[1].as_str() {
-            "test" => run_single_test(),
-            "find" => find_address(),
-            _ => run_benchmark(),
+    loop {
+        let salts = reader.read_batch(settings.batch_size)?;
+        if salts.is_empty() {
+            break;
+        }
+        total += salts.len();
+        
+        let salt_bytes: Vec<[u8; 32]> = salts.iter().map(|(_, bytes)| *bytes).collect();
+        let addresses = predictor.predict_batch_with_salt(&template, &salt_bytes)?;
+        for ((salt, _), address) in salts.into_iter().zip(addresses) {
+            output::emit_prediction(&output, &Prediction { salt, address })?;
+        }
+    }
+    print_device_stats(&predictor);
+    print_buffer_pool_stats(&predictor);
+    
+    status!("--------------------------------------------------------------------------------");
+    status!("✅ 共计算 {} 个地址, 用时 {}", total, format_duration(start_time.elapsed()));
+    Ok(())
+}
+
+// tune：对每个批处理大小、每组线程数和每线程地址数的组合跑一段benchmark负载（GPU生成salt并拷回整个批次），
+// 把TPS最高的组合写入 --tune-cache，之后的运行自动加载；设备放不下的批处理大小跳过
+fn run_tune(settings: &Settings) -> Result<(), Box<dyn std::error::Error>> {
+    let template = settings.template()?;
+    let path = settings.tune_cache.as_deref().ok_or("tune needs --tune-cache")?;
+    
+    status!(
+        "🔧 GPU调优: 批处理大小 {:?} × 每组线程数 {:?} × 每线程地址数 {:?}",
+        TUNE_BATCH_SIZES, TUNE_THREADS_PER_GROUP, TUNE_ADDRESSES_PER_THREAD
+    );
+    status!("--------------------------------------------------------------------------------");
+    
+    let mut best: Option<TunedParams> = None;
+    for &batch_size in TUNE_BATCH_SIZES {
+        let predictor = Create2Predictor::new(true, batch_size, 0, settings.command_queues)?;
+        let (Some(device), Some(max_threads)) = (predictor.device_name(), predictor.max_threads_per_group()) else {
+            status!("跳过批处理大小 {}: GPU不可用", batch_size);
+            continue;
+        };
+        for &threads in TUNE_THREADS_PER_GROUP.iter().filter(|&&threads| threads <= max_threads) {
+            predictor.set_threads_per_group(Some(threads));
+            for &addresses_per_thread in TUNE_ADDRESSES_PER_THREAD {
+                predictor.set_addresses_per_thread(addresses_per_thread)?;
+                // 第一个批次包含管线和缓冲区的预热，不计入
+                predictor.predict_batch_address(&template, batch_size)?;
+                let tps = progress::calibrate(|| predictor.predict_batch_address(&template, batch_size).map_or(0, |_| batch_size));
+                status!(
+                    "批处理大小 {:>8} | 每组线程数 {:>5} | 每线程地址数 {:>3} | TPS {:.0}",
+                    batch_size, threads, addresses_per_thread, tps
+                );
+                if best.as_ref().is_none_or(|best| tps > best.tps) {
+                    best = Some(TunedParams {
+                        device: device.clone(),
+                        batch_size,
+                        threads_per_group: threads,
+                        addresses_per_thread,
+                        tps,
+                    });
+                }
+            }
         }
+    }
+    let best = best.ok_or("GPU initialization failed")?;
+    
+    status!("--------------------------------------------------------------------------------");
+    summary!("🏁 最优组合: {}", best.describe());
+    best.save(path)?;
+    status!("已写入 {}，之后的运行自动加载（--batch-size、--profile 和 --addresses-per-thread 优先）", path.display());
+    Ok(())
+}
+
+// 检查运行参数并打印各模式会做什么：会初始化Metal设备和计算管线，但不提交任何批次
+fn run_validate(settings: &Settings) -> Result<(), Box<dyn std::error::Error>> {
+    let mut validation = Validation::new();
+    let deployers = settings.deployers.iter().map(|deployer| ("find模式部署者", deployer));
+    let implementations = settings.implementations.iter().map(|implementation| ("find模式实现合约", implementation));
+    for (label, address) in [("实现合约", &settings.implementation), ("部署者", &settings.deployer)]
+        .into_iter()
+        .chain(implementations)
+        .chain(deployers)
+    {
+        validation.check(label, address.parse::<Address>().map(|address| address.to_string()));
+    }
+    validation.check("GPU后端", settings.check_backend(BACKENDS).map(|_| settings.backend.to_string()));
+    let command_queues = if (1..=MAX_COMMAND_QUEUES).contains(&settings.command_queues) {
+        Ok(format!("每块GPU {} 个", settings.command_queues))
     } else {
-        run_benchmark()
+        Err(Create2Error::Config(format!("--command-queues 必须在1到{}之间，当前为 {}", MAX_COMMAND_QUEUES, settings.command_queues)))
+    };
+    validation.check("命令队列数", command_queues);
+    let predictor = Create2Predictor::new(true, settings.batch_size, 0, settings.command_queues.clamp(1, MAX_COMMAND_QUEUES))?;
+    let device = predictor
+        .device_name()
+        .map(|name| format!("{}，GPU批处理大小 {}", name, settings.batch_size))
+        .ok_or_else(|| Create2Error::GpuInit("Metal不可用，已回退到CPU；bench、find和predict需要GPU".to_string()));
+    validation.check("Metal设备", device);
+    let addresses_per_thread = predictor
+        .set_addresses_per_thread(settings.addresses_per_thread)
+        .map(|_| settings.addresses_per_thread.to_string());
+    validation.check("每线程地址数", addresses_per_thread);
+    validation.check("benchmark模式", Ok(format!("计算 {} 个随机salt的地址", TOTAL_OPERATIONS)));
+    validation.check_common(settings, AddressKind::Evm);
+    let cpu_threads = hybrid_pool(settings)?.map_or(0, |pool| pool.current_num_threads());
+    if cpu_threads > 0 {
+        validation.check("CPU协同搜索", Ok(format!("find模式同时使用 {} 个CPU线程", cpu_threads)));
+    }
+    if let Some(checkpoint) = validation.check_checkpoint(settings, 1 + cpu_threads) {
+        validation.check("检查点批处理大小", checkpoint.check_batch_size(settings.batch_size).map(|_| settings.batch_size.to_string()));
+        validation.check(
+            "检查点每线程地址数",
+            checkpoint
+                .check_addresses_per_thread(settings.addresses_per_thread)
+                .map(|_| settings.addresses_per_thread.to_string()),
+        );
+    }
+    print_find_limits(settings);
+    validation.finish()?;
+    Ok(())
+}
+
+fn main() {
+    if let Err(e) = run() {
+        output::report_error(e.as_ref());
+        std::process::exit(1);
+    }
+}
+
+fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let defaults = Settings {
+        implementation: IMPLEMENTATION.to_string(),
+        implementations: Vec::new(),
+        deployer: DEPLOYER.to_string(),
+        deployers: Vec::new(),
+        chain: None,
+        init_code_hash: None,
+        scheme: Scheme::Minimal,
+        proxy_args: None,
+        proxy_bytecode: None,
+        account_owner: None,
+        batch_size: GPU_BATCH_SIZE,
+        backend: GpuBackend::Auto,
+        threads: None,
+        hybrid: false,
+        batches_per_dispatch: 1,
+        addresses_per_thread: DEFAULT_ADDRESSES_PER_THREAD as usize,
+        command_queues: 1,
+        pattern: DEFAULT_PATTERN.to_string(),
+        position: PatternPosition::Suffix,
+        patterns: Vec::new(),
+        suffix: None,
+        ignore_case: false,
+        regex: None,
+        mask: None,
+        objective: None,
+        vanity: None,
+        score: None,
+        top: 10,
+        output: None,
+        results: None,
+        notify_url: None,
+        json: false,
+        seed: None,
+        max_attempts: None,
+        max_time: None,
+        max_matches: None,
+        first_match: false,
+        bench_difficulty: DEFAULT_BENCH_DIFFICULTY,
+        nonce_start: 0,
+        nonce_count: DEFAULT_NONCE_COUNT,
+        verbosity: Verbosity::Normal,
+        salts_file: None,
+        salt_policy: SaltPolicy::Pad,
+        resume: None,
+        bloom: None,
+        bloom_capacity: DEFAULT_BLOOM_CAPACITY,
+        bloom_fp_rate: DEFAULT_BLOOM_FP_RATE,
+        tune_cache: Some(PathBuf::from(DEFAULT_TUNE_CACHE)),
+        tuned: None,
+        profile: None,
+        progress_interval: PROGRESS_INTERVAL,
+        progress_every: Duration::from_millis(100),
+    };
+    let args = cli::parse_args(std::env::args())?;
+    if args.help {
+        cli::print_usage(env!("CARGO_BIN_NAME"));
+        return Ok(());
+    }
+    let mut settings = config::load_settings(args.config_path, args.overrides, defaults)?;
+    let mut command = args.command;
+    if prompt::interactive() {
+        command = prompt::ask(&mut settings, check_address)?;
+    }
+    output::set_json_mode(settings.json);
+    output::set_first_match_mode(settings.first_match);
+    output::set_verbosity(settings.verbosity);
+    verbose!("运行参数: {:?}", settings);
+    
+    // validate把 --chain 和 --backend 的问题和其他检查一起报告
+    if command != Command::Validate {
+        settings.check_chain(AddressKind::Evm)?;
+        settings.check_backend(BACKENDS)?;
+    }
+
+    match command {
+        Command::Test => run_single_test(),
+        Command::Find => {
+            // 停止时一个地址都没找到，以非零退出码结束；--first-match 用2与出错的1区分
+            if find_address(&settings)? == 0 {
+                std::process::exit(if settings.first_match { 2 } else { 1 });
+            }
+            Ok(())
+        }
+        Command::Predict => run_predict(&settings),
+        Command::Nonces => nonces::run_nonce_sweep(&settings),
+        Command::Compare => compare::run_scheme_comparison(&settings),
+        Command::Validate => run_validate(&settings),
+        Command::Tune => run_tune(&settings),
+        Command::Completions(shell) => {
+            cli::print_completions(shell, env!("CARGO_BIN_NAME"));
+            Ok(())
+        }
+        Command::Bench => run_benchmark(&settings),
+        Command::BenchFind => run_match_benchmark(&settings),
     }
 }
\ No newline at end of file
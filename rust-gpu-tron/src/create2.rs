@@ -1,31 +1,50 @@
-use hex;
+use crate::gpu_compute::{philox_salt, Candidate, GpuFilter, PendingBatch, MAX_COMMAND_QUEUES};
+use crate::output::PipelineStats;
+use crate::tune::TunedParams;
+use create2_core::salt::IntoSalt;
+use create2_core::{Address, Create2Error, Create2Template, ProxyScheme, TRON_CREATE2_PREFIX};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use rayon::prelude::*;
 use sha2::{Digest, Sha256};
-use sha3::Keccak256;
-use bs58;
-use std::fmt;
+use std::sync::{Arc, Mutex};
 
+// Tron address version byte, the same on mainnet, Nile and Shasta
+const TRON_ADDRESS_PREFIX: u8 = 0x41;
+
+// Salts per rayon task in the CPU fallback of predict_batch_with_salt
+const CPU_CHUNK_SIZE: usize = 4096;
+
+// Cheap to clone: clones share one Metal device, pipeline and buffer pool, and every
+// dispatch gets its own command buffer, so threads can submit batches concurrently.
+// Without Metal (VMs, CI) every batch runs on the rayon pool instead, with the same
+// batch seeds and Philox salts, so seeds, checkpoints and results match a GPU run
+#[derive(Clone)]
 pub struct Create2Predictor {
     #[allow(dead_code)]
     use_gpu: bool,
-    gpu_accelerator: Option<crate::gpu_compute::GpuAccelerator>,
-}
-
-#[derive(Debug)]
-pub struct Create2Error(String);
-
-impl fmt::Display for Create2Error {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.0)
-    }
+    gpu_accelerator: Option<Arc<crate::gpu_compute::GpuAccelerator>>,
+    // Batch seed generator of the CPU fallback, seeded like the GPU's
+    cpu_rng: Arc<Mutex<ChaCha8Rng>>,
 }
 
-impl std::error::Error for Create2Error {}
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Create2Predictor>();
+};
 
 impl Create2Predictor {
-    pub fn new(use_gpu: bool, batch_size: usize) -> Result<Self, Create2Error> {
+    // command_queues is --command-queues; out of range is a usage error, not a reason to fall back to the CPU
+    pub fn new(use_gpu: bool, batch_size: usize, seed: u64, command_queues: usize) -> Result<Self, Create2Error> {
+        if command_queues == 0 || command_queues > MAX_COMMAND_QUEUES {
+            return Err(Create2Error::GpuInit(format!(
+                "Command queues must be between 1 and {}, got {}",
+                MAX_COMMAND_QUEUES, command_queues
+            )));
+        }
         let gpu_accelerator = if use_gpu {
-            match crate::gpu_compute::GpuAccelerator::new(batch_size) {
-                Ok(accel) => Some(accel),
+            match crate::gpu_compute::GpuAccelerator::new(batch_size, seed, command_queues) {
+                Ok(accel) => Some(Arc::new(accel)),
                 Err(e) => {
                     eprintln!("Failed to initialize GPU: {}", e);
                     None
@@ -38,134 +57,331 @@ impl Create2Predictor {
         Ok(Self {
             use_gpu,
             gpu_accelerator,
+            cpu_rng: Arc::new(Mutex::new(ChaCha8Rng::seed_from_u64(seed))),
         })
     }
 
     pub fn is_gpu_enabled(&self) -> bool {
         self.gpu_accelerator.is_some()
     }
+    
+    pub fn device_name(&self) -> Option<String> {
+        self.gpu_accelerator.as_ref().map(|gpu| gpu.device_name().to_string())
+    }
+    
+    pub fn buffer_pool_stats(&self) -> Option<(usize, usize)> {
+        self.gpu_accelerator.as_ref().map(|gpu| gpu.buffer_pool_stats())
+    }
+    
+    pub fn device_stats(&self) -> Vec<crate::gpu_compute::DeviceStats> {
+        self.gpu_accelerator.as_ref().map_or_else(Vec::new, |gpu| gpu.device_stats())
+    }
+    
+    // Pipeline limits and dispatch geometry of a batch of batch_size addresses on every device; empty on the CPU
+    pub fn pipeline_stats(&self, batch_size: usize) -> Vec<PipelineStats> {
+        self.gpu_accelerator.as_ref().map_or_else(Vec::new, |gpu| gpu.pipeline_stats(batch_size))
+    }
+    
+    pub fn max_threads_per_group(&self) -> Option<usize> {
+        self.gpu_accelerator.as_ref().map(|gpu| gpu.max_threads_per_group())
+    }
+    
+    pub fn set_threads_per_group(&self, threads: Option<usize>) {
+        if let Some(ref gpu) = self.gpu_accelerator {
+            gpu.set_threads_per_group(threads);
+        }
+    }
+    
+    // Coarsening factor of the kernel; the CPU fallback has none, so any value is accepted there
+    pub fn set_addresses_per_thread(&self, addresses: usize) -> Result<(), Create2Error> {
+        match self.gpu_accelerator {
+            Some(ref gpu) => gpu.set_addresses_per_thread(addresses),
+            None => Ok(()),
+        }
+    }
+    
+    // Uses the thread group size `tune` measured; a cache measured on other devices is ignored
+    pub fn use_tuned(&self, tuned: Option<&TunedParams>) {
+        let (Some(gpu), Some(tuned)) = (self.gpu_accelerator.as_ref(), tuned) else {
+            return;
+        };
+        if tuned.device != gpu.device_name() {
+            eprintln!("⚠️  Tune cache was measured on {}, not {}; rerun tune", tuned.device, gpu.device_name());
+            return;
+        }
+        gpu.set_threads_per_group(Some(tuned.threads_per_group));
+        status!("Tuned threads per group: {}", tuned.threads_per_group);
+    }
+    
+    pub fn rng_position(&self) -> u128 {
+        match self.gpu_accelerator {
+            Some(ref gpu) => gpu.rng_position(),
+            None => self.cpu_rng.lock().unwrap().get_word_pos(),
+        }
+    }
+    
+    pub fn set_rng_position(&self, position: u128) {
+        match self.gpu_accelerator {
+            Some(ref gpu) => gpu.set_rng_position(position),
+            None => self.cpu_rng.lock().unwrap().set_word_pos(position),
+        }
+    }
+    
+    // Draws the next batch seed without dispatching it, for batches the caller already searched;
+    // GPU and CPU fallback both go through next_seed, so their seed positions stay in step
+    pub fn skip_batch(&self) {
+        self.next_seed();
+    }
+    
+    pub fn next_seed(&self) -> u32 {
+        match self.gpu_accelerator {
+            Some(ref gpu) => gpu.next_seed(),
+            None => self.cpu_rng.lock().unwrap().gen::<u32>(),
+        }
+    }
 
     pub fn predict_batch_address(
         &self,
-        implementation: &str,
-        deployer: &str,
+        template: &Create2Template,
         batch_size: usize,
     ) -> Result<Vec<String>, Create2Error> {
+        let mut results = Vec::new();
+        self.predict_batch_address_into(template, batch_size, &mut results)?;
+        Ok(results.into_iter().map(|(addr, _)| addr).collect())
+    }
+
+    // predict_batch_address into a vector the caller keeps between batches: on the GPU its Strings
+    // are overwritten in place, so a run of equal-sized batches stops allocating after the first
+    pub fn predict_batch_address_into(
+        &self,
+        template: &Create2Template,
+        batch_size: usize,
+        results: &mut Vec<(String, u32)>,
+    ) -> Result<(), Create2Error> {
         if let Some(ref gpu) = self.gpu_accelerator {
-            gpu.process_batch_gpu_random(implementation, deployer, batch_size)
-                .map(|results| results.into_iter().map(|(addr, _)| addr).collect())
-                .map_err(|e| Create2Error(e))
+            gpu.process_batch_gpu_random_into(template, batch_size, results)
         } else {
-            Err(Create2Error("GPU not available".to_string()))
+            let seed = self.next_seed();
+            let candidates = search_batches_cpu(std::slice::from_ref(template), batch_size, None, &[seed])?;
+            results.clear();
+            results.extend(candidates.into_iter().map(|candidate| (candidate.address, candidate.index)));
+            Ok(())
         }
     }
 
-    pub fn predict_batch_with_salt(
+    // One find batch over every template; with a filter only the candidates the kernel kept come back, in batch order
+    pub fn search_batch(
         &self,
-        implementation: &str,
-        deployer: &str,
-        salts: &[String],
-    ) -> Result<Vec<String>, Create2Error> {
+        templates: &[Create2Template],
+        batch_size: usize,
+        filter: Option<&GpuFilter>,
+    ) -> Result<Vec<Candidate>, Create2Error> {
         if let Some(ref gpu) = self.gpu_accelerator {
-            gpu.process_batch_with_salt(implementation, deployer, salts)
-                .map(|results| results.into_iter().map(|(addr, _)| addr).collect())
-                .map_err(|e| Create2Error(e))
+            gpu.process_batch_filtered(templates, batch_size, filter)
         } else {
-            // CPU fallback for single salt verification
-            let mut results = Vec::new();
-            for salt in salts {
-                let address = self.predict_address_cpu(implementation, deployer, salt)?;
-                results.push(address);
-            }
-            Ok(results)
+            let seed = self.next_seed();
+            search_batches_cpu(templates, batch_size, filter, &[seed])
         }
     }
 
-    fn predict_address_cpu(
+    // One find batch per seed from next_seed, all in one dispatch that returns once it is committed:
+    // the kernel loops over the batches, the caller checks the previous dispatch while the GPU runs,
+    // then wait()s for this one or awaits it as a future. Candidates are tagged with their batch.
+    // The CPU fallback computes the batches before returning and hands back a batch that is already done
+    pub fn submit_search_batches(
+        &self,
+        templates: &[Create2Template],
+        batch_size: usize,
+        filter: Option<&GpuFilter>,
+        seeds: &[u32],
+    ) -> Result<PendingBatch<'_>, Create2Error> {
+        if let Some(ref gpu) = self.gpu_accelerator {
+            gpu.submit_batches_filtered(templates, batch_size, filter, seeds)
+        } else {
+            search_batches_cpu(templates, batch_size, filter, seeds).map(PendingBatch::ready)
+        }
+    }
+
+    pub fn predict_batch_with_salt<S: IntoSalt>(
+        &self,
+        template: &Create2Template,
+        salts: &[S],
+    ) -> Result<Vec<String>, Create2Error> {
+        let mut results = Vec::new();
+        self.predict_batch_with_salt_into(template, salts, &mut results)?;
+        Ok(results.into_iter().map(|(addr, _)| addr).collect())
+    }
+
+    // predict_batch_with_salt into a vector the caller keeps between batches, as predict_batch_address_into
+    pub fn predict_batch_with_salt_into<S: IntoSalt>(
         &self,
-        implementation: &str,
-        deployer: &str,
-        salt: &str,
-    ) -> Result<String, Create2Error> {
-        // Decode Tron addresses from Base58
-        let impl_bytes = tron_address_to_hex(implementation)?;
-        let depl_bytes = tron_address_to_hex(deployer)?;
-        
-        // Build bytecode
-        let mut bytecode = Vec::new();
-        
-        // Add PREFIX
-        bytecode.extend_from_slice(&hex::decode("3d602d80600a3d3981f3363d3d373d3d3d363d73").unwrap());
-        
-        // Add implementation
-        bytecode.extend_from_slice(&impl_bytes);
-        
-        // Add TRON_SUFFIX
-        bytecode.extend_from_slice(&hex::decode("5af43d82803e903d91602b57fd5bf341").unwrap());
-        
-        // Add deployer
-        bytecode.extend_from_slice(&depl_bytes);
-        
-        // Add salt (padded to 32 bytes)
-        let salt_bytes = salt.as_bytes();
-        let mut salt_padded = vec![0u8; 32];
-        salt_padded[..salt_bytes.len().min(32)].copy_from_slice(&salt_bytes[..salt_bytes.len().min(32)]);
-        bytecode.extend_from_slice(&salt_padded);
-        
-        // First Keccak256 hash
-        let mut hasher = Keccak256::new();
-        hasher.update(&bytecode[..55]);
-        let first_hash = hasher.finalize();
-        
-        // Second part
-        let mut second_part = Vec::new();
-        second_part.extend_from_slice(&bytecode[55..]);
-        second_part.extend_from_slice(&first_hash);
-        
-        // Second Keccak256 hash
-        let mut hasher = Keccak256::new();
-        hasher.update(&second_part);
-        let second_hash = hasher.finalize();
-        
-        // Take last 20 bytes as address
-        let address_bytes = &second_hash[12..];
-        
-        // Convert to Tron address
-        hex_to_tron_address(address_bytes)
+        template: &Create2Template,
+        salts: &[S],
+        results: &mut Vec<(String, u32)>,
+    ) -> Result<(), Create2Error> {
+        if let Some(ref gpu) = self.gpu_accelerator {
+            gpu.process_batch_with_salt_into(template, salts, results)
+        } else {
+            // CPU fallback, chunked over the rayon pool; hashes into fixed buffers, only the
+            // Base58 output strings are allocated per salt
+            let salts_padded = salts.iter().map(IntoSalt::to_salt).collect::<Result<Vec<_>, _>>()?;
+            let mut addresses = vec![[0u8; 20]; salts.len()];
+            salts_padded
+                .par_chunks(CPU_CHUNK_SIZE)
+                .zip(addresses.par_chunks_mut(CPU_CHUNK_SIZE))
+                .for_each(|(salts, addresses)| template.predict_batch_into(salts, addresses));
+            let addresses = addresses.par_iter().map(|address| hex_to_tron_address(address)).collect::<Result<Vec<_>, _>>()?;
+            results.clear();
+            results.extend(addresses.into_iter().zip(0..));
+            Ok(())
+        }
+    }
+}
+
+// The GPU random search on the rayon pool: the Philox salts the kernel would generate for these
+// seeds, checked against the filter like the kernel does, in (batch, index, template) order
+fn search_batches_cpu(
+    templates: &[Create2Template],
+    batch_size: usize,
+    filter: Option<&GpuFilter>,
+    seeds: &[u32],
+) -> Result<Vec<Candidate>, Create2Error> {
+    let mut candidates = Vec::new();
+    for (batch, &seed) in seeds.iter().enumerate() {
+        let found = (0..batch_size as u32)
+            .into_par_iter()
+            .map(|index| {
+                let salt = philox_salt(seed, index);
+                let mut found = Vec::new();
+                for (template_index, template) in templates.iter().enumerate() {
+                    let address = hex_to_tron_address(&template.predict_bytes(&salt))?;
+                    if filter.is_none_or(|filter| filter.matches(&address)) {
+                        found.push(Candidate { index, batch, seed, template: template_index, address, salt });
+                    }
+                }
+                Ok(found)
+            })
+            .collect::<Result<Vec<_>, Create2Error>>()?;
+        candidates.extend(found.into_iter().flatten());
+    }
+    Ok(candidates)
+}
+
+// Decode Tron addresses from Base58 once per run; same CREATE2 hash as EVM, with Tron's
+// TRON_CREATE2_PREFIX (0x41) in place of the 0xff byte. With an init code hash the implementation and scheme are ignored
+pub fn tron_template(
+    implementation: &str,
+    deployer: &str,
+    init_code_hash: Option<[u8; 32]>,
+    scheme: &ProxyScheme,
+) -> Result<Create2Template, Create2Error> {
+    let depl_bytes = tron_address_to_hex(deployer)?;
+    if let Some(hash) = init_code_hash {
+        return Ok(Create2Template::with_init_code_hash(TRON_CREATE2_PREFIX, Address::new(depl_bytes), hash));
     }
+    let impl_bytes = tron_address_to_hex(implementation)?;
+    Create2Template::with_scheme(
+        TRON_CREATE2_PREFIX,
+        scheme,
+        Address::new(impl_bytes),
+        Address::new(depl_bytes),
+    )
 }
 
 // Convert Tron Base58 address to hex bytes
-fn tron_address_to_hex(base58_addr: &str) -> Result<Vec<u8>, Create2Error> {
+fn tron_address_to_hex(base58_addr: &str) -> Result<[u8; 20], Create2Error> {
     let decoded = bs58::decode(base58_addr)
         .into_vec()
-        .map_err(|e| Create2Error(format!("Invalid Base58 address: {}", e)))?;
+        .map_err(|e| Create2Error::InvalidAddress(format!("{} (invalid Base58: {})", base58_addr, e)))?;
     
     if decoded.len() < 21 {
-        return Err(Create2Error("Invalid Tron address length".to_string()));
+        return Err(Create2Error::InvalidAddress(format!("{} (too short for a Tron address)", base58_addr)));
     }
     
     // Skip the first byte (0x41 for mainnet) and checksum (last 4 bytes)
-    Ok(decoded[1..21].to_vec())
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&decoded[1..21]);
+    Ok(address)
+}
+
+// Full check for the validate command: mainnet prefix, length and double-SHA256 checksum
+pub fn check_address(base58_addr: &str) -> Result<(), Create2Error> {
+    let decoded = bs58::decode(base58_addr)
+        .into_vec()
+        .map_err(|e| Create2Error::InvalidAddress(format!("{} (invalid Base58: {})", base58_addr, e)))?;
+
+    if decoded.len() != 25 || decoded[0] != TRON_ADDRESS_PREFIX {
+        return Err(Create2Error::InvalidAddress(format!("{} (not a Tron mainnet address)", base58_addr)));
+    }
+    let checksum = Sha256::digest(Sha256::digest(&decoded[..21]));
+    if decoded[21..] != checksum[..4] {
+        return Err(Create2Error::InvalidAddress(format!("{} (checksum mismatch)", base58_addr)));
+    }
+    Ok(())
 }
 
 // Convert hex bytes to Tron Base58 address
 fn hex_to_tron_address(address_bytes: &[u8]) -> Result<String, Create2Error> {
     if address_bytes.len() != 20 {
-        return Err(Create2Error("Invalid address length".to_string()));
+        return Err(Create2Error::InvalidAddress(format!("expected 20 address bytes, got {}", address_bytes.len())));
     }
     
     // Add Tron mainnet prefix (0x41)
-    let mut tron_bytes = vec![0x41];
+    let mut tron_bytes = vec![TRON_ADDRESS_PREFIX];
     tron_bytes.extend_from_slice(address_bytes);
     
     // Calculate checksum using double SHA256
     let hash1 = Sha256::digest(&tron_bytes);
-    let hash2 = Sha256::digest(&hash1);
+    let hash2 = Sha256::digest(hash1);
     
     // Add first 4 bytes of second hash as checksum
     tron_bytes.extend_from_slice(&hash2[..4]);
     
     // Encode to Base58
     Ok(bs58::encode(tron_bytes).into_string())
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gpu_compute::GpuAccelerator;
+    use create2_core::test_vectors::{Create2Vector, CREATE2_VECTORS};
+    
+    const RANDOM_BATCH: usize = 256;
+    
+    // None on a machine without a Metal device, where the tests below pass without checking anything
+    fn accelerator() -> Option<GpuAccelerator> {
+        GpuAccelerator::new(RANDOM_BATCH, 0, 1)
+            .inspect_err(|e| eprintln!("skipping the Metal kernel tests: {}", e))
+            .ok()
+    }
+    
+    // The kernel hashes with TRON_CREATE2_PREFIX and returns Base58 addresses
+    fn tron_vectors() -> impl Iterator<Item = &'static Create2Vector> {
+        CREATE2_VECTORS.iter().filter(|vector| vector.prefix == TRON_CREATE2_PREFIX)
+    }
+    
+    #[test]
+    fn salts_kernel_matches_test_vectors() {
+        let Some(gpu) = accelerator() else { return };
+        for vector in tron_vectors() {
+            let results = gpu.process_batch_with_salt(&vector.template(), &[vector.salt()]).unwrap();
+            let expected = hex_to_tron_address(vector.expected().as_bytes()).unwrap();
+            assert_eq!(results[0].0, expected, "{}", vector.name);
+        }
+    }
+    
+    #[test]
+    fn random_kernel_matches_cpu() {
+        let Some(gpu) = accelerator() else { return };
+        for vector in tron_vectors() {
+            let template = vector.template();
+            let candidates = gpu.process_batch_filtered(std::slice::from_ref(&template), RANDOM_BATCH, None).unwrap();
+            assert_eq!(candidates.len(), RANDOM_BATCH, "{}", vector.name);
+            for candidate in candidates {
+                assert_eq!(candidate.salt, philox_salt(candidate.seed, candidate.index), "{}", vector.name);
+                let expected = hex_to_tron_address(&template.predict_bytes(&candidate.salt)).unwrap();
+                assert_eq!(candidate.address, expected, "{}, salt {}", vector.name, candidate.salt());
+            }
+        }
+    }
+}
@@ -214,7 +214,7 @@ constant uchar BASE58_ALPHABET[58] = {
     'a','b','c','d','e','f','g','h','i','j','k','m','n','o','p','q','r','s','t','u','v','w','x','y','z'
 };
 
-uint32_t base58_encode(const thread uchar* input, uint32_t input_len, device uchar* output) {
+uint32_t base58_encode(const thread uchar* input, uint32_t input_len, thread uchar* output) {
     uchar temp[50] = {0};
     uint32_t temp_len = 0;
     
@@ -257,18 +257,6 @@ uint32_t base58_encode(const thread uchar* input, uint32_t input_len, device uch
 }
 
 // ==================== Utility Functions ====================
-inline uchar hex_to_value(uchar c) {
-    return (c <= '9') ? (c - '0') : ((c & 0xDF) - 'A' + 10);
-}
-
-inline void hex_decode_device(const device uchar* hex, thread uchar* bytes, uint32_t len) {
-    #pragma unroll 4
-    for (uint32_t i = 0; i < len; i++) {
-        uint32_t idx = i * 2;
-        bytes[i] = (hex_to_value(hex[idx]) << 4) | hex_to_value(hex[idx + 1]);
-    }
-}
-
 inline void hex_encode(const thread uchar* bytes, thread uchar* hex, uint32_t len) {
     #pragma unroll 4
     for (uint32_t i = 0; i < len; i++) {
@@ -282,82 +270,94 @@ inline void hex_encode(const thread uchar* bytes, thread uchar* hex, uint32_t le
 }
 
 // ==================== Random Number Generation ====================
-struct PCGState {
-    uint64_t state;
-    uint64_t inc;
-};
-
-uint32_t pcg32_random(thread PCGState* rng) {
-    uint64_t oldstate = rng->state;
-    rng->state = oldstate * 6364136223846793005ULL + rng->inc;
-    uint32_t xorshifted = ((oldstate >> 18u) ^ oldstate) >> 27u;
-    uint32_t rot = oldstate >> 59u;
-    return (xorshifted >> rot) | (xorshifted << ((-rot) & 31));
-}
+// Philox4x32-10 (Salmon et al., "Parallel Random Numbers: As Easy as 1, 2, 3"): counter-based,
+// so a salt depends only on the batch seed and its index in the batch, not on the thread group
+// size, the addresses per thread or the device split. philox_salt in gpu_compute.rs computes
+// the same salts on the host
+constant uint32_t PHILOX_M0 = 0xD2511F53;
+constant uint32_t PHILOX_M1 = 0xCD9E8D57;
+constant uint32_t PHILOX_W0 = 0x9E3779B9;
+constant uint32_t PHILOX_W1 = 0xBB67AE85;
 
-void pcg32_init(thread PCGState* rng, uint64_t seed, uint64_t stream) {
-    rng->state = 0U;
-    rng->inc = (stream << 1u) | 1u;
-    pcg32_random(rng);
-    rng->state += seed;
-    pcg32_random(rng);
+uint4 philox4x32_10(uint4 counter, uint2 key) {
+    for (int round = 0; round < 10; round++) {
+        uint32_t lo0 = PHILOX_M0 * counter.x;
+        uint32_t hi0 = mulhi(PHILOX_M0, counter.x);
+        uint32_t lo1 = PHILOX_M1 * counter.z;
+        uint32_t hi1 = mulhi(PHILOX_M1, counter.z);
+        counter = uint4(hi1 ^ counter.y ^ key.x, lo1, hi0 ^ counter.w ^ key.y, lo0);
+        key += uint2(PHILOX_W0, PHILOX_W1);
+    }
+    return counter;
 }
 
-void generate_random_salt(thread PCGState* rng, thread uchar* salt) {
+// Two counter blocks give 8 words; each supplies 4 hex characters from its top 16 bits
+void generate_random_salt(uint32_t seed, uint32_t index, thread uchar* salt) {
     const uchar hex_chars[16] = {'0','1','2','3','4','5','6','7','8','9','a','b','c','d','e','f'};
     
-    for (int i = 0; i < 8; i++) {
-        uint32_t rand = pcg32_random(rng);
-        salt[i*4] = hex_chars[(rand >> 28) & 0xF];
-        salt[i*4+1] = hex_chars[(rand >> 24) & 0xF];
-        salt[i*4+2] = hex_chars[(rand >> 20) & 0xF];
-        salt[i*4+3] = hex_chars[(rand >> 16) & 0xF];
+    for (uint32_t block = 0; block < 2; block++) {
+        uint4 words = philox4x32_10(uint4(index, block, 0, 0), uint2(seed, 0));
+        for (int j = 0; j < 4; j++) {
+            uint32_t rand = words[j];
+            int i = block * 4 + j;
+            salt[i*4] = hex_chars[(rand >> 28) & 0xF];
+            salt[i*4+1] = hex_chars[(rand >> 24) & 0xF];
+            salt[i*4+2] = hex_chars[(rand >> 20) & 0xF];
+            salt[i*4+3] = hex_chars[(rand >> 16) & 0xF];
+        }
     }
 }
 
 // ==================== Create2 Parameters ====================
 struct Create2TronParams {
-    uchar implementation[40];  // Hex address without 0x
-    uchar deployer[40];        // Hex address without 0x
+    uchar implementation[20];  // Raw address bytes
+    uchar deployer[20];        // Raw address bytes
+    uchar init_code_hash[32];  // Used instead of hashing the proxy init code when use_init_code_hash is 1
+    uchar filter[34];          // Required Base58 character per position, 0 for any
     uint32_t batch_size;
     uint32_t addresses_per_thread;
-    uint32_t random_seed;
     uint32_t use_gpu_random;
+    uint32_t use_init_code_hash; // 1 for generic CREATE2 with a host-provided init code hash
+    uint32_t use_filter;       // 1 to write back only addresses passing the filter
+    uint32_t first_thread;     // grid index of this dispatch's first thread when a batch is split across devices
+    uint32_t batches;          // GPU random batches searched in this dispatch, one seed each; 1 without a filter
+    uint32_t result_capacity;  // result slots in the results buffer; filtered candidates past it are only counted
 };
 
 struct Create2TronResult {
     uchar address[64];         // Base58 encoded Tron address
     uint32_t salt_index;
     uint32_t address_len;
+    uint32_t batch;            // which of the dispatch's batches it belongs to
+    uchar salt[32];            // Salt string that produced this address
 };
 
+// Base58 is case-sensitive, so the filter is compared exactly
+bool matches_filter(const thread uchar* address, uint32_t len, device const uchar* filter) {
+    if (len != 34) return false;
+    for (int i = 0; i < 34; i++) {
+        if (filter[i] != 0 && address[i] != filter[i]) return false;
+    }
+    return true;
+}
+
 // ==================== Main Kernel ====================
 kernel void compute_create2_tron_batch(
     device const Create2TronParams* params [[buffer(0)]],
     device const uchar* salts [[buffer(1)]],
     device Create2TronResult* results [[buffer(2)]],
-    uint gid [[thread_position_in_grid]],
-    uint tid [[thread_index_in_threadgroup]]
+    device atomic_uint* result_count [[buffer(3)]],  // Next free result slot when filtering
+    constant uint32_t* seeds [[buffer(4)]],  // Per-batch seeds for GPU random generation
+    uint gid [[thread_position_in_grid]]
 ) {
+    // Index of this thread in the whole batch; batch_size is the end of this device's shard
+    uint32_t thread_index = params->first_thread + gid;
     uint32_t addresses_per_thread = params->addresses_per_thread;
-    uint32_t start_idx = gid * addresses_per_thread;
+    uint32_t start_idx = thread_index * addresses_per_thread;
     uint32_t end_idx = min(start_idx + addresses_per_thread, params->batch_size);
     
     if (start_idx >= params->batch_size) return;
     
-    PCGState rng;
-    if (params->use_gpu_random == 1) {
-        uint64_t unique_seed = params->random_seed + gid;
-        uint64_t stream = (uint64_t)tid * 1099511628211ULL;
-        pcg32_init(&rng, unique_seed, stream);
-    }
-    
-    // Decode hex addresses to bytes
-    uchar impl_bytes[20];
-    uchar depl_bytes[20];
-    hex_decode_device(params->implementation, impl_bytes, 20);
-    hex_decode_device(params->deployer, depl_bytes, 20);
-    
     // Constants
     const uchar PREFIX[20] = {
         0x3d, 0x60, 0x2d, 0x80, 0x60, 0x0a, 0x3d, 0x39, 0x81, 0xf3,
@@ -373,69 +373,91 @@ kernel void compute_create2_tron_batch(
     uint32_t pos = 0;
     
     for (int i = 0; i < 20; i++) bytecode_template[pos++] = PREFIX[i];
-    for (int i = 0; i < 20; i++) bytecode_template[pos++] = impl_bytes[i];
+    for (int i = 0; i < 20; i++) bytecode_template[pos++] = params->implementation[i];
     for (int i = 0; i < 16; i++) bytecode_template[pos++] = TRON_SUFFIX[i];
-    for (int i = 0; i < 20; i++) bytecode_template[pos++] = depl_bytes[i];
+    for (int i = 0; i < 20; i++) bytecode_template[pos++] = params->deployer[i];
     
-    // Process addresses
-    for (uint32_t idx = start_idx; idx < end_idx; idx++) {
-        uchar salt_str[32];
-        
-        if (params->use_gpu_random == 1) {
-            generate_random_salt(&rng, salt_str);
-        } else {
-            device const uchar* salt_ptr = salts + (idx * 32);
-            for (int i = 0; i < 32; i++) {
-                salt_str[i] = salt_ptr[i];
+    // Each batch keys the generator with its own seed, so a batch produces the same
+    // salts whether it is dispatched alone or as one of several batches
+    for (uint32_t batch = 0; batch < params->batches; batch++) {
+        // Process addresses
+        for (uint32_t idx = start_idx; idx < end_idx; idx++) {
+            uchar salt_str[32];
+            
+            if (params->use_gpu_random == 1) {
+                generate_random_salt(seeds[batch], idx, salt_str);
+            } else {
+                device const uchar* salt_ptr = salts + (idx * 32);
+                for (int i = 0; i < 32; i++) {
+                    salt_str[i] = salt_ptr[i];
+                }
             }
+            
+            // Build complete bytecode
+            uchar bytecode[108];
+            for (int i = 0; i < 76; i++) bytecode[i] = bytecode_template[i];
+            for (int i = 0; i < 32; i++) bytecode[76 + i] = salt_str[i];
+            
+            // First Keccak256 hash
+            uchar first_hash[32];
+            if (params->use_init_code_hash == 1) {
+                for (int i = 0; i < 32; i++) {
+                    first_hash[i] = params->init_code_hash[i];
+                }
+            } else {
+                keccak256_thread(bytecode, 55, first_hash);
+            }
+            
+            // Build second part
+            uchar second_part[85];
+            for (int i = 0; i < 53; i++) second_part[i] = bytecode[55 + i];
+            for (int i = 0; i < 32; i++) second_part[53 + i] = first_hash[i];
+            
+            // Second Keccak256 hash
+            uchar second_hash[32];
+            keccak256_thread(second_part, 85, second_hash);
+            
+            // Take last 20 bytes as address
+            uchar address_bytes[20];
+            for (int i = 0; i < 20; i++) {
+                address_bytes[i] = second_hash[12 + i];
+            }
+            
+            // Create Tron address with 0x41 prefix
+            uchar tron_addr_bytes[25];
+            tron_addr_bytes[0] = 0x41;
+            for (int i = 0; i < 20; i++) {
+                tron_addr_bytes[1 + i] = address_bytes[i];
+            }
+            
+            // Calculate checksum using double SHA256
+            uchar hash1[32];
+            sha256(tron_addr_bytes, 21, hash1);
+            
+            uchar hash2[32];
+            sha256(hash1, 32, hash2);
+            
+            // Add checksum
+            for (int i = 0; i < 4; i++) {
+                tron_addr_bytes[21 + i] = hash2[i];
+            }
+            
+            // Encode to Base58
+            uchar encoded[64];
+            uint32_t addr_len = base58_encode(tron_addr_bytes, 25, encoded);
+            
+            bool filtering = params->use_filter == 1;
+            if (filtering && !matches_filter(encoded, addr_len, params->filter)) continue;
+            
+            // Filtered candidates are packed at the front of the buffer; the host reports an
+            // overflow from the count when more matched than there are slots
+            uint32_t slot = filtering ? atomic_fetch_add_explicit(result_count, 1, memory_order_relaxed) : idx;
+            if (slot >= params->result_capacity) continue;
+            for (uint32_t i = 0; i < addr_len; i++) results[slot].address[i] = encoded[i];
+            results[slot].address_len = addr_len;
+            results[slot].salt_index = idx;
+            results[slot].batch = batch;
+            for (int i = 0; i < 32; i++) results[slot].salt[i] = salt_str[i];
         }
-        
-        // Build complete bytecode
-        uchar bytecode[108];
-        for (int i = 0; i < 76; i++) bytecode[i] = bytecode_template[i];
-        for (int i = 0; i < 32; i++) bytecode[76 + i] = salt_str[i];
-        
-        // First Keccak256 hash
-        uchar first_hash[32];
-        keccak256_thread(bytecode, 55, first_hash);
-        
-        // Build second part
-        uchar second_part[85];
-        for (int i = 0; i < 53; i++) second_part[i] = bytecode[55 + i];
-        for (int i = 0; i < 32; i++) second_part[53 + i] = first_hash[i];
-        
-        // Second Keccak256 hash
-        uchar second_hash[32];
-        keccak256_thread(second_part, 85, second_hash);
-        
-        // Take last 20 bytes as address
-        uchar address_bytes[20];
-        for (int i = 0; i < 20; i++) {
-            address_bytes[i] = second_hash[12 + i];
-        }
-        
-        // Create Tron address with 0x41 prefix
-        uchar tron_addr_bytes[25];
-        tron_addr_bytes[0] = 0x41;
-        for (int i = 0; i < 20; i++) {
-            tron_addr_bytes[1 + i] = address_bytes[i];
-        }
-        
-        // Calculate checksum using double SHA256
-        uchar hash1[32];
-        sha256(tron_addr_bytes, 21, hash1);
-        
-        uchar hash2[32];
-        sha256(hash1, 32, hash2);
-        
-        // Add checksum
-        for (int i = 0; i < 4; i++) {
-            tron_addr_bytes[21 + i] = hash2[i];
-        }
-        
-        // Encode to Base58
-        uint32_t addr_len = base58_encode(tron_addr_bytes, 25, results[idx].address);
-        results[idx].address_len = addr_len;
-        results[idx].salt_index = idx;
     }
 }
\ No newline at end of file
@@ -1,47 +1,59 @@
-mod create2;
+#[macro_use]
+extern crate create2_cli;
 
-use create2::predict_deterministic_address;
-use std::io::{self, Write};
+use create2_cli::{bloom, checkpoint, cli, compare, config, format_duration, nonces, notify, output, pattern, progress, prompt, salts, validate};
+use bloom::{SaltBloom, DEFAULT_BLOOM_CAPACITY, DEFAULT_BLOOM_FP_RATE};
+use checkpoint::Checkpoint;
+use cli::Command;
+use config::{GpuBackend, PatternPosition, Scheme, Settings, Verbosity, DEFAULT_BENCH_DIFFICULTY, DEFAULT_NONCE_COUNT};
+use create2_core::salt::SaltPolicy;
+use create2_core::{
+    check_address, predict_deterministic_address, random_salt, search_templates, Address, CancellationToken, Create2Error,
+    Create2Template, RANDOM_SALT_WORDS,
+};
+use notify::Notifier;
+use pattern::{AddressKind, PatternSet};
+use progress::ProgressReporter;
+use salts::SaltReader;
+use validate::Validation;
+use output::{BenchSummary, MatchSink, Output, Prediction, ResultsFile};
+use rand_chacha::ChaCha8Rng;
+use std::hint::black_box;
+use std::ops::ControlFlow;
 use std::time::{Duration, Instant};
 
 const TOTAL_OPERATIONS: usize = 50_000_000;
 const IMPLEMENTATION: &str = "0xa84c57e9966df7df79bff42f35c68aae71796f64";
 const DEPLOYER: &str = "0xfe15afcb5b9831b8af5fd984678250e95de8e312";
 const PROGRESS_INTERVAL: usize = 1000;
+// find模式每隔多少次尝试检查停止条件并保存检查点
+const CHECK_INTERVAL: usize = 1000;
+// predict模式每次从文件读取的salt数
+const PREDICT_BATCH_SIZE: usize = 65536;
+const DEFAULT_PATTERN: &str = "eAce1";
 
-fn format_duration(duration: Duration) -> String {
-    let total_secs = duration.as_secs_f64();
-    
-    if total_secs < 60.0 {
-        format!("{:.1}s", total_secs)
-    } else if total_secs < 3600.0 {
-        let mins = (total_secs / 60.0) as u32;
-        let secs = total_secs % 60.0;
-        format!("{}m{:.1}s", mins, secs)
-    } else {
-        let hours = (total_secs / 3600.0) as u32;
-        let mins = ((total_secs % 3600.0) / 60.0) as u32;
-        let secs = total_secs % 60.0;
-        format!("{}h{}m{:.1}s", hours, mins, secs)
-    }
-}
-
-fn run_benchmark() -> Result<(), Box<dyn std::error::Error>> {
-    println!("🚀 Rust CREATE2地址预测benchmark");
-    println!("总计算量: {} 次", TOTAL_OPERATIONS);
-    println!("实现合约: {}", IMPLEMENTATION);
-    println!("部署者: {}", DEPLOYER);
-    println!("--------------------------------------------------------------------------------");
+fn run_benchmark(settings: &Settings) -> Result<(), Box<dyn std::error::Error>> {
+    let output = Output::open(settings.output.as_deref())?;
+    // 未指定--seed时随机生成并打印，任何一次运行都可以复现
+    let seed = settings.seed.unwrap_or_else(rand::random);
+    
+    status!("🚀 Rust CREATE2地址预测benchmark");
+    status!("总计算量: {} 次", TOTAL_OPERATIONS);
+    status!("实现合约: {}", settings.implementation);
+    status!("部署者: {}", settings.deployer);
+    print_init_code_hash(settings);
+    status!("随机种子: {}", seed);
+    status!("--------------------------------------------------------------------------------");
 
-    use rand::Rng;
-    let mut rng = rand::thread_rng();
+    use rand::{Rng, SeedableRng};
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
     
     // 预分配缓冲区
     let hex_chars = b"0123456789abcdef";
 
     let start_time = Instant::now();
-    let mut last_report_time = start_time;
-    let mut last_report_count = 0;
+    let reporter = ProgressReporter::new(settings);
+    let template = settings.template()?;
 
     for i in 0..TOTAL_OPERATIONS {
         // 生成随机salt
@@ -55,7 +67,7 @@ fn run_benchmark() -> Result<(), Box<dyn std::error::Error>> {
             salt.push(hex_chars[(byte & 0x0f) as usize] as char);
         }
         
-        match predict_deterministic_address(IMPLEMENTATION, DEPLOYER, &salt) {
+        match template.predict(&salt).map(|address| address.to_string()) {
             Ok(_) => {},
             Err(e) => {
                 eprintln!("Error at iteration {}: {}", i, e);
@@ -63,69 +75,408 @@ fn run_benchmark() -> Result<(), Box<dyn std::error::Error>> {
             }
         }
 
-        if i % PROGRESS_INTERVAL == 0 || i == TOTAL_OPERATIONS - 1 {
-            let current_time = Instant::now();
-            let elapsed = current_time.duration_since(start_time);
-            let current_count = i + 1;
-            
-            let avg_tps = current_count as f64 / elapsed.as_secs_f64();
+        let current_count = i + 1;
+        let tick = if current_count == TOTAL_OPERATIONS {
+            Some(reporter.finish(current_count))
+        } else {
+            reporter.tick(current_count)
+        };
+        if let Some(tick) = tick {
+            let percentage = (tick.count as f64 / TOTAL_OPERATIONS as f64) * 100.0;
             
-            let current_tps = if i > 0 {
-                let interval_elapsed = current_time.duration_since(last_report_time);
-                let interval_count = current_count - last_report_count;
-                if interval_elapsed.as_secs_f64() > 0.0 {
-                    interval_count as f64 / interval_elapsed.as_secs_f64()
-                } else {
-                    0.0
-                }
-            } else {
-                avg_tps
-            };
-            
-            let percentage = (current_count as f64 / TOTAL_OPERATIONS as f64) * 100.0;
-            
-            print!("\r进度: {:.2}% ({}/{}) | 平均TPS: {:.0} | 当前TPS: {:.0} | 用时: {}",
-                percentage, current_count, TOTAL_OPERATIONS, avg_tps, current_tps, 
-                format_duration(elapsed));
-            io::stdout().flush().unwrap();
-            
-            last_report_time = current_time;
-            last_report_count = current_count;
+            progress!("\r进度: {:.2}% ({}/{}) | 平均TPS: {:.0} | 当前TPS: {:.0} | 用时: {}",
+                percentage, tick.count, TOTAL_OPERATIONS, tick.avg_tps, tick.current_tps, 
+                format_duration(tick.elapsed));
         }
     }
 
     let total_elapsed = start_time.elapsed();
-    println!("\n--------------------------------------------------------------------------------");
-    println!("✅ 计算完成!");
-    println!();
-    println!("📊 Benchmark 结果:");
-    println!("==================================================");
-    println!("总操作数:     {}", TOTAL_OPERATIONS);
-    println!("总用时:       {:.1}s", total_elapsed.as_secs_f64());
-    println!("平均TPS:      {:.2} ops/sec", TOTAL_OPERATIONS as f64 / total_elapsed.as_secs_f64());
-    println!("每次操作耗时: {:.2} μs", total_elapsed.as_micros() as f64 / TOTAL_OPERATIONS as f64);
+    status!("\n--------------------------------------------------------------------------------");
+    status!("✅ 计算完成!");
+    status!();
+    summary!("📊 Benchmark 结果:");
+    summary!("==================================================");
+    summary!("总操作数:     {}", TOTAL_OPERATIONS);
+    summary!("总用时:       {:.1}s", total_elapsed.as_secs_f64());
+    summary!("平均TPS:      {:.2} ops/sec", TOTAL_OPERATIONS as f64 / total_elapsed.as_secs_f64());
+    summary!("每次操作耗时: {:.2} μs", total_elapsed.as_micros() as f64 / TOTAL_OPERATIONS as f64);
+
+    let mut summary = BenchSummary::new("rust", TOTAL_OPERATIONS, total_elapsed.as_secs_f64());
+    summary.seed = Some(seed);
+    output::emit_json(&summary)?;
+    output.record(&summary.to_string())?;
 
     Ok(())
 }
 
+// bench-find：以末尾 --bench-difficulty 个0为合成规则，按benchmark的计算量跑完整的 生成salt→哈希→匹配→输出 流程，
+// 报告每秒找到的地址数；用时包括find模式启动时的校准
+fn run_match_benchmark(settings: &Settings) -> Result<(), Box<dyn std::error::Error>> {
+    let output = Output::open(settings.output.as_deref())?;
+    let seed = settings.seed.unwrap_or_else(rand::random);
+    let difficulty = settings.bench_difficulty;
+    let settings = settings.bench_find("0".repeat(difficulty), TOTAL_OPERATIONS, seed);
+    let expected = PatternSet::from_settings(&settings, AddressKind::Evm)?
+        .expected_attempts()
+        .ok_or("bench-find needs a pattern with a difficulty estimate")?;
+    
+    let start_time = Instant::now();
+    let matches = find_address(&settings)?;
+    let total_elapsed = start_time.elapsed();
+    let matches_per_sec = matches as f64 / total_elapsed.as_secs_f64();
+    
+    status!();
+    summary!("📊 匹配Benchmark 结果:");
+    summary!("==================================================");
+    summary!("难度:         末尾 {} 个0，平均每 {:.0} 次尝试匹配一次", difficulty, expected);
+    summary!("总尝试次数:   {}", TOTAL_OPERATIONS);
+    summary!("总用时:       {:.1}s", total_elapsed.as_secs_f64());
+    summary!("平均TPS:      {:.2} ops/sec", TOTAL_OPERATIONS as f64 / total_elapsed.as_secs_f64());
+    summary!("找到地址数:   {}（期望 {:.1}）", matches, TOTAL_OPERATIONS as f64 / expected);
+    summary!("每秒匹配数:   {:.2} matches/sec", matches_per_sec);
+    
+    let mut summary = BenchSummary::new("rust", TOTAL_OPERATIONS, total_elapsed.as_secs_f64());
+    summary.seed = Some(seed);
+    summary.difficulty = Some(difficulty);
+    summary.matches = Some(matches);
+    summary.matches_per_sec = Some(matches_per_sec);
+    output::emit_json(&summary)?;
+    output.record(&summary.to_string())?;
+    
+    Ok(())
+}
+
 fn run_single_test() -> Result<(), Box<dyn std::error::Error>> {
-    println!("Running single test for verification...");
+    status!("Running single test for verification...");
     let implementation = "0xa84c57e9966df7df79bff42f35c68aae71796f64";
     let deployer = "0xfe15afcb5b9831b8af5fd984678250e95de8e312";
     let salt = "test-salt-test";
 
     let result = predict_deterministic_address(implementation, deployer, salt)?;
-    println!("Single test result: {}", result);
+    summary!("Single test result: {}", result);
+    
+    Ok(())
+}
+
+// 返回找到的地址数，达到停止条件后正常退出
+fn find_address(settings: &Settings) -> Result<usize, Box<dyn std::error::Error>> {
+    let targets = settings.find_targets();
+    let pattern = PatternSet::from_settings(settings, AddressKind::Evm)?.per_salt(targets.len());
+    let templates = settings.templates()?;
+    let output = Output::open(settings.output.as_deref())?;
+    let results = ResultsFile::open(settings.results.as_deref(), "rust", pattern.spec())?;
+    let notifier = Notifier::new(settings.notify_url.as_deref(), "rust", pattern.spec())?;
+    // --resume 恢复时沿用检查点里的种子和随机数位置
+    let mut checkpoint = Checkpoint::open(settings, settings.seed.unwrap_or_else(rand::random), 1)?;
+    pattern.restore(&checkpoint.matches);
+    let resume_path = settings.resume.as_deref();
+    let mut bloom = SaltBloom::open(settings)?;
+    
+    status!("🔍 开始搜索{}的EVM CREATE2地址...", pattern);
+    if let Some(attempts) = pattern.expected_attempts() {
+        status!("难度: 平均每 {:.0} 次尝试匹配一次", attempts);
+        let tps = calibrate_tps(&templates);
+        status!("校准TPS: {:.0} | 预计平均每 {} 找到一个地址", tps, progress::format_eta(attempts, tps));
+    }
+    pattern.print_rules();
+    status!("Implementation: {}", settings.find_implementations().join(", "));
+    status!("Deployer: {}", settings.find_deployers().join(", "));
+    print_init_code_hash(settings);
+    status!("随机种子: {}", checkpoint.seed);
+    if let Some(ref bloom) = bloom {
+        status!("布隆过滤器: {}", bloom.describe());
+    }
+    print_find_limits(settings);
+    status!("按Ctrl+C停止搜索");
+    status!("--------------------------------------------------------------------------------");
+    
+    use rand::SeedableRng;
+    let mut rng = ChaCha8Rng::seed_from_u64(checkpoint.seed);
+    rng.set_word_pos(checkpoint.rng_positions[0]);
+    
+    let start_time = Instant::now();
+    let elapsed_offset = checkpoint.elapsed();
+    let reporter = ProgressReporter::resumed(settings, checkpoint.attempts, elapsed_offset);
+    let mut attempts = checkpoint.attempts;
+    let sink = MatchSink::resumed(settings, &targets, &pattern, &output, &results, &notifier, &checkpoint);
+    let mut skipped = 0;
+    let cancel = CancellationToken::new();
+    
+    // 每次search最多CHECK_INTERVAL个salt，两次之间刷新进度、保存检查点并检查停止条件
+    while !settings.find_limit_reached(attempts, sink.count(), elapsed_offset + start_time.elapsed()) {
+        let chunk = (CHECK_INTERVAL - attempts % CHECK_INTERVAL)
+            .min(settings.max_attempts.map_or(usize::MAX, |max| max - attempts));
+        let base = attempts;
+        let chunk_position = rng.get_word_pos();
+        
+        // 布隆过滤器中已有的批次直接把随机数位置移到批次末尾，不生成salt，也不计入尝试次数
+        if bloom.as_ref().is_some_and(|bloom| bloom.contains(checkpoint.seed, 0, chunk_position)) {
+            rng.set_word_pos(chunk_position + chunk as u128 * RANDOM_SALT_WORDS);
+            skipped += 1;
+            continue;
+        }
+        
+        let salts = std::iter::repeat_with(|| random_salt(&mut rng)).take(chunk);
+        let searched = search_templates(
+            &templates,
+            salts,
+            &cancel,
+            |address| pattern.matches(&address.to_string()),
+            |index, salt, address, count| -> Result<ControlFlow<()>, Box<dyn std::error::Error>> {
+                let Some(record) = sink.report(index, salt.to_string(), &address.to_string(), base + count)? else {
+                    return Ok(ControlFlow::Break(()));
+                };
+                checkpoint.matches.push(record);
+                
+                Ok(if settings.max_matches.is_some_and(|max| sink.count() >= max) {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
+                })
+            },
+        )?;
+        attempts += searched;
+        
+        // 只记录完整搜索过的批次，中途停止的批次下次仍会搜索
+        if let Some(ref mut bloom) = bloom {
+            if searched == chunk {
+                bloom.insert(checkpoint.seed, 0, chunk_position);
+            }
+            bloom.save_if_due()?;
+        }
+        
+        if let Some(tick) = reporter.tick(attempts) {
+            progress!("\r已尝试: {} | 平均TPS: {:.0} | 当前TPS: {:.0} | 用时: {}{}{}     ",
+                tick.count, tick.avg_tps, tick.current_tps, format_duration(tick.elapsed),
+                tick.eta(pattern.expected_attempts()), pattern.best(&checkpoint.matches));
+        }
+        
+        checkpoint.attempts = attempts;
+        checkpoint.elapsed_secs = (elapsed_offset + start_time.elapsed()).as_secs_f64();
+        checkpoint.rng_positions[0] = rng.get_word_pos();
+        checkpoint.save_if_due(resume_path)?;
+    }
     
+    let elapsed = elapsed_offset + start_time.elapsed();
+    if let Some(path) = resume_path {
+        checkpoint.attempts = attempts;
+        checkpoint.elapsed_secs = elapsed.as_secs_f64();
+        checkpoint.rng_positions[0] = rng.get_word_pos();
+        checkpoint.save(path)?;
+    }
+    if let Some(ref bloom) = bloom {
+        bloom.save()?;
+        summary!("\n♻️  跳过了 {} 个已搜索过的批次", skipped);
+    }
+    
+    let found = sink.count();
+    summary!("\n🏁 搜索结束: 已尝试 {} 次, 找到 {} 个地址, 用时 {}",
+        attempts, found, format_duration(elapsed));
+    pattern.print_hits();
+    pattern.print_top(&checkpoint.matches, &output)?;
+    Ok(found)
+}
+
+// 与搜索相同的salt生成和地址格式化，只是不做匹配；使用单独的随机数流，不影响检查点
+fn calibrate_tps(templates: &[Create2Template]) -> f64 {
+    use rand::SeedableRng;
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let cancel = CancellationToken::new();
+    progress::calibrate(|| {
+        let salts = std::iter::repeat_with(|| random_salt(&mut rng)).take(CHECK_INTERVAL);
+        search_templates(templates, salts, &cancel, |address| black_box(address.to_string()).is_empty(), |_, _, _, _| {
+            Ok::<_, Create2Error>(ControlFlow::Continue(()))
+        })
+        .unwrap_or(0)
+    })
+}
+
+fn print_init_code_hash(settings: &Settings) {
+    if let Some(ref hash) = settings.init_code_hash {
+        status!("Init code hash: {}（通用CREATE2，忽略实现合约）", hash);
+    } else if settings.scheme != Scheme::Minimal {
+        status!("代理方案: {}", settings.scheme);
+        if let Some(ref path) = settings.proxy_bytecode {
+            status!("Creation code: {}", path.display());
+        }
+        if let Some(ref owner) = settings.account_owner {
+            status!("账户owner: {}", owner);
+        }
+        if let Some(ref args) = settings.proxy_args {
+            status!("代理参数: {}", args);
+        }
+    }
+}
+
+fn print_find_limits(settings: &Settings) {
+    if let Some(max) = settings.max_attempts {
+        status!("最大尝试次数: {}", max);
+    }
+    if let Some(max) = settings.max_time {
+        status!("最长搜索时间: {}秒", max);
+    }
+    if let Some(max) = settings.max_matches {
+        status!("最多找到地址数: {}", max);
+    }
+}
+
+// 逐行计算salt文件中每个salt对应的地址，按输入顺序流式输出
+fn run_predict(settings: &Settings) -> Result<(), Box<dyn std::error::Error>> {
+    let path = settings.salts_file.as_deref().ok_or("predict requires --salts-file")?;
+    let mut reader = SaltReader::open(path, settings.salt_policy)?;
+    let template = settings.template()?;
+    let output = Output::open(settings.output.as_deref())?;
+    
+    status!("📄 从 {} 读取salt", path.display());
+    status!("实现合约: {}", settings.implementation);
+    status!("部署者: {}", settings.deployer);
+    print_init_code_hash(settings);
+    status!("--------------------------------------------------------------------------------");
+    
+    let start_time = Instant::now();
+    let mut total = 0;
+    
+    loop {
+        let salts = reader.read_batch(PREDICT_BATCH_SIZE)?;
+        if salts.is_empty() {
+            break;
+        }
+        total += salts.len();
+        
+        for (salt, bytes) in salts {
+            let address = Address::new(template.predict_bytes(&bytes)).to_string();
+            output::emit_prediction(&output, &Prediction { salt, address })?;
+        }
+    }
+    
+    status!("--------------------------------------------------------------------------------");
+    status!("✅ 共计算 {} 个地址, 用时 {}", total, format_duration(start_time.elapsed()));
+    Ok(())
+}
+
+// 检查运行参数并打印各模式会做什么，不执行任何哈希计算
+fn run_validate(settings: &Settings) -> Result<(), Box<dyn std::error::Error>> {
+    let mut validation = Validation::new();
+    let deployers = settings.deployers.iter().map(|deployer| ("find模式部署者", deployer));
+    let implementations = settings.implementations.iter().map(|implementation| ("find模式实现合约", implementation));
+    for (label, address) in [("实现合约", &settings.implementation), ("部署者", &settings.deployer)]
+        .into_iter()
+        .chain(implementations)
+        .chain(deployers)
+    {
+        validation.check(label, address.parse::<Address>().map(|address| address.to_string()));
+    }
+    validation.check("计算后端", Ok("CPU单线程".to_string()));
+    validation.check("benchmark模式", Ok(format!("计算 {} 个随机salt的地址", TOTAL_OPERATIONS)));
+    validation.check_common(settings, AddressKind::Evm);
+    validation.check_checkpoint(settings, 1);
+    print_find_limits(settings);
+    validation.finish()?;
     Ok(())
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let args: Vec<String> = std::env::args().collect();
+fn main() {
+    if let Err(e) = run() {
+        output::report_error(e.as_ref());
+        std::process::exit(1);
+    }
+}
+
+fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let defaults = Settings {
+        implementation: IMPLEMENTATION.to_string(),
+        implementations: Vec::new(),
+        deployer: DEPLOYER.to_string(),
+        deployers: Vec::new(),
+        chain: None,
+        init_code_hash: None,
+        scheme: Scheme::Minimal,
+        proxy_args: None,
+        proxy_bytecode: None,
+        account_owner: None,
+        batch_size: 1,
+        backend: GpuBackend::Auto,
+        threads: None,
+        hybrid: false,
+        batches_per_dispatch: 1,
+        addresses_per_thread: 4,
+        command_queues: 1,
+        pattern: DEFAULT_PATTERN.to_string(),
+        position: PatternPosition::Suffix,
+        patterns: Vec::new(),
+        suffix: None,
+        ignore_case: false,
+        regex: None,
+        mask: None,
+        objective: None,
+        vanity: None,
+        score: None,
+        top: 10,
+        output: None,
+        results: None,
+        notify_url: None,
+        json: false,
+        seed: None,
+        max_attempts: None,
+        max_time: None,
+        max_matches: None,
+        first_match: false,
+        bench_difficulty: DEFAULT_BENCH_DIFFICULTY,
+        nonce_start: 0,
+        nonce_count: DEFAULT_NONCE_COUNT,
+        verbosity: Verbosity::Normal,
+        salts_file: None,
+        salt_policy: SaltPolicy::Pad,
+        resume: None,
+        bloom: None,
+        bloom_capacity: DEFAULT_BLOOM_CAPACITY,
+        bloom_fp_rate: DEFAULT_BLOOM_FP_RATE,
+        tune_cache: None,
+        tuned: None,
+        profile: None,
+        progress_interval: PROGRESS_INTERVAL,
+        progress_every: Duration::from_millis(100),
+    };
+    let args = cli::parse_args(std::env::args())?;
+    args.reject_gpu_flags(env!("CARGO_BIN_NAME"))?;
+    if args.help {
+        cli::print_usage(env!("CARGO_BIN_NAME"));
+        return Ok(());
+    }
+    let mut settings = config::load_settings(args.config_path, args.overrides, defaults)?;
+    let mut command = args.command;
+    if prompt::interactive() {
+        command = prompt::ask(&mut settings, check_address)?;
+    }
+    output::set_json_mode(settings.json);
+    output::set_first_match_mode(settings.first_match);
+    output::set_verbosity(settings.verbosity);
+    verbose!("运行参数: {:?}", settings);
     
-    if args.len() > 1 && args[1] == "test" {
-        run_single_test()
-    } else {
-        run_benchmark()
+    // validate把 --chain 的问题和其他检查一起报告
+    if command != Command::Validate {
+        settings.check_chain(AddressKind::Evm)?;
+    }
+
+    match command {
+        Command::Test => run_single_test(),
+        Command::Find => {
+            // 停止时一个地址都没找到，以非零退出码结束；--first-match 用2与出错的1区分
+            if find_address(&settings)? == 0 {
+                std::process::exit(if settings.first_match { 2 } else { 1 });
+            }
+            Ok(())
+        }
+        Command::Predict => run_predict(&settings),
+        Command::Nonces => nonces::run_nonce_sweep(&settings),
+        Command::Compare => compare::run_scheme_comparison(&settings),
+        Command::Validate => run_validate(&settings),
+        Command::Tune => Err("tune (GPU autotuning) is only supported on the Metal builds".into()),
+        Command::Completions(shell) => {
+            cli::print_completions(shell, env!("CARGO_BIN_NAME"));
+            Ok(())
+        }
+        Command::Bench => run_benchmark(&settings),
+        Command::BenchFind => run_match_benchmark(&settings),
     }
 }
\ No newline at end of file